@@ -17,14 +17,6 @@ if [ ! -f $SQLITE_DB_PATH ]; then
 fi
 cp $SQLITE_DB_PATH $TARGET_DIRECTORY/db/Chinook.db
 
-if [ ! -f $SCHEMA_INFERRER_PATH ]; then
-    echo "Schema Inferrer Bin not build yet. Building it now."
-    cd .. 
-    sbt schema-inferrer-bin/prisma-native-image:packageBin
-    cd -
-fi
-cp $SCHEMA_INFERRER_PATH $TARGET_DIRECTORY/
-
 echo "Preview package ready in directory $TARGET_DIRECTORY"
 cd target/$PREVIEW_FOLDER && zip -r $PREVIEW_FOLDER.zip * && cd -
 echo "Zip available in $TARGET_DIRECTORY/$PREVIEW_FOLDER.zip"
\ No newline at end of file
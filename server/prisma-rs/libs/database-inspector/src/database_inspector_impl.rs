@@ -53,13 +53,14 @@ impl DatabaseInspectorImpl {
     fn get_table(&self, schema: &String, table: &String) -> Table {
         let introspected_columns = self.get_columns(&schema, &table);
         let introspected_foreign_keys = self.get_foreign_constraints(&schema, &table);
-        // let _index = get_index(&schema, &table);
+        let indexes = self.get_indexes(&schema, &table);
         // let _seq = get_sequence(&schema, &table);
 
         Table {
             name: table.to_string(),
+            primary_columns: primary_columns(&introspected_columns),
             columns: convert_introspected_columns(introspected_columns, introspected_foreign_keys),
-            indexes: Vec::new(),
+            indexes: indexes,
         }
     }
 
@@ -77,6 +78,7 @@ impl DatabaseInspectorImpl {
                 tpe: row.get("type"),
                 is_required: row.get("notnull"),
                 default: row.get("dflt_value"),
+                primary_key_position: row.get("pk"),
             });
         }
 
@@ -107,8 +109,43 @@ impl DatabaseInspectorImpl {
         unimplemented!()
     }
 
-    fn get_index(&self, _schema: &String, _table: &String) -> Index {
-        unimplemented!()
+    // Only returns indexes created with CREATE INDEX. Indexes backing primary keys and
+    // UNIQUE column constraints are created implicitly by SQLite and are not listed.
+    fn get_indexes(&self, schema: &String, table: &String) -> Vec<Index> {
+        let sql = format!(r#"Pragma "{}".index_list("{}");"#, schema, table);
+        let mut stmt = self.connection.prepare_cached(&sql).unwrap();
+        let mut rows = stmt.query(NO_PARAMS).unwrap();
+        let mut result = Vec::new();
+
+        while let Some(row_result) = rows.next() {
+            let row = row_result.unwrap();
+            let origin: String = row.get("origin");
+
+            if origin == "c" {
+                let name: String = row.get("name");
+                result.push(Index {
+                    columns: self.get_index_columns(schema, &name),
+                    name: name,
+                    unique: row.get("unique"),
+                });
+            }
+        }
+
+        result
+    }
+
+    fn get_index_columns(&self, schema: &String, index: &String) -> Vec<String> {
+        let sql = format!(r#"Pragma "{}".index_info("{}");"#, schema, index);
+        let mut stmt = self.connection.prepare_cached(&sql).unwrap();
+        let mut rows = stmt.query(NO_PARAMS).unwrap();
+        let mut result = Vec::new();
+
+        while let Some(row_result) = rows.next() {
+            let row = row_result.unwrap();
+            result.push(row.get("name"));
+        }
+
+        result
     }
 
     // fn query<F>(&self, schema: &String, parse: F) ->
@@ -134,24 +171,21 @@ fn convert_introspected_columns(
                 is_required: c.is_required,
                 foreign_key: foreign_key,
                 sequence: None,
+                default: c.default.clone(),
             }
         })
         .collect()
 }
 
+// The pk column of table_info is the 1-based position of the column within the primary key, or 0.
+fn primary_columns(columns: &Vec<IntrospectedColumn>) -> Vec<String> {
+    let mut primary: Vec<&IntrospectedColumn> = columns.iter().filter(|c| c.primary_key_position > 0).collect();
+    primary.sort_by_key(|c| c.primary_key_position);
+    primary.iter().map(|c| c.name.clone()).collect()
+}
+
 fn column_type(column: &IntrospectedColumn) -> ColumnType {
-    match column.tpe.as_ref() {
-        "INTEGER" => ColumnType::Int,
-        "REAL" => ColumnType::Float,
-        "BOOLEAN" => ColumnType::Boolean,
-        "TEXT" => ColumnType::String,
-        s if s.starts_with("VARCHAR") => ColumnType::String,
-        "DATE" => ColumnType::DateTime,
-        x => panic!(format!(
-            "type {} is not supported here yet. Column was: {}",
-            x, column.name
-        )),
-    }
+    ColumnType::from_sql(&column.tpe)
 }
 
 #[derive(Debug)]
@@ -161,6 +195,7 @@ struct IntrospectedColumn {
     tpe: String,
     default: Option<String>,
     is_required: bool,
+    primary_key_position: i64,
 }
 
 #[derive(Debug)]
@@ -28,6 +28,7 @@ pub struct Table {
     pub name: String,
     pub columns: Vec<Column>,
     pub indexes: Vec<Index>,
+    pub primary_columns: Vec<String>,
 }
 
 impl Table {
@@ -38,6 +39,14 @@ impl Table {
     pub fn has_column(&self, name: &str) -> bool {
         self.column(name).is_some()
     }
+
+    pub fn index(&self, name: &str) -> Option<&Index> {
+        self.indexes.iter().find(|i| i.name == name)
+    }
+
+    pub fn has_index(&self, name: &str) -> bool {
+        self.index(name).is_some()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -47,6 +56,8 @@ pub struct Column {
     pub is_required: bool,
     pub foreign_key: Option<ForeignKey>,
     pub sequence: Option<Sequence>,
+    // The default value as SQL expression, e.g. a function call.
+    pub default: Option<String>,
 }
 
 impl Column {
@@ -57,6 +68,7 @@ impl Column {
             is_required,
             foreign_key: None,
             sequence: None,
+            default: None,
         }
     }
 
@@ -67,17 +79,54 @@ impl Column {
             is_required,
             foreign_key: Some(foreign_key),
             sequence: None,
+            default: None,
         }
     }
 }
 
-#[derive(Debug, Copy, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ColumnType {
     Int,
     Float,
     Boolean,
     String,
     DateTime,
+    Json,
+    Bytes,
+    BigInt,
+    Uuid,
+    // Any other type, as declared in the database, e.g. NUMERIC(10, 2).
+    Native(String),
+}
+
+impl ColumnType {
+    // Maps a declared SQL type to the closest column type.
+    pub fn from_sql(sql: &str) -> ColumnType {
+        match sql {
+            "INTEGER" => ColumnType::Int,
+            "REAL" => ColumnType::Float,
+            "BOOLEAN" => ColumnType::Boolean,
+            "TEXT" => ColumnType::String,
+            s if s.starts_with("VARCHAR") => ColumnType::String,
+            "DATE" => ColumnType::DateTime,
+            "JSON" => ColumnType::Json,
+            "BLOB" => ColumnType::Bytes,
+            "BIGINT" => ColumnType::BigInt,
+            "UUID" => ColumnType::Uuid,
+            x => ColumnType::Native(x.to_string()),
+        }
+    }
+
+    // Native types which are introspected as another column type, like VARCHAR(255) as String, are
+    // considered the same type.
+    pub fn is_compatible_with(&self, other: &ColumnType) -> bool {
+        let normalize = |tpe: &ColumnType| match tpe {
+            ColumnType::Native(sql) => ColumnType::from_sql(sql),
+            tpe => tpe.clone(),
+        };
+
+        normalize(self) == normalize(other)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -31,6 +31,7 @@ fn all_columns_types_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
         Column {
             name: "float".to_string(),
@@ -38,6 +39,7 @@ fn all_columns_types_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
         Column {
             name: "boolean".to_string(),
@@ -45,6 +47,7 @@ fn all_columns_types_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
         Column {
             name: "string1".to_string(),
@@ -52,6 +55,7 @@ fn all_columns_types_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
         Column {
             name: "string2".to_string(),
@@ -59,6 +63,7 @@ fn all_columns_types_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
         Column {
             name: "date_time".to_string(),
@@ -66,6 +71,7 @@ fn all_columns_types_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
     ];
 
@@ -91,6 +97,7 @@ fn is_required_must_work() {
             is_required: true,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
         Column {
             name: "column2".to_string(),
@@ -98,6 +105,7 @@ fn is_required_must_work() {
             is_required: false,
             foreign_key: None,
             sequence: None,
+            default: None,
         },
     ];
     assert_eq!(user_table.columns, expected_columns);
@@ -126,10 +134,67 @@ fn foreign_keys_must_work() {
             column: "id".to_string(),
         }),
         sequence: None,
+        default: None,
     }];
     assert_eq!(user_table.columns, expected_columns);
 }
 
+#[test]
+fn primary_keys_and_indexes_must_work() {
+    let inspector = setup(|mut migration| {
+        migration.create_table("User", |t| {
+            t.add_column("id", types::primary());
+            t.add_column("firstName", types::text());
+            t.add_column("lastName", types::text());
+        });
+        migration.inject_custom(format!(
+            r#"CREATE UNIQUE INDEX "{}"."User.firstName_lastName" ON "User"("firstName", "lastName")"#,
+            SCHEMA
+        ));
+        migration.inject_custom(format!(r#"CREATE INDEX "{}"."User.lastName" ON "User"("lastName")"#, SCHEMA));
+    });
+
+    let result = inspector.introspect(&SCHEMA.to_string());
+
+    let user_table = result.table("User").unwrap();
+    assert_eq!(user_table.primary_columns, vec!["id".to_string()]);
+
+    let mut indexes = user_table.indexes.clone();
+    indexes.sort_by(|a, b| a.name.cmp(&b.name));
+    let expected_indexes = vec![
+        Index {
+            name: "User.firstName_lastName".to_string(),
+            columns: vec!["firstName".to_string(), "lastName".to_string()],
+            unique: true,
+        },
+        Index {
+            name: "User.lastName".to_string(),
+            columns: vec!["lastName".to_string()],
+            unique: false,
+        },
+    ];
+    assert_eq!(indexes, expected_indexes);
+}
+
+#[test]
+fn compound_primary_keys_must_work() {
+    let inspector = setup(|mut migration| {
+        migration.create_table("User", |t| {
+            t.add_column("firstName", types::text());
+            t.add_column("lastName", types::text());
+            t.inject_custom(String::from(r#"PRIMARY KEY ("lastName","firstName")"#));
+        });
+    });
+
+    let result = inspector.introspect(&SCHEMA.to_string());
+
+    let user_table = result.table("User").unwrap();
+    assert_eq!(
+        user_table.primary_columns,
+        vec!["lastName".to_string(), "firstName".to_string()]
+    );
+}
+
 fn setup<F>(mut migrationFn: F) -> Box<DatabaseInspector>
 where
     F: FnMut(&mut Migration) -> (),
@@ -0,0 +1,81 @@
+use datamodel::ast::Span;
+use serde_json::{json, Value};
+
+// Spans are byte offsets, while LSP positions are zero-based lines and
+// characters counted in UTF-16 code units.
+
+pub fn offset_at(text: &str, line: usize, character: usize) -> usize {
+    let mut offset = 0;
+
+    for (index, content) in text.split('\n').enumerate() {
+        if index == line {
+            let mut units = 0;
+
+            for (byte_index, c) in content.char_indices() {
+                if units >= character {
+                    return offset + byte_index;
+                }
+                units += c.len_utf16();
+            }
+
+            return offset + content.len();
+        }
+
+        offset += content.len() + 1;
+    }
+
+    text.len()
+}
+
+pub fn position_at(text: &str, offset: usize) -> Value {
+    let offset = std::cmp::min(offset, text.len());
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let character: usize = before[line_start..].chars().map(|c| c.len_utf16()).sum();
+
+    json!({ "line": line, "character": character })
+}
+
+// Spans of declarations include the whitespace which follows them, it is not part of the range.
+pub fn range(text: &str, span: &Span) -> Value {
+    let start = std::cmp::min(span.start, text.len());
+    let end = std::cmp::max(start, std::cmp::min(span.end, text.len()));
+    let end = start + text[start..end].trim_end().len();
+
+    json!({ "start": position_at(text, start), "end": position_at(text, end) })
+}
+
+// The byte offset of the position given in the params of a request.
+pub fn offset_from_params(text: &str, params: &Value) -> Option<usize> {
+    let position = params.get("position")?;
+    let line = position.get("line")?.as_u64()?;
+    let character = position.get("character")?.as_u64()?;
+
+    Some(offset_at(text, line as usize, character as usize))
+}
+
+// The identifier at the given offset, with its span.
+pub fn word_at(text: &str, offset: usize) -> Option<(&str, Span)> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let offset = std::cmp::min(offset, text.len());
+
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word(*c))
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(offset);
+    let end = text[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_word(*c))
+        .map(|(i, _)| offset + i)
+        .unwrap_or(text.len());
+
+    if start == end {
+        None
+    } else {
+        Some((&text[start..end], Span::new(start, end)))
+    }
+}
@@ -0,0 +1,202 @@
+use crate::document::{position_at, range, word_at};
+use datamodel::ast::{self, parser, renderer::Renderer};
+use datamodel::dml::validator::BUILTIN_TYPE_NAMES;
+use datamodel::{configuration, dmmf, Validator};
+use serde_json::{json, Value};
+
+// Completion item kinds as defined by LSP.
+const KIND_CLASS: u64 = 7;
+const KIND_KEYWORD: u64 = 14;
+const KIND_ENUM: u64 = 13;
+const KIND_PROPERTY: u64 = 10;
+
+const SEVERITY_ERROR: u64 = 1;
+
+// Parser and validation errors of the document. Declarations with syntax errors are skipped,
+// all other declarations are still validated.
+pub fn diagnostics(text: &str) -> Vec<Value> {
+    let (ast, parser_errors) = parser::parse_with_recovery(text);
+    let mut result: Vec<Value> = parser_errors
+        .iter()
+        .map(|err| diagnostic(text, &err.span, &err.message))
+        .collect();
+
+    let mut errors = Vec::new();
+
+    if let Err(errs) = Validator::new().validate(&ast) {
+        errors.extend(errs);
+    }
+
+    if let Err(errs) = configuration::load(&ast) {
+        errors.extend(errs);
+    }
+
+    result.extend(errors.iter().map(|error| {
+        let message = if error.directive_name.is_empty() {
+            error.message.clone()
+        } else {
+            format!(
+                "Error validating directive @{}: {}",
+                error.directive_name, error.message
+            )
+        };
+        diagnostic(text, &error.span, &message)
+    }));
+
+    result
+}
+
+fn diagnostic(text: &str, span: &ast::Span, message: &str) -> Value {
+    json!({
+        "range": range(text, span),
+        "severity": SEVERITY_ERROR,
+        "source": "datamodel",
+        "message": message,
+    })
+}
+
+// Shows the DMMF of the field at the given offset, as the query engine and generators see it.
+pub fn hover(text: &str, offset: usize) -> Option<Value> {
+    let ast = parser::parse(text).ok()?;
+    let (ast_model, ast_field) = field_at(&ast, offset)?;
+    let schema = Validator::new().validate(&ast).ok()?;
+    let field = schema.find_model(&ast_model.name)?.find_field(&ast_field.name)?;
+    let json = serde_json::to_string_pretty(&dmmf::field_to_dmmf(field)).ok()?;
+
+    Some(json!({
+        "contents": { "kind": "markdown", "value": format!("```json\n{}\n```", json) },
+        "range": range(text, &ast_field.span),
+    }))
+}
+
+// Jumps from the type of a field to the declaration of the model or enum.
+pub fn definition(uri: &str, text: &str, offset: usize) -> Option<Value> {
+    let ast = parser::parse(text).ok()?;
+    let (_, field) = field_at(&ast, offset)?;
+    let (word, _) = word_at(text, offset)?;
+
+    if word != field.field_type {
+        return None;
+    }
+
+    let span = ast.models.iter().find_map(|obj| match obj {
+        ast::ModelOrEnum::Model(model) if model.name == word => Some(model.span),
+        ast::ModelOrEnum::Enum(en) if en.name == word => Some(en.span),
+        _ => None,
+    })?;
+
+    Some(json!({ "uri": uri, "range": range(text, &span) }))
+}
+
+// Completes directive names after @ and @@, and type names after the name of a field.
+// Works on the raw text, as the document is usually not valid while typing.
+pub fn completion(text: &str, offset: usize) -> Vec<Value> {
+    let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_prefix = &text[line_start..offset];
+    let word_start = line_prefix
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let before_word = &line_prefix[..word_start];
+    let validator = Validator::new();
+
+    if before_word.ends_with("@@") {
+        return items(validator.model_block_directive_names(), KIND_PROPERTY);
+    }
+
+    if before_word.ends_with('@') {
+        return items(validator.field_directive_names(), KIND_PROPERTY);
+    }
+
+    let in_model = enclosing_block(&text[..line_start]) == Some("model");
+    let tokens: Vec<String> = line_prefix
+        .replace(':', " ")
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let at_field_type = match tokens.len() {
+        1 => word_start == line_prefix.len(),
+        2 => word_start < line_prefix.len(),
+        _ => false,
+    };
+
+    if !in_model || !at_field_type {
+        return vec![];
+    }
+
+    let mut result = items(BUILTIN_TYPE_NAMES.to_vec(), KIND_KEYWORD);
+
+    for (keyword, name) in declarations(text) {
+        let kind = if keyword == "model" { KIND_CLASS } else { KIND_ENUM };
+        result.push(item(name, kind));
+    }
+
+    result
+}
+
+// Replaces the whole document with its canonical form. Documents with syntax errors are not formatted.
+pub fn formatting(text: &str) -> Option<Vec<Value>> {
+    let ast = parser::parse(text).ok()?;
+    let formatted = Renderer::new().render(&ast);
+
+    if formatted == text {
+        return Some(vec![]);
+    }
+
+    Some(vec![json!({
+        "range": { "start": position_at(text, 0), "end": position_at(text, text.len()) },
+        "newText": formatted,
+    })])
+}
+
+fn field_at(ast: &ast::Schema, offset: usize) -> Option<(&ast::Model, &ast::Field)> {
+    ast.models.iter().find_map(|obj| match obj {
+        ast::ModelOrEnum::Model(model) => model
+            .fields
+            .iter()
+            .find(|field| field.span.start <= offset && offset <= field.span.end)
+            .map(|field| (model, field)),
+        _ => None,
+    })
+}
+
+// The keyword of the block which is still open at the end of the text, e.g. `model`.
+fn enclosing_block(text: &str) -> Option<&str> {
+    let mut current = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if line.starts_with('}') {
+            current = None;
+        } else if line.ends_with('{') {
+            current = line.split_whitespace().next();
+        }
+    }
+
+    current
+}
+
+// All model and enum declarations, e.g. `model User {`.
+fn declarations(text: &str) -> Vec<(&str, &str)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let keyword = tokens.next()?;
+            let name = tokens.next()?.trim_end_matches('{');
+
+            match keyword {
+                "model" | "enum" if !name.is_empty() => Some((keyword, name)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn items(names: Vec<&str>, kind: u64) -> Vec<Value> {
+    names.into_iter().map(|name| item(name, kind)).collect()
+}
+
+fn item(label: &str, kind: u64) -> Value {
+    json!({ "label": label, "kind": kind })
+}
@@ -0,0 +1,29 @@
+// A language server for the datamodel, see https://microsoft.github.io/language-server-protocol/.
+// Documents are kept in memory and fully parsed and validated on every request.
+
+mod document;
+mod features;
+pub mod protocol;
+mod server;
+
+pub use server::Server;
+
+use std::io::{self, BufRead, Write};
+
+// Handles messages until the client sends `exit`. Returns the exit code of the process,
+// which is an error if the client did not request a shutdown before.
+pub fn run<R: BufRead, W: Write>(mut input: R, mut output: W) -> io::Result<i32> {
+    let mut server = Server::new();
+
+    while let Some(message) = protocol::read_message(&mut input)? {
+        if message["method"] == "exit" {
+            return Ok(if server.is_shut_down() { 0 } else { 1 });
+        }
+
+        for outgoing in server.handle(&message) {
+            protocol::write_message(&mut output, &outgoing)?;
+        }
+    }
+
+    Ok(1)
+}
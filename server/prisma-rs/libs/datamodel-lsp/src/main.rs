@@ -0,0 +1,11 @@
+use std::io;
+use std::process;
+
+// Speaks the Language Server Protocol over stdin and stdout.
+fn main() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    let exit_code = datamodel_lsp::run(stdin.lock(), stdout.lock()).expect("Unable to communicate with the client");
+    process::exit(exit_code);
+}
@@ -0,0 +1,141 @@
+use crate::document::offset_from_params;
+use crate::features;
+use crate::protocol::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+// Full text synchronization, the client always sends the whole document.
+const TEXT_DOCUMENT_SYNC_FULL: u64 = 1;
+
+#[derive(Default)]
+pub struct Server {
+    // The open documents by uri.
+    documents: HashMap<String, String>,
+    shut_down: bool,
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server::default()
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down
+    }
+
+    // Handles a request or notification, and returns the messages for the client.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or("");
+        let params = &message["params"];
+
+        match message.get("id") {
+            Some(id) => vec![self.handle_request(id, method, params)],
+            None => self.handle_notification(method, params),
+        }
+    }
+
+    fn handle_request(&mut self, id: &Value, method: &str, params: &Value) -> Value {
+        if self.shut_down {
+            return error_response(id, INVALID_REQUEST, "The server is shut down.");
+        }
+
+        match method {
+            "initialize" => response(id, capabilities()),
+            "shutdown" => {
+                self.shut_down = true;
+                response(id, Value::Null)
+            }
+            "textDocument/hover" => self.with_document(id, params, |_, text, offset| {
+                features::hover(text, offset).unwrap_or(Value::Null)
+            }),
+            "textDocument/definition" => self.with_document(id, params, |uri, text, offset| {
+                features::definition(uri, text, offset).unwrap_or(Value::Null)
+            }),
+            "textDocument/completion" => self.with_document(id, params, |_, text, offset| {
+                Value::from(features::completion(text, offset))
+            }),
+            "textDocument/formatting" => match self.document(params) {
+                Some((_, text)) => response(id, features::formatting(text).map_or(Value::Null, Value::from)),
+                None => error_response(id, INVALID_PARAMS, "Unknown document."),
+            },
+            _ => error_response(id, METHOD_NOT_FOUND, &format!("Unknown method {}.", method)),
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = match params["textDocument"]["uri"].as_str() {
+            Some(uri) => uri.to_string(),
+            None => return vec![],
+        };
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or("").to_string();
+                self.documents.insert(uri.clone(), text);
+                vec![self.publish_diagnostics(&uri)]
+            }
+            // With full synchronization, the last change contains the whole document.
+            "textDocument/didChange" => match params["contentChanges"].as_array().and_then(|c| c.last()) {
+                Some(change) => {
+                    let text = change["text"].as_str().unwrap_or("").to_string();
+                    self.documents.insert(uri.clone(), text);
+                    vec![self.publish_diagnostics(&uri)]
+                }
+                None => vec![],
+            },
+            // Diagnostics of closed documents are cleared.
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                vec![notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let diagnostics = self
+            .documents
+            .get(uri)
+            .map_or(vec![], |text| features::diagnostics(text));
+
+        notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )
+    }
+
+    fn document<'a>(&'a self, params: &'a Value) -> Option<(&'a str, &'a str)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        self.documents.get(uri).map(|text| (uri, text.as_str()))
+    }
+
+    // Runs a request on a position in an open document.
+    fn with_document<F>(&self, id: &Value, params: &Value, f: F) -> Value
+    where
+        F: FnOnce(&str, &str, usize) -> Value,
+    {
+        match self.document(params) {
+            Some((uri, text)) => match offset_from_params(text, params) {
+                Some(offset) => response(id, f(uri, text, offset)),
+                None => error_response(id, INVALID_PARAMS, "Missing position."),
+            },
+            None => error_response(id, INVALID_PARAMS, "Unknown document."),
+        }
+    }
+}
+
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": TEXT_DOCUMENT_SYNC_FULL,
+            "hoverProvider": true,
+            "definitionProvider": true,
+            "completionProvider": { "triggerCharacters": ["@"] },
+            "documentFormattingProvider": true,
+        },
+        "serverInfo": { "name": "datamodel-lsp" },
+    })
+}
@@ -0,0 +1,337 @@
+use datamodel_lsp::protocol;
+use serde_json::{json, Value};
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const URI: &str = "file:///schema.prisma";
+
+const DATAMODEL: &str = r#"model User {
+    id: ID @primary
+    name: String
+    role: Role
+    posts: Post[]
+}
+
+model Post {
+    id: ID @primary
+    author: User
+}
+
+enum Role {
+    ADMIN
+    USER
+}
+"#;
+
+#[test]
+fn initialize_must_return_capabilities() {
+    let (responses, _) = run_session(vec![request(1, "initialize", json!({ "capabilities": {} }))]);
+
+    let capabilities = &find_response(&responses, 1)["result"]["capabilities"];
+    assert_eq!(capabilities["textDocumentSync"], 1);
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["definitionProvider"], true);
+    assert_eq!(capabilities["documentFormattingProvider"], true);
+    assert_eq!(capabilities["completionProvider"]["triggerCharacters"], json!(["@"]));
+}
+
+#[test]
+fn valid_documents_must_have_no_diagnostics() {
+    let (responses, _) = run_session(vec![did_open(DATAMODEL)]);
+
+    assert_eq!(diagnostics(&responses), vec![json!([])]);
+}
+
+#[test]
+fn validation_errors_must_be_published_with_their_range() {
+    let dml = "model User {\n    id: ID @primary\n    name: String @foo\n}\n";
+    let (responses, _) = run_session(vec![did_open(dml)]);
+
+    let diagnostics = diagnostics(&responses);
+    assert_eq!(
+        diagnostics[0],
+        json!([{
+            "range": { "start": { "line": 2, "character": 17 }, "end": { "line": 2, "character": 21 } },
+            "severity": 1,
+            "source": "datamodel",
+            "message": "Error validating directive @foo: Encountered unknown directive",
+        }])
+    );
+}
+
+#[test]
+fn parser_errors_must_be_published() {
+    let (responses, _) = run_session(vec![did_open("model User {\n    id: ID @primary\n")]);
+
+    let diagnostics = diagnostics(&responses);
+    assert_eq!(diagnostics[0].as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0][0]["severity"], 1);
+}
+
+#[test]
+fn syntax_errors_must_not_hide_validation_errors() {
+    let dml = "model User {\n    id: ID @primary\n    name String?!\n}\n\nmodel Post {\n    id: ID @primary\n    title: String @foo\n}\n";
+    let (responses, _) = run_session(vec![did_open(dml)]);
+
+    let diagnostics = diagnostics(&responses);
+    let messages: Vec<&str> = diagnostics[0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["message"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].starts_with("Unexpected token."));
+    assert_eq!(diagnostics[0][0]["range"]["start"]["line"], 2);
+    assert_eq!(
+        messages[1],
+        "Error validating directive @foo: Encountered unknown directive"
+    );
+}
+
+#[test]
+fn changes_must_update_diagnostics_and_close_must_clear_them() {
+    let (responses, _) = run_session(vec![
+        did_open(DATAMODEL),
+        notification(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": "model User {\n    id: ID @primary\n    name: Strin\n}\n" }],
+            }),
+        ),
+        notification("textDocument/didClose", json!({ "textDocument": { "uri": URI } })),
+    ]);
+
+    let diagnostics = diagnostics(&responses);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0], json!([]));
+    assert_eq!(
+        diagnostics[1][0]["message"],
+        "Type Strin is neither a built-in type, nor refers to another model or enum."
+    );
+    assert_eq!(diagnostics[2], json!([]));
+}
+
+#[test]
+fn hover_must_show_the_dmmf_of_a_field() {
+    let (responses, _) = run_session(vec![
+        did_open(DATAMODEL),
+        position_request(1, "textDocument/hover", 2, 6),
+    ]);
+
+    let result = &find_response(&responses, 1)["result"];
+    let markdown = result["contents"]["value"].as_str().unwrap();
+
+    assert_eq!(result["contents"]["kind"], "markdown");
+    assert!(markdown.starts_with("```json\n"));
+    assert!(markdown.contains("\"name\": \"name\""));
+    assert!(markdown.contains("\"type\": \"String\""));
+    assert_eq!(
+        result["range"],
+        json!({ "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 16 } })
+    );
+}
+
+#[test]
+fn hover_outside_of_fields_must_return_null() {
+    let (responses, _) = run_session(vec![
+        did_open(DATAMODEL),
+        position_request(1, "textDocument/hover", 0, 2),
+    ]);
+
+    assert_eq!(find_response(&responses, 1)["result"], Value::Null);
+}
+
+#[test]
+fn definition_must_jump_to_models_and_enums() {
+    let (responses, _) = run_session(vec![
+        did_open(DATAMODEL),
+        position_request(1, "textDocument/definition", 3, 11),
+        position_request(2, "textDocument/definition", 4, 13),
+        position_request(3, "textDocument/definition", 2, 6),
+    ]);
+
+    assert_eq!(
+        find_response(&responses, 1)["result"],
+        json!({
+            "uri": URI,
+            "range": { "start": { "line": 12, "character": 0 }, "end": { "line": 15, "character": 1 } },
+        })
+    );
+    assert_eq!(
+        find_response(&responses, 2)["result"]["range"]["start"],
+        json!({ "line": 7, "character": 0 })
+    );
+    assert_eq!(find_response(&responses, 3)["result"], Value::Null);
+}
+
+#[test]
+fn completion_must_offer_type_names() {
+    let dml = "model User {\n    id: ID @primary\n    role: \n}\n\nenum Role {\n    ADMIN\n}\n";
+    let (responses, _) = run_session(vec![
+        did_open(dml),
+        position_request(1, "textDocument/completion", 2, 10),
+    ]);
+
+    let labels = labels(&find_response(&responses, 1)["result"]);
+    assert!(labels.contains(&String::from("String")));
+    assert!(labels.contains(&String::from("DateTime")));
+    assert!(labels.contains(&String::from("User")));
+    assert!(labels.contains(&String::from("Role")));
+}
+
+#[test]
+fn completion_must_offer_directive_names() {
+    let dml = "model User {\n    id: ID @primary\n    name: String @\n    @@\n}\n";
+    let (responses, _) = run_session(vec![
+        did_open(dml),
+        position_request(1, "textDocument/completion", 2, 18),
+        position_request(2, "textDocument/completion", 3, 6),
+    ]);
+
+    let field_directives = labels(&find_response(&responses, 1)["result"]);
+    assert!(field_directives.contains(&String::from("unique")));
+    assert!(field_directives.contains(&String::from("default")));
+    assert!(!field_directives.contains(&String::from("index")));
+
+    let model_directives = labels(&find_response(&responses, 2)["result"]);
+    assert!(model_directives.contains(&String::from("index")));
+    assert!(model_directives.contains(&String::from("unique")));
+    assert!(!model_directives.contains(&String::from("db")));
+}
+
+#[test]
+fn formatting_must_replace_the_document() {
+    let dml = "model User {\n  id: ID @primary\n  name:   String\n}\n\n";
+    let (responses, _) = run_session(vec![
+        did_open(dml),
+        request(
+            1,
+            "textDocument/formatting",
+            json!({ "textDocument": { "uri": URI }, "options": { "tabSize": 4, "insertSpaces": true } }),
+        ),
+    ]);
+
+    let edits = &find_response(&responses, 1)["result"];
+    let expected = datamodel::renderer::Renderer::new().render(&datamodel::parser::parse(dml).unwrap());
+
+    assert_eq!(
+        edits,
+        &json!([{
+            "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 5, "character": 0 } },
+            "newText": expected,
+        }])
+    );
+}
+
+#[test]
+fn unknown_methods_must_be_rejected() {
+    let (responses, _) = run_session(vec![request(1, "textDocument/rename", json!({}))]);
+
+    assert_eq!(find_response(&responses, 1)["error"]["code"], -32601);
+}
+
+#[test]
+fn exit_code_must_depend_on_shutdown() {
+    let (responses, exit_code) = run_session(vec![
+        request(1, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+
+    assert_eq!(find_response(&responses, 1)["result"], Value::Null);
+    assert_eq!(exit_code, 0);
+
+    let (_, exit_code) = run_session(vec![notification("exit", Value::Null)]);
+    assert_eq!(exit_code, 1);
+}
+
+// Sends all messages to a new server process through stdin and collects everything it writes to stdout.
+fn run_session(messages: Vec<Value>) -> (Vec<Value>, i32) {
+    let mut child = Command::new(server_binary())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Unable to start the language server");
+
+    {
+        let stdin = child.stdin.as_mut().unwrap();
+        for message in messages {
+            protocol::write_message(stdin, &message).unwrap();
+        }
+    }
+
+    // Closing stdin ends the session if the messages did not.
+    drop(child.stdin.take());
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut responses = vec![];
+
+    while let Some(message) = protocol::read_message(&mut stdout).unwrap() {
+        responses.push(message);
+    }
+
+    let status = child.wait().unwrap();
+    (responses, status.code().unwrap())
+}
+
+// Integration tests are built into target/<profile>/deps, next to the directory of the binary.
+fn server_binary() -> PathBuf {
+    let mut path = std::env::current_exe().unwrap();
+    path.pop();
+
+    if path.ends_with("deps") {
+        path.pop();
+    }
+
+    path.join(format!("datamodel-lsp{}", std::env::consts::EXE_SUFFIX))
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn did_open(text: &str) -> Value {
+    notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": URI, "languageId": "prisma", "version": 1, "text": text } }),
+    )
+}
+
+fn position_request(id: u64, method: &str, line: u64, character: u64) -> Value {
+    request(
+        id,
+        method,
+        json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } }),
+    )
+}
+
+fn find_response(messages: &[Value], id: u64) -> &Value {
+    messages
+        .iter()
+        .find(|message| message["id"] == id)
+        .unwrap_or_else(|| panic!("No response for request {}", id))
+}
+
+fn diagnostics(messages: &[Value]) -> Vec<Value> {
+    messages
+        .iter()
+        .filter(|message| message["method"] == "textDocument/publishDiagnostics")
+        .map(|message| message["params"]["diagnostics"].clone())
+        .collect()
+}
+
+fn labels(items: &Value) -> Vec<String> {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect()
+}
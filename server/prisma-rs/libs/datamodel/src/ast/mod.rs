@@ -1,6 +1,7 @@
 pub mod parser;
+pub mod renderer;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -40,6 +41,16 @@ pub struct Comment {
     pub is_error: bool,
 }
 
+// Line comments (`//` and `#`) are not part of the grammar. After parsing, each one is attached to the
+// declaration it belongs to, so the renderer can write it back. The texts include the comment marker.
+#[derive(Debug, Default)]
+pub struct Trivia {
+    // Comments on their own lines in front of the declaration.
+    pub leading: Vec<String>,
+    // A comment at the end of the last line of the declaration.
+    pub trailing: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct DirectiveArgument {
     pub name: String,
@@ -53,12 +64,91 @@ pub enum Value {
     BooleanValue(String, Span),
     StringValue(String, Span),
     ConstantValue(String, Span),
+    Array(Vec<Value>, Span),
+    // A function call, e.g. env("DATABASE_URL").
+    Function(String, Vec<Value>, Span),
+}
+
+impl Value {
+    pub fn span(&self) -> &Span {
+        match self {
+            Value::NumericValue(_, span) => span,
+            Value::BooleanValue(_, span) => span,
+            Value::StringValue(_, span) => span,
+            Value::ConstantValue(_, span) => span,
+            Value::Array(_, span) => span,
+            Value::Function(_, _, span) => span,
+        }
+    }
+}
+
+// Renders the value as it was written in the source.
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::NumericValue(value, _) => write!(f, "{}", value),
+            Value::BooleanValue(value, _) => write!(f, "{}", value),
+            Value::StringValue(value, _) => write!(f, "\"{}\"", value),
+            Value::ConstantValue(value, _) => write!(f, "{}", value),
+            Value::Array(values, _) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "[{}]", values.join(", "))
+            }
+            Value::Function(name, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "{}({})", name, args.join(", "))
+            }
+        }
+    }
+}
+
+// String values keep their escape sequences, these convert between the source and the actual content.
+pub fn unescape_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('0') => result.push('\0'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+pub fn escape_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\0' => result.push_str("\\0"),
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            other => result.push(other),
+        }
+    }
+
+    result
 }
 
 #[derive(Debug)]
 pub struct Directive {
     pub name: String,
     pub arguments: Vec<DirectiveArgument>,
+    pub trivia: Trivia,
     pub span: Span,
 }
 
@@ -79,6 +169,7 @@ pub struct Field {
     pub default_value: Option<Value>,
     pub directives: Vec<Directive>,
     pub comments: Vec<Comment>,
+    pub trivia: Trivia,
     pub span: Span,
 }
 
@@ -94,12 +185,30 @@ impl WithComments for Field {
     }
 }
 
+#[derive(Debug)]
+pub struct EnumValue {
+    pub name: String,
+    pub comments: Vec<Comment>,
+    pub trivia: Trivia,
+    pub span: Span,
+}
+
+impl WithComments for EnumValue {
+    fn comments(&self) -> &Vec<Comment> {
+        &self.comments
+    }
+}
+
 #[derive(Debug)]
 pub struct Enum {
     pub name: String,
-    pub values: Vec<String>,
+    pub values: Vec<EnumValue>,
     pub directives: Vec<Directive>,
     pub comments: Vec<Comment>,
+    pub trivia: Trivia,
+    // Line comments after the last declaration inside the braces.
+    pub end_comments: Vec<String>,
+    pub span: Span,
 }
 
 impl WithDirectives for Enum {
@@ -118,8 +227,15 @@ impl WithComments for Enum {
 pub struct Model {
     pub name: String,
     pub fields: Vec<Field>,
+    // Directives after the closing brace, e.g. @db("user").
     pub directives: Vec<Directive>,
+    // Block directives inside the braces, e.g. @@unique([a, b]).
+    pub block_directives: Vec<Directive>,
     pub comments: Vec<Comment>,
+    pub trivia: Trivia,
+    // Line comments after the last declaration inside the braces.
+    pub end_comments: Vec<String>,
+    pub span: Span,
 }
 
 impl WithDirectives for Model {
@@ -134,14 +250,63 @@ impl WithComments for Model {
     }
 }
 
+// A key value pair in a datasource or generator block, e.g. provider = "postgresql".
+#[derive(Debug)]
+pub struct Argument {
+    pub name: String,
+    pub value: Value,
+    pub trivia: Trivia,
+    pub span: Span,
+}
+
+#[derive(Debug)]
+pub struct SourceConfig {
+    pub name: String,
+    pub properties: Vec<Argument>,
+    pub comments: Vec<Comment>,
+    pub trivia: Trivia,
+    // Line comments after the last declaration inside the braces.
+    pub end_comments: Vec<String>,
+    pub span: Span,
+}
+
+impl WithComments for SourceConfig {
+    fn comments(&self) -> &Vec<Comment> {
+        &self.comments
+    }
+}
+
+#[derive(Debug)]
+pub struct GeneratorConfig {
+    pub name: String,
+    pub properties: Vec<Argument>,
+    pub comments: Vec<Comment>,
+    pub trivia: Trivia,
+    // Line comments after the last declaration inside the braces.
+    pub end_comments: Vec<String>,
+    pub span: Span,
+}
+
+impl WithComments for GeneratorConfig {
+    fn comments(&self) -> &Vec<Comment> {
+        &self.comments
+    }
+}
+
 #[derive(Debug)]
 pub enum ModelOrEnum {
     Enum(Enum),
     Model(Model),
+    Source(SourceConfig),
+    Generator(GeneratorConfig),
 }
 
 #[derive(Debug)]
 pub struct Schema {
     pub models: Vec<ModelOrEnum>,
     pub comments: Vec<Comment>,
+    // Line comments after the last declaration of the file.
+    pub end_comments: Vec<String>,
+    // Names of the models and enums skipped because of syntax errors, see `parse_with_recovery`.
+    pub broken_declarations: Vec<String>,
 }
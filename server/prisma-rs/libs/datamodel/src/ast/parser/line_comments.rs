@@ -0,0 +1,272 @@
+use crate::ast::*;
+
+// A line comment of the input, which is attached to the AST as trivia.
+struct LineComment {
+    start: usize,
+    // The comment including its marker, without trailing whitespace.
+    text: String,
+    // True if there are other tokens in front of the comment on its line.
+    trailing: bool,
+}
+
+// Attaches all line comments of the input to the declarations of the parsed schema:
+// * A comment at the end of the last line of a declaration is its trailing comment.
+// * All other comments lead the following declaration on the same level.
+// * Comments after the last declaration of a block or of the file are kept as end comments.
+pub fn attach(schema: &mut Schema, input: &str) {
+    let mut comments = scan(input);
+
+    for declaration in schema.models.iter_mut() {
+        let span = trimmed(input, *declaration_span(declaration));
+        let (inside, outside): (Vec<LineComment>, Vec<LineComment>) = comments
+            .into_iter()
+            .partition(|comment| span.start <= comment.start && comment.start < span.end);
+        comments = outside;
+
+        match declaration {
+            ModelOrEnum::Model(model) => {
+                let entries = model
+                    .fields
+                    .iter_mut()
+                    .map(|field| (field.span, &mut field.trivia))
+                    .chain(
+                        model
+                            .block_directives
+                            .iter_mut()
+                            .map(|directive| (directive.span, &mut directive.trivia)),
+                    )
+                    .chain(
+                        model
+                            .directives
+                            .iter_mut()
+                            .map(|directive| (directive.span, &mut directive.trivia)),
+                    )
+                    .collect();
+                attach_to_block(input, inside, span, entries, &mut model.trivia, &mut model.end_comments);
+            }
+            ModelOrEnum::Enum(en) => {
+                let entries = en
+                    .values
+                    .iter_mut()
+                    .map(|value| (value.span, &mut value.trivia))
+                    .chain(
+                        en.directives
+                            .iter_mut()
+                            .map(|directive| (directive.span, &mut directive.trivia)),
+                    )
+                    .collect();
+                attach_to_block(input, inside, span, entries, &mut en.trivia, &mut en.end_comments);
+            }
+            ModelOrEnum::Source(source) => {
+                let entries = source
+                    .properties
+                    .iter_mut()
+                    .map(|property| (property.span, &mut property.trivia))
+                    .collect();
+                attach_to_block(
+                    input,
+                    inside,
+                    span,
+                    entries,
+                    &mut source.trivia,
+                    &mut source.end_comments,
+                );
+            }
+            ModelOrEnum::Generator(generator) => {
+                let entries = generator
+                    .properties
+                    .iter_mut()
+                    .map(|property| (property.span, &mut property.trivia))
+                    .collect();
+                attach_to_block(
+                    input,
+                    inside,
+                    span,
+                    entries,
+                    &mut generator.trivia,
+                    &mut generator.end_comments,
+                );
+            }
+        }
+    }
+
+    let mut declarations: Vec<(Span, &mut Trivia)> = schema
+        .models
+        .iter_mut()
+        .map(|declaration| match declaration {
+            ModelOrEnum::Model(model) => (model.span, &mut model.trivia),
+            ModelOrEnum::Enum(en) => (en.span, &mut en.trivia),
+            ModelOrEnum::Source(source) => (source.span, &mut source.trivia),
+            ModelOrEnum::Generator(generator) => (generator.span, &mut generator.trivia),
+        })
+        .collect();
+
+    let end_comments = attach_to_entries(input, comments, &mut declarations);
+    schema.end_comments.extend(end_comments);
+}
+
+fn declaration_span(declaration: &ModelOrEnum) -> &Span {
+    match declaration {
+        ModelOrEnum::Model(model) => &model.span,
+        ModelOrEnum::Enum(en) => &en.span,
+        ModelOrEnum::Source(source) => &source.span,
+        ModelOrEnum::Generator(generator) => &generator.span,
+    }
+}
+
+// Comments in front of the opening brace lead the block itself. Comments inside the braces belong to the
+// declarations in the block, comments after the closing brace to the directives of the block.
+fn attach_to_block(
+    input: &str,
+    comments: Vec<LineComment>,
+    span: Span,
+    mut entries: Vec<(Span, &mut Trivia)>,
+    trivia: &mut Trivia,
+    end_comments: &mut Vec<String>,
+) {
+    let open = find_token(input, span.start, '{').unwrap_or(span.end);
+    let close = find_token(input, open, '}').unwrap_or(span.end);
+
+    entries.sort_by_key(|(span, _)| span.start);
+    let (mut inner, mut outer): (Vec<(Span, &mut Trivia)>, Vec<(Span, &mut Trivia)>) =
+        entries.into_iter().partition(|(span, _)| span.start < close);
+    let (mut inner_comments, mut outer_comments) = (vec![], vec![]);
+
+    for comment in comments {
+        if comment.start < open {
+            trivia.leading.push(comment.text);
+        } else if comment.start < close {
+            inner_comments.push(comment);
+        } else {
+            outer_comments.push(comment);
+        }
+    }
+
+    end_comments.extend(attach_to_entries(input, inner_comments, &mut inner));
+    // There is nothing after the last directive of a block, so no comments are left over.
+    end_comments.extend(attach_to_entries(input, outer_comments, &mut outer));
+}
+
+// Attaches comments to a list of entries sorted by position. Returns the comments after the last entry.
+fn attach_to_entries(input: &str, comments: Vec<LineComment>, entries: &mut [(Span, &mut Trivia)]) -> Vec<String> {
+    let mut rest = vec![];
+
+    for entry in entries.iter_mut() {
+        entry.0 = trimmed(input, entry.0);
+    }
+
+    for comment in comments {
+        let previous = entries.iter().rposition(|(span, _)| span.start <= comment.start);
+
+        match previous {
+            // A comment within an entry, e.g. between its doc comments and its name.
+            Some(i) if comment.start < entries[i].0.end => entries[i].1.leading.push(comment.text),
+            Some(i) if comment.trailing && !input[entries[i].0.end..comment.start].contains('\n') => {
+                entries[i].1.trailing = Some(comment.text)
+            }
+            _ => match entries.get_mut(previous.map_or(0, |i| i + 1)) {
+                Some((_, trivia)) => trivia.leading.push(comment.text),
+                None => rest.push(comment.text),
+            },
+        }
+    }
+
+    rest
+}
+
+// All line comments of the input. Comment markers in strings and doc comments (`///`) are skipped.
+fn scan(input: &str) -> Vec<LineComment> {
+    let mut comments = vec![];
+    let mut line_start = 0;
+    let mut position = 0;
+
+    while let Some(c) = input[position..].chars().next() {
+        match c {
+            '\n' => line_start = position + 1,
+            '"' => {
+                position = string_end(input, position);
+                continue;
+            }
+            '/' | '#' if c == '#' || input[position..].starts_with("//") => {
+                let end = line_end(input, position);
+
+                if !input[position..].starts_with("///") {
+                    comments.push(LineComment {
+                        start: position,
+                        text: String::from(input[position..end].trim_end()),
+                        trailing: !input[line_start..position].trim().is_empty(),
+                    });
+                }
+
+                position = end;
+                continue;
+            }
+            _ => {}
+        }
+
+        position += c.len_utf8();
+    }
+
+    comments
+}
+
+// Spans of declarations can include the whitespace and comments after their last token, this removes them.
+fn trimmed(input: &str, span: Span) -> Span {
+    let mut position = span.start;
+    let mut end = span.start;
+
+    while let Some(c) = input[position..span.end].chars().next() {
+        match c {
+            '"' => {
+                position = string_end(input, position);
+                end = position;
+            }
+            '/' | '#' if c == '#' || input[position..].starts_with("//") => position = line_end(input, position),
+            c if c.is_whitespace() => position += c.len_utf8(),
+            _ => {
+                position += c.len_utf8();
+                end = position;
+            }
+        }
+    }
+
+    Span::new(span.start, end)
+}
+
+// The position of the first occurrence of the token from `start` on, outside of strings and comments.
+fn find_token(input: &str, start: usize, token: char) -> Option<usize> {
+    let mut position = start;
+
+    while let Some(c) = input[position..].chars().next() {
+        match c {
+            c if c == token => return Some(position),
+            '"' => position = string_end(input, position),
+            '/' | '#' if c == '#' || input[position..].starts_with("//") => position = line_end(input, position),
+            _ => position += c.len_utf8(),
+        }
+    }
+
+    None
+}
+
+// The position after the closing quote of the string starting at `start`. Strings cannot span lines.
+fn string_end(input: &str, start: usize) -> usize {
+    let mut chars = input[start + 1..].char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return start + 1 + i + 1,
+            '\n' => return start + 1 + i,
+            _ => {}
+        }
+    }
+
+    input.len()
+}
+
+fn line_end(input: &str, start: usize) -> usize {
+    input[start..].find('\n').map_or(input.len(), |i| start + i)
+}
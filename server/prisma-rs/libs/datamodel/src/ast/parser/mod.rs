@@ -12,10 +12,14 @@ pub struct PrismaDatamodelParser;
 
 use crate::ast::*;
 
+mod line_comments;
+
 #[derive(Debug)]
 pub struct ParserError {
     pub message: String,
     pub span: Span,
+    // Human readable names of the tokens which were expected at the error, e.g. `field type`.
+    pub expected: Vec<String>,
 }
 
 impl ParserError {
@@ -23,6 +27,7 @@ impl ParserError {
         ParserError {
             message: String::from(message),
             span: span.clone(),
+            expected: vec![],
         }
     }
 }
@@ -87,13 +92,43 @@ fn parse_literal(token: &pest::iterators::Pair<'_, Rule>) -> Value {
         Rule::string_literal => Value::StringValue(parse_string_literal(&current), Span::from_pest(&current.as_span())),
         Rule::boolean_literal => Value::BooleanValue(current.as_str().to_string(), Span::from_pest(&current.as_span())),
         Rule::constant_Literal => Value::ConstantValue(current.as_str().to_string(), Span::from_pest(&current.as_span())),
+        Rule::array_literal => Value::Array(parse_array_literal(&current), Span::from_pest(&current.as_span())),
         _ => unreachable!("Encounterd impossible literal during parsing: {:?}", current.tokens())
     };
 }
 
+fn parse_array_literal(token: &pest::iterators::Pair<'_, Rule>) -> Vec<Value> {
+    let mut values: Vec<Value> = vec![];
+
+    match_children! { token, current,
+        Rule::any_literal => values.push(parse_literal(&current)),
+        _ => unreachable!("Encounterd impossible array element during parsing: {:?}", current.tokens())
+    }
+
+    values
+}
+
+// Doc comment parsing
+fn parse_doc_comment(token: &pest::iterators::Pair<'_, Rule>) -> Comment {
+    return match_first! { token, current,
+        Rule::doc_content => {
+            let text = current.as_str();
+            // Strip the single space which conventionally follows the slashes.
+            let text = if text.starts_with(' ') { &text[1..] } else { text };
+
+            Comment {
+                text: text.trim_end().to_string(),
+                is_error: false,
+            }
+        },
+        _ => unreachable!("Encountered impossible doc comment during parsing: {:?}", current.tokens())
+    };
+}
+
 // Directive parsing
 fn parse_directive_arg_value(token: &pest::iterators::Pair<'_, Rule>) -> Value {
     return match_first! { token, current,
+        Rule::function => parse_function(&current),
         Rule::any_literal => parse_literal(&current),
         _ => unreachable!("Encounterd impossible value during parsing: {:?}", current.tokens())
     };
@@ -121,8 +156,8 @@ fn parse_directive_arg(token: &pest::iterators::Pair<'_, Rule>) -> DirectiveArgu
     };
 
     return match (name, argument) {
-        (Some(name), Some(value)) => DirectiveArgument {
-            name: name,
+        (name, Some(value)) => DirectiveArgument {
+            name: name.unwrap_or_else(|| String::from("")),
             value: value,
             span: Span::from_pest(&token.as_span()),
         },
@@ -146,6 +181,7 @@ fn parse_directive(token: &pest::iterators::Pair<'_, Rule>) -> Directive {
 
     match_children! { token, current,
         Rule::identifier => name = Some(current.as_str().to_string()),
+        Rule::directive_name => name = Some(current.as_str().to_string()),
         Rule::directive_arguments => parse_directive_args(&current, &mut arguments),
         Rule::directive_single_argument => parse_directive_default_arg(&current, &mut arguments),
         _ => unreachable!("Encounterd impossible directive during parsing: {:?}", current.tokens())
@@ -155,6 +191,7 @@ fn parse_directive(token: &pest::iterators::Pair<'_, Rule>) -> Directive {
         Some(name) => Directive {
             name,
             arguments,
+            trivia: Trivia::default(),
             span: Span::from_pest(&token.as_span()),
         },
         _ => panic!("Encounterd impossible type during parsing: {:?}", token.as_str()),
@@ -181,6 +218,7 @@ fn parse_field_type(token: &pest::iterators::Pair<'_, Rule>) -> (FieldArity, Str
 // Field parsing
 fn parse_default_value(token: &pest::iterators::Pair<'_, Rule>) -> Value {
     return match_first! { token, current,
+        Rule::function => parse_function(&current),
         Rule::any_literal => parse_literal(&current),
         _ => unreachable!("Encounterd impossible value during parsing: {:?}", current.tokens())
     };
@@ -192,8 +230,10 @@ fn parse_field(token: &pest::iterators::Pair<'_, Rule>) -> Field {
     let mut default_value: Option<Value> = None;
     let mut field_type: Option<(FieldArity, String)> = None;
     let mut field_link: Option<String> = None;
+    let mut comments: Vec<Comment> = vec![];
 
     match_children! { token, current,
+        Rule::doc_comment => comments.push(parse_doc_comment(&current)),
         Rule::identifier => name = Some(current.as_str().to_string()),
         Rule::field_type => field_type = Some(parse_field_type(&current)),
         Rule::field_link => field_link = Some(current.as_str().to_string()),
@@ -210,7 +250,8 @@ fn parse_field(token: &pest::iterators::Pair<'_, Rule>) -> Field {
             arity,
             default_value,
             directives,
-            comments: vec![],
+            comments,
+            trivia: Trivia::default(),
             span: Span::from_pest(&token.as_span())
         },
         _ => panic!(
@@ -224,12 +265,17 @@ fn parse_field(token: &pest::iterators::Pair<'_, Rule>) -> Field {
 fn parse_model(token: &pest::iterators::Pair<'_, Rule>) -> Model {
     let mut name: Option<String> = None;
     let mut directives: Vec<Directive> = vec![];
+    let mut block_directives: Vec<Directive> = vec![];
     let mut fields: Vec<Field> = vec![];
+    let mut comments: Vec<Comment> = vec![];
 
     match_children! { token, current,
+        Rule::doc_comment => comments.push(parse_doc_comment(&current)),
         Rule::identifier => name = Some(current.as_str().to_string()),
         Rule::directive => directives.push(parse_directive(&current)),
+        Rule::block_directive => block_directives.push(parse_directive(&current)),
         Rule::field_declaration => fields.push(parse_field(&current)),
+        Rule::dangling_doc_comment => {},
         _ => unreachable!("Encounterd impossible model declaration during parsing: {:?}", current.tokens())
     }
 
@@ -238,7 +284,11 @@ fn parse_model(token: &pest::iterators::Pair<'_, Rule>) -> Model {
             name,
             fields,
             directives,
-            comments: vec![],
+            block_directives,
+            comments,
+            trivia: Trivia::default(),
+            end_comments: vec![],
+            span: Span::from_pest(&token.as_span()),
         },
         _ => panic!(
             "Encounterd impossible model declaration during parsing: {:?}",
@@ -248,15 +298,42 @@ fn parse_model(token: &pest::iterators::Pair<'_, Rule>) -> Model {
 }
 
 // Enum parsing
+fn parse_enum_value(token: &pest::iterators::Pair<'_, Rule>) -> EnumValue {
+    let mut name: Option<String> = None;
+    let mut comments: Vec<Comment> = vec![];
+
+    match_children! { token, current,
+        Rule::doc_comment => comments.push(parse_doc_comment(&current)),
+        Rule::enum_value => name = Some(current.as_str().to_string()),
+        _ => unreachable!("Encounterd impossible enum value declaration during parsing: {:?}", current.tokens())
+    }
+
+    return match name {
+        Some(name) => EnumValue {
+            name,
+            comments,
+            trivia: Trivia::default(),
+            span: Span::from_pest(&token.as_span()),
+        },
+        _ => panic!(
+            "Encounterd impossible enum value declaration during parsing: {:?}",
+            token.as_str()
+        ),
+    };
+}
+
 fn parse_enum(token: &pest::iterators::Pair<'_, Rule>) -> Enum {
     let mut name: Option<String> = None;
     let mut directives: Vec<Directive> = vec![];
-    let mut values: Vec<String> = vec![];
+    let mut values: Vec<EnumValue> = vec![];
+    let mut comments: Vec<Comment> = vec![];
 
     match_children! { token, current,
+        Rule::doc_comment => comments.push(parse_doc_comment(&current)),
         Rule::identifier => name = Some(current.as_str().to_string()),
         Rule::directive => directives.push(parse_directive(&current)),
-        Rule::enum_field_declaration => values.push(current.as_str().to_string()),
+        Rule::enum_field_declaration => values.push(parse_enum_value(&current)),
+        Rule::dangling_doc_comment => {},
         _ => unreachable!("Encounterd impossible enum declaration during parsing: {:?}", current.tokens())
     }
 
@@ -265,7 +342,10 @@ fn parse_enum(token: &pest::iterators::Pair<'_, Rule>) -> Enum {
             name,
             values,
             directives,
-            comments: vec![],
+            comments,
+            trivia: Trivia::default(),
+            end_comments: vec![],
+            span: Span::from_pest(&token.as_span()),
         },
         _ => panic!(
             "Encounterd impossible enum declaration during parsing: {:?}",
@@ -274,32 +354,302 @@ fn parse_enum(token: &pest::iterators::Pair<'_, Rule>) -> Enum {
     };
 }
 
+// Source and generator parsing
+fn parse_function(token: &pest::iterators::Pair<'_, Rule>) -> Value {
+    let mut name: Option<String> = None;
+    let mut arguments: Vec<Value> = vec![];
+
+    match_children! { token, current,
+        Rule::identifier => name = Some(current.as_str().to_string()),
+        Rule::any_literal => arguments.push(parse_literal(&current)),
+        _ => unreachable!("Encounterd impossible function during parsing: {:?}", current.tokens())
+    }
+
+    return match name {
+        Some(name) => Value::Function(name, arguments, Span::from_pest(&token.as_span())),
+        _ => panic!("Encounterd impossible function during parsing: {:?}", token.as_str()),
+    };
+}
+
+fn parse_config_value(token: &pest::iterators::Pair<'_, Rule>) -> Value {
+    return match_first! { token, current,
+        Rule::function => parse_function(&current),
+        Rule::any_literal => parse_literal(&current),
+        _ => unreachable!("Encounterd impossible config value during parsing: {:?}", current.tokens())
+    };
+}
+
+fn parse_key_value(token: &pest::iterators::Pair<'_, Rule>) -> Argument {
+    let mut name: Option<String> = None;
+    let mut value: Option<Value> = None;
+
+    match_children! { token, current,
+        Rule::identifier => name = Some(current.as_str().to_string()),
+        Rule::config_value => value = Some(parse_config_value(&current)),
+        _ => unreachable!("Encounterd impossible key value during parsing: {:?}", current.tokens())
+    }
+
+    return match (name, value) {
+        (Some(name), Some(value)) => Argument {
+            name,
+            value,
+            trivia: Trivia::default(),
+            span: Span::from_pest(&token.as_span()),
+        },
+        _ => panic!("Encounterd impossible key value during parsing: {:?}", token.as_str()),
+    };
+}
+
+// Sources and generators share the same structure, they only differ in their keyword.
+fn parse_config_block(token: &pest::iterators::Pair<'_, Rule>) -> (String, Vec<Argument>, Vec<Comment>) {
+    let mut name: Option<String> = None;
+    let mut properties: Vec<Argument> = vec![];
+    let mut comments: Vec<Comment> = vec![];
+
+    match_children! { token, current,
+        Rule::doc_comment => comments.push(parse_doc_comment(&current)),
+        Rule::identifier => name = Some(current.as_str().to_string()),
+        Rule::key_value => properties.push(parse_key_value(&current)),
+        Rule::dangling_doc_comment => {},
+        _ => unreachable!("Encounterd impossible config block during parsing: {:?}", current.tokens())
+    }
+
+    return match name {
+        Some(name) => (name, properties, comments),
+        _ => panic!(
+            "Encounterd impossible config block during parsing: {:?}",
+            token.as_str()
+        ),
+    };
+}
+
+fn parse_source(token: &pest::iterators::Pair<'_, Rule>) -> SourceConfig {
+    let (name, properties, comments) = parse_config_block(token);
+
+    SourceConfig {
+        name,
+        properties,
+        comments,
+        trivia: Trivia::default(),
+        end_comments: vec![],
+        span: Span::from_pest(&token.as_span()),
+    }
+}
+
+fn parse_generator(token: &pest::iterators::Pair<'_, Rule>) -> GeneratorConfig {
+    let (name, properties, comments) = parse_config_block(token);
+
+    GeneratorConfig {
+        name,
+        properties,
+        comments,
+        trivia: Trivia::default(),
+        end_comments: vec![],
+        span: Span::from_pest(&token.as_span()),
+    }
+}
+
 // Whole datamodel parsing
+fn parse_schema(token: &pest::iterators::Pair<'_, Rule>) -> Schema {
+    let mut models: Vec<ModelOrEnum> = vec![];
+
+    match_children! { token, current,
+        Rule::model_declaration => models.push(ModelOrEnum::Model(parse_model(&current))),
+        Rule::enum_declaration => models.push(ModelOrEnum::Enum(parse_enum(&current))),
+        Rule::source_block => models.push(ModelOrEnum::Source(parse_source(&current))),
+        Rule::generator_block => models.push(ModelOrEnum::Generator(parse_generator(&current))),
+        Rule::dangling_doc_comment => {},
+        Rule::EOI => {},
+        _ => panic!("Encounterd impossible datamodel declaration during parsing: {:?}", current.tokens())
+    }
+
+    Schema {
+        models,
+        comments: vec![],
+        end_comments: vec![],
+        broken_declarations: vec![],
+    }
+}
+
+// Parses the schema and attaches the line comments of the input to its declarations.
+fn parse_schema_with_comments(token: &pest::iterators::Pair<'_, Rule>, input: &str) -> Schema {
+    let mut schema = parse_schema(token);
+    line_comments::attach(&mut schema, input);
+    schema
+}
+
 pub fn parse(datamodel_string: &str) -> Result<Schema, ParserError> {
-    let mut datamodel_result = PrismaDatamodelParser::parse(Rule::datamodel, datamodel_string);
-
-    match datamodel_result {
-        Ok(mut datamodel_wrapped) => {
-            let datamodel = datamodel_wrapped.next().unwrap();
-            let mut models: Vec<ModelOrEnum> = vec![];
-
-            match_children! { datamodel, current,
-                Rule::model_declaration => models.push(ModelOrEnum::Model(parse_model(&current))),
-                Rule::enum_declaration => models.push(ModelOrEnum::Enum(parse_enum(&current))),
-                Rule::EOI => {},
-                _ => panic!("Encounterd impossible datamodel declaration during parsing: {:?}", current.tokens())
+    match PrismaDatamodelParser::parse(Rule::datamodel, datamodel_string) {
+        Ok(mut datamodel_wrapped) => Ok(parse_schema_with_comments(
+            &datamodel_wrapped.next().unwrap(),
+            datamodel_string,
+        )),
+        Err(err) => Err(parser_error(&err)),
+    }
+}
+
+// Parses as much of the datamodel as possible. After a syntax error, parsing continues with the next
+// model, enum, datasource or generator, so the schema contains all valid declarations.
+pub fn parse_with_recovery(datamodel_string: &str) -> (Schema, Vec<ParserError>) {
+    let mut errors: Vec<ParserError> = vec![];
+    let mut broken_declarations: Vec<String> = vec![];
+    // Broken declarations are blanked out, which keeps all offsets of the original input intact.
+    let mut input = String::from(datamodel_string);
+
+    loop {
+        let err = match PrismaDatamodelParser::parse(Rule::datamodel, &input) {
+            Ok(mut datamodel_wrapped) => {
+                let mut schema = parse_schema_with_comments(&datamodel_wrapped.next().unwrap(), &input);
+                schema.broken_declarations = broken_declarations;
+                return (schema, errors);
             }
+            Err(err) => err,
+        };
+
+        // The valid declarations in front of the error. This rule cannot fail.
+        let valid = PrismaDatamodelParser::parse(Rule::partial_datamodel, &input)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        match broken_declaration(&input, valid.as_span().end()) {
+            Some((start, end)) => {
+                errors.push(parser_error(&err));
+                broken_declarations.extend(declaration_name(&input[start..end]));
+                input = blank_out(&input, start, end);
+            }
+            // Nothing is left after blanking out all broken declarations.
+            None => {
+                let mut schema = parse_schema_with_comments(&valid, &input[..valid.as_span().end()]);
+                schema.broken_declarations = broken_declarations;
+
+                match errors.is_empty() {
+                    true => return (schema, vec![parser_error(&err)]),
+                    false => return (schema, errors),
+                }
+            }
+        }
+    }
+}
+
+// The region from the end of the last valid declaration to the start of the next declaration
+// after the broken one, or None if there is nothing but whitespace left.
+fn broken_declaration(input: &str, valid_until: usize) -> Option<(usize, usize)> {
+    let broken_start = valid_until + input[valid_until..].find(|c: char| !c.is_whitespace())?;
+    let mut line_start = match input[broken_start..].find('\n') {
+        Some(pos) => broken_start + pos + 1,
+        None => return Some((valid_until, input.len())),
+    };
+    let mut doc_comments_start: Option<usize> = None;
 
-            Ok(Schema {
-                models,
-                comments: vec![],
-            })
+    while line_start < input.len() {
+        let line = input[line_start..].lines().next().unwrap_or("");
+
+        if is_declaration_start(line) {
+            // Doc comments belong to the following declaration.
+            return Some((valid_until, doc_comments_start.unwrap_or(line_start)));
         }
-        Err(err) => match err.location {
-            pest::error::InputLocation::Pos(pos) => Err(ParserError::new("Error during parsing", &Span::new(pos, pos))),
-            pest::error::InputLocation::Span((from, to)) => {
-                Err(ParserError::new("Error during parsing", &Span::new(from, to)))
+
+        doc_comments_start = match line.trim_start().starts_with("///") {
+            true => doc_comments_start.or(Some(line_start)),
+            false => None,
+        };
+        line_start += line.len() + 1;
+    }
+
+    Some((valid_until, input.len()))
+}
+
+fn is_declaration_start(line: &str) -> bool {
+    let mut tokens = line.split_whitespace();
+    let keyword = tokens.next().unwrap_or("");
+
+    ["model", "enum", "datasource", "generator"].contains(&keyword)
+        && tokens.next().is_some()
+        && line.trim_end().ends_with('{')
+}
+
+// The name of the first model or enum declared in the given region, if its header is readable.
+fn declaration_name(region: &str) -> Option<String> {
+    region.lines().find_map(|line| {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("model") | Some("enum") => tokens
+                .next()
+                .map(|name| name.trim_end_matches('{'))
+                .filter(|name| !name.is_empty())
+                .map(String::from),
+            _ => None,
+        }
+    })
+}
+
+fn blank_out(input: &str, start: usize, end: usize) -> String {
+    let mut result = String::with_capacity(input.len());
+    result.push_str(&input[..start]);
+    result.extend(input[start..end].bytes().map(|b| if b == b'\n' { '\n' } else { ' ' }));
+    result.push_str(&input[end..]);
+    result
+}
+
+fn parser_error(err: &pest::error::Error<Rule>) -> ParserError {
+    let span = match err.location {
+        pest::error::InputLocation::Pos(pos) => Span::new(pos, pos),
+        pest::error::InputLocation::Span((from, to)) => Span::new(from, to),
+    };
+
+    let mut expected: Vec<String> = vec![];
+
+    if let pest::error::ErrorVariant::ParsingError { positives, .. } = &err.variant {
+        for rule in positives {
+            let name = String::from(rule_name(rule));
+            if !expected.contains(&name) {
+                expected.push(name);
             }
-        },
+        }
+    }
+
+    let message = match expected.len() {
+        0 => String::from("Unexpected token."),
+        _ => format!("Unexpected token. Expected one of: {}.", expected.join(", ")),
+    };
+
+    ParserError {
+        message,
+        span,
+        expected,
+    }
+}
+
+// Human readable names for the rules reported as expected after a syntax error.
+fn rule_name(rule: &Rule) -> &'static str {
+    match rule {
+        Rule::EOI => "end of input",
+        Rule::identifier => "identifier",
+        Rule::numeric_literal => "number",
+        Rule::string_literal | Rule::string_content => "string",
+        Rule::boolean_literal => "boolean",
+        Rule::constant_Literal => "constant",
+        Rule::array_literal => "array",
+        Rule::any_literal | Rule::directive_argument_value | Rule::config_value => "value",
+        Rule::function => "function call",
+        Rule::doc_comment | Rule::dangling_doc_comment => "doc comment",
+        Rule::directive => "directive",
+        Rule::directive_name => "directive name",
+        Rule::directive_argument | Rule::directive_argument_name => "directive argument",
+        Rule::directive_arguments | Rule::directive_single_argument => "directive arguments",
+        Rule::block_directive => "block directive",
+        Rule::field_declaration => "field declaration",
+        Rule::field_type | Rule::base_type | Rule::optional_type | Rule::list_type => "field type",
+        Rule::field_link => "field link",
+        Rule::default_value => "default value",
+        Rule::model_declaration => "model declaration",
+        Rule::enum_declaration => "enum declaration",
+        Rule::enum_field_declaration | Rule::enum_value => "enum value",
+        Rule::key_value => "key value pair",
+        Rule::source_block => "datasource block",
+        Rule::generator_block => "generator block",
+        _ => "token",
     }
 }
@@ -0,0 +1,328 @@
+use crate::ast::*;
+
+// Renders an AST back to source text in canonical form:
+// * Four spaces of indentation.
+// * Field names, types and default values are aligned in columns.
+// * Directives are ordered by a fixed precedence, unknown directives keep their source order at the end.
+// * Block directives, like @@unique([a, b]), are rendered inside the model in source order, as the order of
+//   indexes is kept in the dml.
+// * Doc comments are kept. Line comments are kept as trivia of the declaration they belong to: Comments on their
+//   own lines are rendered in front of it, a trailing comment at the end of its last line.
+
+const INDENT: &str = "    ";
+
+// Canonical order of directives. Directives not in this list are rendered after all known ones.
+const DIRECTIVE_ORDER: &[&str] = &[
+    "primary",
+    "unique",
+    "default",
+    "relation",
+    "onDelete",
+    "db",
+    "scalarList",
+    "sequence",
+    "embedded",
+];
+
+pub struct Renderer {}
+
+impl Renderer {
+    pub fn new() -> Renderer {
+        Renderer {}
+    }
+
+    pub fn render(&self, schema: &Schema) -> String {
+        let blocks: Vec<String> = schema
+            .models
+            .iter()
+            .map(|obj| match obj {
+                ModelOrEnum::Model(model) => self.render_model(model),
+                ModelOrEnum::Enum(en) => self.render_enum(en),
+                ModelOrEnum::Source(source) => self.render_config_block(
+                    "datasource",
+                    &source.name,
+                    &source.properties,
+                    &source.comments,
+                    &source.trivia,
+                    &source.end_comments,
+                ),
+                ModelOrEnum::Generator(generator) => self.render_config_block(
+                    "generator",
+                    &generator.name,
+                    &generator.properties,
+                    &generator.comments,
+                    &generator.trivia,
+                    &generator.end_comments,
+                ),
+            })
+            .collect();
+
+        let mut result = blocks.join("\n");
+
+        if !schema.end_comments.is_empty() {
+            if !result.is_empty() {
+                result.push('\n');
+            }
+
+            self.render_line_comments(&mut result, &schema.end_comments, "");
+        }
+
+        if result.is_empty() {
+            result.push('\n');
+        }
+
+        result
+    }
+
+    fn render_model(&self, model: &Model) -> String {
+        let mut result = String::new();
+
+        self.render_line_comments(&mut result, &model.trivia.leading, "");
+        self.render_comments(&mut result, &model.comments, "");
+        result.push_str(&format!("model {} {{\n", model.name));
+
+        let rows: Vec<Vec<String>> = model
+            .fields
+            .iter()
+            .map(|field| {
+                vec![
+                    format!("{}:", field.name),
+                    self.render_field_type(field),
+                    match &field.default_value {
+                        Some(value) => format!("= {}", self.render_value(value)),
+                        None => String::new(),
+                    },
+                    self.render_directives(&field.directives, " "),
+                ]
+            })
+            .collect();
+
+        let widths = Self::column_widths(&rows);
+
+        for (field, row) in model.fields.iter().zip(rows.iter()) {
+            self.render_line_comments(&mut result, &field.trivia.leading, INDENT);
+            self.render_comments(&mut result, &field.comments, INDENT);
+            result.push_str(INDENT);
+            result.push_str(&Self::render_row(row, &widths));
+            result.push('\n');
+            self.render_trailing_comment(&mut result, &field.trivia);
+        }
+
+        if !model.block_directives.is_empty() {
+            result.push('\n');
+
+            for directive in &model.block_directives {
+                self.render_line_comments(&mut result, &directive.trivia.leading, INDENT);
+                result.push_str(&format!("{}@{}\n", INDENT, self.render_directive(directive)));
+                self.render_trailing_comment(&mut result, &directive.trivia);
+            }
+        }
+
+        self.render_line_comments(&mut result, &model.end_comments, INDENT);
+        result.push_str("}\n");
+        self.render_trailing_directives(&mut result, model.directives.iter().collect());
+        self.render_trailing_comment(&mut result, &model.trivia);
+
+        result
+    }
+
+    fn render_enum(&self, en: &Enum) -> String {
+        let mut result = String::new();
+
+        self.render_line_comments(&mut result, &en.trivia.leading, "");
+        self.render_comments(&mut result, &en.comments, "");
+        result.push_str(&format!("enum {} {{\n", en.name));
+
+        for value in &en.values {
+            self.render_line_comments(&mut result, &value.trivia.leading, INDENT);
+            self.render_comments(&mut result, &value.comments, INDENT);
+            result.push_str(&format!("{}{}\n", INDENT, value.name));
+            self.render_trailing_comment(&mut result, &value.trivia);
+        }
+
+        self.render_line_comments(&mut result, &en.end_comments, INDENT);
+        result.push_str("}\n");
+        self.render_trailing_directives(&mut result, en.directives.iter().collect());
+        self.render_trailing_comment(&mut result, &en.trivia);
+
+        result
+    }
+
+    // Datasources and generators, with the values of all key value pairs aligned.
+    fn render_config_block(
+        &self,
+        keyword: &str,
+        name: &str,
+        properties: &Vec<Argument>,
+        comments: &Vec<Comment>,
+        trivia: &Trivia,
+        end_comments: &Vec<String>,
+    ) -> String {
+        let mut result = String::new();
+
+        self.render_line_comments(&mut result, &trivia.leading, "");
+        self.render_comments(&mut result, comments, "");
+        result.push_str(&format!("{} {} {{\n", keyword, name));
+
+        let rows: Vec<Vec<String>> = properties
+            .iter()
+            .map(|property| {
+                vec![
+                    property.name.clone(),
+                    format!("= {}", self.render_value(&property.value)),
+                ]
+            })
+            .collect();
+
+        let widths = Self::column_widths(&rows);
+
+        for (property, row) in properties.iter().zip(rows.iter()) {
+            self.render_line_comments(&mut result, &property.trivia.leading, INDENT);
+            result.push_str(INDENT);
+            result.push_str(&Self::render_row(row, &widths));
+            result.push('\n');
+            self.render_trailing_comment(&mut result, &property.trivia);
+        }
+
+        self.render_line_comments(&mut result, end_comments, INDENT);
+        result.push_str("}\n");
+        self.render_trailing_comment(&mut result, trivia);
+
+        result
+    }
+
+    fn render_trailing_directives(&self, target: &mut String, directives: Vec<&Directive>) {
+        for directive in Self::sort_directives(directives) {
+            self.render_line_comments(target, &directive.trivia.leading, "");
+            target.push_str(&self.render_directive(directive));
+            target.push('\n');
+            self.render_trailing_comment(target, &directive.trivia);
+        }
+    }
+
+    fn render_line_comments(&self, target: &mut String, comments: &Vec<String>, indent: &str) {
+        for comment in comments {
+            target.push_str(&format!("{}{}\n", indent, comment));
+        }
+    }
+
+    // Appends the trailing comment to the last rendered line.
+    fn render_trailing_comment(&self, target: &mut String, trivia: &Trivia) {
+        if let Some(comment) = &trivia.trailing {
+            target.pop();
+            target.push_str(&format!(" {}\n", comment));
+        }
+    }
+
+    fn render_comments(&self, target: &mut String, comments: &Vec<Comment>, indent: &str) {
+        for comment in comments {
+            if comment.text.is_empty() {
+                target.push_str(&format!("{}///\n", indent));
+            } else {
+                target.push_str(&format!("{}/// {}\n", indent, comment.text));
+            }
+        }
+    }
+
+    fn render_field_type(&self, field: &Field) -> String {
+        let mut result = match field.arity {
+            FieldArity::Required => field.field_type.clone(),
+            FieldArity::Optional => format!("{}?", field.field_type),
+            FieldArity::List => format!("{}[]", field.field_type),
+        };
+
+        if let Some(link) = &field.field_link {
+            result.push_str(&format!("({})", link));
+        }
+
+        result
+    }
+
+    fn render_directives<'a>(&self, directives: impl IntoIterator<Item = &'a Directive>, separator: &str) -> String {
+        Self::sort_directives(directives)
+            .iter()
+            .map(|directive| self.render_directive(directive))
+            .collect::<Vec<String>>()
+            .join(separator)
+    }
+
+    fn sort_directives<'a>(directives: impl IntoIterator<Item = &'a Directive>) -> Vec<&'a Directive> {
+        let mut sorted: Vec<&Directive> = directives.into_iter().collect();
+        // Stable sort, so duplicates and unknown directives keep their relative order.
+        sorted.sort_by_key(|directive| Self::directive_rank(&directive.name));
+        sorted
+    }
+
+    fn directive_rank(name: &str) -> usize {
+        DIRECTIVE_ORDER
+            .iter()
+            .position(|known| *known == name)
+            .unwrap_or(DIRECTIVE_ORDER.len())
+    }
+
+    fn render_directive(&self, directive: &Directive) -> String {
+        if directive.arguments.is_empty() {
+            return format!("@{}", directive.name);
+        }
+
+        let args: Vec<String> = directive
+            .arguments
+            .iter()
+            .map(|arg| {
+                if arg.name.is_empty() {
+                    self.render_value(&arg.value)
+                } else {
+                    format!("{}: {}", arg.name, self.render_value(&arg.value))
+                }
+            })
+            .collect();
+
+        format!("@{}({})", directive.name, args.join(", "))
+    }
+
+    fn render_value(&self, value: &Value) -> String {
+        match value {
+            Value::NumericValue(value, _) => value.clone(),
+            Value::BooleanValue(value, _) => value.clone(),
+            Value::ConstantValue(value, _) => value.clone(),
+            // The parser keeps escape sequences in string content, so no escaping is needed here.
+            Value::StringValue(value, _) => format!("\"{}\"", value),
+            Value::Array(values, _) => {
+                let values: Vec<String> = values.iter().map(|value| self.render_value(value)).collect();
+                format!("[{}]", values.join(", "))
+            }
+            Value::Function(name, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| self.render_value(arg)).collect();
+                format!("{}({})", name, args.join(", "))
+            }
+        }
+    }
+
+    fn column_widths(rows: &Vec<Vec<String>>) -> Vec<usize> {
+        let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        (0..column_count)
+            .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or(0))
+            .collect()
+    }
+
+    fn render_row(row: &Vec<String>, widths: &Vec<usize>) -> String {
+        // Only pad columns which are followed by a non-empty column, to avoid trailing whitespace.
+        let last = row.iter().rposition(|cell| !cell.is_empty()).unwrap_or(0);
+        let mut result = String::new();
+
+        for (i, cell) in row.iter().enumerate().take(last + 1) {
+            if widths[i] == 0 {
+                continue;
+            }
+
+            if i == last {
+                result.push_str(cell);
+            } else {
+                result.push_str(&format!("{:width$} ", cell, width = widths[i]));
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,233 @@
+use crate::ast;
+use crate::dml::validator::directive::DirectiveValidationError;
+use std::collections::HashMap;
+
+// Datasources and generators are declared next to models and enums:
+//
+// datasource pg {
+//     provider = "postgresql"
+//     url      = env("POSTGRES_URL")
+// }
+//
+// Known keys are available as fields, all other keys end up in `config` as strings.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Source {
+    pub name: String,
+    pub provider: String,
+    pub url: String,
+    pub enabled: bool,
+    pub config: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub name: String,
+    pub provider: String,
+    pub enabled: bool,
+    pub config: HashMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Configuration {
+    pub datasources: Vec<Source>,
+    pub generators: Vec<Generator>,
+}
+
+impl Configuration {
+    // The first enabled datasource, which is the one used by the query engine.
+    pub fn default_source(&self) -> Option<&Source> {
+        self.datasources.iter().find(|source| source.enabled)
+    }
+
+    pub fn find_source(&self, name: &str) -> Option<&Source> {
+        self.datasources.iter().find(|source| source.name == name)
+    }
+
+    pub fn find_generator(&self, name: &str) -> Option<&Generator> {
+        self.generators.iter().find(|generator| generator.name == name)
+    }
+}
+
+// Loads all datasources and generators of the schema. Like the validator, this accumulates all errors.
+pub fn load(ast_schema: &ast::Schema) -> Result<Configuration, Vec<DirectiveValidationError>> {
+    let mut configuration = Configuration::default();
+    let mut errors = Vec::<DirectiveValidationError>::new();
+
+    for obj in &ast_schema.models {
+        match obj {
+            ast::ModelOrEnum::Source(source) => {
+                if configuration.find_source(&source.name).is_some() {
+                    errors.push(error(
+                        &format!("A datasource with the name {} is already declared.", source.name),
+                        &source.span,
+                    ));
+                    continue;
+                }
+
+                match load_source(source) {
+                    Ok(source) => configuration.datasources.push(source),
+                    Err(mut errs) => errors.append(&mut errs),
+                }
+            }
+            ast::ModelOrEnum::Generator(generator) => {
+                if configuration.find_generator(&generator.name).is_some() {
+                    errors.push(error(
+                        &format!("A generator with the name {} is already declared.", generator.name),
+                        &generator.span,
+                    ));
+                    continue;
+                }
+
+                match load_generator(generator) {
+                    Ok(generator) => configuration.generators.push(generator),
+                    Err(mut errs) => errors.append(&mut errs),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if errors.len() > 0 {
+        return Err(errors);
+    }
+
+    Ok(configuration)
+}
+
+fn load_source(source: &ast::SourceConfig) -> Result<Source, Vec<DirectiveValidationError>> {
+    let mut properties = evaluate_properties(&source.properties)?;
+    let mut errors = Vec::<DirectiveValidationError>::new();
+
+    let provider = required_property(&mut properties, "provider", "datasource", &source.name, &source.span);
+    let url = required_property(&mut properties, "url", "datasource", &source.name, &source.span);
+    let enabled = enabled_property(&mut properties, &source.properties);
+
+    match (provider, url, enabled) {
+        (Ok(provider), Ok(url), Ok(enabled)) => Ok(Source {
+            name: source.name.clone(),
+            provider,
+            url,
+            enabled,
+            config: properties,
+        }),
+        (provider, url, enabled) => {
+            errors.extend(provider.err());
+            errors.extend(url.err());
+            errors.extend(enabled.err());
+            Err(errors)
+        }
+    }
+}
+
+fn load_generator(generator: &ast::GeneratorConfig) -> Result<Generator, Vec<DirectiveValidationError>> {
+    let mut properties = evaluate_properties(&generator.properties)?;
+    let mut errors = Vec::<DirectiveValidationError>::new();
+
+    let provider = required_property(
+        &mut properties,
+        "provider",
+        "generator",
+        &generator.name,
+        &generator.span,
+    );
+    let enabled = enabled_property(&mut properties, &generator.properties);
+
+    match (provider, enabled) {
+        (Ok(provider), Ok(enabled)) => Ok(Generator {
+            name: generator.name.clone(),
+            provider,
+            enabled,
+            config: properties,
+        }),
+        (provider, enabled) => {
+            errors.extend(provider.err());
+            errors.extend(enabled.err());
+            Err(errors)
+        }
+    }
+}
+
+fn required_property(
+    properties: &mut HashMap<String, String>,
+    key: &str,
+    kind: &str,
+    name: &str,
+    span: &ast::Span,
+) -> Result<String, DirectiveValidationError> {
+    properties.remove(key).ok_or_else(|| {
+        error(
+            &format!("The {} {} is missing the required property {}.", kind, name, key),
+            span,
+        )
+    })
+}
+
+// Sources and generators are enabled unless stated otherwise.
+fn enabled_property(
+    properties: &mut HashMap<String, String>,
+    ast_properties: &Vec<ast::Argument>,
+) -> Result<bool, DirectiveValidationError> {
+    match properties.remove("enabled") {
+        None => Ok(true),
+        Some(value) => value.parse::<bool>().map_err(|_| {
+            // The property exists, otherwise it would not have been evaluated.
+            let span = ast_properties.iter().find(|p| p.name == "enabled").unwrap().span;
+            error(
+                &format!("Expected a boolean value for enabled, but received {}.", value),
+                &span,
+            )
+        }),
+    }
+}
+
+fn evaluate_properties(
+    properties: &Vec<ast::Argument>,
+) -> Result<HashMap<String, String>, Vec<DirectiveValidationError>> {
+    let mut result: HashMap<String, String> = HashMap::new();
+    let mut errors = Vec::<DirectiveValidationError>::new();
+
+    for property in properties {
+        if result.contains_key(&property.name) {
+            errors.push(error(
+                &format!("The property {} is declared more than once.", property.name),
+                &property.span,
+            ));
+            continue;
+        }
+
+        match evaluate(&property.value) {
+            Ok(value) => {
+                result.insert(property.name.clone(), value);
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if errors.len() > 0 {
+        return Err(errors);
+    }
+
+    Ok(result)
+}
+
+// Config values are kept as plain strings, only env() is evaluated.
+fn evaluate(value: &ast::Value) -> Result<String, DirectiveValidationError> {
+    match value {
+        ast::Value::StringValue(value, _) => Ok(value.clone()),
+        ast::Value::NumericValue(value, _) => Ok(value.clone()),
+        ast::Value::BooleanValue(value, _) => Ok(value.clone()),
+        ast::Value::ConstantValue(value, _) => Ok(value.clone()),
+        ast::Value::Function(name, args, span) if name == "env" => match args.as_slice() {
+            [ast::Value::StringValue(var_name, _)] => std::env::var(var_name)
+                .map_err(|_| error(&format!("Environment variable not found: {}.", var_name), span)),
+            _ => Err(error("The env function expects exactly one string argument.", span)),
+        },
+        ast::Value::Function(name, _, span) => Err(error(&format!("Unknown function {}.", name), span)),
+        ast::Value::Array(_, span) => Err(error("Arrays are not supported in datasources and generators.", span)),
+    }
+}
+
+fn error(message: &str, span: &ast::Span) -> DirectiveValidationError {
+    DirectiveValidationError::new(message, "", span)
+}
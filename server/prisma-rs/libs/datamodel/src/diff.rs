@@ -0,0 +1,367 @@
+//! Diffing of two datamodels
+//!
+//! Models, fields and enums of the previous and the next datamodel are paired
+//! up by name. Items without a partner are checked for renames, which are
+//! detected in two ways:
+//!
+//! * An explicit `@renamedFrom("OldName")` on the item in the next datamodel.
+//! * Continuity of the database name, e.g. `model User` becoming
+//!   `model Account @db(name: "User")`.
+//!
+//! Everything else without a partner is created or deleted.
+
+use crate::dml::*;
+
+/// Compares two datamodels and returns the changes that turn `previous` into `next`.
+///
+/// Models and enums of updates are referred to by their previous name, while the
+/// model of a field change is referred to by its name in `next`.
+pub fn diff(previous: &Schema, next: &Schema) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    let models = pair(previous.models().collect(), next.models().collect(), |m| {
+        m.database_name.clone()
+    });
+
+    for model in &models.created {
+        changes.push(Change::CreateModel((*model).clone()));
+    }
+
+    for model in &models.deleted {
+        changes.push(Change::DeleteModel((*model).clone()));
+    }
+
+    for (previous_model, next_model, hint) in &models.pairs {
+        let update = ModelUpdate {
+            name: previous_model.name.clone(),
+            rename: rename(&previous_model.name, &next_model.name, *hint),
+            changes: model_changes(previous_model, next_model),
+        };
+
+        if update.rename.is_some() || !update.changes.is_empty() {
+            changes.push(Change::UpdateModel(update));
+        }
+    }
+
+    for (previous_model, next_model, _) in &models.pairs {
+        changes.append(&mut field_changes(previous_model, next_model));
+    }
+
+    let enums = pair(previous.enums().collect(), next.enums().collect(), |_| None);
+
+    for en in &enums.created {
+        changes.push(Change::CreateEnum((*en).clone()));
+    }
+
+    for en in &enums.deleted {
+        changes.push(Change::DeleteEnum((*en).clone()));
+    }
+
+    for (previous_enum, next_enum, hint) in &enums.pairs {
+        let update = EnumUpdate {
+            name: previous_enum.name.clone(),
+            rename: rename(&previous_enum.name, &next_enum.name, *hint),
+            changes: enum_changes(previous_enum, next_enum),
+        };
+
+        if update.rename.is_some() || !update.changes.is_empty() {
+            changes.push(Change::UpdateEnum(update));
+        }
+    }
+
+    changes
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Change {
+    CreateModel(Model),
+    DeleteModel(Model),
+    UpdateModel(ModelUpdate),
+    CreateField { model: String, field: Field },
+    DeleteField { model: String, field: Field },
+    UpdateField(FieldUpdate),
+    CreateEnum(Enum),
+    DeleteEnum(Enum),
+    UpdateEnum(EnumUpdate),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rename {
+    pub new_name: String,
+    pub hint: RenameHint,
+}
+
+/// How a rename was detected.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum RenameHint {
+    // The database name stayed the same.
+    DatabaseName,
+    // The item was marked with @renamedFrom.
+    RenamedFrom,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Changed<T> {
+    pub previous: T,
+    pub next: T,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ModelUpdate {
+    pub name: String,
+    pub rename: Option<Rename>,
+    pub changes: Vec<ModelChange>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModelChange {
+    DatabaseName(Changed<Option<String>>),
+    Embedded(Changed<bool>),
+    Indexes(Changed<Vec<IndexDefinition>>),
+    IdFields(Changed<Vec<String>>),
+    CustomDirectives(Changed<Vec<CustomDirective>>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FieldUpdate {
+    pub model: String,
+    pub name: String,
+    pub rename: Option<Rename>,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum FieldChange {
+    Type(Changed<FieldType>),
+    Arity(Changed<FieldArity>),
+    DatabaseName(Changed<Option<String>>),
+    Default(Changed<Option<Value>>),
+    Unique(Changed<bool>),
+    UpdatedAt(Changed<bool>),
+    Id(Changed<Option<IdInfo>>),
+    ScalarList(Changed<Option<ScalarListStrategy>>),
+    CustomDirectives(Changed<Vec<CustomDirective>>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumUpdate {
+    pub name: String,
+    pub rename: Option<Rename>,
+    pub changes: Vec<EnumChange>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum EnumChange {
+    AddValue(String),
+    RemoveValue(String),
+    CustomDirectives(Changed<Vec<CustomDirective>>),
+}
+
+fn model_changes(previous: &Model, next: &Model) -> Vec<ModelChange> {
+    let mut changes = Vec::new();
+
+    if let Some(changed) = changed(&previous.database_name, &next.database_name) {
+        changes.push(ModelChange::DatabaseName(changed));
+    }
+    if let Some(changed) = changed(&previous.is_embedded, &next.is_embedded) {
+        changes.push(ModelChange::Embedded(changed));
+    }
+    if let Some(changed) = changed(&previous.indexes, &next.indexes) {
+        changes.push(ModelChange::Indexes(changed));
+    }
+    if let Some(changed) = changed(&previous.id_fields, &next.id_fields) {
+        changes.push(ModelChange::IdFields(changed));
+    }
+    if let Some(changed) = changed(&previous.custom_directives, &next.custom_directives) {
+        changes.push(ModelChange::CustomDirectives(changed));
+    }
+
+    changes
+}
+
+fn field_changes(previous_model: &Model, next_model: &Model) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let fields = pair(previous_model.fields().collect(), next_model.fields().collect(), |f| {
+        f.database_name.clone()
+    });
+
+    for field in &fields.created {
+        changes.push(Change::CreateField {
+            model: next_model.name.clone(),
+            field: (*field).clone(),
+        });
+    }
+
+    for field in &fields.deleted {
+        changes.push(Change::DeleteField {
+            model: next_model.name.clone(),
+            field: (*field).clone(),
+        });
+    }
+
+    for (previous, next, hint) in &fields.pairs {
+        let mut field_changes = Vec::new();
+
+        if let Some(changed) = changed(&previous.field_type, &next.field_type) {
+            field_changes.push(FieldChange::Type(changed));
+        }
+        if let Some(changed) = changed(&previous.arity, &next.arity) {
+            field_changes.push(FieldChange::Arity(changed));
+        }
+        if let Some(changed) = changed(&previous.database_name, &next.database_name) {
+            field_changes.push(FieldChange::DatabaseName(changed));
+        }
+        if let Some(changed) = changed(&previous.default_value, &next.default_value) {
+            field_changes.push(FieldChange::Default(changed));
+        }
+        if let Some(changed) = changed(&previous.is_unique, &next.is_unique) {
+            field_changes.push(FieldChange::Unique(changed));
+        }
+        if let Some(changed) = changed(&previous.is_updated_at, &next.is_updated_at) {
+            field_changes.push(FieldChange::UpdatedAt(changed));
+        }
+        if let Some(changed) = changed(&previous.id_info, &next.id_info) {
+            field_changes.push(FieldChange::Id(changed));
+        }
+        if let Some(changed) = changed(&previous.scalar_list_strategy, &next.scalar_list_strategy) {
+            field_changes.push(FieldChange::ScalarList(changed));
+        }
+        if let Some(changed) = changed(&previous.custom_directives, &next.custom_directives) {
+            field_changes.push(FieldChange::CustomDirectives(changed));
+        }
+
+        let update = FieldUpdate {
+            model: next_model.name.clone(),
+            name: previous.name.clone(),
+            rename: rename(&previous.name, &next.name, *hint),
+            changes: field_changes,
+        };
+
+        if update.rename.is_some() || !update.changes.is_empty() {
+            changes.push(Change::UpdateField(update));
+        }
+    }
+
+    changes
+}
+
+fn enum_changes(previous: &Enum, next: &Enum) -> Vec<EnumChange> {
+    let mut changes = Vec::new();
+
+    for value in &next.values {
+        if !previous.values.iter().any(|v| v.name == value.name) {
+            changes.push(EnumChange::AddValue(value.name.clone()));
+        }
+    }
+
+    for value in &previous.values {
+        if !next.values.iter().any(|v| v.name == value.name) {
+            changes.push(EnumChange::RemoveValue(value.name.clone()));
+        }
+    }
+
+    if let Some(changed) = changed(&previous.custom_directives, &next.custom_directives) {
+        changes.push(EnumChange::CustomDirectives(changed));
+    }
+
+    changes
+}
+
+fn changed<T: PartialEq + Clone>(previous: &T, next: &T) -> Option<Changed<T>> {
+    if previous == next {
+        None
+    } else {
+        Some(Changed {
+            previous: previous.clone(),
+            next: next.clone(),
+        })
+    }
+}
+
+fn rename(previous_name: &str, next_name: &str, hint: Option<RenameHint>) -> Option<Rename> {
+    hint.filter(|_| previous_name != next_name).map(|hint| Rename {
+        new_name: String::from(next_name),
+        hint,
+    })
+}
+
+// The items of both datamodels, paired up one-to-one.
+struct Pairing<'a, T> {
+    // Paired items in the order of the next datamodel, with the hint if it was a rename.
+    pairs: Vec<(&'a T, &'a T, Option<RenameHint>)>,
+    created: Vec<&'a T>,
+    deleted: Vec<&'a T>,
+}
+
+fn pair<'a, T, F>(previous: Vec<&'a T>, next: Vec<&'a T>, database_name: F) -> Pairing<'a, T>
+where
+    T: WithName + WithRenamedFrom,
+    F: Fn(&T) -> Option<String>,
+{
+    let mut partners: Vec<Option<(usize, Option<RenameHint>)>> = vec![None; next.len()];
+    let mut is_paired = vec![false; previous.len()];
+
+    // Items keeping their name are always the same item.
+    for (n, next_item) in next.iter().enumerate() {
+        if let Some(p) = previous.iter().position(|p| p.name() == next_item.name()) {
+            partners[n] = Some((p, None));
+            is_paired[p] = true;
+        }
+    }
+
+    // Only items whose name is gone can be renamed.
+    let is_free =
+        |p: usize, is_paired: &Vec<bool>| !is_paired[p] && !next.iter().any(|n| n.name() == previous[p].name());
+
+    for (n, next_item) in next.iter().enumerate() {
+        if partners[n].is_some() {
+            continue;
+        }
+
+        if let Some(renamed_from) = next_item.renamed_from() {
+            let found = (0..previous.len()).find(|p| is_free(*p, &is_paired) && previous[*p].name() == renamed_from);
+
+            if let Some(p) = found {
+                partners[n] = Some((p, Some(RenameHint::RenamedFrom)));
+                is_paired[p] = true;
+            }
+        }
+    }
+
+    let effective_name = |item: &T| database_name(item).unwrap_or_else(|| item.name().clone());
+
+    for (n, next_item) in next.iter().enumerate() {
+        if partners[n].is_some() || previous.iter().any(|p| p.name() == next_item.name()) {
+            continue;
+        }
+
+        let db_name = effective_name(next_item);
+        let found = (0..previous.len()).find(|p| is_free(*p, &is_paired) && effective_name(previous[*p]) == db_name);
+
+        if let Some(p) = found {
+            partners[n] = Some((p, Some(RenameHint::DatabaseName)));
+            is_paired[p] = true;
+        }
+    }
+
+    let mut pairing = Pairing {
+        pairs: Vec::new(),
+        created: Vec::new(),
+        deleted: Vec::new(),
+    };
+
+    for (n, partner) in partners.into_iter().enumerate() {
+        match partner {
+            Some((p, hint)) => pairing.pairs.push((previous[p], next[n], hint)),
+            None => pairing.created.push(next[n]),
+        }
+    }
+
+    for (p, paired) in is_paired.into_iter().enumerate() {
+        if !paired {
+            pairing.deleted.push(previous[p]);
+        }
+    }
+
+    pairing
+}
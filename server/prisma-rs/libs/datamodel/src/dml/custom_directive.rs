@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+// A directive of a passthrough namespace, e.g. @search.boost(2). It is not interpreted
+// by the validator, but kept as written so tooling can pick it up from the dml or the DMMF.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CustomDirective {
+    pub name: String,
+    pub arguments: Vec<CustomDirectiveArgument>,
+}
+
+// Values are kept in source form, e.g. "\"text\"" for a string. Unnamed arguments have an empty name.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CustomDirectiveArgument {
+    pub name: String,
+    pub value: String,
+}
+
+impl CustomDirective {
+    pub fn new(name: &str, arguments: Vec<CustomDirectiveArgument>) -> CustomDirective {
+        CustomDirective {
+            name: String::from(name),
+            arguments,
+        }
+    }
+
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.arguments
+            .iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| arg.value.as_str())
+    }
+}
@@ -1,22 +1,60 @@
 use super::comment::*;
+use super::custom_directive::*;
 use super::traits::*;
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EnumValue {
+    pub name: String,
+    pub comments: Vec<Comment>,
+}
+
+impl EnumValue {
+    pub fn new(name: &str) -> EnumValue {
+        EnumValue {
+            name: String::from(name),
+            comments: vec![],
+        }
+    }
+}
+
+impl WithName for EnumValue {
+    fn name(&self) -> &String {
+        &self.name
+    }
+    fn set_name(&mut self, name: &str) {
+        self.name = String::from(name)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Enum {
     pub name: String,
-    pub values: Vec<String>,
+    pub values: Vec<EnumValue>,
     pub comments: Vec<Comment>,
+    // Set with @renamedFrom, the name of the enum in the previous datamodel.
+    pub renamed_from: Option<String>,
+    pub custom_directives: Vec<CustomDirective>,
 }
 
 impl Enum {
     pub fn new(name: &str, values: Vec<String>) -> Enum {
         Enum {
             name: String::from(name),
-            values: values,
+            values: values.iter().map(|v| EnumValue::new(v)).collect(),
             comments: vec![],
+            renamed_from: None,
+            custom_directives: vec![],
         }
     }
+
+    pub fn has_value(&self, name: &str) -> bool {
+        self.values.iter().any(|v| v.name == *name)
+    }
+
+    pub fn value_names(&self) -> Vec<String> {
+        self.values.iter().map(|v| v.name.clone()).collect()
+    }
 }
 
 impl WithName for Enum {
@@ -27,3 +65,21 @@ impl WithName for Enum {
         self.name = String::from(name)
     }
 }
+
+impl WithRenamedFrom for Enum {
+    fn renamed_from(&self) -> &Option<String> {
+        &self.renamed_from
+    }
+    fn set_renamed_from(&mut self, renamed_from: &Option<String>) {
+        self.renamed_from = renamed_from.clone()
+    }
+}
+
+impl WithCustomDirectives for Enum {
+    fn custom_directives(&self) -> &Vec<CustomDirective> {
+        &self.custom_directives
+    }
+    fn add_custom_directive(&mut self, directive: CustomDirective) {
+        self.custom_directives.push(directive)
+    }
+}
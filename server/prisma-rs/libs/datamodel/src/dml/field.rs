@@ -1,4 +1,5 @@
 use super::comment::*;
+use super::custom_directive::*;
 use super::id::*;
 use super::relation::*;
 use super::scalar::*;
@@ -41,9 +42,14 @@ pub struct Field {
     pub database_name: Option<String>,
     pub default_value: Option<Value>,
     pub is_unique: bool,
+    // Set with @updatedAt, the field is set to the current time on every update.
+    pub is_updated_at: bool,
     pub id_info: Option<IdInfo>,
     pub scalar_list_strategy: Option<ScalarListStrategy>,
+    // Set with @renamedFrom, the name of the field in the previous datamodel.
+    pub renamed_from: Option<String>,
     pub comments: Vec<Comment>,
+    pub custom_directives: Vec<CustomDirective>,
 }
 
 impl WithName for Field {
@@ -64,6 +70,15 @@ impl WithDatabaseName for Field {
     }
 }
 
+impl WithRenamedFrom for Field {
+    fn renamed_from(&self) -> &Option<String> {
+        &self.renamed_from
+    }
+    fn set_renamed_from(&mut self, renamed_from: &Option<String>) {
+        self.renamed_from = renamed_from.clone()
+    }
+}
+
 impl Field {
     pub fn new(name: &str, field_type: FieldType) -> Field {
         Field {
@@ -73,9 +88,21 @@ impl Field {
             database_name: None,
             default_value: None,
             is_unique: false,
+            is_updated_at: false,
             id_info: None,
             scalar_list_strategy: None,
+            renamed_from: None,
             comments: vec![],
+            custom_directives: vec![],
         }
     }
 }
+
+impl WithCustomDirectives for Field {
+    fn custom_directives(&self) -> &Vec<CustomDirective> {
+        &self.custom_directives
+    }
+    fn add_custom_directive(&mut self, directive: CustomDirective) {
+        self.custom_directives.push(directive)
+    }
+}
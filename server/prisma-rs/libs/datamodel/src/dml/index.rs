@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, PartialEq, Clone, Serialize, Deserialize)]
+pub enum IndexType {
+    // A unique constraint, declared via @@unique([a, b]).
+    Unique,
+    // A plain index, declared via @@index([a, b]).
+    Normal,
+}
+
+// An index spanning one or more fields of a model.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct IndexDefinition {
+    pub name: Option<String>,
+    pub fields: Vec<String>,
+    pub tpe: IndexType,
+}
+
+impl IndexDefinition {
+    pub fn new(fields: Vec<String>, tpe: IndexType) -> IndexDefinition {
+        IndexDefinition { name: None, fields, tpe }
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.tpe == IndexType::Unique
+    }
+
+    // The name of the index, see `index_name`.
+    pub fn name(&self) -> String {
+        index_name(&self.name, &self.fields)
+    }
+
+    // The name of the index in the database. Index names have to be unique within a database schema,
+    // so generated names are prefixed with the model name, e.g. `User.firstName_lastName`.
+    pub fn database_name(&self, model_name: &str) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{}.{}", model_name, self.name()),
+        }
+    }
+}
+
+// The explicit name of an index, or the names of its fields joined by `_`, e.g. `firstName_lastName`.
+// Compound selectors in the API are addressed by this name.
+pub fn index_name(name: &Option<String>, fields: &[String]) -> String {
+    name.clone().unwrap_or_else(|| fields.join("_"))
+}
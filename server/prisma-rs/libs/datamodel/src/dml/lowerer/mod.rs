@@ -0,0 +1,370 @@
+use crate::{ast, dml};
+
+// The lowerer is the inverse of the validator: It turns a dml schema back into an AST,
+// which can then be rendered to source text.
+pub struct Lowerer {}
+
+impl Lowerer {
+    pub fn new() -> Lowerer {
+        Lowerer {}
+    }
+
+    pub fn lower(&self, schema: &dml::Schema) -> ast::Schema {
+        let mut models: Vec<ast::ModelOrEnum> = vec![];
+
+        for model in schema.models() {
+            models.push(ast::ModelOrEnum::Model(self.lower_model(model, schema)));
+        }
+
+        for en in schema.enums() {
+            models.push(ast::ModelOrEnum::Enum(self.lower_enum(en)));
+        }
+
+        ast::Schema {
+            models,
+            comments: self.lower_comments(&schema.comments),
+            end_comments: vec![],
+            broken_declarations: vec![],
+        }
+    }
+
+    fn lower_model(&self, model: &dml::Model, schema: &dml::Schema) -> ast::Model {
+        let mut directives: Vec<ast::Directive> = vec![];
+        let mut block_directives: Vec<ast::Directive> = vec![];
+
+        if let Some(database_name) = &model.database_name {
+            directives.push(self.db_directive(database_name));
+        }
+
+        if model.is_embedded {
+            directives.push(self.directive("embedded", vec![]));
+        }
+
+        if model.has_compound_id() {
+            block_directives.push(self.directive("id", vec![self.argument("", self.field_list(&model.id_fields))]));
+        }
+
+        for index in &model.indexes {
+            block_directives.push(self.lower_index(index));
+        }
+
+        if let Some(renamed_from) = &model.renamed_from {
+            directives.push(self.renamed_from_directive(renamed_from));
+        }
+
+        directives.append(&mut self.lower_custom_directives(&model.custom_directives));
+
+        ast::Model {
+            name: model.name.clone(),
+            fields: model.fields().map(|f| self.lower_field(f, schema)).collect(),
+            directives,
+            block_directives,
+            comments: self.lower_comments(&model.comments),
+            trivia: ast::Trivia::default(),
+            end_comments: vec![],
+            span: ast::Span::empty(),
+        }
+    }
+
+    fn lower_index(&self, index: &dml::IndexDefinition) -> ast::Directive {
+        let name = match index.tpe {
+            dml::IndexType::Unique => "unique",
+            dml::IndexType::Normal => "index",
+        };
+
+        let fields = self.field_list(&index.fields);
+
+        match &index.name {
+            Some(index_name) => self.directive(
+                name,
+                vec![
+                    self.argument("fields", fields),
+                    self.argument("name", ast::Value::StringValue(index_name.clone(), ast::Span::empty())),
+                ],
+            ),
+            None => self.directive(name, vec![self.argument("", fields)]),
+        }
+    }
+
+    fn field_list(&self, fields: &Vec<String>) -> ast::Value {
+        ast::Value::Array(
+            fields
+                .iter()
+                .map(|field| ast::Value::ConstantValue(field.clone(), ast::Span::empty()))
+                .collect(),
+            ast::Span::empty(),
+        )
+    }
+
+    fn lower_enum(&self, en: &dml::Enum) -> ast::Enum {
+        let mut directives: Vec<ast::Directive> = vec![];
+
+        if let Some(renamed_from) = &en.renamed_from {
+            directives.push(self.renamed_from_directive(renamed_from));
+        }
+
+        directives.append(&mut self.lower_custom_directives(&en.custom_directives));
+
+        ast::Enum {
+            name: en.name.clone(),
+            values: en
+                .values
+                .iter()
+                .map(|value| ast::EnumValue {
+                    name: value.name.clone(),
+                    comments: self.lower_comments(&value.comments),
+                    trivia: ast::Trivia::default(),
+                    span: ast::Span::empty(),
+                })
+                .collect(),
+            directives,
+            comments: self.lower_comments(&en.comments),
+            trivia: ast::Trivia::default(),
+            end_comments: vec![],
+            span: ast::Span::empty(),
+        }
+    }
+
+    fn lower_field(&self, field: &dml::Field, schema: &dml::Schema) -> ast::Field {
+        let mut directives: Vec<ast::Directive> = vec![];
+
+        if let Some(id_info) = &field.id_info {
+            match id_info.strategy {
+                dml::IdStrategy::Auto => directives.push(self.directive("primary", vec![])),
+                dml::IdStrategy::None => directives.push(self.directive(
+                    "primary",
+                    vec![self.argument("name", ast::Value::ConstantValue(String::from("NONE"), ast::Span::empty()))],
+                )),
+            }
+
+            if let Some(sequence) = &id_info.sequence {
+                directives.push(self.directive(
+                    "sequence",
+                    vec![
+                        self.argument("name", ast::Value::StringValue(sequence.name.clone(), ast::Span::empty())),
+                        self.argument(
+                            "initialValue",
+                            ast::Value::NumericValue(sequence.initial_value.to_string(), ast::Span::empty()),
+                        ),
+                        self.argument(
+                            "allocationSize",
+                            ast::Value::NumericValue(sequence.allocation_size.to_string(), ast::Span::empty()),
+                        ),
+                    ],
+                ));
+            }
+        }
+
+        if field.is_unique {
+            directives.push(self.directive("unique", vec![]));
+        }
+
+        if field.is_updated_at {
+            directives.push(self.directive("updatedAt", vec![]));
+        }
+
+        if let dml::FieldType::Relation(relation_info) = &field.field_type {
+            if let Some(name) = &relation_info.name {
+                directives.push(self.directive(
+                    "relation",
+                    vec![self.argument("name", ast::Value::StringValue(name.clone(), ast::Span::empty()))],
+                ));
+            }
+
+            if relation_info.on_delete == dml::OnDeleteStrategy::Cascade {
+                directives.push(self.directive(
+                    "onDelete",
+                    vec![self.argument(
+                        "strategy",
+                        ast::Value::ConstantValue(String::from("CASCADE"), ast::Span::empty()),
+                    )],
+                ));
+            }
+        }
+
+        if let Some(database_name) = &field.database_name {
+            directives.push(self.db_directive(database_name));
+        }
+
+        if let dml::FieldType::ConnectorSpecific {
+            connector_type: Some(connector_type),
+            ..
+        } = &field.field_type
+        {
+            let (name, args) = dml::parse_connector_type(connector_type);
+            let args = args
+                .into_iter()
+                .map(|arg| self.argument("", ast::Value::NumericValue(arg, ast::Span::empty())))
+                .collect();
+            directives.push(self.directive(&format!("{}.{}", dml::NATIVE_TYPE_NAMESPACE, name), args));
+        }
+
+        if let Some(strategy) = &field.scalar_list_strategy {
+            let strategy = match strategy {
+                dml::ScalarListStrategy::Embedded => "EMBEDDED",
+                dml::ScalarListStrategy::Relation => "RELATION",
+            };
+            directives.push(self.directive(
+                "scalarList",
+                vec![self.argument(
+                    "strategy",
+                    ast::Value::ConstantValue(String::from(strategy), ast::Span::empty()),
+                )],
+            ));
+        }
+
+        if let Some(renamed_from) = &field.renamed_from {
+            directives.push(self.renamed_from_directive(renamed_from));
+        }
+
+        directives.append(&mut self.lower_custom_directives(&field.custom_directives));
+
+        ast::Field {
+            field_type: self.lower_field_type(&field.field_type),
+            field_link: self.lower_field_link(&field.field_type, schema),
+            name: field.name.clone(),
+            arity: self.lower_field_arity(&field.arity),
+            default_value: field.default_value.as_ref().map(|value| self.lower_value(value)),
+            directives,
+            comments: self.lower_comments(&field.comments),
+            trivia: ast::Trivia::default(),
+            span: ast::Span::empty(),
+        }
+    }
+
+    // The related field only needs to be given explicitly if it is not the id field of the related model.
+    fn lower_field_link(&self, field_type: &dml::FieldType, schema: &dml::Schema) -> Option<String> {
+        if let dml::FieldType::Relation(relation_info) = field_type {
+            let id_field = schema.find_model(&relation_info.to).and_then(|m| m.id_field());
+
+            match id_field {
+                Some(id_field) if id_field.name == relation_info.to_field => None,
+                _ if relation_info.to_field.is_empty() => None,
+                _ => Some(relation_info.to_field.clone()),
+            }
+        } else {
+            None
+        }
+    }
+
+    fn lower_field_arity(&self, arity: &dml::FieldArity) -> ast::FieldArity {
+        match arity {
+            dml::FieldArity::Required => ast::FieldArity::Required,
+            dml::FieldArity::Optional => ast::FieldArity::Optional,
+            dml::FieldArity::List => ast::FieldArity::List,
+        }
+    }
+
+    fn lower_field_type(&self, field_type: &dml::FieldType) -> String {
+        match field_type {
+            dml::FieldType::Base(scalar_type) => self.lower_scalar_type(scalar_type),
+            dml::FieldType::ConnectorSpecific { base_type, .. } => self.lower_scalar_type(base_type),
+            dml::FieldType::Enum(name) => name.clone(),
+            dml::FieldType::Relation(relation_info) => relation_info.to.clone(),
+        }
+    }
+
+    fn lower_scalar_type(&self, scalar_type: &dml::ScalarType) -> String {
+        match scalar_type {
+            dml::ScalarType::Int => String::from("Int"),
+            dml::ScalarType::Float => String::from("Float"),
+            dml::ScalarType::Decimal => String::from("Decimal"),
+            dml::ScalarType::Boolean => String::from("Boolean"),
+            dml::ScalarType::String => String::from("String"),
+            dml::ScalarType::DateTime => String::from("DateTime"),
+            dml::ScalarType::Json => String::from("Json"),
+            dml::ScalarType::Bytes => String::from("Bytes"),
+            dml::ScalarType::BigInt => String::from("BigInt"),
+            dml::ScalarType::Uuid => String::from("UUID"),
+            dml::ScalarType::Enum => panic!("Enum is an internally used type and should never be lowered."),
+        }
+    }
+
+    fn lower_value(&self, value: &dml::Value) -> ast::Value {
+        match value {
+            dml::Value::Int(value) => ast::Value::NumericValue(value.to_string(), ast::Span::empty()),
+            dml::Value::Float(value) => ast::Value::NumericValue(value.to_string(), ast::Span::empty()),
+            dml::Value::Decimal(value) => ast::Value::NumericValue(value.to_string(), ast::Span::empty()),
+            dml::Value::Boolean(value) => ast::Value::BooleanValue(value.to_string(), ast::Span::empty()),
+            dml::Value::String(value) => ast::Value::StringValue(value.clone(), ast::Span::empty()),
+            dml::Value::DateTime(value) => ast::Value::StringValue(value.to_rfc3339(), ast::Span::empty()),
+            dml::Value::Json(value) => {
+                ast::Value::StringValue(ast::escape_string(&value.to_string()), ast::Span::empty())
+            }
+            dml::Value::Bytes(value) => ast::Value::StringValue(value.clone(), ast::Span::empty()),
+            dml::Value::BigInt(value) => ast::Value::NumericValue(value.to_string(), ast::Span::empty()),
+            dml::Value::Uuid(value) => ast::Value::StringValue(value.clone(), ast::Span::empty()),
+            dml::Value::ConstantLiteral(value) => ast::Value::ConstantValue(value.clone(), ast::Span::empty()),
+            dml::Value::Function(function) => {
+                ast::Value::Function(String::from(function.name()), vec![], ast::Span::empty())
+            }
+        }
+    }
+
+    fn lower_comments(&self, comments: &Vec<dml::Comment>) -> Vec<ast::Comment> {
+        comments
+            .iter()
+            .map(|comment| ast::Comment {
+                text: comment.text.clone(),
+                is_error: comment.is_error,
+            })
+            .collect()
+    }
+
+    fn db_directive(&self, database_name: &str) -> ast::Directive {
+        self.directive(
+            "db",
+            vec![self.argument(
+                "name",
+                ast::Value::StringValue(String::from(database_name), ast::Span::empty()),
+            )],
+        )
+    }
+
+    fn renamed_from_directive(&self, renamed_from: &str) -> ast::Directive {
+        self.directive(
+            "renamedFrom",
+            vec![self.argument(
+                "",
+                ast::Value::StringValue(String::from(renamed_from), ast::Span::empty()),
+            )],
+        )
+    }
+
+    // Custom directive values are kept in source form, so they are lowered as constants to be rendered verbatim.
+    fn lower_custom_directives(&self, directives: &Vec<dml::CustomDirective>) -> Vec<ast::Directive> {
+        directives
+            .iter()
+            .map(|directive| {
+                let args = directive
+                    .arguments
+                    .iter()
+                    .map(|arg| {
+                        self.argument(
+                            &arg.name,
+                            ast::Value::ConstantValue(arg.value.clone(), ast::Span::empty()),
+                        )
+                    })
+                    .collect();
+                self.directive(&directive.name, args)
+            })
+            .collect()
+    }
+
+    fn directive(&self, name: &str, arguments: Vec<ast::DirectiveArgument>) -> ast::Directive {
+        ast::Directive {
+            name: String::from(name),
+            arguments,
+            trivia: ast::Trivia::default(),
+            span: ast::Span::empty(),
+        }
+    }
+
+    fn argument(&self, name: &str, value: ast::Value) -> ast::DirectiveArgument {
+        ast::DirectiveArgument {
+            name: String::from(name),
+            value,
+            span: ast::Span::empty(),
+        }
+    }
+}
@@ -3,23 +3,30 @@
 // * Should this structure contain circular references? (Would make renaming models/fields MUCH easier)
 // * How do we handle ocnnector specific settings, like indeces? Maybe inheritance, traits and having a Connector<T>?
 mod comment;
+mod custom_directive;
 mod enummodel;
 mod field;
 mod id;
+mod index;
 mod model;
+mod native_type;
 mod relation;
 mod scalar;
 mod schema;
 mod traits;
 
 pub use comment::*;
+pub use custom_directive::*;
 pub use enummodel::*;
 pub use field::*;
 pub use id::*;
+pub use index::*;
 pub use model::*;
+pub use native_type::*;
 pub use relation::*;
 pub use scalar::*;
 pub use schema::*;
 pub use traits::*;
 
+pub mod lowerer;
 pub mod validator;
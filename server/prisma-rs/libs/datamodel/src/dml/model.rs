@@ -1,5 +1,8 @@
 use super::comment::*;
+use super::custom_directive::*;
 use super::field::*;
+use super::index::*;
+use super::relation::*;
 use super::traits::*;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +13,13 @@ pub struct Model {
     pub comments: Vec<Comment>,
     pub database_name: Option<String>,
     pub is_embedded: bool,
+    pub indexes: Vec<IndexDefinition>,
+    // The fields of a compound id, declared via @@id([a, b]). Empty if the model
+    // uses a single @primary field instead.
+    pub id_fields: Vec<String>,
+    // Set with @renamedFrom, the name of the model in the previous datamodel.
+    pub renamed_from: Option<String>,
+    pub custom_directives: Vec<CustomDirective>,
 }
 
 impl Model {
@@ -20,6 +30,10 @@ impl Model {
             comments: vec![],
             database_name: None,
             is_embedded: false,
+            indexes: vec![],
+            id_fields: vec![],
+            renamed_from: None,
+            custom_directives: vec![],
         }
     }
 
@@ -38,6 +52,35 @@ impl Model {
     pub fn find_field(&self, name: &str) -> Option<&Field> {
         self.fields().find(|f| f.name == *name)
     }
+
+    pub fn find_field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields_mut().find(|f| f.name == *name)
+    }
+
+    pub fn id_field(&self) -> Option<&Field> {
+        self.fields().find(|f| f.id_info.is_some())
+    }
+
+    pub fn has_compound_id(&self) -> bool {
+        !self.id_fields.is_empty()
+    }
+
+    pub fn add_index(&mut self, index: IndexDefinition) {
+        self.indexes.push(index)
+    }
+
+    pub fn unique_indexes(&self) -> impl Iterator<Item = &IndexDefinition> {
+        self.indexes.iter().filter(|index| index.is_unique())
+    }
+
+    pub fn relation_fields(&self) -> Vec<(&Field, &RelationInfo)> {
+        self.fields()
+            .filter_map(|f| match &f.field_type {
+                FieldType::Relation(relation_info) => Some((f, relation_info)),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl WithName for Model {
@@ -57,3 +100,21 @@ impl WithDatabaseName for Model {
         self.database_name = database_name.clone()
     }
 }
+
+impl WithRenamedFrom for Model {
+    fn renamed_from(&self) -> &Option<String> {
+        &self.renamed_from
+    }
+    fn set_renamed_from(&mut self, renamed_from: &Option<String>) {
+        self.renamed_from = renamed_from.clone()
+    }
+}
+
+impl WithCustomDirectives for Model {
+    fn custom_directives(&self) -> &Vec<CustomDirective> {
+        &self.custom_directives
+    }
+    fn add_custom_directive(&mut self, directive: CustomDirective) {
+        self.custom_directives.push(directive)
+    }
+}
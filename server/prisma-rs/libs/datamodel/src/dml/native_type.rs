@@ -0,0 +1,145 @@
+use super::scalar::*;
+
+// Native database types, which can be requested on scalar fields with @db.<Name>, e.g. @db.VarChar(255).
+// A field with a native type has the field type ConnectorSpecific, with the connector type in source form,
+// e.g. `VarChar(255)`.
+// The directive namespace of native types, as in @db.VarChar(255).
+pub const NATIVE_TYPE_NAMESPACE: &str = "db";
+
+#[derive(Debug)]
+pub struct NativeTypeDefinition {
+    pub name: &'static str,
+    pub sql_name: &'static str,
+    pub argument_count: usize,
+    // The field types the native type can be used with.
+    pub scalar_types: &'static [ScalarType],
+}
+
+const fn native(
+    name: &'static str,
+    sql_name: &'static str,
+    argument_count: usize,
+    scalar_types: &'static [ScalarType],
+) -> NativeTypeDefinition {
+    NativeTypeDefinition {
+        name,
+        sql_name,
+        argument_count,
+        scalar_types,
+    }
+}
+
+const POSTGRES_TYPES: &[NativeTypeDefinition] = &[
+    native("SmallInt", "SMALLINT", 0, &[ScalarType::Int]),
+    native("Integer", "INTEGER", 0, &[ScalarType::Int]),
+    native("BigInt", "BIGINT", 0, &[ScalarType::Int, ScalarType::BigInt]),
+    native("Real", "REAL", 0, &[ScalarType::Float]),
+    native("DoublePrecision", "DOUBLE PRECISION", 0, &[ScalarType::Float]),
+    native("Numeric", "NUMERIC", 2, &[ScalarType::Decimal, ScalarType::Float]),
+    native("VarChar", "VARCHAR", 1, &[ScalarType::String]),
+    native("Char", "CHAR", 1, &[ScalarType::String]),
+    native("Text", "TEXT", 0, &[ScalarType::String]),
+    native("Uuid", "UUID", 0, &[ScalarType::String, ScalarType::Uuid]),
+    native("Json", "JSON", 0, &[ScalarType::String, ScalarType::Json]),
+    native("JsonB", "JSONB", 0, &[ScalarType::String, ScalarType::Json]),
+    native("ByteA", "BYTEA", 0, &[ScalarType::Bytes]),
+    native("Boolean", "BOOLEAN", 0, &[ScalarType::Boolean]),
+    native("Timestamp", "TIMESTAMP", 0, &[ScalarType::DateTime]),
+    native("Timestamptz", "TIMESTAMPTZ", 0, &[ScalarType::DateTime]),
+    native("Date", "DATE", 0, &[ScalarType::DateTime]),
+];
+
+const MYSQL_TYPES: &[NativeTypeDefinition] = &[
+    native("TinyInt", "TINYINT", 0, &[ScalarType::Int, ScalarType::Boolean]),
+    native("SmallInt", "SMALLINT", 0, &[ScalarType::Int]),
+    native("Int", "INT", 0, &[ScalarType::Int]),
+    native("BigInt", "BIGINT", 0, &[ScalarType::Int, ScalarType::BigInt]),
+    native("Float", "FLOAT", 0, &[ScalarType::Float]),
+    native("Double", "DOUBLE", 0, &[ScalarType::Float]),
+    native("Decimal", "DECIMAL", 2, &[ScalarType::Decimal, ScalarType::Float]),
+    native("VarChar", "VARCHAR", 1, &[ScalarType::String]),
+    native("Char", "CHAR", 1, &[ScalarType::String]),
+    native("Text", "TEXT", 0, &[ScalarType::String]),
+    native("LongText", "LONGTEXT", 0, &[ScalarType::String]),
+    native("Json", "JSON", 0, &[ScalarType::String, ScalarType::Json]),
+    native("Blob", "BLOB", 0, &[ScalarType::Bytes]),
+    native("LongBlob", "LONGBLOB", 0, &[ScalarType::Bytes]),
+    native("DateTime", "DATETIME", 0, &[ScalarType::DateTime]),
+    native("Timestamp", "TIMESTAMP", 0, &[ScalarType::DateTime]),
+    native("Date", "DATE", 0, &[ScalarType::DateTime]),
+];
+
+// SQLite only knows type affinities, so only the common spellings are allowed.
+const SQLITE_TYPES: &[NativeTypeDefinition] = &[
+    native(
+        "Integer",
+        "INTEGER",
+        0,
+        &[ScalarType::Int, ScalarType::BigInt, ScalarType::Boolean],
+    ),
+    native("Real", "REAL", 0, &[ScalarType::Float]),
+    native("Numeric", "NUMERIC", 0, &[ScalarType::Decimal, ScalarType::Float]),
+    native(
+        "Text",
+        "TEXT",
+        0,
+        &[
+            ScalarType::String,
+            ScalarType::DateTime,
+            ScalarType::Json,
+            ScalarType::Uuid,
+        ],
+    ),
+    native("VarChar", "VARCHAR", 1, &[ScalarType::String]),
+    native("Blob", "BLOB", 0, &[ScalarType::Bytes]),
+];
+
+// The native types of the connector with the given datasource provider, or None for unknown providers.
+pub fn native_types(provider: &str) -> Option<&'static [NativeTypeDefinition]> {
+    match provider {
+        "postgresql" => Some(POSTGRES_TYPES),
+        "mysql" => Some(MYSQL_TYPES),
+        "sqlite" => Some(SQLITE_TYPES),
+        _ => None,
+    }
+}
+
+// Finds a native type by name. Without a provider, the native types of all connectors are searched.
+pub fn find_native_type(provider: Option<&str>, name: &str) -> Option<&'static NativeTypeDefinition> {
+    let all: &[&[NativeTypeDefinition]] = &[POSTGRES_TYPES, MYSQL_TYPES, SQLITE_TYPES];
+
+    match provider {
+        Some(provider) => native_types(provider)?.iter().find(|t| t.name == name),
+        None => all.iter().flat_map(|types| types.iter()).find(|t| t.name == name),
+    }
+}
+
+// Splits a connector type like `Numeric(10, 2)` into its name and arguments.
+pub fn parse_connector_type(connector_type: &str) -> (String, Vec<String>) {
+    match connector_type.find('(') {
+        Some(start) => {
+            let name = connector_type[..start].trim().to_string();
+            let args = connector_type[start + 1..]
+                .trim_end_matches(')')
+                .split(',')
+                .map(|arg| arg.trim().to_string())
+                .filter(|arg| !arg.is_empty())
+                .collect();
+            (name, args)
+        }
+        None => (connector_type.trim().to_string(), vec![]),
+    }
+}
+
+// Renders a connector type as SQL type, e.g. `DoublePrecision` as `DOUBLE PRECISION`.
+// Unknown types are passed on as they are.
+pub fn connector_type_to_sql(connector_type: &str) -> String {
+    let (name, args) = parse_connector_type(connector_type);
+    let sql_name = find_native_type(None, &name).map_or(name.clone(), |t| t.sql_name.to_string());
+
+    if args.is_empty() {
+        sql_name
+    } else {
+        format!("{}({})", sql_name, args.join(", "))
+    }
+}
@@ -1,3 +1,5 @@
+use super::field::*;
+use super::model::*;
 use super::validator::value::ValueParserError;
 use serde::{Deserialize, Serialize};
 
@@ -40,3 +42,126 @@ impl FromStr for OnDeleteStrategy {
         }
     }
 }
+
+// A relation between two models, found by `Schema::relations`. The query engine and the migration
+// engine both derive the names and the storage of relations from it, so they agree on the database.
+//
+// Side A is the model whose name sorts first. In a self relation, side A is the list field, or the
+// field declared first.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Relation<'a> {
+    pub model_a: &'a Model,
+    pub field_a: &'a Field,
+    pub model_b: &'a Model,
+    pub field_b: &'a Field,
+    name: Option<String>,
+}
+
+// How the records of a relation are linked in the database.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RelationManifestation {
+    // Many-to-many relations use a relation table with one column per side.
+    Table {
+        table: String,
+        model_a_column: String,
+        model_b_column: String,
+    },
+    // All other relations use a column in the table of the model holding the to-one field.
+    Inline { in_table_of_model: String, column: String },
+}
+
+impl<'a> Relation<'a> {
+    pub const MODEL_A_COLUMN: &'static str = "A";
+    pub const MODEL_B_COLUMN: &'static str = "B";
+
+    pub fn new(model: &'a Model, field: &'a Field, related_model: &'a Model, related_field: &'a Field) -> Relation<'a> {
+        let is_list = |field: &Field| field.arity == FieldArity::List;
+        // In an inline self relation, the column belongs to the field on side B.
+        let is_a = if model.name != related_model.name {
+            model.name < related_model.name
+        } else if is_list(field) != is_list(related_field) {
+            is_list(field)
+        } else {
+            let position = |f: &Field| model.fields().position(|mf| mf.name == f.name);
+            position(field) < position(related_field)
+        };
+
+        let name = match &field.field_type {
+            FieldType::Relation(info) => info.name.clone(),
+            _ => None,
+        };
+
+        if is_a {
+            Relation {
+                model_a: model,
+                field_a: field,
+                model_b: related_model,
+                field_b: related_field,
+                name,
+            }
+        } else {
+            Relation {
+                model_a: related_model,
+                field_a: related_field,
+                model_b: model,
+                field_b: field,
+                name,
+            }
+        }
+    }
+
+    // The name given with @relation, or the names of the models, e.g. `PostToUser`.
+    pub fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{}To{}", self.model_a.name, self.model_b.name))
+    }
+
+    pub fn contains(&self, model: &Model, field: &Field) -> bool {
+        (self.model_a.name == model.name && self.field_a.name == field.name)
+            || (self.model_b.name == model.name && self.field_b.name == field.name)
+    }
+
+    pub fn is_self_relation(&self) -> bool {
+        self.model_a.name == self.model_b.name
+    }
+
+    // Relations to embedded models have no manifestation, the records are stored on the parent.
+    pub fn manifestation(&self) -> Option<RelationManifestation> {
+        let is_list = |field: &Field| field.arity == FieldArity::List;
+
+        if self.model_a.is_embedded || self.model_b.is_embedded {
+            return None;
+        }
+
+        let manifestation = match (is_list(self.field_a), is_list(self.field_b)) {
+            (true, true) => RelationManifestation::Table {
+                table: format!("_{}", self.name()),
+                model_a_column: String::from(Self::MODEL_A_COLUMN),
+                model_b_column: String::from(Self::MODEL_B_COLUMN),
+            },
+            // Field A points to many records of model B, so the column is in the table of model B.
+            (true, false) => Self::inline_in(self.model_b, self.field_b),
+            (false, true) => Self::inline_in(self.model_a, self.field_a),
+            // One-to-one relations are inlined on the required side, or on side B.
+            (false, false) if !self.is_self_relation() && self.is_required_only_on_side_a() => {
+                Self::inline_in(self.model_a, self.field_a)
+            }
+            (false, false) => Self::inline_in(self.model_b, self.field_b),
+        };
+
+        Some(manifestation)
+    }
+
+    fn is_required_only_on_side_a(&self) -> bool {
+        self.field_a.arity == FieldArity::Required && self.field_b.arity != FieldArity::Required
+    }
+
+    // The column is named after the field, or its @db name.
+    fn inline_in(model: &Model, field: &Field) -> RelationManifestation {
+        RelationManifestation::Inline {
+            in_table_of_model: model.name.clone(),
+            column: field.database_name.clone().unwrap_or_else(|| field.name.clone()),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Copy, PartialEq, Clone, Serialize, Deserialize)]
@@ -9,17 +10,68 @@ pub enum ScalarType {
     Boolean,
     String,
     DateTime,
+    Json,
+    Bytes,
+    BigInt,
+    Uuid,
     Enum,
 }
 
-// TODO, Check if data types are correct
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Value {
-    Int(i32),
-    Float(f32),
-    Decimal(f32),
+    Int(i64),
+    Float(f64),
+    Decimal(Decimal),
     Boolean(bool),
     String(String),
     DateTime(DateTime<Utc>),
+    Json(serde_json::Value),
+    // Base64 encoded.
+    Bytes(String),
+    BigInt(i64),
+    Uuid(String),
     ConstantLiteral(String),
+    // A value which is generated when a record is created.
+    Function(DefaultFunction),
+}
+
+// Functions which can be used as default values, e.g. `createdAt: DateTime = now()`.
+#[derive(Debug, Copy, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DefaultFunction {
+    Now,
+    Cuid,
+    Uuid,
+    Autoincrement,
+}
+
+impl DefaultFunction {
+    pub fn from_name(name: &str) -> Option<DefaultFunction> {
+        match name {
+            "now" => Some(DefaultFunction::Now),
+            "cuid" => Some(DefaultFunction::Cuid),
+            "uuid" => Some(DefaultFunction::Uuid),
+            "autoincrement" => Some(DefaultFunction::Autoincrement),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DefaultFunction::Now => "now",
+            DefaultFunction::Cuid => "cuid",
+            DefaultFunction::Uuid => "uuid",
+            DefaultFunction::Autoincrement => "autoincrement",
+        }
+    }
+
+    // The field types the function can generate values for.
+    pub fn scalar_types(&self) -> &'static [ScalarType] {
+        match self {
+            DefaultFunction::Now => &[ScalarType::DateTime],
+            DefaultFunction::Cuid => &[ScalarType::String],
+            DefaultFunction::Uuid => &[ScalarType::String, ScalarType::Uuid],
+            DefaultFunction::Autoincrement => &[ScalarType::Int, ScalarType::BigInt],
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use super::comment::*;
 use super::enummodel::*;
+use super::field::*;
 use super::model::*;
+use super::relation::*;
 use serde::{Deserialize, Serialize};
 
 // TODO: Is schema the right name here?
@@ -62,4 +64,52 @@ impl Schema {
     pub fn find_enum(&self, name: &str) -> Option<&Enum> {
         self.enums().find(|m| m.name == *name)
     }
+
+    pub fn find_model_mut(&mut self, name: &str) -> Option<&mut Model> {
+        self.models_mut().find(|m| m.name == *name)
+    }
+
+    // Finds the field on the opposite side of a relation. Both sides of a relation
+    // point to each other's model and carry the same relation name, or no name at all.
+    pub fn related_field(&self, model_name: &str, field_name: &str) -> Option<&Field> {
+        let model = self.find_model(model_name)?;
+        let relation_info = match &model.find_field(field_name)?.field_type {
+            FieldType::Relation(relation_info) => relation_info,
+            _ => return None,
+        };
+        let related_model = self.find_model(&relation_info.to)?;
+
+        related_model
+            .relation_fields()
+            .into_iter()
+            .find(|(f, info)| {
+                info.to == model_name
+                    && info.name == relation_info.name
+                    && !(related_model.name == model_name && f.name == field_name)
+            })
+            .map(|(f, _)| f)
+    }
+
+    // All relations of the datamodel. Both sides of a relation are declared, the relation is
+    // returned once. Relation fields without an opposite field are skipped.
+    pub fn relations(&self) -> Vec<Relation> {
+        let mut result: Vec<Relation> = Vec::new();
+
+        for model in self.models() {
+            for (field, relation_info) in model.relation_fields() {
+                if result.iter().any(|relation| relation.contains(model, field)) {
+                    continue;
+                }
+
+                let related_model = self.find_model(&relation_info.to);
+                let related_field = self.related_field(&model.name, &field.name);
+
+                if let (Some(related_model), Some(related_field)) = (related_model, related_field) {
+                    result.push(Relation::new(model, field, related_model, related_field));
+                }
+            }
+        }
+
+        result
+    }
 }
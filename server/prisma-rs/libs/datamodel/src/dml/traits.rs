@@ -1,3 +1,5 @@
+use super::custom_directive::*;
+
 // Setters are a bit untypical for rust,
 // but we want to have "composeable" struct creation.
 pub trait WithName {
@@ -9,3 +11,15 @@ pub trait WithDatabaseName {
     fn database_name(&self) -> &Option<String>;
     fn set_database_name(&mut self, database_name: &Option<String>);
 }
+
+// The previous name of a model, field or enum, set with @renamedFrom("...").
+// It tells datamodel::diff to treat the change as a rename.
+pub trait WithRenamedFrom {
+    fn renamed_from(&self) -> &Option<String>;
+    fn set_renamed_from(&mut self, renamed_from: &Option<String>);
+}
+
+pub trait WithCustomDirectives {
+    fn custom_directives(&self) -> &Vec<CustomDirective>;
+    fn add_custom_directive(&mut self, directive: CustomDirective);
+}
@@ -0,0 +1,54 @@
+use super::index::parse_field_list;
+use crate::dml;
+use crate::dml::validator::directive::{Args, DirectiveValidator, Error};
+
+// Validates a compound id, e.g. @@id([firstName, lastName]).
+pub struct ModelIdDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for ModelIdDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"id"
+    }
+    fn validate_and_apply(&self, args: &Args, obj: &mut dml::Model) -> Option<Error> {
+        if let Some(id_field) = obj.id_field() {
+            return self.error(
+                &format!(
+                    "Model {} cannot have a compound id, because field {} is already marked as @primary.",
+                    obj.name, id_field.name
+                ),
+                &args.span(),
+            );
+        }
+
+        if obj.has_compound_id() {
+            return self.error(
+                &format!("Model {} must not declare more than one compound id.", obj.name),
+                &args.span(),
+            );
+        }
+
+        let fields = match parse_field_list(args, obj, "id", self.directive_name()) {
+            Ok(fields) => fields,
+            Err(err) => return Some(err),
+        };
+
+        for field_name in &fields {
+            // UNWRAP: parse_field_list only returns existing fields.
+            let field = obj.find_field(field_name).unwrap();
+
+            if field.arity == dml::FieldArity::Optional {
+                return self.error(
+                    &format!(
+                        "Field {} referenced in id is optional, but all fields of an id must be required.",
+                        field_name
+                    ),
+                    &args.span(),
+                );
+            }
+        }
+
+        obj.id_fields = fields;
+
+        return None;
+    }
+}
@@ -0,0 +1,131 @@
+use crate::dml;
+use crate::dml::validator::directive::{Args, DirectiveValidator, Error};
+
+// Parses the field list of a model level directive, e.g. @@unique([a, b]) or
+// @@index(fields: [a, b], name: "my_index"), and checks it against the fields of the model.
+// The kind ("index" or "id") is only used for error messages.
+pub fn parse_field_list(args: &Args, model: &dml::Model, kind: &str, directive_name: &str) -> Result<Vec<String>, Error> {
+    let restriction = match kind {
+        "id" => "part of an id",
+        _ => "indexed",
+    };
+
+    let values = args
+        .default_arg("fields")
+        .as_array()
+        .map_err(|err| Error::new(&err.message, directive_name, &err.span))?;
+
+    if values.is_empty() {
+        return Err(Error::new(
+            &format!("An {} must reference at least one field.", kind),
+            directive_name,
+            &args.span(),
+        ));
+    }
+
+    let mut fields: Vec<String> = vec![];
+
+    for value in values {
+        let field_name = value
+            .as_constant_literal()
+            .map_err(|err| Error::new(&err.message, directive_name, &err.span))?;
+
+        match model.find_field(&field_name) {
+            None => {
+                return Err(Error::new(
+                    &format!(
+                        "Field {} referenced in {} does not exist on model {}.",
+                        field_name, kind, model.name
+                    ),
+                    directive_name,
+                    value.span(),
+                ))
+            }
+            Some(dml::Field {
+                field_type: dml::FieldType::Relation(_),
+                ..
+            }) => {
+                return Err(Error::new(
+                    &format!(
+                        "Field {} referenced in {} is a relation field, which cannot be {}.",
+                        field_name, kind, restriction
+                    ),
+                    directive_name,
+                    value.span(),
+                ))
+            }
+            Some(field) if field.arity == dml::FieldArity::List => {
+                return Err(Error::new(
+                    &format!(
+                        "Field {} referenced in {} is a list field, which cannot be {}.",
+                        field_name, kind, restriction
+                    ),
+                    directive_name,
+                    value.span(),
+                ))
+            }
+            Some(_) if fields.contains(&field_name) => {
+                return Err(Error::new(
+                    &format!("Field {} is referenced more than once in {}.", field_name, kind),
+                    directive_name,
+                    value.span(),
+                ))
+            }
+            Some(_) => fields.push(field_name),
+        }
+    }
+
+    Ok(fields)
+}
+
+fn parse_index(
+    args: &Args,
+    model: &dml::Model,
+    tpe: dml::IndexType,
+    directive_name: &str,
+) -> Result<dml::IndexDefinition, Error> {
+    let fields = parse_field_list(args, model, "index", directive_name)?;
+    let mut index = dml::IndexDefinition::new(fields, tpe);
+
+    if args.arg("name").is_valid() {
+        index.name = Some(
+            args.arg("name")
+                .as_str()
+                .map_err(|err| Error::new(&err.message, directive_name, &err.span))?,
+        );
+    }
+
+    Ok(index)
+}
+
+pub struct ModelUniqueDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for ModelUniqueDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"unique"
+    }
+    fn validate_and_apply(&self, args: &Args, obj: &mut dml::Model) -> Option<Error> {
+        match parse_index(args, obj, dml::IndexType::Unique, self.directive_name()) {
+            Ok(index) => obj.add_index(index),
+            Err(err) => return Some(err),
+        };
+
+        return None;
+    }
+}
+
+pub struct IndexDirectiveValidator {}
+
+impl DirectiveValidator<dml::Model> for IndexDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"index"
+    }
+    fn validate_and_apply(&self, args: &Args, obj: &mut dml::Model) -> Option<Error> {
+        match parse_index(args, obj, dml::IndexType::Normal, self.directive_name()) {
+            Ok(index) => obj.add_index(index),
+            Err(err) => return Some(err),
+        };
+
+        return None;
+    }
+}
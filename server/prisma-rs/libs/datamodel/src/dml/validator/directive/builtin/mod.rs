@@ -1,52 +1,111 @@
 use crate::ast;
 use crate::dml;
 use crate::dml::validator::argument::DirectiveArguments;
-use crate::dml::validator::directive::{DirectiveValidator, DirectiveValidationError};
+use crate::dml::validator::directive::{DirectiveValidator, DirectiveValidationError, DuplicateDirectiveError};
 
 use std::collections::HashMap;
 
 mod db;
 mod default;
 mod embedded;
+mod id;
+mod index;
 mod ondelete;
 mod primary;
 mod relation;
+mod renamed_from;
 mod scalarlist;
 mod sequence;
 mod unique;
+mod updatedat;
 
 // TODO: This should not be in the builtin mod.
 pub struct DirectiveListValidator<T> {
     known_directives: HashMap<&'static str, Box<DirectiveValidator<T>>>,
+    // Unknown directives in these namespaces are kept as custom directives instead of being reported.
+    passthrough_namespaces: Vec<String>,
+    // Directives in this namespace are native types, which are validated separately.
+    native_type_namespace: Option<&'static str>,
 }
 
-impl<T> DirectiveListValidator<T> {
+impl<T: dml::WithCustomDirectives> DirectiveListValidator<T> {
     pub fn new() -> Self {
         DirectiveListValidator {
             known_directives: HashMap::new(),
+            passthrough_namespaces: vec![],
+            native_type_namespace: None,
         }
     }
 
-    pub fn add(&mut self, validator: Box<DirectiveValidator<T>>) {
+    pub fn add(&mut self, validator: Box<DirectiveValidator<T>>) -> Result<(), DuplicateDirectiveError> {
         let name = validator.directive_name();
 
         if self.known_directives.contains_key(name) {
-            panic!("Duplicate directive definition: {:?}", name);
+            return Err(DuplicateDirectiveError {
+                directive_name: String::from(name),
+            });
         }
 
         self.known_directives.insert(name, validator);
+
+        Ok(())
+    }
+
+    // Builtin directives are known to be unique.
+    fn add_builtin(&mut self, validator: Box<DirectiveValidator<T>>) {
+        self.known_directives.insert(validator.directive_name(), validator);
+    }
+
+    pub fn add_passthrough_namespace(&mut self, namespace: &str) {
+        if !self.passthrough_namespaces.iter().any(|n| n == namespace) {
+            self.passthrough_namespaces.push(String::from(namespace));
+        }
+    }
+
+    pub fn set_native_type_namespace(&mut self, namespace: &'static str) {
+        self.native_type_namespace = Some(namespace);
+    }
+
+    // The names of all known directives, in alphabetical order.
+    pub fn directive_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.known_directives.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn is_passthrough(&self, directive_name: &str) -> bool {
+        match namespace(directive_name) {
+            Some(namespace) => self.passthrough_namespaces.iter().any(|n| n == namespace),
+            None => false,
+        }
+    }
+
+    fn is_native_type(&self, directive_name: &str) -> bool {
+        match (namespace(directive_name), self.native_type_namespace) {
+            (Some(namespace), Some(native_type_namespace)) => namespace == native_type_namespace,
+            _ => false,
+        }
     }
 
     pub fn validate_and_apply(&self, ast: &ast::WithDirectives, t: &mut T) -> Vec<DirectiveValidationError> {
+        self.validate_and_apply_directives(ast.directives(), t)
+    }
+
+    pub fn validate_and_apply_directives(&self, directives: &[ast::Directive], t: &mut T) -> Vec<DirectiveValidationError> {
         let mut errors = Vec::<DirectiveValidationError>::new();
 
-        for directive in ast.directives() {
+        for directive in directives {
+            if self.is_native_type(&directive.name) {
+                continue;
+            }
+
             match self.known_directives.get(directive.name.as_str()) {
                 Some(validator) => {
                     if let Some(err) = validator.validate_and_apply(&DirectiveArguments::new(&directive.arguments, directive.span), t) {
                         errors.push(err);   
                     }   
                 }
+                None if self.is_passthrough(&directive.name) => t.add_custom_directive(custom_directive(directive)),
                 None => errors.push(DirectiveValidationError::new("Encountered unknown directive", &directive.name, &directive.span))
             };
         }
@@ -55,40 +114,60 @@ impl<T> DirectiveListValidator<T> {
     }
 }
 
+// The namespace of a directive, e.g. `search` for @search.boost.
+fn namespace(directive_name: &str) -> Option<&str> {
+    directive_name.find('.').map(|pos| &directive_name[..pos])
+}
+
+fn custom_directive(directive: &ast::Directive) -> dml::CustomDirective {
+    dml::CustomDirective::new(
+        &directive.name,
+        directive
+            .arguments
+            .iter()
+            .map(|arg| dml::CustomDirectiveArgument {
+                name: arg.name.clone(),
+                value: arg.value.to_string(),
+            })
+            .collect(),
+    )
+}
+
 pub fn new_builtin_field_directives() -> DirectiveListValidator<dml::Field> {
-    let mut validator = DirectiveListValidator::<dml::Field> {
-        known_directives: HashMap::new(),
-    };
-
-    validator.add(Box::new(db::DbDirectiveValidator {}));
-    validator.add(Box::new(primary::PrimaryDirectiveValidator {}));
-    validator.add(Box::new(scalarlist::ScalarListDirectiveValidator {}));
-    validator.add(Box::new(sequence::SequenceDirectiveValidator {}));
-    validator.add(Box::new(unique::UniqueDirectiveValidator {}));
-    validator.add(Box::new(default::DefaultDirectiveValidator {}));
-    validator.add(Box::new(relation::RelationDirectiveValidator {}));
-    validator.add(Box::new(ondelete::OnDeleteDirectiveValidator {}));
+    let mut validator = DirectiveListValidator::<dml::Field>::new();
+
+    validator.add_builtin(Box::new(db::DbDirectiveValidator {}));
+    validator.add_builtin(Box::new(primary::PrimaryDirectiveValidator {}));
+    validator.add_builtin(Box::new(scalarlist::ScalarListDirectiveValidator {}));
+    validator.add_builtin(Box::new(sequence::SequenceDirectiveValidator {}));
+    validator.add_builtin(Box::new(unique::UniqueDirectiveValidator {}));
+    validator.add_builtin(Box::new(updatedat::UpdatedAtDirectiveValidator {}));
+    validator.add_builtin(Box::new(default::DefaultDirectiveValidator {}));
+    validator.add_builtin(Box::new(relation::RelationDirectiveValidator {}));
+    validator.add_builtin(Box::new(ondelete::OnDeleteDirectiveValidator {}));
+    validator.add_builtin(Box::new(renamed_from::RenamedFromDirectiveValidator {}));
+    validator.set_native_type_namespace(dml::NATIVE_TYPE_NAMESPACE);
 
     return validator;
 }
 
 pub fn new_builtin_model_directives() -> DirectiveListValidator<dml::Model> {
-    let mut validator = DirectiveListValidator::<dml::Model> {
-        known_directives: HashMap::new(),
-    };
+    let mut validator = DirectiveListValidator::<dml::Model>::new();
 
-    validator.add(Box::new(db::DbDirectiveValidator {}));
-    validator.add(Box::new(embedded::EmbeddedDirectiveValidator {}));
+    validator.add_builtin(Box::new(db::DbDirectiveValidator {}));
+    validator.add_builtin(Box::new(embedded::EmbeddedDirectiveValidator {}));
+    validator.add_builtin(Box::new(index::ModelUniqueDirectiveValidator {}));
+    validator.add_builtin(Box::new(index::IndexDirectiveValidator {}));
+    validator.add_builtin(Box::new(id::ModelIdDirectiveValidator {}));
+    validator.add_builtin(Box::new(renamed_from::RenamedFromDirectiveValidator {}));
 
     return validator;
 }
 
 pub fn new_builtin_enum_directives() -> DirectiveListValidator<dml::Enum> {
-    let mut validator = DirectiveListValidator::<dml::Enum> {
-        known_directives: HashMap::new(),
-    };
+    let mut validator = DirectiveListValidator::<dml::Enum>::new();
 
-    // Adds are missing
+    validator.add_builtin(Box::new(renamed_from::RenamedFromDirectiveValidator {}));
 
     return validator;
 }
@@ -0,0 +1,19 @@
+use crate::dml;
+use crate::dml::validator::directive::{Args, DirectiveValidator, Error};
+
+// @renamedFrom("OldName") marks a model, field or enum as renamed, see datamodel::diff.
+pub struct RenamedFromDirectiveValidator {}
+
+impl<T: dml::WithRenamedFrom> DirectiveValidator<T> for RenamedFromDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"renamedFrom"
+    }
+    fn validate_and_apply(&self, args: &Args, obj: &mut T) -> Option<Error> {
+        match args.default_arg("name").as_str() {
+            Ok(value) => obj.set_renamed_from(&Some(value)),
+            Err(err) => return Some(Error::new(&err.message, "renamedFrom", &err.span)),
+        };
+
+        return None;
+    }
+}
@@ -1,5 +1,6 @@
 use crate::dml;
 use crate::dml::validator::directive::{Args, DirectiveValidator, Error};
+use std::convert::TryFrom;
 
 pub struct SequenceDirectiveValidator {}
 
@@ -22,16 +23,30 @@ impl DirectiveValidator<dml::Field> for SequenceDirectiveValidator {
             Err(err) => return self.parser_error(&err),
         }
 
-        match args.arg("allocationSize").as_int() {
-            Ok(allocation_size) => seq.allocation_size = allocation_size,
+        let allocation_size = args.arg("allocationSize");
+        match allocation_size.as_int().map(i32::try_from) {
+            Ok(Ok(allocation_size)) => seq.allocation_size = allocation_size,
+            Ok(Err(_)) => return self.error("number too large to fit in target type", allocation_size.span()),
             Err(err) => return self.parser_error(&err),
         }
 
-        match args.arg("initialValie").as_int() {
-            Ok(initial_value) => seq.initial_value = initial_value,
+        let initial_value = args.arg("initialValue");
+        match initial_value.as_int().map(i32::try_from) {
+            Ok(Ok(initial_value)) => seq.initial_value = initial_value,
+            Ok(Err(_)) => return self.error("number too large to fit in target type", initial_value.span()),
             Err(err) => return self.parser_error(&err),
         }
 
-        return None;
+        // The sequence belongs to the id of the field, so @primary has to come first.
+        match &mut obj.id_info {
+            Some(id_info) => {
+                id_info.sequence = Some(seq);
+                None
+            }
+            None => self.error(
+                "Fields that are marked with @sequence must be marked with @primary first.",
+                args.span(),
+            ),
+        }
     }
 }
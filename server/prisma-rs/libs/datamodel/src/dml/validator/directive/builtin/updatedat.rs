@@ -0,0 +1,23 @@
+use crate::dml;
+use crate::dml::validator::directive::{Args, DirectiveValidator, Error};
+
+pub struct UpdatedAtDirectiveValidator {}
+
+impl DirectiveValidator<dml::Field> for UpdatedAtDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"updatedAt"
+    }
+    fn validate_and_apply(&self, args: &Args, obj: &mut dml::Field) -> Option<Error> {
+        match obj.field_type {
+            dml::FieldType::Base(dml::ScalarType::DateTime)
+            | dml::FieldType::ConnectorSpecific {
+                base_type: dml::ScalarType::DateTime,
+                ..
+            } => {
+                obj.is_updated_at = true;
+                None
+            }
+            _ => self.error("Fields that are marked with @updatedAt must be of type DateTime.", args.span()),
+        }
+    }
+}
@@ -37,6 +37,21 @@ impl std::error::Error for DirectiveValidationError {
     }
 }
 
+// Registering a directive under a name which is already known. This is a mistake in the code
+// registering the directive, not in the datamodel, so there is no location to report.
+#[derive(Debug)]
+pub struct DuplicateDirectiveError {
+    pub directive_name: String,
+}
+
+impl fmt::Display for DuplicateDirectiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Duplicate directive definition: {}", self.directive_name)
+    }
+}
+
+impl std::error::Error for DuplicateDirectiveError {}
+
 pub type Error = DirectiveValidationError;
 pub type Args<'a> = dml::validator::argument::DirectiveArguments<'a>;
 
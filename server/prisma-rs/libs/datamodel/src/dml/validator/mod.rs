@@ -1,13 +1,23 @@
-use crate::{ast, dml};
+use crate::{ast, configuration, dml};
 
 pub mod argument;
 pub mod directive;
+pub mod names;
 pub mod value;
 
-use directive::DirectiveValidationError;
+use directive::{DirectiveValidationError, DuplicateDirectiveError};
 use directive::builtin::{new_builtin_enum_directives, new_builtin_field_directives, new_builtin_model_directives, DirectiveListValidator};
 use value::{ValueValidator, WrappedValue};
 
+// The names of all builtin field types, as accepted by validate_field_type.
+pub const BUILTIN_TYPE_NAMES: &[&str] = &[
+    "ID", "Int", "Float", "Decimal", "Boolean", "String", "DateTime", "Json", "Bytes", "BigInt", "UUID",
+];
+
+// Model directives which reference fields. They are declared as block directives (@@) inside the model,
+// all other model directives after the closing brace.
+pub const MODEL_BLOCK_DIRECTIVES: &[&str] = &["id", "unique", "index"];
+
 pub trait DirectiveSource<T> {
     fn get_directives(validator: &mut DirectiveListValidator<T>);
 }
@@ -28,41 +38,247 @@ impl Validator {
         }
     }
 
+    // Registers an additional field directive, e.g. a team specific @pii.
+    // Fails if a directive with the same name is already known.
+    pub fn add_field_directive(
+        &mut self,
+        validator: Box<directive::DirectiveValidator<dml::Field>>,
+    ) -> Result<(), DuplicateDirectiveError> {
+        self.field_directives.add(validator)
+    }
+
+    pub fn add_model_directive(
+        &mut self,
+        validator: Box<directive::DirectiveValidator<dml::Model>>,
+    ) -> Result<(), DuplicateDirectiveError> {
+        self.model_directives.add(validator)
+    }
+
+    pub fn add_enum_directive(
+        &mut self,
+        validator: Box<directive::DirectiveValidator<dml::Enum>>,
+    ) -> Result<(), DuplicateDirectiveError> {
+        self.enum_directives.add(validator)
+    }
+
+    // Keeps unknown directives of the given namespace, e.g. @search.boost(2) for "search",
+    // as custom directives on fields, models and enums instead of reporting them.
+    pub fn allow_passthrough(&mut self, namespace: &str) {
+        self.field_directives.add_passthrough_namespace(namespace);
+        self.model_directives.add_passthrough_namespace(namespace);
+        self.enum_directives.add_passthrough_namespace(namespace);
+    }
+
+    // The names of all directives known on fields, e.g. for completion in editors.
+    pub fn field_directive_names(&self) -> Vec<&'static str> {
+        self.field_directives.directive_names()
+    }
+
+    pub fn model_directive_names(&self) -> Vec<&'static str> {
+        self.model_directives.directive_names()
+    }
+
+    pub fn model_block_directive_names(&self) -> Vec<&'static str> {
+        self.model_directives
+            .directive_names()
+            .into_iter()
+            .filter(|name| MODEL_BLOCK_DIRECTIVES.contains(name))
+            .collect()
+    }
+
+    // Validates the whole schema. Validation does not stop at the first invalid model,
+    // enum or field, so that all errors in the schema are reported at once.
     pub fn validate(&self, ast_schema: &ast::Schema) -> Result<dml::Schema, Vec<DirectiveValidationError>> {
         let mut schema = dml::Schema::new();
+        let mut errors = Vec::<DirectiveValidationError>::new();
 
         for ast_obj in &ast_schema.models {
             match ast_obj {
-                ast::ModelOrEnum::Enum(en) => schema.add_enum(self.validate_enum(&en)?),
-                ast::ModelOrEnum::Model(ty) => schema.add_model(self.validate_model(&ty, ast_schema)?),
+                ast::ModelOrEnum::Enum(en) => match self.validate_enum(&en) {
+                    Ok(en) => schema.add_enum(en),
+                    Err(mut errs) => errors.append(&mut errs),
+                },
+                ast::ModelOrEnum::Model(ty) => match self.validate_model(&ty, ast_schema) {
+                    Ok(model) => schema.add_model(model),
+                    Err(mut errs) => errors.append(&mut errs),
+                },
+                // Datasources and generators are not part of the dml, see configuration::load.
+                ast::ModelOrEnum::Source(_) | ast::ModelOrEnum::Generator(_) => {}
             }
         }
 
+        // Relations can only be resolved once all models are known.
+        errors.append(&mut self.resolve_relations(ast_schema, &mut schema));
+        errors.append(&mut self.validate_relations(ast_schema, &schema));
+
+        let provider = self.datasource_provider(ast_schema);
+        errors.append(&mut names::validate_names(ast_schema, provider.as_ref().map(String::as_str)));
+
+        if errors.len() > 0 {
+            return Err(errors);
+        }
+
         return Ok(schema);
     }
 
+    // Resolves the field each relation field points to: Either the field given in braces
+    // after the type, e.g. `author: User(email)`, or the id field of the related model.
+    fn resolve_relations(&self, ast_schema: &ast::Schema, schema: &mut dml::Schema) -> Vec<DirectiveValidationError> {
+        let mut errors = Vec::<DirectiveValidationError>::new();
+        let mut resolved: Vec<(String, String, String)> = vec![];
+
+        for model in schema.models() {
+            for (field, relation_info) in model.relation_fields() {
+                let ast_field = self.find_ast_field(ast_schema, &model.name, &field.name);
+
+                // A missing related model was already reported when validating the field type.
+                if let (Some(related_model), Some(ast_field)) = (schema.find_model(&relation_info.to), ast_field) {
+                    match &ast_field.field_link {
+                        Some(link) if related_model.find_field(link).is_none() => errors.push(DirectiveValidationError::new(
+                            &format!("Field {} referenced in relation does not exist on model {}.", link, related_model.name),
+                            "",
+                            &ast_field.span,
+                        )),
+                        Some(link) => resolved.push((model.name.clone(), field.name.clone(), link.clone())),
+                        // A related model with a compound id is referenced by all of its id fields,
+                        // so to_field stays empty in that case.
+                        None => {
+                            if let Some(id_field) = related_model.id_field() {
+                                resolved.push((model.name.clone(), field.name.clone(), id_field.name.clone()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for (model_name, field_name, to_field) in resolved {
+            let field = schema
+                .find_model_mut(&model_name)
+                .and_then(|m| m.find_field_mut(&field_name))
+                .expect("Resolved a relation on a non-existing field.");
+
+            if let dml::FieldType::Relation(relation_info) = &mut field.field_type {
+                relation_info.to_field = to_field;
+            }
+        }
+
+        errors
+    }
+
+    // Checks that every relation field has a back-relation field on the related model,
+    // and that the fields of a relation can be paired up unambiguously.
+    fn validate_relations(&self, ast_schema: &ast::Schema, schema: &dml::Schema) -> Vec<DirectiveValidationError> {
+        let mut errors = Vec::<DirectiveValidationError>::new();
+
+        for model in schema.models() {
+            for (field, relation_info) in model.relation_fields() {
+                let span = match self.find_ast_field(ast_schema, &model.name, &field.name) {
+                    Some(ast_field) => ast_field.span,
+                    None => ast::Span::empty(),
+                };
+
+                if !schema.has_model(&relation_info.to) {
+                    continue;
+                }
+
+                let is_self_relation = relation_info.to == model.name;
+                let parallel_fields = model
+                    .relation_fields()
+                    .into_iter()
+                    .filter(|(_, info)| info.to == relation_info.to && info.name == relation_info.name)
+                    .count();
+
+                // A self relation consists of two fields on the same model.
+                if (is_self_relation && parallel_fields > 2) || (!is_self_relation && parallel_fields > 1) {
+                    let message = match &relation_info.name {
+                        Some(name) => format!(
+                            "Ambiguous relation detected. The relation name {} is used by more than one relation between {} and {}.",
+                            name, model.name, relation_info.to
+                        ),
+                        None => format!(
+                            "Ambiguous relation detected. There is more than one unnamed relation between {} and {}. Please name the relations using @relation(name: ...).",
+                            model.name, relation_info.to
+                        ),
+                    };
+                    errors.push(DirectiveValidationError::new(&message, "relation", &span));
+                } else if schema.related_field(&model.name, &field.name).is_none() {
+                    let message = match &relation_info.name {
+                        Some(name) => format!(
+                            "The relation field {} on model {} is missing an opposite relation field named {} on model {}.",
+                            field.name, model.name, name, relation_info.to
+                        ),
+                        None => format!(
+                            "The relation field {} on model {} is missing an opposite relation field on model {}.",
+                            field.name, model.name, relation_info.to
+                        ),
+                    };
+                    errors.push(DirectiveValidationError::new(&message, "", &span));
+                }
+            }
+        }
+
+        errors
+    }
+
+    fn find_ast_field<'a>(&self, ast_schema: &'a ast::Schema, model_name: &str, field_name: &str) -> Option<&'a ast::Field> {
+        for obj in &ast_schema.models {
+            if let ast::ModelOrEnum::Model(model) = obj {
+                if model.name == model_name {
+                    return model.fields.iter().find(|f| f.name == field_name);
+                }
+            }
+        }
+
+        None
+    }
+
     fn validate_model(&self, ast_model: &ast::Model, ast_schema: &ast::Schema) -> Result<dml::Model, Vec<DirectiveValidationError>> {
         let mut model = dml::Model::new(&ast_model.name);
+        model.comments = self.validate_comments(ast_model);
+
+        let mut errors = Vec::<DirectiveValidationError>::new();
 
         for ast_field in &ast_model.fields {
-            model.add_field(self.validate_field(ast_field, ast_schema)?);
+            // Declarations with syntax errors are already reported by the parser.
+            if ast_schema.broken_declarations.contains(&ast_field.field_type) {
+                continue;
+            }
+
+            match self.validate_field(ast_field, ast_schema) {
+                Ok(field) => model.add_field(field),
+                Err(mut errs) => errors.append(&mut errs),
+            }
         }
 
-        let errs = self.model_directives.validate_and_apply(ast_model, &mut model);
+        errors.append(&mut self.validate_model_directive_positions(ast_model));
+        errors.append(&mut self.model_directives.validate_and_apply(ast_model, &mut model));
+        errors.append(
+            &mut self
+                .model_directives
+                .validate_and_apply_directives(&ast_model.block_directives, &mut model),
+        );
 
-        if(errs.len() > 0) {
-            return Err(errs);
+        if errors.len() > 0 {
+            return Err(errors);
         }
 
         return Ok(model);
     }
 
     fn validate_enum(&self, ast_enum: &ast::Enum) -> Result<dml::Enum, Vec<DirectiveValidationError>> {
-        let mut en = dml::Enum::new(&ast_enum.name, ast_enum.values.clone());
+        let mut en = dml::Enum::new(&ast_enum.name, vec![]);
+        en.comments = self.validate_comments(ast_enum);
+
+        for ast_value in &ast_enum.values {
+            let mut value = dml::EnumValue::new(&ast_value.name);
+            value.comments = self.validate_comments(ast_value);
+            en.values.push(value);
+        }
 
         let errs = self.enum_directives.validate_and_apply(ast_enum, &mut en);
 
-        if(errs.len() > 0) {
+        if errs.len() > 0 {
             return Err(errs);
         }
 
@@ -75,31 +291,211 @@ impl Validator {
         let mut field = dml::Field::new(&ast_field.name, field_type.clone());
 
         field.arity = self.validate_field_arity(&ast_field.arity);
+        field.comments = self.validate_comments(ast_field);
+
+        let mut errors = Vec::<DirectiveValidationError>::new();
 
         if let Some(value) = &ast_field.default_value {
-            if let dml::FieldType::Base(base_type) = &field_type {
-                // TODO: Proper error handling.
-                // TODO: WrappedValue is not the tool of choice here,
-                // there should be a static func for converting stuff.
-                field.default_value = Some(
-                    (WrappedValue { value: value.clone() })
-                        .as_type(base_type)
-                        .expect("Unable to parse."),
-                );
-            } else {
-                unimplemented!("Found a default value for a non-scalar type.")
+            match (WrappedValue { value: value.clone() }).as_default_value(&field.field_type) {
+                Ok(value) => field.default_value = Some(value),
+                Err(err) => errors.push(DirectiveValidationError::new(&err.message, "", &err.span)),
             }
         }
 
-        let errs = self.field_directives.validate_and_apply(ast_field, &mut field);
+        errors.append(&mut self.field_directives.validate_and_apply(ast_field, &mut field));
+        errors.append(&mut self.validate_native_types(ast_field, ast_schema, &mut field));
 
-        if(errs.len() > 0) {
-            return Err(errs);
+        // Enum defaults can only be checked against the enum declaration once all directives are applied.
+        if let (dml::FieldType::Enum(enum_name), Some(dml::Value::ConstantLiteral(value))) =
+            (&field.field_type, &field.default_value)
+        {
+            if !self.enum_has_value(ast_schema, enum_name, value) {
+                errors.push(DirectiveValidationError::new(
+                    &format!("Default value {} is not a value of enum {}.", value, enum_name),
+                    "",
+                    &self.default_value_span(ast_field),
+                ));
+            }
+        }
+
+        // Only ids are generated when a record is created, so generated defaults require an id field.
+        if let Some(dml::Value::Function(function)) = &field.default_value {
+            if *function != dml::DefaultFunction::Now && field.id_info.is_none() {
+                errors.push(DirectiveValidationError::new(
+                    &format!("The function {}() can only be used as default value for id fields.", function.name()),
+                    "",
+                    &self.default_value_span(ast_field),
+                ));
+            }
+        }
+
+        if errors.len() > 0 {
+            return Err(errors);
         }
 
         return Ok(field);
     }
 
+    // Applies native types like @db.VarChar(255). The type is checked against the connector of the
+    // datasource, or against all connectors if the schema does not declare a datasource.
+    fn validate_native_types(
+        &self,
+        ast_field: &ast::Field,
+        ast_schema: &ast::Schema,
+        field: &mut dml::Field,
+    ) -> Vec<DirectiveValidationError> {
+        let mut errors = Vec::<DirectiveValidationError>::new();
+        let prefix = format!("{}.", dml::NATIVE_TYPE_NAMESPACE);
+        let native_types: Vec<&ast::Directive> = ast_field.directives.iter().filter(|d| d.name.starts_with(&prefix)).collect();
+
+        if native_types.is_empty() {
+            return errors;
+        }
+
+        let provider = self.datasource_provider(ast_schema);
+
+        for directive in native_types {
+            let error = |message: String| DirectiveValidationError::new(&message, &directive.name, &directive.span);
+            let type_name = &directive.name[prefix.len()..];
+
+            let base_type = match &field.field_type {
+                dml::FieldType::Base(base_type) => *base_type,
+                dml::FieldType::ConnectorSpecific { .. } => {
+                    errors.push(error(String::from("A field can only have one native type.")));
+                    continue;
+                }
+                _ => {
+                    errors.push(error(String::from("Native types can only be used on scalar fields.")));
+                    continue;
+                }
+            };
+
+            let native_type = match (dml::find_native_type(provider.as_ref().map(|p| p.as_str()), type_name), &provider) {
+                (Some(native_type), _) => native_type,
+                (None, Some(provider)) => {
+                    errors.push(error(format!(
+                        "Native type {} is not supported by the {} connector.",
+                        type_name, provider
+                    )));
+                    continue;
+                }
+                (None, None) => {
+                    errors.push(error(format!("Unknown native type {}.", type_name)));
+                    continue;
+                }
+            };
+
+            if !native_type.scalar_types.contains(&base_type) {
+                errors.push(error(format!(
+                    "Native type {} is not compatible with field type {}.",
+                    type_name, ast_field.field_type
+                )));
+                continue;
+            }
+
+            if directive.arguments.len() != native_type.argument_count {
+                errors.push(error(format!(
+                    "Native type {} expects {} arguments, but received {}.",
+                    type_name,
+                    native_type.argument_count,
+                    directive.arguments.len()
+                )));
+                continue;
+            }
+
+            let mut args: Vec<String> = vec![];
+
+            for arg in &directive.arguments {
+                match (WrappedValue { value: arg.value.clone() }).as_int() {
+                    Ok(value) => args.push(value.to_string()),
+                    Err(err) => errors.push(DirectiveValidationError::new(&err.message, &directive.name, &err.span)),
+                }
+            }
+
+            field.field_type = dml::FieldType::ConnectorSpecific {
+                base_type,
+                connector_type: Some(if args.is_empty() {
+                    String::from(type_name)
+                } else {
+                    format!("{}({})", type_name, args.join(", "))
+                }),
+            };
+        }
+
+        errors
+    }
+
+    // The provider of the enabled datasource, e.g. "postgresql". Invalid datasources are
+    // reported by configuration::load, the schema is then validated against all connectors.
+    fn datasource_provider(&self, ast_schema: &ast::Schema) -> Option<String> {
+        let configuration = configuration::load(ast_schema).ok()?;
+        configuration.default_source().map(|source| source.provider.clone())
+    }
+
+    fn enum_has_value(&self, ast_schema: &ast::Schema, enum_name: &str, value: &str) -> bool {
+        ast_schema.models.iter().any(|obj| match obj {
+            ast::ModelOrEnum::Enum(en) => en.name == enum_name && en.values.iter().any(|v| v.name == value),
+            _ => false,
+        })
+    }
+
+    // The span of the default value syntax sugar, the default directive, or the whole field.
+    fn default_value_span(&self, ast_field: &ast::Field) -> ast::Span {
+        if let Some(value) = &ast_field.default_value {
+            return value.span().clone();
+        }
+
+        match ast_field.directives.iter().rev().find(|d| d.name == "default") {
+            Some(directive) => directive.span,
+            None => ast_field.span,
+        }
+    }
+
+    // Directives referencing fields have to be declared inside the model, e.g. @@unique([a, b]), all others after it.
+    fn validate_model_directive_positions(&self, ast_model: &ast::Model) -> Vec<DirectiveValidationError> {
+        let misplaced_directives = ast_model
+            .directives
+            .iter()
+            .filter(|directive| MODEL_BLOCK_DIRECTIVES.contains(&directive.name.as_str()))
+            .map(|directive| {
+                DirectiveValidationError::new(
+                    &format!(
+                        "The directive @{} has to be declared inside the model as @@{}.",
+                        directive.name, directive.name
+                    ),
+                    &directive.name,
+                    &directive.span,
+                )
+            });
+
+        let misplaced_block_directives = ast_model
+            .block_directives
+            .iter()
+            .filter(|directive| !MODEL_BLOCK_DIRECTIVES.contains(&directive.name.as_str()))
+            .map(|directive| {
+                DirectiveValidationError::new(
+                    &format!(
+                        "The directive @@{} is not a block directive, it has to be declared after the model as @{}.",
+                        directive.name, directive.name
+                    ),
+                    &directive.name,
+                    &directive.span,
+                )
+            });
+
+        misplaced_directives.chain(misplaced_block_directives).collect()
+    }
+
+    fn validate_comments(&self, ast: &ast::WithComments) -> Vec<dml::Comment> {
+        ast.comments()
+            .iter()
+            .map(|comment| dml::Comment {
+                text: comment.text.clone(),
+                is_error: comment.is_error,
+            })
+            .collect()
+    }
+
     fn validate_field_arity(&self, ast_field: &ast::FieldArity) -> dml::FieldArity {
         match ast_field {
             ast::FieldArity::Required => dml::FieldArity::Required,
@@ -117,11 +513,15 @@ impl Validator {
             "Boolean" => Ok(dml::FieldType::Base(dml::ScalarType::Boolean)),
             "String" => Ok(dml::FieldType::Base(dml::ScalarType::String)),
             "DateTime" => Ok(dml::FieldType::Base(dml::ScalarType::DateTime)),
+            "Json" => Ok(dml::FieldType::Base(dml::ScalarType::Json)),
+            "Bytes" => Ok(dml::FieldType::Base(dml::ScalarType::Bytes)),
+            "BigInt" => Ok(dml::FieldType::Base(dml::ScalarType::BigInt)),
+            "UUID" => Ok(dml::FieldType::Base(dml::ScalarType::Uuid)),
             // Distinguish between relation and enum.
             _ => {
                 for model in &ast_schema.models {
                     match &model {
-                        // TODO: Get primary key field and hook up String::from.
+                        // The related field is resolved once all models are validated.
                         ast::ModelOrEnum::Model(model) if model.name == *type_name => {
                             return Ok(dml::FieldType::Relation(dml::RelationInfo::new(&type_name, "")))
                         }
@@ -132,7 +532,11 @@ impl Validator {
                     }
                 }
 
-                Err(vec![DirectiveValidationError::new("Unknown type encountered.", "", span)])
+                Err(vec![DirectiveValidationError::new(
+                    &format!("Type {} is neither a built-in type, nor refers to another model or enum.", type_name),
+                    "",
+                    span,
+                )])
             }
         }
     }
@@ -0,0 +1,272 @@
+use crate::ast;
+use crate::dml::validator::directive::DirectiveValidationError;
+
+// The table the migration engine keeps the migration history in.
+pub const MIGRATION_TABLE_NAME: &str = "_Migration";
+
+// Types of the generated API which do not depend on the models. Models and enums with these names
+// would collide with them. All other names are quoted in SQL, so they need no reservation.
+pub const RESERVED_NAMES: &[&str] = &["Query", "Mutation", "PageInfo", "BatchPayload"];
+
+// Checks the names of models, enums and fields for duplicates and reserved names, and the
+// database names they are mapped to for collisions. Names are compared case-insensitively
+// if the database of the datasource does so.
+pub fn validate_names(ast_schema: &ast::Schema, provider: Option<&str>) -> Vec<DirectiveValidationError> {
+    let names = Names {
+        provider: provider.map(String::from),
+    };
+
+    let mut errors = Vec::new();
+    let mut declarations: Vec<Declaration> = Vec::new();
+
+    for obj in &ast_schema.models {
+        let declaration = match obj {
+            ast::ModelOrEnum::Model(model) => Declaration {
+                kind: "model",
+                name: &model.name,
+                span: model.span,
+            },
+            ast::ModelOrEnum::Enum(en) => Declaration {
+                kind: "enum",
+                name: &en.name,
+                span: en.span,
+            },
+            ast::ModelOrEnum::Source(_) | ast::ModelOrEnum::Generator(_) => continue,
+        };
+
+        if RESERVED_NAMES.contains(&declaration.name) {
+            errors.push(DirectiveValidationError::new(
+                &format!(
+                    "The {} name {} is reserved for a type of the generated API. Please choose another name.",
+                    declaration.kind, declaration.name
+                ),
+                "",
+                &declaration.span,
+            ));
+        }
+
+        match declarations.iter().find(|d| names.eq(d.name, declaration.name)) {
+            Some(previous) => errors.push(names.conflict(&declaration, previous, "")),
+            None => declarations.push(declaration),
+        }
+    }
+
+    let mut tables: Vec<Mapping> = Vec::new();
+
+    for obj in &ast_schema.models {
+        match obj {
+            ast::ModelOrEnum::Model(model) => {
+                errors.append(&mut names.validate_fields(model));
+
+                // Only the first declaration of a name is checked, the others are already reported.
+                let is_first = declarations
+                    .iter()
+                    .any(|d| d.name == model.name && d.span == model.span);
+                let is_embedded = model.directives.iter().any(|d| d.name == "embedded");
+
+                if is_first && !is_embedded {
+                    let table = Mapping::new(&model.name, &model.directives, model.span);
+                    errors.append(&mut names.validate_table(&table, &tables));
+                    tables.push(table);
+                }
+            }
+            ast::ModelOrEnum::Enum(en) => errors.append(&mut names.validate_enum_values(en)),
+            ast::ModelOrEnum::Source(_) | ast::ModelOrEnum::Generator(_) => {}
+        }
+    }
+
+    errors
+}
+
+struct Declaration<'a> {
+    kind: &'static str,
+    name: &'a str,
+    span: ast::Span,
+}
+
+// A model or field with the name it has in the database.
+struct Mapping<'a> {
+    name: &'a str,
+    db_name: String,
+    // The span of the @db directive, if the name is mapped.
+    directive_span: Option<ast::Span>,
+    span: ast::Span,
+}
+
+impl<'a> Mapping<'a> {
+    fn new(name: &'a str, directives: &[ast::Directive], span: ast::Span) -> Mapping<'a> {
+        let mapped = directives.iter().filter(|d| d.name == "db").find_map(|directive| {
+            directive
+                .arguments
+                .iter()
+                .find(|arg| arg.name == "name" || arg.name.is_empty())
+                .and_then(|arg| match &arg.value {
+                    ast::Value::StringValue(value, _) => Some((value.clone(), directive.span)),
+                    _ => None,
+                })
+        });
+
+        match mapped {
+            Some((db_name, directive_span)) => Mapping {
+                name,
+                db_name,
+                directive_span: Some(directive_span),
+                span,
+            },
+            None => Mapping {
+                name,
+                db_name: String::from(name),
+                directive_span: None,
+                span,
+            },
+        }
+    }
+
+    // Errors on mapped names are reported on the @db directive.
+    fn error(&self, message: &str) -> DirectiveValidationError {
+        match &self.directive_span {
+            Some(span) => DirectiveValidationError::new(message, "db", span),
+            None => DirectiveValidationError::new(message, "", &self.span),
+        }
+    }
+}
+
+struct Names {
+    provider: Option<String>,
+}
+
+impl Names {
+    // MySQL and SQLite compare table and column names case-insensitively.
+    fn is_case_insensitive(&self) -> bool {
+        match self.provider.as_ref().map(String::as_str) {
+            Some("mysql") | Some("sqlite") => true,
+            _ => false,
+        }
+    }
+
+    fn eq(&self, a: &str, b: &str) -> bool {
+        if self.is_case_insensitive() {
+            a.to_lowercase() == b.to_lowercase()
+        } else {
+            a == b
+        }
+    }
+
+    fn conflict(&self, declaration: &Declaration, previous: &Declaration, location: &str) -> DirectiveValidationError {
+        let message = if declaration.name == previous.name && declaration.kind == previous.kind {
+            format!(
+                "{} {} is already declared{}.",
+                capitalize(declaration.kind),
+                declaration.name,
+                location
+            )
+        } else if declaration.name == previous.name {
+            format!(
+                "{} {} conflicts with {} {}{}.",
+                capitalize(declaration.kind),
+                declaration.name,
+                previous.kind,
+                previous.name,
+                location
+            )
+        } else {
+            format!(
+                "{} {} conflicts with {} {}{}, names are case-insensitive in {}.",
+                capitalize(declaration.kind),
+                declaration.name,
+                previous.kind,
+                previous.name,
+                location,
+                self.provider.as_ref().map(String::as_str).unwrap_or("the database")
+            )
+        };
+
+        DirectiveValidationError::new(&message, "", &declaration.span)
+    }
+
+    fn validate_fields(&self, model: &ast::Model) -> Vec<DirectiveValidationError> {
+        let location = format!(" on model {}", model.name);
+        let mut errors = Vec::new();
+        let mut declarations: Vec<Declaration> = Vec::new();
+        let mut columns: Vec<Mapping> = Vec::new();
+
+        for field in &model.fields {
+            let declaration = Declaration {
+                kind: "field",
+                name: &field.name,
+                span: field.span,
+            };
+
+            if let Some(previous) = declarations.iter().find(|d| self.eq(d.name, &field.name)) {
+                errors.push(self.conflict(&declaration, previous, &location));
+                continue;
+            }
+
+            declarations.push(declaration);
+
+            // Lists are stored in tables of their own.
+            if let ast::FieldArity::List = field.arity {
+                continue;
+            }
+
+            let column = Mapping::new(&field.name, &field.directives, field.span);
+
+            if let Some(previous) = columns.iter().find(|c| self.eq(&c.db_name, &column.db_name)) {
+                errors.push(column.error(&format!(
+                    "Field {} is mapped to column {}, which is already used by field {}{}.",
+                    column.name, column.db_name, previous.name, location
+                )));
+                continue;
+            }
+
+            columns.push(column);
+        }
+
+        errors
+    }
+
+    fn validate_table(&self, table: &Mapping, previous_tables: &[Mapping]) -> Vec<DirectiveValidationError> {
+        let mut errors = Vec::new();
+
+        if self.eq(&table.db_name, MIGRATION_TABLE_NAME) {
+            errors.push(table.error(&format!(
+                "The table name {} of model {} is reserved for the migration history.",
+                table.db_name, table.name
+            )));
+        }
+
+        if let Some(previous) = previous_tables.iter().find(|t| self.eq(&t.db_name, &table.db_name)) {
+            errors.push(table.error(&format!(
+                "Model {} is mapped to table {}, which is already used by model {}.",
+                table.name, table.db_name, previous.name
+            )));
+        }
+
+        errors
+    }
+
+    fn validate_enum_values(&self, en: &ast::Enum) -> Vec<DirectiveValidationError> {
+        let mut errors = Vec::new();
+
+        for (index, value) in en.values.iter().enumerate() {
+            if en.values[..index].iter().any(|previous| previous.name == value.name) {
+                errors.push(DirectiveValidationError::new(
+                    &format!("Value {} is already declared on enum {}.", value.name, en.name),
+                    "",
+                    &value.span,
+                ));
+            }
+        }
+
+        errors
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -2,8 +2,8 @@ use crate::ast;
 use crate::dml;
 
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use std::error;
-use std::error::Error;
 use std::fmt;
 
 
@@ -24,7 +24,7 @@ impl ValueParserError {
     ) -> Result<T, ValueParserError> {
         match result {
             Ok(val) => Ok(val),
-            Err(err) => Err(ValueParserError::new(err.description(), raw_value, span)),
+            Err(err) => Err(ValueParserError::new(&err.to_string(), raw_value, span)),
         }
     }
 
@@ -57,7 +57,7 @@ macro_rules! wrap_value (
     ($value:expr, $wrapper:expr, $raw:expr, $span:expr) => ({
         match $value {
             Ok(val) => Ok($wrapper(val)),
-            Err(err) => Err(ValueParserError::new(err.description(), $raw, $span))
+            Err(err) => Err(ValueParserError::new(&err.message, $raw, $span))
         }
     })
 );
@@ -65,29 +65,114 @@ macro_rules! wrap_value (
 pub trait ValueValidator {
     fn is_valid(&self) -> bool;
 
-    fn raw(&self) -> &String;
+    fn raw(&self) -> String;
     fn span(&self) -> &ast::Span;
     fn as_str(&self) -> Result<String, ValueParserError>;
-    fn as_int(&self) -> Result<i32, ValueParserError>;
-    fn as_float(&self) -> Result<f32, ValueParserError>;
-    fn as_decimal(&self) -> Result<f32, ValueParserError>;
+    fn as_int(&self) -> Result<i64, ValueParserError>;
+    fn as_float(&self) -> Result<f64, ValueParserError>;
+    fn as_decimal(&self) -> Result<Decimal, ValueParserError>;
     fn as_bool(&self) -> Result<bool, ValueParserError>;
     fn as_date_time(&self) -> Result<DateTime<Utc>, ValueParserError>;
     fn as_constant_literal(&self) -> Result<String, ValueParserError>;
+    fn as_array(&self) -> Result<Vec<Box<ValueValidator>>, ValueParserError>;
+    fn as_default_function(&self) -> Result<dml::DefaultFunction, ValueParserError>;
+
+    fn is_function(&self) -> bool {
+        false
+    }
 
     fn as_type(&self, scalar_type: &dml::ScalarType) -> Result<dml::Value, ValueParserError> {
         match scalar_type {
-            dml::ScalarType::Int => wrap_value!(self.as_int(), dml::Value::Int, self.raw(), self.span()),
-            dml::ScalarType::Float => wrap_value!(self.as_float(), dml::Value::Float, self.raw(), self.span()),
-            dml::ScalarType::Decimal => wrap_value!(self.as_decimal(), dml::Value::Decimal, self.raw(), self.span()),
-            dml::ScalarType::Boolean => wrap_value!(self.as_bool(), dml::Value::Boolean, self.raw(), self.span()),
+            dml::ScalarType::Int => wrap_value!(self.as_int(), dml::Value::Int, &self.raw(), self.span()),
+            dml::ScalarType::Float => wrap_value!(self.as_float(), dml::Value::Float, &self.raw(), self.span()),
+            dml::ScalarType::Decimal => wrap_value!(self.as_decimal(), dml::Value::Decimal, &self.raw(), self.span()),
+            dml::ScalarType::Boolean => wrap_value!(self.as_bool(), dml::Value::Boolean, &self.raw(), self.span()),
             dml::ScalarType::DateTime => {
-                wrap_value!(self.as_date_time(), dml::Value::DateTime, self.raw(), self.span())
+                wrap_value!(self.as_date_time(), dml::Value::DateTime, &self.raw(), self.span())
+            }
+            dml::ScalarType::Enum => wrap_value!(self.as_str(), dml::Value::ConstantLiteral, &self.raw(), self.span()),
+            dml::ScalarType::String => wrap_value!(self.as_str(), dml::Value::String, &self.raw(), self.span()),
+            dml::ScalarType::BigInt => wrap_value!(self.as_int(), dml::Value::BigInt, &self.raw(), self.span()),
+            dml::ScalarType::Json => {
+                let value = ast::unescape_string(&self.as_str()?);
+                ValueParserError::wrap(serde_json::from_str(&value), &self.raw(), self.span()).map(dml::Value::Json)
+            }
+            dml::ScalarType::Bytes => {
+                let value = self.as_str()?;
+                if is_base64(&value) {
+                    Ok(dml::Value::Bytes(value))
+                } else {
+                    Err(ValueParserError::new(
+                        "Expected a base64 encoded value.",
+                        &self.raw(),
+                        self.span(),
+                    ))
+                }
+            }
+            dml::ScalarType::Uuid => {
+                let value = self.as_str()?;
+                if is_uuid(&value) {
+                    Ok(dml::Value::Uuid(value))
+                } else {
+                    Err(ValueParserError::new(
+                        "Expected a UUID value.",
+                        &self.raw(),
+                        self.span(),
+                    ))
+                }
             }
-            dml::ScalarType::Enum => wrap_value!(self.as_str(), dml::Value::ConstantLiteral, self.raw(), self.span()),
-            dml::ScalarType::String => wrap_value!(self.as_str(), dml::Value::String, self.raw(), self.span()),
         }
     }
+
+    // Converts the value into a default value for a field of the given type.
+    // Enum values are only checked for their syntax here, the validator checks
+    // if the value is declared on the enum.
+    fn as_default_value(&self, field_type: &dml::FieldType) -> Result<dml::Value, ValueParserError> {
+        if self.is_function() {
+            let function = self.as_default_function()?;
+
+            return match field_type {
+                dml::FieldType::Base(scalar_type) | dml::FieldType::ConnectorSpecific { base_type: scalar_type, .. }
+                    if function.scalar_types().contains(scalar_type) =>
+                {
+                    Ok(dml::Value::Function(function))
+                }
+                _ => Err(ValueParserError::new(
+                    &format!("The function {}() cannot be used as default value for this field.", function.name()),
+                    &self.raw(),
+                    self.span(),
+                )),
+            };
+        }
+
+        match field_type {
+            dml::FieldType::Base(scalar_type) => self.as_type(scalar_type),
+            dml::FieldType::ConnectorSpecific { base_type, .. } => self.as_type(base_type),
+            dml::FieldType::Enum(_) => self.as_constant_literal().map(dml::Value::ConstantLiteral),
+            dml::FieldType::Relation(_) => Err(ValueParserError::new(
+                "Cannot set a default value on a relation field.",
+                &self.raw(),
+                self.span(),
+            )),
+        }
+    }
+}
+
+// Standard base64 with padding.
+fn is_base64(value: &str) -> bool {
+    let data = value.trim_end_matches('=');
+
+    value.len() % 4 == 0
+        && value.len() - data.len() <= 2
+        && data.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+}
+
+// UUIDs in their hyphenated form, e.g. 123e4567-e89b-12d3-a456-426655440000.
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+
+    lengths == [8, 4, 4, 4, 12] && groups.iter().all(|group| group.chars().all(|c| c.is_ascii_hexdigit()))
 }
 
 // TODO: Inject error accumulation.
@@ -101,64 +186,68 @@ impl ValueValidator for WrappedValue {
         true
     }
 
-    fn raw(&self) -> &String {
+    fn raw(&self) -> String {
         match &self.value {
-            ast::Value::StringValue(x, _) => x,
-            ast::Value::NumericValue(x, _) => x,
-            ast::Value::BooleanValue(x, _) => x,
-            ast::Value::ConstantValue(x, _) => x,
+            ast::Value::StringValue(x, _) => x.clone(),
+            ast::Value::NumericValue(x, _) => x.clone(),
+            ast::Value::BooleanValue(x, _) => x.clone(),
+            ast::Value::ConstantValue(x, _) => x.clone(),
+            ast::Value::Array(_, _) => self.value.to_string(),
+            ast::Value::Function(_, _, _) => self.value.to_string(),
         }
     }
 
     fn span(&self) -> &ast::Span {
-        match &self.value {
-            ast::Value::StringValue(_, s) => s,
-            ast::Value::NumericValue(_, s) => s,
-            ast::Value::BooleanValue(_, s) => s,
-            ast::Value::ConstantValue(_, s) => s,
-        }
+        self.value.span()
     }
 
     fn as_str(&self) -> Result<String, ValueParserError> {
         match &self.value {
             ast::Value::StringValue(value, _) => Ok(value.to_string()),
             _ => Err(ValueParserError::new(
-                &format!("Expected String Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected String Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
     }
 
-    fn as_int(&self) -> Result<i32, ValueParserError> {
+    fn as_int(&self) -> Result<i64, ValueParserError> {
         match &self.value {
-            ast::Value::NumericValue(value, span) => ValueParserError::wrap(value.parse::<i32>(), value, span),
+            ast::Value::NumericValue(value, span) => ValueParserError::wrap(value.parse::<i64>(), value, span),
             _ => Err(ValueParserError::new(
-                &format!("Expected Numeric Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected Numeric Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
     }
 
-    fn as_float(&self) -> Result<f32, ValueParserError> {
+    fn as_float(&self) -> Result<f64, ValueParserError> {
         match &self.value {
-            ast::Value::NumericValue(value, span) => ValueParserError::wrap(value.parse::<f32>(), value, span),
+            // Parsing doesn't fail for values out of range, they become infinite.
+            ast::Value::NumericValue(value, span) => match value.parse::<f64>() {
+                Ok(float) if !float.is_finite() => Err(ValueParserError::new(
+                    "number too large to fit in target type",
+                    value,
+                    span,
+                )),
+                result => ValueParserError::wrap(result, value, span),
+            },
             _ => Err(ValueParserError::new(
-                &format!("Expected Numeric Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected Numeric Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
     }
 
-    // TODO: Ask which decimal type to take.
-    fn as_decimal(&self) -> Result<f32, ValueParserError> {
+    fn as_decimal(&self) -> Result<Decimal, ValueParserError> {
         match &self.value {
-            ast::Value::NumericValue(value, span) => ValueParserError::wrap(value.parse::<f32>(), value, span),
+            ast::Value::NumericValue(value, span) => ValueParserError::wrap(value.parse::<Decimal>(), value, span),
             _ => Err(ValueParserError::new(
-                &format!("Expected Numeric Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected Numeric Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
@@ -168,8 +257,8 @@ impl ValueValidator for WrappedValue {
         match &self.value {
             ast::Value::BooleanValue(value, span) => ValueParserError::wrap(value.parse::<bool>(), value, span),
             _ => Err(ValueParserError::new(
-                &format!("Expected Boolean Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected Boolean Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
@@ -180,8 +269,8 @@ impl ValueValidator for WrappedValue {
         match &self.value {
             ast::Value::StringValue(value, span) => ValueParserError::wrap(value.parse::<DateTime<Utc>>(), value, span),
             _ => Err(ValueParserError::new(
-                &format!("Expected Boolean Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected String Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
@@ -191,12 +280,56 @@ impl ValueValidator for WrappedValue {
         match &self.value {
             ast::Value::ConstantValue(value, _) => Ok(value.to_string()),
             _ => Err(ValueParserError::new(
-                &format!("Expected Constant Value, received {:?}", self.value),
-                self.raw(),
+                &format!("Expected Constant Value, received {}", self.value),
+                &self.raw(),
                 self.span(),
             )),
         }
     }
+
+    fn as_array(&self) -> Result<Vec<Box<ValueValidator>>, ValueParserError> {
+        match &self.value {
+            ast::Value::Array(values, _) => Ok(values
+                .iter()
+                .map(|value| Box::new(WrappedValue { value: value.clone() }) as Box<ValueValidator>)
+                .collect()),
+            _ => Err(ValueParserError::new(
+                &format!("Expected Array Value, received {}", self.value),
+                &self.raw(),
+                self.span(),
+            )),
+        }
+    }
+
+    fn as_default_function(&self) -> Result<dml::DefaultFunction, ValueParserError> {
+        match &self.value {
+            ast::Value::Function(name, args, span) => match dml::DefaultFunction::from_name(name) {
+                Some(_) if args.len() > 0 => Err(ValueParserError::new(
+                    &format!("The function {}() takes no arguments.", name),
+                    &self.raw(),
+                    span,
+                )),
+                Some(function) => Ok(function),
+                None => Err(ValueParserError::new(
+                    &format!("Unknown function {}().", name),
+                    &self.raw(),
+                    span,
+                )),
+            },
+            _ => Err(ValueParserError::new(
+                &format!("Expected Function Value, received {}", self.value),
+                &self.raw(),
+                self.span(),
+            )),
+        }
+    }
+
+    fn is_function(&self) -> bool {
+        match &self.value {
+            ast::Value::Function(_, _, _) => true,
+            _ => false,
+        }
+    }
 }
 
 pub struct WrappedErrorValue {
@@ -210,8 +343,8 @@ impl ValueValidator for WrappedErrorValue {
         false
     }
 
-    fn raw(&self) -> &String {
-        &self.raw
+    fn raw(&self) -> String {
+        self.raw.clone()
     }
 
     fn span(&self) -> &ast::Span {
@@ -221,13 +354,13 @@ impl ValueValidator for WrappedErrorValue {
     fn as_str(&self) -> Result<String, ValueParserError> {
         Err(ValueParserError::new(&self.message, &self.raw, &self.span))
     }
-    fn as_int(&self) -> Result<i32, ValueParserError> {
+    fn as_int(&self) -> Result<i64, ValueParserError> {
         Err(ValueParserError::new(&self.message, &self.raw, &self.span))
     }
-    fn as_float(&self) -> Result<f32, ValueParserError> {
+    fn as_float(&self) -> Result<f64, ValueParserError> {
         Err(ValueParserError::new(&self.message, &self.raw, &self.span))
     }
-    fn as_decimal(&self) -> Result<f32, ValueParserError> {
+    fn as_decimal(&self) -> Result<Decimal, ValueParserError> {
         Err(ValueParserError::new(&self.message, &self.raw, &self.span))
     }
     fn as_bool(&self) -> Result<bool, ValueParserError> {
@@ -239,4 +372,10 @@ impl ValueValidator for WrappedErrorValue {
     fn as_constant_literal(&self) -> Result<String, ValueParserError> {
         Err(ValueParserError::new(&self.message, &self.raw, &self.span))
     }
+    fn as_array(&self) -> Result<Vec<Box<ValueValidator>>, ValueParserError> {
+        Err(ValueParserError::new(&self.message, &self.raw, &self.span))
+    }
+    fn as_default_function(&self) -> Result<dml::DefaultFunction, ValueParserError> {
+        Err(ValueParserError::new(&self.message, &self.raw, &self.span))
+    }
 }
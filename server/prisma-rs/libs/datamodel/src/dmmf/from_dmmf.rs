@@ -0,0 +1,292 @@
+use super::*;
+use crate::dml::lowerer::Lowerer;
+use crate::dml::validator::directive::DirectiveValidationError;
+use crate::dml::validator::Validator;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+// Reconstructs a dml schema from DMMF, as produced by `render_to_dmmf`.
+
+#[derive(Debug)]
+pub enum DmmfError {
+    // The input is not valid DMMF JSON.
+    Json(serde_json::Error),
+    // The input is valid JSON, but contains an unknown type, strategy or an invalid default value.
+    InvalidValue(String),
+    // The reconstructed schema does not pass validation, e.g. because of a missing back-relation.
+    Validation(Vec<DirectiveValidationError>),
+}
+
+impl std::fmt::Display for DmmfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DmmfError::Json(err) => write!(f, "Invalid DMMF JSON: {}", err),
+            DmmfError::InvalidValue(message) => write!(f, "Invalid DMMF: {}", message),
+            DmmfError::Validation(errors) => {
+                let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+                write!(f, "Invalid datamodel: {}", messages.join(" "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DmmfError {}
+
+fn invalid(message: String) -> DmmfError {
+    DmmfError::InvalidValue(message)
+}
+
+fn parse_scalar_type(type_name: &str) -> Result<dml::ScalarType, DmmfError> {
+    match type_name {
+        "Int" => Ok(dml::ScalarType::Int),
+        "Float" => Ok(dml::ScalarType::Float),
+        "Decimal" => Ok(dml::ScalarType::Decimal),
+        "Boolean" => Ok(dml::ScalarType::Boolean),
+        "String" => Ok(dml::ScalarType::String),
+        "DateTime" => Ok(dml::ScalarType::DateTime),
+        "Json" => Ok(dml::ScalarType::Json),
+        "Bytes" => Ok(dml::ScalarType::Bytes),
+        "BigInt" => Ok(dml::ScalarType::BigInt),
+        "UUID" => Ok(dml::ScalarType::Uuid),
+        _ => Err(invalid(format!("Unknown scalar type {}.", type_name))),
+    }
+}
+
+fn parse_field_type(field: &Field) -> Result<dml::FieldType, DmmfError> {
+    match field.kind.as_str() {
+        "scalar" => {
+            let base_type = parse_scalar_type(&field.field_type)?;
+
+            match &field.connector_type {
+                Some(connector_type) => Ok(dml::FieldType::ConnectorSpecific {
+                    base_type,
+                    connector_type: Some(connector_type.clone()),
+                }),
+                None => Ok(dml::FieldType::Base(base_type)),
+            }
+        }
+        "enum" => Ok(dml::FieldType::Enum(field.field_type.clone())),
+        "relation" => {
+            let mut relation_info =
+                dml::RelationInfo::new(&field.field_type, field.relation_to_field.as_ref().map_or("", |f| f.as_str()));
+            relation_info.name = field.relation_name.clone();
+
+            if let Some(on_delete) = &field.relation_on_delete {
+                relation_info.on_delete = on_delete
+                    .parse::<dml::OnDeleteStrategy>()
+                    .map_err(|err| invalid(err.message))?;
+            }
+
+            Ok(dml::FieldType::Relation(relation_info))
+        }
+        _ => Err(invalid(format!("Unknown field kind {}.", field.kind))),
+    }
+}
+
+fn parse_field_arity(arity: &str) -> Result<dml::FieldArity, DmmfError> {
+    match arity {
+        "required" => Ok(dml::FieldArity::Required),
+        "optional" => Ok(dml::FieldArity::Optional),
+        "list" => Ok(dml::FieldArity::List),
+        _ => Err(invalid(format!("Unknown field arity {}.", arity))),
+    }
+}
+
+fn parse_default_value(value: &serde_json::Value, field_type: &dml::FieldType) -> Result<dml::Value, DmmfError> {
+    let scalar_type = match field_type {
+        dml::FieldType::Base(scalar_type) => scalar_type,
+        dml::FieldType::ConnectorSpecific { base_type, .. } => base_type,
+        dml::FieldType::Enum(_) => &dml::ScalarType::Enum,
+        dml::FieldType::Relation(_) => return Err(invalid(String::from("Relation fields cannot have a default value."))),
+    };
+
+    // Function defaults are rendered as objects, which are only ambiguous with Json defaults.
+    if let (Some(name), false) = (value.get("name"), scalar_type == &dml::ScalarType::Json) {
+        return name
+            .as_str()
+            .and_then(dml::DefaultFunction::from_name)
+            .map(dml::Value::Function)
+            .ok_or_else(|| invalid(format!("Unknown default function {}.", name)));
+    }
+
+    let parsed = match scalar_type {
+        dml::ScalarType::Int => value.as_i64().map(dml::Value::Int),
+        dml::ScalarType::Float => value.as_f64().map(dml::Value::Float),
+        dml::ScalarType::Decimal => match value {
+            serde_json::Value::String(v) => v.parse::<Decimal>().ok(),
+            serde_json::Value::Number(v) => v.to_string().parse::<Decimal>().ok(),
+            _ => None,
+        }
+        .map(dml::Value::Decimal),
+        dml::ScalarType::Boolean => value.as_bool().map(dml::Value::Boolean),
+        dml::ScalarType::String => value.as_str().map(|v| dml::Value::String(String::from(v))),
+        dml::ScalarType::DateTime => value
+            .as_str()
+            .and_then(|v| v.parse::<DateTime<Utc>>().ok())
+            .map(dml::Value::DateTime),
+        dml::ScalarType::Json => Some(dml::Value::Json(value.clone())),
+        dml::ScalarType::Bytes => value.as_str().map(|v| dml::Value::Bytes(String::from(v))),
+        dml::ScalarType::BigInt => match value {
+            serde_json::Value::String(v) => v.parse::<i64>().ok(),
+            _ => value.as_i64(),
+        }
+        .map(dml::Value::BigInt),
+        dml::ScalarType::Uuid => value.as_str().map(|v| dml::Value::Uuid(String::from(v))),
+        dml::ScalarType::Enum => value.as_str().map(|v| dml::Value::ConstantLiteral(String::from(v))),
+    };
+
+    parsed.ok_or_else(|| invalid(format!("Invalid default value {} for type {:?}.", value, scalar_type)))
+}
+
+fn parse_id_info(field: &Field) -> Result<Option<dml::IdInfo>, DmmfError> {
+    if !field.is_id {
+        return Ok(None);
+    }
+
+    let strategy = match &field.id_strategy {
+        Some(strategy) => strategy.parse::<dml::IdStrategy>().map_err(|err| invalid(err.message))?,
+        None => dml::IdStrategy::Auto,
+    };
+
+    let sequence = field.id_sequence.as_ref().map(|sequence| dml::Sequence {
+        name: sequence.name.clone(),
+        initial_value: sequence.initial_value,
+        allocation_size: sequence.allocation_size,
+    });
+
+    Ok(Some(dml::IdInfo { strategy, sequence }))
+}
+
+fn parse_comments(documentation: &Option<String>) -> Vec<dml::Comment> {
+    match documentation {
+        Some(documentation) => documentation
+            .split('\n')
+            .map(|line| dml::Comment {
+                text: String::from(line),
+                is_error: false,
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn field_from_dmmf(field: &Field) -> Result<dml::Field, DmmfError> {
+    let field_type = parse_field_type(field)?;
+    let mut result = dml::Field::new(&field.name, field_type);
+
+    result.arity = parse_field_arity(&field.arity)?;
+    result.database_name = field.db_name.clone();
+    result.is_unique = field.is_unique;
+    result.is_updated_at = field.is_updated_at;
+    result.id_info = parse_id_info(field)?;
+    result.comments = parse_comments(&field.documentation);
+    result.custom_directives = field.custom_directives.clone();
+
+    if let Some(default) = &field.default {
+        result.default_value = Some(parse_default_value(default, &result.field_type)?);
+    }
+
+    if let Some(strategy) = &field.scalar_list_strategy {
+        result.scalar_list_strategy = Some(
+            strategy
+                .parse::<dml::ScalarListStrategy>()
+                .map_err(|err| invalid(err.message))?,
+        );
+    }
+
+    Ok(result)
+}
+
+pub fn index_from_dmmf(index: &Index) -> dml::IndexDefinition {
+    let tpe = if index.is_unique {
+        dml::IndexType::Unique
+    } else {
+        dml::IndexType::Normal
+    };
+
+    let mut result = dml::IndexDefinition::new(index.fields.clone(), tpe);
+    result.name = index.name.clone();
+
+    result
+}
+
+pub fn model_from_dmmf(model: &Model) -> Result<dml::Model, DmmfError> {
+    let mut result = dml::Model::new(&model.name);
+
+    result.database_name = model.db_name.clone();
+    result.is_embedded = model.is_embedded;
+    result.comments = parse_comments(&model.documentation);
+    result.custom_directives = model.custom_directives.clone();
+
+    for field in &model.fields {
+        result.add_field(field_from_dmmf(field)?);
+    }
+
+    result.id_fields = model.id_fields.clone();
+
+    for index in &model.indexes {
+        result.add_index(index_from_dmmf(index));
+    }
+
+    Ok(result)
+}
+
+pub fn enum_from_dmmf(en: &Enum) -> dml::Enum {
+    let mut result = dml::Enum::new(&en.name, vec![]);
+
+    result.comments = parse_comments(&en.documentation);
+    result.custom_directives = en.custom_directives.clone();
+
+    for value in &en.values {
+        let mut enum_value = dml::EnumValue::new(value);
+        enum_value.comments = parse_comments(&en.values_documentation.get(value).cloned());
+        result.values.push(enum_value);
+    }
+
+    result
+}
+
+pub fn schema_from_dmmf(datamodel: &Datamodel) -> Result<dml::Schema, DmmfError> {
+    let mut schema = dml::Schema::new();
+
+    for model in &datamodel.models {
+        schema.add_model(model_from_dmmf(model)?);
+    }
+
+    for en in &datamodel.enums {
+        schema.add_enum(enum_from_dmmf(en));
+    }
+
+    // DMMF carries no source text, so the schema is validated by running
+    // the validator on its lowered representation. Custom directives are opaque
+    // to the validator, they were validated before the DMMF was rendered.
+    if let Err(errors) = Validator::new().validate(&Lowerer::new().lower(&without_custom_directives(&schema))) {
+        return Err(DmmfError::Validation(errors));
+    }
+
+    Ok(schema)
+}
+
+fn without_custom_directives(schema: &dml::Schema) -> dml::Schema {
+    let mut schema = schema.clone();
+
+    for model in schema.models_mut() {
+        model.custom_directives.clear();
+
+        for field in model.fields_mut() {
+            field.custom_directives.clear();
+        }
+    }
+
+    for en in schema.enums_mut() {
+        en.custom_directives.clear();
+    }
+
+    schema
+}
+
+pub fn parse_from_dmmf(dmmf: &str) -> Result<dml::Schema, DmmfError> {
+    let datamodel: Datamodel = serde_json::from_str(dmmf).map_err(DmmfError::Json)?;
+
+    schema_from_dmmf(&datamodel)
+}
@@ -1,40 +1,95 @@
 use crate::dml;
 use serde;
 use serde_json;
+use std::collections::BTreeMap;
+
+mod from_dmmf;
+pub use from_dmmf::*;
 
 // This is a simple JSON serialization using Serde.
-// The JSON format follows the DMMF spec, but is incomplete.
+// The JSON format follows the DMMF spec.
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sequence {
+    pub name: String,
+    pub initial_value: i32,
+    pub allocation_size: i32,
+}
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Field {
     pub name: String,
     pub kind: String,
-    pub dbName: Option<String>,
+    pub db_name: Option<String>,
     pub arity: String,
-    pub isUnique: bool,
+    pub is_unique: bool,
+    #[serde(default)]
+    pub is_updated_at: bool,
+    pub is_id: bool,
     #[serde(rename = "type")]
     pub field_type: String,
+    pub connector_type: Option<String>,
+    pub default: Option<serde_json::Value>,
+    pub id_strategy: Option<String>,
+    pub id_sequence: Option<Sequence>,
+    pub scalar_list_strategy: Option<String>,
+    pub relation_name: Option<String>,
+    pub relation_to_field: Option<String>,
+    pub relation_on_delete: Option<String>,
+    pub documentation: Option<String>,
+    #[serde(default)]
+    pub custom_directives: Vec<dml::CustomDirective>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Index {
+    pub name: Option<String>,
+    pub fields: Vec<String>,
+    pub is_unique: bool,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Model {
-    pub isEnum: bool,
+    #[serde(default)]
+    pub is_enum: bool,
     pub name: String,
-    pub isEmbedded: bool,
-    pub dbName: Option<String>,
+    pub is_embedded: bool,
+    pub db_name: Option<String>,
     pub fields: Vec<Field>,
+    pub id_fields: Vec<String>,
+    pub indexes: Vec<Index>,
+    pub documentation: Option<String>,
+    #[serde(default)]
+    pub custom_directives: Vec<dml::CustomDirective>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Enum {
-    pub isEnum: bool,
+    #[serde(default = "enum_marker")]
+    pub is_enum: bool,
     pub name: String,
     pub values: Vec<String>,
+    pub documentation: Option<String>,
+    // Documentation of the enum values by value name. Values without documentation are left out.
+    #[serde(default)]
+    pub values_documentation: BTreeMap<String, String>,
+    #[serde(default)]
+    pub custom_directives: Vec<dml::CustomDirective>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct Datamodel {
-    pub models: Vec<serde_json::Value>,
+    pub models: Vec<Model>,
+    pub enums: Vec<Enum>,
+}
+
+fn enum_marker() -> bool {
+    true
 }
 
 fn get_field_kind(field: &dml::Field) -> String {
@@ -42,7 +97,7 @@ fn get_field_kind(field: &dml::Field) -> String {
         dml::FieldType::Relation(_) => String::from("relation"),
         dml::FieldType::Enum(_) => String::from("enum"),
         dml::FieldType::Base(_) => String::from("scalar"),
-        _ => unimplemented!("DMMF does not support field type {:?}", field.field_type),
+        dml::FieldType::ConnectorSpecific { .. } => String::from("scalar"),
     }
 }
 
@@ -54,6 +109,10 @@ fn type_to_string(scalar: &dml::ScalarType) -> String {
         dml::ScalarType::Boolean => String::from("Boolean"),
         dml::ScalarType::String => String::from("String"),
         dml::ScalarType::DateTime => String::from("DateTime"),
+        dml::ScalarType::Json => String::from("Json"),
+        dml::ScalarType::Bytes => String::from("Bytes"),
+        dml::ScalarType::BigInt => String::from("BigInt"),
+        dml::ScalarType::Uuid => String::from("UUID"),
         dml::ScalarType::Enum => panic!("Enum is an internally used type and should never be rendered."),
     }
 }
@@ -70,6 +129,13 @@ fn get_field_type(field: &dml::Field) -> String {
     }
 }
 
+fn get_connector_type(field: &dml::Field) -> Option<String> {
+    match &field.field_type {
+        dml::FieldType::ConnectorSpecific { connector_type, .. } => connector_type.clone(),
+        _ => None,
+    }
+}
+
 fn get_field_arity(field: &dml::Field) -> String {
     match field.arity {
         dml::FieldArity::Required => String::from("required"),
@@ -78,11 +144,104 @@ fn get_field_arity(field: &dml::Field) -> String {
     }
 }
 
+fn get_default_value(field: &dml::Field) -> Option<serde_json::Value> {
+    field.default_value.as_ref().map(|value| match value {
+        dml::Value::Int(value) => serde_json::json!(value),
+        dml::Value::Float(value) => float_to_json(*value),
+        // Rendered as string, as decimals can't be represented as JSON numbers without losing precision.
+        dml::Value::Decimal(value) => serde_json::json!(value.to_string()),
+        dml::Value::Boolean(value) => serde_json::json!(value),
+        dml::Value::String(value) => serde_json::json!(value),
+        dml::Value::DateTime(value) => serde_json::json!(value.to_rfc3339()),
+        dml::Value::Json(value) => value.clone(),
+        dml::Value::Bytes(value) => serde_json::json!(value),
+        // Rendered as string, as JSON numbers lose precision beyond 2^53 in most clients.
+        dml::Value::BigInt(value) => serde_json::json!(value.to_string()),
+        dml::Value::Uuid(value) => serde_json::json!(value),
+        dml::Value::ConstantLiteral(value) => serde_json::json!(value),
+        dml::Value::Function(function) => serde_json::json!({ "name": function.name(), "args": [] }),
+    })
+}
+
+// JSON numbers cannot be infinite or NaN. Such values are rejected by the validator, but can still be set
+// programmatically, so they are rendered as strings in the JavaScript notation instead of failing.
+fn float_to_json(value: f64) -> serde_json::Value {
+    match serde_json::Number::from_f64(value) {
+        Some(number) => serde_json::Value::Number(number),
+        None if value.is_nan() => serde_json::json!("NaN"),
+        None if value.is_sign_positive() => serde_json::json!("Infinity"),
+        None => serde_json::json!("-Infinity"),
+    }
+}
+
+fn get_id_strategy(field: &dml::Field) -> Option<String> {
+    field.id_info.as_ref().map(|id_info| match id_info.strategy {
+        dml::IdStrategy::Auto => String::from("AUTO"),
+        dml::IdStrategy::None => String::from("NONE"),
+    })
+}
+
+fn get_id_sequence(field: &dml::Field) -> Option<Sequence> {
+    match &field.id_info {
+        Some(dml::IdInfo {
+            sequence: Some(sequence),
+            ..
+        }) => Some(Sequence {
+            name: sequence.name.clone(),
+            initial_value: sequence.initial_value,
+            allocation_size: sequence.allocation_size,
+        }),
+        _ => None,
+    }
+}
+
+fn get_scalar_list_strategy(field: &dml::Field) -> Option<String> {
+    field.scalar_list_strategy.map(|strategy| match strategy {
+        dml::ScalarListStrategy::Embedded => String::from("EMBEDDED"),
+        dml::ScalarListStrategy::Relation => String::from("RELATION"),
+    })
+}
+
+fn get_relation_info(field: &dml::Field) -> Option<&dml::RelationInfo> {
+    match &field.field_type {
+        dml::FieldType::Relation(relation_info) => Some(relation_info),
+        _ => None,
+    }
+}
+
+fn get_relation_to_field(field: &dml::Field) -> Option<String> {
+    get_relation_info(field)
+        .map(|relation_info| relation_info.to_field.clone())
+        .filter(|to_field| !to_field.is_empty())
+}
+
+fn get_relation_on_delete(field: &dml::Field) -> Option<String> {
+    get_relation_info(field).map(|relation_info| match relation_info.on_delete {
+        dml::OnDeleteStrategy::Cascade => String::from("CASCADE"),
+        dml::OnDeleteStrategy::None => String::from("NONE"),
+    })
+}
+
+fn get_documentation(comments: &Vec<dml::Comment>) -> Option<String> {
+    if comments.is_empty() {
+        None
+    } else {
+        Some(comments.iter().map(|c| c.text.clone()).collect::<Vec<String>>().join("\n"))
+    }
+}
+
 pub fn enum_to_dmmf(en: &dml::Enum) -> Enum {
     Enum {
+        is_enum: true,
         name: en.name.clone(),
-        values: en.values.clone(),
-        isEnum: true,
+        values: en.value_names(),
+        documentation: get_documentation(&en.comments),
+        values_documentation: en
+            .values
+            .iter()
+            .filter_map(|value| get_documentation(&value.comments).map(|documentation| (value.name.clone(), documentation)))
+            .collect(),
+        custom_directives: en.custom_directives.clone(),
     }
 }
 
@@ -90,39 +249,52 @@ pub fn field_to_dmmf(field: &dml::Field) -> Field {
     Field {
         name: field.name.clone(),
         kind: get_field_kind(field),
-        dbName: field.database_name.clone(),
+        db_name: field.database_name.clone(),
         arity: get_field_arity(field),
-        isUnique: field.is_unique,
+        is_unique: field.is_unique,
+        is_updated_at: field.is_updated_at,
+        is_id: field.id_info.is_some(),
         field_type: get_field_type(field),
+        connector_type: get_connector_type(field),
+        default: get_default_value(field),
+        id_strategy: get_id_strategy(field),
+        id_sequence: get_id_sequence(field),
+        scalar_list_strategy: get_scalar_list_strategy(field),
+        relation_name: get_relation_info(field).and_then(|relation_info| relation_info.name.clone()),
+        relation_to_field: get_relation_to_field(field),
+        relation_on_delete: get_relation_on_delete(field),
+        documentation: get_documentation(&field.comments),
+        custom_directives: field.custom_directives.clone(),
+    }
+}
+
+pub fn index_to_dmmf(index: &dml::IndexDefinition) -> Index {
+    Index {
+        name: index.name.clone(),
+        fields: index.fields.clone(),
+        is_unique: index.is_unique(),
     }
 }
 
 pub fn model_to_dmmf(model: &dml::Model) -> Model {
     Model {
+        is_enum: false,
         name: model.name.clone(),
-        dbName: model.database_name.clone(),
-        isEmbedded: model.is_embedded,
+        db_name: model.database_name.clone(),
+        is_embedded: model.is_embedded,
         fields: model.fields().map(&field_to_dmmf).collect(),
-        isEnum: false,
+        id_fields: model.id_fields.clone(),
+        indexes: model.indexes.iter().map(&index_to_dmmf).collect(),
+        documentation: get_documentation(&model.comments),
+        custom_directives: model.custom_directives.clone(),
     }
 }
 
 pub fn schema_to_dmmf(schema: &dml::Schema) -> Datamodel {
-    let mut datamodel = Datamodel { models: vec![] };
-
-    for model in schema.models() {
-        datamodel
-            .models
-            .push(serde_json::to_value(&model_to_dmmf(&model)).expect("Failed to render enum"))
+    Datamodel {
+        models: schema.models().map(&model_to_dmmf).collect(),
+        enums: schema.enums().map(&enum_to_dmmf).collect(),
     }
-
-    for enum_model in schema.enums() {
-        datamodel
-            .models
-            .push(serde_json::to_value(&enum_to_dmmf(&enum_model)).expect("Failed to render enum"))
-    }
-
-    return datamodel;
 }
 
 pub fn render_to_dmmf(schema: &dml::Schema) -> String {
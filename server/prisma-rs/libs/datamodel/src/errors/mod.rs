@@ -0,0 +1,52 @@
+use crate::ast::Span;
+use std::io;
+
+// Error reporting in the style of rustc diagnostics:
+//
+// error: Encountered unknown directive
+//   --> schema.prisma:3:18
+//    |
+//  3 |     name: String @foo
+//    |                  ^^^^
+//    |
+
+// Converts a byte offset into a 1-based line and column pair.
+pub fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let offset = std::cmp::min(offset, text.len());
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = text[line_start..offset].chars().count() + 1;
+
+    (line, column)
+}
+
+pub fn pretty_print(
+    f: &mut io::Write,
+    file_name: &str,
+    text: &str,
+    span: &Span,
+    message: &str,
+) -> io::Result<()> {
+    let (line, column) = line_and_column(text, span.start);
+    let line_content = text.lines().nth(line - 1).unwrap_or("");
+
+    // The caret spans until the end of the error or the end of the line, whichever comes first.
+    let line_chars = line_content.chars().count();
+    let span_chars = text[std::cmp::min(span.start, text.len())..std::cmp::min(span.end, text.len())]
+        .chars()
+        .count();
+    let caret_length = std::cmp::max(1, std::cmp::min(span_chars, line_chars.saturating_sub(column - 1)));
+
+    let line_number = line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    writeln!(f, "error: {}", message)?;
+    writeln!(f, "{}--> {}:{}:{}", gutter, file_name, line, column)?;
+    writeln!(f, "{} |", gutter)?;
+    writeln!(f, "{} | {}", line_number, line_content)?;
+    writeln!(f, "{} | {}{}", gutter, " ".repeat(column - 1), "^".repeat(caret_length))?;
+    writeln!(f, "{} |", gutter)?;
+
+    Ok(())
+}
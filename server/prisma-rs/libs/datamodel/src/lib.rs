@@ -1,6 +1,13 @@
 pub mod ast;
 pub use ast::parser;
+pub use ast::renderer;
+pub mod configuration;
+pub use configuration::{Configuration, Generator, Source};
 pub mod dml;
+pub mod diff;
+pub use diff::diff;
+pub mod dmmf;
+pub mod errors;
 pub use dml::validator::Validator;
 pub use dml::*;
 
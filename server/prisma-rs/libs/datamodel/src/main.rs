@@ -1,8 +1,13 @@
 use std::fs;
+use std::io;
+use std::process;
 
 pub mod ast;
+pub mod configuration;
 pub mod dmmf;
+pub mod errors;
 use ast::parser;
+use ast::renderer::Renderer;
 pub mod dml;
 use dml::validator::Validator;
 
@@ -12,7 +17,7 @@ extern crate pest;
 extern crate pest_derive;
 
 extern crate clap;
-use clap::{App, Arg, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 
 fn main() {
     let formats = ["sorenbs", "matthewmueller"];
@@ -21,52 +26,102 @@ fn main() {
         .version("0.1")
         .author("Emanuel Jöbstl <emanuel.joebstl@gmail.com>")
         .about("Alpha implementation of different datamodel definition grammars.")
+        .setting(AppSettings::SubcommandsNegateReqs)
         .arg(
             Arg::with_name("INPUT")
                 .help("Sets the input datamodel file to use")
                 .required(true)
                 .index(1),
         )
+        .subcommand(
+            SubCommand::with_name("format")
+                .about("Prints the datamodel file in canonical form.")
+                .arg(
+                    Arg::with_name("INPUT")
+                        .help("Sets the input datamodel file to use")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("write")
+                        .short("w")
+                        .long("write")
+                        .help("Overwrites the input file instead of printing to stdout"),
+                )
+                .arg(
+                    Arg::with_name("check")
+                        .long("check")
+                        .conflicts_with("write")
+                        .help("Exits with a non-zero status if the input file is not formatted"),
+                ),
+        )
         .get_matches();
 
+    if let Some(matches) = matches.subcommand_matches("format") {
+        format(matches);
+        return;
+    }
+
+    let file_name = matches.value_of("INPUT").unwrap();
+    let file = fs::read_to_string(&file_name).expect(&format!("Unable to open file {}", file_name));
+
+    // Syntax errors do not stop validation, the valid declarations are still checked.
+    let (ast, parser_errors) = parser::parse_with_recovery(&file);
+
+    for error in &parser_errors {
+        print_parser_error(&file_name, &file, error);
+    }
+
+    let validator = Validator::new();
+
+    match validator.validate(&ast) {
+        Ok(dml) if parser_errors.is_empty() => {
+            let json = dmmf::render_to_dmmf(&dml);
+            println!("{}", json);
+        }
+        Ok(_) => process::exit(1),
+        Err(errs) => {
+            for error in errs {
+                let message = if error.directive_name.is_empty() {
+                    error.message.clone()
+                } else {
+                    format!("Error validating directive @{}: {}", error.directive_name, error.message)
+                };
+                errors::pretty_print(&mut io::stderr(), &file_name, &file, &error.span, &message)
+                    .expect("Unable to write error");
+            }
+            process::exit(1);
+        }
+    }
+}
+
+fn format(matches: &ArgMatches) {
     let file_name = matches.value_of("INPUT").unwrap();
     let file = fs::read_to_string(&file_name).expect(&format!("Unable to open file {}", file_name));
 
     match parser::parse(&file) {
         Ok(ast) => {
-            let validator = Validator::new();
+            let formatted = Renderer::new().render(&ast);
 
-            match validator.validate(&ast) {
-                Ok(dml) => {
-                    let json = dmmf::render_to_dmmf(&dml);
-                    println!("{}", json);
-                }
-                Err(errors) => {
-                    for error in errors {
-                        println!("");
-                        println!("Error: {}", error.message);
-                        println!("File: {}:", file_name);
-                        println!("");
-                        let line = &file[..error.span.end].matches("\n").count();
-                        let text = &file[error.span.start..error.span.end];
-                        println!("{} |    {}", line, text);
-                        println!("");
-                    }
+            if matches.is_present("check") {
+                if formatted != file {
+                    println!("File {} is not formatted.", file_name);
+                    process::exit(1);
                 }
+            } else if matches.is_present("write") {
+                fs::write(&file_name, formatted).expect(&format!("Unable to write file {}", file_name));
+            } else {
+                print!("{}", formatted);
             }
         }
         Err(error) => {
-            println!("");
-            println!("Error while parsing, unexpected token");
-            println!("File: {}:", file_name);
-            println!("");
-            let line = &file[..error.span.end].matches("\n").count();
-            let text = file.split("\n").collect::<Vec<&str>>()[*line];
-            println!("{} |    {}", line, text);
-            println!("");
+            print_parser_error(&file_name, &file, &error);
+            process::exit(1);
         }
     }
+}
 
-   
-   
+fn print_parser_error(file_name: &str, file: &str, error: &parser::ParserError) {
+    errors::pretty_print(&mut io::stderr(), file_name, file, &error.span, &error.message)
+        .expect("Unable to write error");
 }
@@ -1,5 +1,7 @@
 mod common;
 use common::*;
+use chrono::{DateTime, Utc};
+use datamodel::ast::Span;
 use datamodel::dml;
 
 #[test]
@@ -88,9 +90,320 @@ fn parse_defaults() {
     user_model
         .assert_has_field("balance")
         .assert_base_type(&dml::ScalarType::Decimal)
-        .assert_default_value(dml::Value::Decimal(1.2));
+        .assert_default_value(dml::Value::Decimal("1.2".parse().unwrap()));
     user_model
         .assert_has_field("averageGrade")
         .assert_base_type(&dml::ScalarType::Float)
         .assert_default_value(dml::Value::Float(3.4));
 }
+
+#[test]
+fn parse_date_time_and_directive_defaults() {
+    let dml = r#"
+    model User {
+        createdAt: DateTime = "2019-05-15T12:00:00Z"
+        nickname: String @default("Anonymous")
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+    user_model
+        .assert_has_field("createdAt")
+        .assert_base_type(&dml::ScalarType::DateTime)
+        .assert_default_value(dml::Value::DateTime(
+            "2019-05-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+        ));
+    user_model
+        .assert_has_field("nickname")
+        .assert_default_value(dml::Value::String(String::from("Anonymous")));
+}
+
+#[test]
+fn parse_enum_defaults() {
+    let dml = r#"
+    model User {
+        role: Role = USER
+        otherRole: Role @default(ADMIN)
+    }
+
+    enum Role {
+        ADMIN
+        USER
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+    user_model
+        .assert_has_field("role")
+        .assert_enum_type("Role")
+        .assert_default_value(dml::Value::ConstantLiteral(String::from("USER")));
+    user_model
+        .assert_has_field("otherRole")
+        .assert_default_value(dml::Value::ConstantLiteral(String::from("ADMIN")));
+}
+
+#[test]
+fn fail_on_default_of_wrong_type() {
+    let dml = r#"
+    model User {
+        count: Int = "abc"
+        ratio: Float = true
+        balance: Decimal = "one"
+        isPro: Boolean = 1
+        name: String = 42
+        createdAt: DateTime = "yesterday"
+        overflow: Int = 9223372036854775808
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 7);
+    assert_eq!(errors[0].message, "Expected Numeric Value, received \"abc\"");
+    assert_eq!(errors[0].span, Span::new(39, 44));
+    assert_eq!(errors[1].message, "Expected Numeric Value, received true");
+    assert_eq!(errors[2].message, "Expected Numeric Value, received \"one\"");
+    assert_eq!(errors[3].message, "Expected Boolean Value, received 1");
+    assert_eq!(errors[4].message, "Expected String Value, received 42");
+    assert_eq!(errors[6].message, "number too large to fit in target type");
+}
+
+#[test]
+fn fail_on_unknown_enum_default() {
+    let dml = r#"
+    model User {
+        role: Role = SUPERUSER
+        otherRole: Role @default(GUEST)
+        thirdRole: Role = "ADMIN"
+    }
+
+    enum Role {
+        ADMIN
+        USER
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].message, "Default value SUPERUSER is not a value of enum Role.");
+    assert_eq!(errors[1].message, "Default value GUEST is not a value of enum Role.");
+    assert_eq!(errors[1].directive_name, "");
+    assert_eq!(errors[2].message, "Expected Constant Value, received \"ADMIN\"");
+}
+
+#[test]
+fn fail_on_relation_default() {
+    let dml = r#"
+    model User {
+        post: Post = "abc"
+        otherPost: Post @default("abc")
+    }
+
+    model Post {
+        text: String
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "Cannot set a default value on a relation field.");
+    assert_eq!(errors[1].message, "Cannot set a default value on a relation field.");
+    assert_eq!(errors[1].directive_name, "default");
+}
+
+#[test]
+fn parse_json_bytes_big_int_and_uuid() {
+    let dml = r#"
+    model User {
+        settings: Json = "{\"theme\": \"dark\"}"
+        avatar: Bytes = "aGVsbG8="
+        views: BigInt = 9007199254740993
+        token: UUID @default("123e4567-e89b-12d3-a456-426655440000")
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+    user_model
+        .assert_has_field("settings")
+        .assert_base_type(&dml::ScalarType::Json)
+        .assert_default_value(dml::Value::Json(serde_json::json!({ "theme": "dark" })));
+    user_model
+        .assert_has_field("avatar")
+        .assert_base_type(&dml::ScalarType::Bytes)
+        .assert_default_value(dml::Value::Bytes(String::from("aGVsbG8=")));
+    user_model
+        .assert_has_field("views")
+        .assert_base_type(&dml::ScalarType::BigInt)
+        .assert_default_value(dml::Value::BigInt(9007199254740993));
+    user_model
+        .assert_has_field("token")
+        .assert_base_type(&dml::ScalarType::Uuid)
+        .assert_default_value(dml::Value::Uuid(String::from("123e4567-e89b-12d3-a456-426655440000")));
+}
+
+#[test]
+fn fail_on_invalid_json_bytes_big_int_and_uuid_defaults() {
+    let dml = r#"
+    model User {
+        settings: Json = "{theme: dark}"
+        avatar: Bytes = "not base64!"
+        views: BigInt = "many"
+        token: UUID = "123e4567"
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(errors[1].message, "Expected a base64 encoded value.");
+    assert_eq!(errors[2].message, "Expected Numeric Value, received \"many\"");
+    assert_eq!(errors[3].message, "Expected a UUID value.");
+}
+
+#[test]
+fn parse_large_and_precise_numeric_defaults() {
+    let dml = r#"
+    model Account {
+        id: Int = 9007199254740993
+        balance: Decimal = 12345678901234567890.12345678
+        ratio: Float = 0.1
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let account_model = schema.assert_has_model("Account");
+    account_model
+        .assert_has_field("id")
+        .assert_default_value(dml::Value::Int(9007199254740993));
+    account_model
+        .assert_has_field("balance")
+        .assert_default_value(dml::Value::Decimal("12345678901234567890.12345678".parse().unwrap()));
+    account_model
+        .assert_has_field("ratio")
+        .assert_default_value(dml::Value::Float(0.1));
+}
+
+#[test]
+fn fail_on_numeric_default_overflow() {
+    let huge_float = format!("1{}", "0".repeat(400));
+    let huge_decimal = "123456789012345678901234567890.5";
+    let dml = format!(
+        r#"
+    model Account {{
+        id: Int = 9223372036854775808
+        ratio: Float = {}
+        balance: Decimal = {}
+    }}
+    "#,
+        huge_float, huge_decimal
+    );
+
+    let errors = parse_and_validate_error(&dml);
+    let decimal_start = dml.find(huge_decimal).unwrap();
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].message, "number too large to fit in target type");
+    assert_eq!(errors[1].message, "number too large to fit in target type");
+    assert_eq!(
+        errors[2].span,
+        Span::new(decimal_start, decimal_start + huge_decimal.len())
+    );
+}
+
+#[test]
+fn parse_function_defaults() {
+    let dml = r#"
+    model User {
+        id: Int = autoincrement() @primary
+        createdAt: DateTime = now()
+        updatedAt: DateTime @updatedAt
+    }
+
+    model Post {
+        id: String @primary @default(cuid())
+    }
+
+    model Comment {
+        id: UUID = uuid() @primary
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+    user_model
+        .assert_has_field("id")
+        .assert_default_value(dml::Value::Function(dml::DefaultFunction::Autoincrement));
+    schema
+        .assert_has_model("Post")
+        .assert_has_field("id")
+        .assert_default_value(dml::Value::Function(dml::DefaultFunction::Cuid));
+    schema
+        .assert_has_model("Comment")
+        .assert_has_field("id")
+        .assert_default_value(dml::Value::Function(dml::DefaultFunction::Uuid));
+    user_model
+        .assert_has_field("createdAt")
+        .assert_default_value(dml::Value::Function(dml::DefaultFunction::Now));
+    assert!(user_model.assert_has_field("updatedAt").is_updated_at);
+}
+
+#[test]
+fn fail_on_invalid_function_defaults() {
+    let dml = r#"
+    model User {
+        count: Int = now()
+        name: String = random()
+        createdAt: DateTime @default(now(1))
+        updatedAt: String @updatedAt
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(
+        errors[0].message,
+        "The function now() cannot be used as default value for this field."
+    );
+    assert_eq!(errors[0].span, Span::new(39, 44));
+    assert_eq!(errors[1].message, "Unknown function random().");
+    assert_eq!(errors[2].message, "The function now() takes no arguments.");
+    assert_eq!(errors[2].directive_name, "default");
+    assert_eq!(
+        errors[3].message,
+        "Fields that are marked with @updatedAt must be of type DateTime."
+    );
+}
+
+#[test]
+fn fail_on_generated_defaults_for_non_id_fields() {
+    let dml = r#"
+    model User {
+        id: Int @primary
+        token: String @default(cuid())
+        externalId: UUID = uuid()
+        position: Int = autoincrement()
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(
+        errors[0].message,
+        "The function cuid() can only be used as default value for id fields."
+    );
+    assert_eq!(
+        errors[1].message,
+        "The function uuid() can only be used as default value for id fields."
+    );
+    assert_eq!(
+        errors[2].message,
+        "The function autoincrement() can only be used as default value for id fields."
+    );
+}
@@ -25,3 +25,45 @@ fn db_directive() {
     let post_model = schema.assert_has_model("Post").assert_with_db_name("posti");
     post_model.assert_has_field("text").assert_with_db_name("post_text");
 }
+
+#[test]
+fn sequence_directive() {
+    let dml = r#"
+    model User {
+        id: Int @primary @sequence(name: "user_ids", initialValue: 100, allocationSize: 10)
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let id = schema.assert_has_model("User").assert_has_field("id");
+
+    assert_eq!(
+        id.id_info,
+        Some(dml::IdInfo {
+            strategy: dml::IdStrategy::Auto,
+            sequence: Some(dml::Sequence {
+                name: String::from("user_ids"),
+                initial_value: 100,
+                allocation_size: 10,
+            }),
+        })
+    );
+}
+
+#[test]
+fn fail_on_sequence_without_primary() {
+    let dml = r#"
+    model User {
+        id: Int @sequence(name: "user_ids", initialValue: 100, allocationSize: 10) @primary
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "Fields that are marked with @sequence must be marked with @primary first."
+    );
+    assert_eq!(errors[0].directive_name, "sequence");
+}
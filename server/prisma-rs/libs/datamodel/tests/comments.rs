@@ -0,0 +1,84 @@
+mod common;
+use common::*;
+use datamodel::dml;
+
+#[test]
+fn ignore_line_comments() {
+    let dml = r#"
+    // This is a line comment.
+    # This is a line comment, too.
+    model User {
+        firstName: String // Trailing comment.
+        lastName: String # Trailing comment.
+        email: String @db("mail#address") // Hashes in strings are not comments.
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+    assert_eq!(user_model.comments, vec![]);
+    user_model
+        .assert_has_field("firstName")
+        .assert_base_type(&dml::ScalarType::String);
+    user_model
+        .assert_has_field("lastName")
+        .assert_base_type(&dml::ScalarType::String);
+    user_model
+        .assert_has_field("email")
+        .assert_with_db_name("mail#address");
+}
+
+#[test]
+fn attach_doc_comments() {
+    let dml = r#"
+    /// The user model.
+    /// Spans multiple lines.
+    model User {
+        /// The first name.
+        firstName: String
+        lastName: String
+        role: Role
+    }
+
+    /// The user roles.
+    enum Role {
+        /// Can do everything.
+        ADMIN
+        USER
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+    user_model.assert_with_comments(&["The user model.", "Spans multiple lines."]);
+    user_model
+        .assert_has_field("firstName")
+        .assert_with_comments(&["The first name."]);
+    user_model.assert_has_field("lastName").assert_with_comments(&[]);
+
+    let role_enum = schema.assert_has_enum("Role");
+    role_enum.assert_with_comments(&["The user roles."]);
+    assert_eq!(role_enum.values[0].comments[0].text, "Can do everything.");
+    assert_eq!(role_enum.values[1].comments, vec![]);
+}
+
+#[test]
+fn render_doc_comments_to_dmmf() {
+    let dml = r#"
+    /// The user model.
+    /// Spans multiple lines.
+    model User {
+        /// The first name.
+        firstName: String
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let dmmf = datamodel::dmmf::model_to_dmmf(schema.assert_has_model("User"));
+
+    assert_eq!(
+        dmmf.documentation,
+        Some(String::from("The user model.\nSpans multiple lines."))
+    );
+    assert_eq!(dmmf.fields[0].documentation, Some(String::from("The first name.")));
+}
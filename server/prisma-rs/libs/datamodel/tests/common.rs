@@ -6,21 +6,25 @@ use datamodel::Validator;
 pub trait FieldAsserts {
     fn assert_base_type(&self, t: &dml::ScalarType) -> &Self;
     fn assert_enum_type(&self, en: &str) -> &Self;
+    fn assert_connector_type(&self, base_type: &dml::ScalarType, connector_type: &str) -> &Self;
     fn assert_relation_to(&self, t: &str) -> &Self;
     fn assert_relation_to_field(&self, t: &str) -> &Self;
     fn assert_arity(&self, arity: &dml::FieldArity) -> &Self;
     fn assert_with_db_name(&self, t: &str) -> &Self;
     fn assert_default_value(&self, t: dml::Value) -> &Self;
+    fn assert_with_comments(&self, t: &[&str]) -> &Self;
 }
 
 pub trait ModelAsserts {
     fn assert_has_field(&self, t: &str) -> &dml::Field;
     fn assert_is_embedded(&self, t: bool) -> &Self;
     fn assert_with_db_name(&self, t: &str) -> &Self;
+    fn assert_with_comments(&self, t: &[&str]) -> &Self;
 }
 
 pub trait EnumAsserts {
     fn assert_has_value(&self, t: &str) -> &Self;
+    fn assert_with_comments(&self, t: &[&str]) -> &Self;
 }
 
 pub trait SchemaAsserts {
@@ -49,6 +53,18 @@ impl FieldAsserts for dml::Field {
         return self;
     }
 
+    fn assert_connector_type(&self, base_type: &dml::ScalarType, connector_type: &str) -> &Self {
+        assert_eq!(
+            self.field_type,
+            dml::FieldType::ConnectorSpecific {
+                base_type: *base_type,
+                connector_type: Some(String::from(connector_type)),
+            }
+        );
+
+        return self;
+    }
+
     fn assert_relation_to(&self, t: &str) -> &Self {
         if let dml::FieldType::Relation(info) = &self.field_type {
             assert_eq!(info.to, t);
@@ -86,6 +102,12 @@ impl FieldAsserts for dml::Field {
 
         return self;
     }
+
+    fn assert_with_comments(&self, t: &[&str]) -> &Self {
+        assert_comments(&self.comments, t);
+
+        return self;
+    }
 }
 
 impl SchemaAsserts for dml::Schema {
@@ -112,20 +134,35 @@ impl ModelAsserts for dml::Model {
     fn assert_with_db_name(&self, t: &str) -> &Self {
         assert_eq!(self.database_name, Some(String::from(t)));
 
+        return self;
+    }
+    fn assert_with_comments(&self, t: &[&str]) -> &Self {
+        assert_comments(&self.comments, t);
+
         return self;
     }
 }
 
 impl EnumAsserts for dml::Enum {
     fn assert_has_value(&self, t: &str) -> &Self {
-        let pred = String::from(t);
         self.values
             .iter()
-            .find(|x| **x == pred)
+            .find(|x| x.name == t)
             .expect(format!("Field {} not found", t).as_str());
 
         return self;
     }
+
+    fn assert_with_comments(&self, t: &[&str]) -> &Self {
+        assert_comments(&self.comments, t);
+
+        return self;
+    }
+}
+
+fn assert_comments(comments: &Vec<dml::Comment>, t: &[&str]) {
+    let texts: Vec<&str> = comments.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, t);
 }
 
 pub fn parse_and_validate(input: &str) -> dml::Schema {
@@ -133,3 +170,13 @@ pub fn parse_and_validate(input: &str) -> dml::Schema {
     let validator = datamodel::validator::Validator::new();
     validator.validate(&ast).expect("Validation error")
 }
+
+pub fn parse_and_validate_error(input: &str) -> Vec<datamodel::validator::directive::DirectiveValidationError> {
+    let ast = datamodel::parser::parse(&String::from(input)).expect("Unable to parse datamodel.");
+    let validator = datamodel::validator::Validator::new();
+
+    match validator.validate(&ast) {
+        Ok(_) => panic!("Expected an error when validating datamodel."),
+        Err(errs) => errs,
+    }
+}
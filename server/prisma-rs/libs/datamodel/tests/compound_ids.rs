@@ -0,0 +1,114 @@
+mod common;
+use common::*;
+use datamodel::lowerer::Lowerer;
+use datamodel::renderer::Renderer;
+
+#[test]
+fn parse_compound_id() {
+    let dml = r#"
+    model User {
+        firstName: String
+        lastName: String
+        age: Int?
+
+        @@id([firstName, lastName])
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+
+    assert!(user_model.has_compound_id());
+    assert_eq!(
+        user_model.id_fields,
+        vec![String::from("firstName"), String::from("lastName")]
+    );
+    assert!(user_model.id_field().is_none());
+}
+
+#[test]
+fn fail_on_compound_id_with_primary_field() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        firstName: String
+        @@id([firstName])
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].directive_name, "id");
+    assert_eq!(
+        errors[0].message,
+        "Model User cannot have a compound id, because field id is already marked as @primary."
+    );
+}
+
+#[test]
+fn fail_on_invalid_compound_id_fields() {
+    let dml = r#"
+    model User {
+        firstName: String
+        nickname: String?
+        posts: Post[]
+        @@id([firstName, nickname])
+    }
+
+    model Post {
+        author: User
+        title: String
+        @@id([author, title])
+    }
+
+    model Comment {
+        text: String
+        @@id([text])
+        @@id([text])
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(
+        errors[0].message,
+        "Field nickname referenced in id is optional, but all fields of an id must be required."
+    );
+    assert_eq!(
+        errors[1].message,
+        "Field author referenced in id is a relation field, which cannot be part of an id."
+    );
+    assert_eq!(errors[2].message, "Model Comment must not declare more than one compound id.");
+}
+
+#[test]
+fn render_compound_id_inside_model() {
+    let dml = r#"
+    model User {
+        firstName: String
+        lastName: String
+        @@unique([lastName])
+        @@id([firstName, lastName])
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let rendered = Renderer::new().render(&Lowerer::new().lower(&schema));
+
+    let expected = r#"model User {
+    firstName: String
+    lastName:  String
+
+    @@id([firstName, lastName])
+    @@unique([lastName])
+}
+"#;
+
+    assert_eq!(rendered, expected);
+    assert_eq!(parse_and_validate(&rendered), schema);
+
+    let ast = datamodel::parser::parse(dml).expect("Unable to parse datamodel.");
+    assert_eq!(parse_and_validate(&Renderer::new().render(&ast)), schema);
+}
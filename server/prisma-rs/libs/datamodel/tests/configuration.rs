@@ -0,0 +1,135 @@
+extern crate datamodel;
+
+use datamodel::configuration;
+
+fn load(input: &str) -> datamodel::Configuration {
+    let ast = datamodel::parser::parse(input).expect("Unable to parse datamodel.");
+    configuration::load(&ast).expect("Configuration error")
+}
+
+fn load_error(input: &str) -> Vec<datamodel::validator::directive::DirectiveValidationError> {
+    let ast = datamodel::parser::parse(input).expect("Unable to parse datamodel.");
+
+    match configuration::load(&ast) {
+        Ok(_) => panic!("Expected an error when loading the configuration."),
+        Err(errs) => errs,
+    }
+}
+
+#[test]
+fn parse_datasource_and_generator() {
+    let dml = r#"
+    datasource pg {
+        provider = "postgresql"
+        url = "postgresql://localhost/prisma"
+        schema = "public"
+        connectionLimit = 10
+    }
+
+    generator js {
+        provider = "javascript"
+        output = "./client"
+    }
+
+    model User {
+        id: ID @primary
+    }
+    "#;
+
+    let config = load(dml);
+
+    assert_eq!(config.datasources.len(), 1);
+    let source = config.default_source().expect("Datasource not found");
+    assert_eq!(source.name, "pg");
+    assert_eq!(source.provider, "postgresql");
+    assert_eq!(source.url, "postgresql://localhost/prisma");
+    assert!(source.enabled);
+    assert_eq!(source.config.get("schema"), Some(&String::from("public")));
+    assert_eq!(source.config.get("connectionLimit"), Some(&String::from("10")));
+
+    let generator = config.find_generator("js").expect("Generator not found");
+    assert_eq!(generator.provider, "javascript");
+    assert_eq!(generator.config.get("output"), Some(&String::from("./client")));
+}
+
+#[test]
+fn resolve_env_in_datasource_url() {
+    std::env::set_var("CONFIGURATION_TEST_DATABASE_URL", "postgresql://localhost/test");
+
+    let dml = r#"
+    datasource pg {
+        provider = "postgresql"
+        url = env("CONFIGURATION_TEST_DATABASE_URL")
+    }
+    "#;
+
+    let config = load(dml);
+
+    assert_eq!(config.datasources[0].url, "postgresql://localhost/test");
+}
+
+#[test]
+fn skip_disabled_datasources() {
+    let dml = r#"
+    datasource sqlite {
+        provider = "sqlite"
+        url = "file:dev.db"
+        enabled = false
+    }
+
+    datasource pg {
+        provider = "postgresql"
+        url = "postgresql://localhost/prisma"
+    }
+    "#;
+
+    let config = load(dml);
+
+    assert_eq!(config.datasources.len(), 2);
+    assert_eq!(config.default_source().unwrap().name, "pg");
+}
+
+#[test]
+fn fail_on_missing_or_invalid_properties() {
+    let dml = r#"
+    datasource pg {
+        url = env("CONFIGURATION_TEST_UNDEFINED_VARIABLE")
+        enabled = maybe
+    }
+
+    generator js {
+        output = "./client"
+    }
+    "#;
+
+    let errors = load_error(dml);
+    let messages: Vec<&str> = errors.iter().map(|err| err.message.as_str()).collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Environment variable not found: CONFIGURATION_TEST_UNDEFINED_VARIABLE.",
+            "The generator js is missing the required property provider.",
+        ]
+    );
+}
+
+#[test]
+fn fail_on_duplicate_datasources() {
+    let dml = r#"
+    datasource pg {
+        provider = "postgresql"
+        url = "postgresql://localhost/prisma"
+    }
+
+    datasource pg {
+        provider = "postgresql"
+        url = "postgresql://localhost/other"
+    }
+    "#;
+
+    let errors = load_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "A datasource with the name pg is already declared.");
+}
@@ -0,0 +1,155 @@
+mod common;
+use common::*;
+use datamodel::dml;
+use datamodel::dml::WithCustomDirectives;
+use datamodel::lowerer::Lowerer;
+use datamodel::renderer::Renderer;
+use datamodel::validator::directive::{Args, DirectiveValidator, Error};
+use datamodel::Validator;
+
+// Marks a field as personally identifiable information, optionally with a retention in days.
+struct PiiDirectiveValidator {}
+
+impl DirectiveValidator<dml::Field> for PiiDirectiveValidator {
+    fn directive_name(&self) -> &'static str {
+        &"pii"
+    }
+
+    fn validate_and_apply(&self, args: &Args, field: &mut dml::Field) -> Option<Error> {
+        let mut arguments = vec![];
+        let retention = args.arg("retention");
+
+        if retention.is_valid() {
+            match retention.as_int() {
+                Ok(days) => arguments.push(dml::CustomDirectiveArgument {
+                    name: String::from("retention"),
+                    value: days.to_string(),
+                }),
+                Err(err) => return self.parser_error(&err),
+            }
+        }
+
+        field.add_custom_directive(dml::CustomDirective::new("pii", arguments));
+
+        None
+    }
+}
+
+fn validate(validator: &Validator, input: &str) -> Result<dml::Schema, Vec<Error>> {
+    let ast = datamodel::parser::parse(input).expect("Unable to parse datamodel.");
+    validator.validate(&ast)
+}
+
+#[test]
+fn apply_registered_custom_directive() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        email: String @pii(retention: 30)
+    }
+    "#;
+
+    let mut validator = Validator::new();
+    validator
+        .add_field_directive(Box::new(PiiDirectiveValidator {}))
+        .unwrap();
+
+    let schema = validate(&validator, dml).expect("Validation error");
+    let email = schema.assert_has_model("User").assert_has_field("email");
+
+    assert_eq!(email.custom_directives.len(), 1);
+    assert_eq!(email.custom_directives[0].name, "pii");
+    assert_eq!(email.custom_directives[0].arg("retention"), Some("30"));
+}
+
+#[test]
+fn fail_on_duplicate_directive_registration() {
+    let mut validator = Validator::new();
+
+    assert!(validator
+        .add_field_directive(Box::new(PiiDirectiveValidator {}))
+        .is_ok());
+
+    let error = validator
+        .add_field_directive(Box::new(PiiDirectiveValidator {}))
+        .expect_err("Expected an error for a duplicate directive.");
+    assert_eq!(error.directive_name, "pii");
+    assert_eq!(error.to_string(), "Duplicate directive definition: pii");
+}
+
+#[test]
+fn keep_passthrough_directives() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        name: String @search.searchable @search.boost(2)
+    }
+    @search.index(name: "users")
+
+    enum Role {
+        ADMIN
+    }
+    @search.facet
+    "#;
+
+    let mut validator = Validator::new();
+    validator.allow_passthrough("search");
+
+    let schema = validate(&validator, dml).expect("Validation error");
+    let user_model = schema.assert_has_model("User");
+    let name = user_model.assert_has_field("name");
+
+    assert_eq!(
+        name.custom_directives,
+        vec![
+            dml::CustomDirective::new("search.searchable", vec![]),
+            dml::CustomDirective::new(
+                "search.boost",
+                vec![dml::CustomDirectiveArgument {
+                    name: String::from(""),
+                    value: String::from("2"),
+                }]
+            ),
+        ]
+    );
+    assert_eq!(user_model.custom_directives[0].arg("name"), Some("\"users\""));
+    assert_eq!(schema.assert_has_enum("Role").custom_directives[0].name, "search.facet");
+}
+
+#[test]
+fn fail_on_directives_of_unknown_namespace() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        name: String @search.searchable
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].directive_name, "search.searchable");
+    assert_eq!(errors[0].message, "Encountered unknown directive");
+}
+
+#[test]
+fn passthrough_directives_round_trip() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        name: String @search.boost(weight: 2, analyzer: "english")
+    }
+    "#;
+
+    let mut validator = Validator::new();
+    validator.allow_passthrough("search");
+
+    let schema = validate(&validator, dml).expect("Validation error");
+    let rendered = Renderer::new().render(&Lowerer::new().lower(&schema));
+    assert!(rendered.contains(r#"@search.boost(weight: 2, analyzer: "english")"#));
+    assert_eq!(validate(&validator, &rendered).expect("Validation error"), schema);
+
+    let dmmf = datamodel::dmmf::render_to_dmmf(&schema);
+    assert!(dmmf.contains("\"customDirectives\""));
+    assert_eq!(datamodel::dmmf::parse_from_dmmf(&dmmf).unwrap(), schema);
+}
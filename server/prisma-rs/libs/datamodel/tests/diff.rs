@@ -0,0 +1,255 @@
+mod common;
+use common::*;
+use datamodel::diff::*;
+use datamodel::dml;
+use datamodel::lowerer::Lowerer;
+use datamodel::renderer::Renderer;
+
+#[test]
+fn diff_creates_deletes_and_updates() {
+    let previous = parse_and_validate(
+        r#"
+    model User {
+        id: ID @primary
+        name: String
+        age: Int?
+    }
+
+    model Comment {
+        id: ID @primary
+    }
+
+    enum Role {
+        ADMIN
+        USER
+    }
+    "#,
+    );
+    let next = parse_and_validate(
+        r#"
+    model User {
+        id: ID @primary
+        name: String?
+        email: String @unique
+    }
+
+    model Post {
+        id: ID @primary
+    }
+
+    enum Role {
+        ADMIN
+        EDITOR
+    }
+
+    enum Status {
+        DRAFT
+    }
+    "#,
+    );
+
+    let changes = datamodel::diff(&previous, &next);
+
+    match &changes[0] {
+        Change::CreateModel(model) => assert_eq!(model.name, "Post"),
+        change => panic!("Expected CreateModel, got {:?}", change),
+    }
+    match &changes[1] {
+        Change::DeleteModel(model) => assert_eq!(model.name, "Comment"),
+        change => panic!("Expected DeleteModel, got {:?}", change),
+    }
+    match &changes[2] {
+        Change::CreateField { model, field } => assert_eq!((model.as_str(), field.name.as_str()), ("User", "email")),
+        change => panic!("Expected CreateField, got {:?}", change),
+    }
+    match &changes[3] {
+        Change::DeleteField { model, field } => assert_eq!((model.as_str(), field.name.as_str()), ("User", "age")),
+        change => panic!("Expected DeleteField, got {:?}", change),
+    }
+    assert_eq!(
+        changes[4],
+        Change::UpdateField(FieldUpdate {
+            model: String::from("User"),
+            name: String::from("name"),
+            rename: None,
+            changes: vec![FieldChange::Arity(Changed {
+                previous: dml::FieldArity::Required,
+                next: dml::FieldArity::Optional,
+            })],
+        })
+    );
+    match &changes[5] {
+        Change::CreateEnum(en) => assert_eq!(en.name, "Status"),
+        change => panic!("Expected CreateEnum, got {:?}", change),
+    }
+    assert_eq!(
+        changes[6],
+        Change::UpdateEnum(EnumUpdate {
+            name: String::from("Role"),
+            rename: None,
+            changes: vec![
+                EnumChange::AddValue(String::from("EDITOR")),
+                EnumChange::RemoveValue(String::from("USER")),
+            ],
+        })
+    );
+    assert_eq!(changes.len(), 7);
+}
+
+#[test]
+fn diff_detects_renames_by_database_name() {
+    let previous = parse_and_validate(
+        r#"
+    model User {
+        id: ID @primary
+        email: String
+    }
+    "#,
+    );
+    let next = parse_and_validate(
+        r#"
+    model Account {
+        id: ID @primary
+        mail: String @db(name: "email")
+    } @db(name: "User")
+    "#,
+    );
+
+    let changes = datamodel::diff(&previous, &next);
+
+    assert_eq!(
+        changes,
+        vec![
+            Change::UpdateModel(ModelUpdate {
+                name: String::from("User"),
+                rename: Some(Rename {
+                    new_name: String::from("Account"),
+                    hint: RenameHint::DatabaseName,
+                }),
+                changes: vec![ModelChange::DatabaseName(Changed {
+                    previous: None,
+                    next: Some(String::from("User")),
+                })],
+            }),
+            Change::UpdateField(FieldUpdate {
+                model: String::from("Account"),
+                name: String::from("email"),
+                rename: Some(Rename {
+                    new_name: String::from("mail"),
+                    hint: RenameHint::DatabaseName,
+                }),
+                changes: vec![FieldChange::DatabaseName(Changed {
+                    previous: None,
+                    next: Some(String::from("email")),
+                })],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn diff_detects_renames_by_renamed_from() {
+    let previous = parse_and_validate(
+        r#"
+    model Post {
+        id: ID @primary
+        text: String
+    }
+
+    enum Role {
+        ADMIN
+    }
+    "#,
+    );
+    let next = parse_and_validate(
+        r#"
+    model Article {
+        id: ID @primary
+        body: String @renamedFrom("text")
+    } @renamedFrom("Post")
+
+    enum Permission {
+        ADMIN
+    } @renamedFrom("Role")
+    "#,
+    );
+
+    let changes = datamodel::diff(&previous, &next);
+
+    assert_eq!(
+        changes,
+        vec![
+            Change::UpdateModel(ModelUpdate {
+                name: String::from("Post"),
+                rename: Some(Rename {
+                    new_name: String::from("Article"),
+                    hint: RenameHint::RenamedFrom,
+                }),
+                changes: vec![],
+            }),
+            Change::UpdateField(FieldUpdate {
+                model: String::from("Article"),
+                name: String::from("text"),
+                rename: Some(Rename {
+                    new_name: String::from("body"),
+                    hint: RenameHint::RenamedFrom,
+                }),
+                changes: vec![],
+            }),
+            Change::UpdateEnum(EnumUpdate {
+                name: String::from("Role"),
+                rename: Some(Rename {
+                    new_name: String::from("Permission"),
+                    hint: RenameHint::RenamedFrom,
+                }),
+                changes: vec![],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn renamed_from_is_ignored_if_the_old_name_is_still_used() {
+    let previous = parse_and_validate(
+        r#"
+    model User {
+        id: ID @primary
+        name: String
+    }
+    "#,
+    );
+    let next = parse_and_validate(
+        r#"
+    model User {
+        id: ID @primary
+        name: String
+        fullName: String @renamedFrom("name")
+    }
+    "#,
+    );
+
+    let changes = datamodel::diff(&previous, &next);
+
+    assert_eq!(changes.len(), 1);
+    match &changes[0] {
+        Change::CreateField { model, field } => {
+            assert_eq!((model.as_str(), field.name.as_str()), ("User", "fullName"));
+        }
+        change => panic!("Expected CreateField, got {:?}", change),
+    }
+}
+
+#[test]
+fn renamed_from_is_rendered() {
+    let dml = r#"
+    model Article {
+        id: ID @primary
+        body: String @renamedFrom("text")
+    } @renamedFrom("Post")
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let rendered = Renderer::new().render(&Lowerer::new().lower(&schema));
+
+    assert_eq!(parse_and_validate(&rendered), schema);
+}
@@ -0,0 +1,41 @@
+mod common;
+use common::*;
+use std::fs;
+
+// Snapshot tests for the DMMF renderer. Each case consists of a datamodel in `tests/dmmf/<name>.prisma`
+// and the expected DMMF in `tests/dmmf/<name>.json`. Run with `UPDATE_SNAPSHOTS=1` to regenerate
+// the expected files after an intentional change, and review the diff.
+
+fn assert_dmmf_snapshot(name: &str) {
+    let datamodel_path = format!("{}/tests/dmmf/{}.prisma", env!("CARGO_MANIFEST_DIR"), name);
+    let snapshot_path = format!("{}/tests/dmmf/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+
+    let datamodel = fs::read_to_string(&datamodel_path).expect("Unable to read datamodel.");
+    let dmmf = datamodel::dmmf::render_to_dmmf(&parse_and_validate(&datamodel));
+
+    if std::env::var("UPDATE_SNAPSHOTS").is_ok() {
+        fs::write(&snapshot_path, format!("{}\n", dmmf)).expect("Unable to write snapshot.");
+        return;
+    }
+
+    let snapshot = fs::read_to_string(&snapshot_path).expect("Unable to read snapshot.");
+    let expected: serde_json::Value = serde_json::from_str(&snapshot).expect("Snapshot is not valid JSON.");
+    let actual: serde_json::Value = serde_json::from_str(&dmmf).expect("DMMF is not valid JSON.");
+
+    assert_eq!(actual, expected, "DMMF for {} does not match the snapshot.", name);
+}
+
+#[test]
+fn dmmf_scalars() {
+    assert_dmmf_snapshot("scalars");
+}
+
+#[test]
+fn dmmf_relations() {
+    assert_dmmf_snapshot("relations");
+}
+
+#[test]
+fn dmmf_enums() {
+    assert_dmmf_snapshot("enums");
+}
@@ -0,0 +1,326 @@
+mod common;
+use common::*;
+use datamodel::dmmf;
+
+use proptest::prelude::*;
+
+// Property tests: Random datamodels must survive the way source -> dml -> DMMF -> dml unchanged.
+// Datamodels are generated as plain values and rendered afterwards, so failing cases shrink.
+// Failing cases are saved to proptest-regressions and run first from then on.
+// Indices are taken modulo the available choices when rendering.
+
+const ENUM_VALUES: &[&str] = &["ADMIN", "USER", "GUEST", "OWNER"];
+const SCALAR_TYPES: &[&str] = &["Int", "Float", "Decimal", "Boolean", "String", "DateTime"];
+
+#[derive(Debug, Clone)]
+struct FieldSpec {
+    type_index: usize,
+    // 0: required, 1: optional, 2: list
+    arity: usize,
+    default: Option<usize>,
+    unique: bool,
+    mapped: bool,
+    documented: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ModelSpec {
+    primary: bool,
+    fields: Vec<FieldSpec>,
+    compound_id: Option<usize>,
+    // (unique, number of fields)
+    index: Option<(bool, usize)>,
+    documented: bool,
+    mapped: bool,
+    embedded: bool,
+}
+
+#[derive(Debug, Clone)]
+struct RelationSpec {
+    from: usize,
+    to: usize,
+    cascade: bool,
+}
+
+#[derive(Debug, Clone)]
+struct DatamodelSpec {
+    // The number of values of each enum.
+    enums: Vec<usize>,
+    models: Vec<ModelSpec>,
+    relations: Vec<RelationSpec>,
+}
+
+fn field_spec() -> impl Strategy<Value = FieldSpec> {
+    (
+        0..10usize,
+        0..3usize,
+        prop::option::weighted(0.4, 0..4usize),
+        prop::bool::weighted(0.2),
+        prop::bool::weighted(0.2),
+        prop::bool::weighted(0.2),
+    )
+        .prop_map(|(type_index, arity, default, unique, mapped, documented)| FieldSpec {
+            type_index,
+            arity,
+            default,
+            unique,
+            mapped,
+            documented,
+        })
+}
+
+fn model_spec() -> impl Strategy<Value = ModelSpec> {
+    (
+        prop::bool::weighted(0.7),
+        prop::collection::vec(field_spec(), 0..6),
+        prop::option::weighted(0.5, 0..6usize),
+        prop::option::weighted(0.4, (any::<bool>(), 0..6usize)),
+        prop::bool::weighted(0.3),
+        prop::bool::weighted(0.2),
+        prop::bool::weighted(0.1),
+    )
+        .prop_map(
+            |(primary, fields, compound_id, index, documented, mapped, embedded)| ModelSpec {
+                primary,
+                fields,
+                compound_id,
+                index,
+                documented,
+                mapped,
+                embedded,
+            },
+        )
+}
+
+fn datamodel_spec() -> impl Strategy<Value = DatamodelSpec> {
+    (
+        prop::collection::vec(1..=ENUM_VALUES.len(), 0..3),
+        prop::collection::vec(model_spec(), 1..5),
+        prop::collection::vec(
+            (0..4usize, 0..3usize, prop::bool::weighted(0.3)).prop_map(|(from, to, cascade)| RelationSpec {
+                from,
+                to,
+                cascade,
+            }),
+            0..4,
+        ),
+    )
+        .prop_map(|(enums, models, relations)| DatamodelSpec {
+            enums,
+            models,
+            relations,
+        })
+}
+
+fn default_value(type_name: &str, index: usize) -> String {
+    let choices: &[&str] = match type_name {
+        "Int" => &["0", "21", "-7"],
+        "Float" | "Decimal" => &["1.5", "-2.25", "0.1", "3.4"],
+        "Boolean" => &["true", "false"],
+        "String" => &["\"\"", "\"Hello\"", "\"with space\""],
+        "DateTime" => &["\"2019-05-15T12:00:00Z\""],
+        _ => &["ADMIN"],
+    };
+
+    String::from(choices[index % choices.len()])
+}
+
+fn render_datamodel(spec: &DatamodelSpec) -> String {
+    let mut result = String::new();
+    let model_count = spec.models.len();
+
+    let enum_names: Vec<String> = (0..spec.enums.len()).map(|i| format!("E{}", i)).collect();
+    let mut type_names: Vec<&str> = SCALAR_TYPES.to_vec();
+    type_names.extend(enum_names.iter().map(String::as_str));
+
+    let mut model_bodies: Vec<Vec<String>> = vec![];
+
+    for (i, model) in spec.models.iter().enumerate() {
+        let mut body: Vec<String> = vec![];
+        let mut indexable: Vec<String> = vec![];
+        let mut required: Vec<String> = vec![];
+
+        if model.primary {
+            body.push(String::from("id: ID @primary"));
+        }
+
+        for (j, field) in model.fields.iter().enumerate() {
+            let type_name = type_names[field.type_index % type_names.len()];
+            let mut line = String::new();
+
+            if field.documented {
+                line.push_str(&format!("/// Field {} of model {}.\n", j, i));
+            }
+
+            line.push_str(&format!(
+                "f{}: {}{}",
+                j,
+                type_name,
+                match field.arity {
+                    0 => "",
+                    1 => "?",
+                    _ => "[]",
+                }
+            ));
+
+            if let (Some(default), true) = (field.default, field.arity != 2) {
+                line.push_str(&format!(" = {}", default_value(type_name, default)));
+            }
+            if field.unique {
+                line.push_str(" @unique");
+            }
+            if field.mapped {
+                line.push_str(&format!(" @db(\"column_{}\")", j));
+            }
+
+            body.push(line);
+
+            // List fields cannot be indexed.
+            if field.arity != 2 {
+                indexable.push(format!("f{}", j));
+            }
+            if field.arity == 0 {
+                required.push(format!("f{}", j));
+            }
+        }
+
+        // Compound ids consist of required fields and exclude a @primary field.
+        if let (Some(count), false, false) = (model.compound_id, model.primary, required.is_empty()) {
+            let count = 1 + count % required.len();
+            body.push(format!("@@id([{}])", required[..count].join(", ")));
+        }
+
+        if let (Some((unique, count)), false) = (model.index, indexable.is_empty()) {
+            let directive = if unique { "unique" } else { "index" };
+            let count = 1 + count % indexable.len();
+            body.push(format!("@@{}([{}])", directive, indexable[..count].join(", ")));
+        }
+
+        model_bodies.push(body);
+    }
+
+    // Relations between distinct models, each named uniquely to avoid ambiguity.
+    if model_count > 1 {
+        for (k, relation) in spec.relations.iter().enumerate() {
+            let a = relation.from % model_count;
+            let b = (a + 1 + relation.to % (model_count - 1)) % model_count;
+            let on_delete = if relation.cascade {
+                " @onDelete(strategy: CASCADE)"
+            } else {
+                ""
+            };
+
+            model_bodies[a].push(format!("rel{}: M{}? @relation(name: \"r{}\")", k, b, k));
+            model_bodies[b].push(format!("rel{}: M{}[] @relation(name: \"r{}\"){}", k, a, k, on_delete));
+        }
+    }
+
+    for (i, (model, body)) in spec.models.iter().zip(model_bodies.iter_mut()).enumerate() {
+        // The grammar requires at least one field per model.
+        if body.is_empty() {
+            body.push(String::from("name: String"));
+        }
+
+        if model.documented {
+            result.push_str(&format!("/// Model {}.\n", i));
+        }
+        result.push_str(&format!("model M{} {{\n{}\n}}\n", i, body.join("\n")));
+
+        if model.mapped {
+            result.push_str(&format!("@db(\"table_{}\")\n", i));
+        }
+        if model.embedded {
+            result.push_str("@embedded\n");
+        }
+    }
+
+    for (name, count) in enum_names.iter().zip(&spec.enums) {
+        result.push_str(&format!("enum {} {{\n{}\n}}\n", name, ENUM_VALUES[..*count].join("\n")));
+    }
+
+    result
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    #[test]
+    fn random_datamodels_round_trip_through_dmmf(spec in datamodel_spec()) {
+        let datamodel = render_datamodel(&spec);
+        let schema = parse_and_validate(&datamodel);
+        let rendered = dmmf::render_to_dmmf(&schema);
+
+        match dmmf::parse_from_dmmf(&rendered) {
+            Ok(parsed) => {
+                prop_assert_eq!(&parsed, &schema, "Round trip failed for datamodel:\n{}", datamodel);
+                prop_assert_eq!(dmmf::render_to_dmmf(&parsed), rendered);
+            }
+            Err(err) => panic!("Unable to parse DMMF: {}\nDatamodel:\n{}", err, datamodel),
+        }
+    }
+}
+
+#[test]
+fn fail_on_invalid_dmmf() {
+    match dmmf::parse_from_dmmf("{ \"models\": 1 }") {
+        Err(dmmf::DmmfError::Json(_)) => {}
+        other => panic!("Expected a JSON error, received {:?}", other),
+    }
+}
+
+#[test]
+fn fail_on_unknown_scalar_type() {
+    let schema = parse_and_validate("model User {\n name: String\n}");
+    let rendered = dmmf::render_to_dmmf(&schema).replace("\"String\"", "\"Text\"");
+
+    match dmmf::parse_from_dmmf(&rendered) {
+        Err(dmmf::DmmfError::InvalidValue(message)) => assert_eq!(message, "Unknown scalar type Text."),
+        other => panic!("Expected an invalid value error, received {:?}", other),
+    }
+}
+
+#[test]
+fn fail_on_invalid_relations() {
+    let schema = parse_and_validate(
+        r#"
+        model User {
+            posts: Post[]
+        }
+        model Post {
+            author: User
+        }
+        "#,
+    );
+    // Rename only one side of the relation, which breaks the pairing.
+    let mut json: serde_json::Value = serde_json::from_str(&dmmf::render_to_dmmf(&schema)).unwrap();
+    json["models"][0]["fields"][0]["relationName"] = serde_json::json!("other");
+    let rendered = json.to_string();
+
+    match dmmf::parse_from_dmmf(&rendered) {
+        Err(dmmf::DmmfError::Validation(errors)) => assert_eq!(errors.len(), 2),
+        other => panic!("Expected a validation error, received {:?}", other),
+    }
+}
+
+#[test]
+fn render_non_finite_float_defaults_as_strings() {
+    let mut schema = parse_and_validate("model User {\n    id: ID @primary\n    score: Float = 1.5\n}\n");
+
+    for (value, rendered) in &[
+        (std::f64::NAN, "NaN"),
+        (std::f64::INFINITY, "Infinity"),
+        (std::f64::NEG_INFINITY, "-Infinity"),
+    ] {
+        let field = schema.find_model_mut("User").unwrap().find_field_mut("score").unwrap();
+        field.default_value = Some(datamodel::dml::Value::Float(*value));
+
+        let json: serde_json::Value = serde_json::from_str(&dmmf::render_to_dmmf(&schema)).unwrap();
+        assert_eq!(json["models"][0]["fields"][1]["default"], serde_json::json!(rendered));
+
+        // Non-finite floats are not valid in a datamodel, so they cannot be parsed back.
+        match dmmf::parse_from_dmmf(&json.to_string()) {
+            Err(dmmf::DmmfError::InvalidValue(_)) => {}
+            other => panic!("Expected an invalid value error, received {:?}", other),
+        }
+    }
+}
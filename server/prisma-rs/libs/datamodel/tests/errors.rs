@@ -0,0 +1,61 @@
+mod common;
+use common::*;
+use datamodel::errors;
+
+const DATAMODEL: &str = r#"
+model User {
+    id: ID @primary
+    name: String @foo
+    post: Pots
+}
+@bar
+
+model Post {
+    text: String @db(1)
+}
+"#;
+
+#[test]
+fn collect_all_errors() {
+    let errors = parse_and_validate_error(DATAMODEL);
+
+    let messages: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|e| (e.directive_name.as_str(), e.message.as_str()))
+        .collect();
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(messages[0], ("foo", "Encountered unknown directive"));
+    assert_eq!(
+        messages[1],
+        ("", "Type Pots is neither a built-in type, nor refers to another model or enum.")
+    );
+    assert_eq!(messages[2], ("bar", "Encountered unknown directive"));
+    assert_eq!(messages[3].0, "db");
+}
+
+#[test]
+fn line_and_column() {
+    let text = "model User {\n    name: String @foo\n}";
+
+    assert_eq!(errors::line_and_column(text, 0), (1, 1));
+    assert_eq!(errors::line_and_column(text, 17), (2, 5));
+    assert_eq!(errors::line_and_column(text, text.len()), (3, 2));
+}
+
+#[test]
+fn pretty_print_error() {
+    let errors = parse_and_validate_error(DATAMODEL);
+    let mut out: Vec<u8> = vec![];
+
+    errors::pretty_print(&mut out, "schema.prisma", DATAMODEL, &errors[0].span, &errors[0].message).unwrap();
+
+    let expected = r#"error: Encountered unknown directive
+ --> schema.prisma:4:18
+  |
+4 |     name: String @foo
+  |                  ^^^^
+  |
+"#;
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
@@ -0,0 +1,183 @@
+mod common;
+use common::*;
+use datamodel::dml;
+use datamodel::renderer::Renderer;
+
+#[test]
+fn parse_compound_unique_and_index() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        firstName: String
+        lastName: String
+        age: Int
+
+        @@unique([firstName, lastName])
+        @@index(fields: [age, lastName], name: "age_index")
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+
+    assert_eq!(
+        user_model.indexes,
+        vec![
+            dml::IndexDefinition {
+                name: None,
+                fields: vec![String::from("firstName"), String::from("lastName")],
+                tpe: dml::IndexType::Unique,
+            },
+            dml::IndexDefinition {
+                name: Some(String::from("age_index")),
+                fields: vec![String::from("age"), String::from("lastName")],
+                tpe: dml::IndexType::Normal,
+            },
+        ]
+    );
+    assert_eq!(user_model.unique_indexes().count(), 1);
+}
+
+#[test]
+fn fail_on_unknown_index_field() {
+    let dml = r#"
+    model User {
+        firstName: String
+        @@unique([firstName, lastName])
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].directive_name, "unique");
+    assert_eq!(
+        errors[0].message,
+        "Field lastName referenced in index does not exist on model User."
+    );
+    assert_eq!(&dml[errors[0].span.start..errors[0].span.end], "lastName");
+}
+
+#[test]
+fn fail_on_relation_and_list_index_fields() {
+    let dml = r#"
+    model User {
+        posts: Post[]
+        tags: String[]
+        @@index([posts])
+        @@unique([tags])
+    }
+
+    model Post {
+        author: User
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].message,
+        "Field posts referenced in index is a relation field, which cannot be indexed."
+    );
+    assert_eq!(
+        errors[1].message,
+        "Field tags referenced in index is a list field, which cannot be indexed."
+    );
+}
+
+#[test]
+fn fail_on_invalid_index_arguments() {
+    let dml = r#"
+    model User {
+        firstName: String
+        @@unique(firstName)
+        @@index([])
+        @@index([firstName, firstName])
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].message, "Expected Array Value, received firstName");
+    assert_eq!(errors[1].message, "An index must reference at least one field.");
+    assert_eq!(errors[2].message, "Field firstName is referenced more than once in index.");
+}
+
+#[test]
+fn render_indexes_inside_model() {
+    let dml = r#"
+    model User {
+        @@index([lastName])
+        firstName: String
+        @@unique(fields: [firstName, lastName], name: "full_name")
+        lastName: String
+    }
+    @db("user")
+    "#;
+
+    let expected = r#"model User {
+    firstName: String
+    lastName:  String
+
+    @@index([lastName])
+    @@unique(fields: [firstName, lastName], name: "full_name")
+}
+@db("user")
+"#;
+
+    let ast = datamodel::parser::parse(dml).expect("Unable to parse datamodel.");
+    let rendered = Renderer::new().render(&ast);
+
+    assert_eq!(rendered, expected);
+    assert_eq!(parse_and_validate(&rendered), parse_and_validate(dml));
+}
+
+#[test]
+fn fail_on_index_directives_after_the_model() {
+    let dml = r#"
+    model User {
+        firstName: String
+        lastName: String
+        @@db("user")
+    }
+    @unique([firstName, lastName]) @index([lastName])
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(
+        errors[0].message,
+        "The directive @unique has to be declared inside the model as @@unique."
+    );
+    assert_eq!(
+        errors[1].message,
+        "The directive @index has to be declared inside the model as @@index."
+    );
+    assert_eq!(
+        errors[2].message,
+        "The directive @@db is not a block directive, it has to be declared after the model as @db."
+    );
+}
+
+#[test]
+fn derive_index_names() {
+    let dml = r#"
+    model User {
+        firstName: String
+        lastName: String
+        @@index([firstName, lastName])
+        @@unique(fields: [lastName], name: "last_name")
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+
+    assert_eq!(user_model.indexes[0].name(), "firstName_lastName");
+    assert_eq!(user_model.indexes[0].database_name("User"), "User.firstName_lastName");
+    assert_eq!(user_model.indexes[1].name(), "last_name");
+    assert_eq!(user_model.indexes[1].database_name("User"), "last_name");
+}
@@ -0,0 +1,210 @@
+mod common;
+use common::*;
+
+#[test]
+fn identifiers_may_contain_underscores() {
+    let dml = r#"
+    model blog_post {
+        id: ID @primary
+        created_at: DateTime
+        status: post_status
+        @@unique([created_at, status])
+    }
+
+    enum post_status {
+        IN_REVIEW
+        PUBLISHED_2019
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let model = schema.assert_has_model("blog_post");
+    model.assert_has_field("created_at");
+    model.assert_has_field("status").assert_enum_type("post_status");
+    schema
+        .assert_has_enum("post_status")
+        .assert_has_value("IN_REVIEW")
+        .assert_has_value("PUBLISHED_2019");
+}
+
+#[test]
+fn duplicate_declarations_must_error() {
+    let dml = "model User {\n    id: ID @primary\n}\n\nenum User {\n    A\n}\n\nmodel User {\n    id: ID @primary\n}\n";
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "Enum User conflicts with model User.");
+    assert!(dml[errors[0].span.start..errors[0].span.end].starts_with("enum User {"));
+    assert_eq!(errors[1].message, "Model User is already declared.");
+    assert!(dml[errors[1].span.start..].starts_with("model User {"));
+    assert!(errors[1].span.start >= errors[0].span.end);
+}
+
+#[test]
+fn duplicate_fields_and_enum_values_must_error() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        name: String
+        name: Int
+    }
+
+    enum Role {
+        ADMIN
+        ADMIN
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+    let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Field name is already declared on model User.",
+            "Value ADMIN is already declared on enum Role.",
+        ]
+    );
+}
+
+#[test]
+fn names_must_be_case_insensitive_where_the_database_is() {
+    let models = r#"
+    model User {
+        id: ID @primary
+        name: String
+        Name: String
+    }
+
+    model user {
+        id: ID @primary
+    }
+    "#;
+
+    let mysql = format!(
+        "datasource db {{\n    provider = \"mysql\"\n    url = \"mysql://localhost\"\n}}\n{}",
+        models
+    );
+    let messages: Vec<String> = parse_and_validate_error(&mysql)
+        .into_iter()
+        .map(|e| e.message)
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Model user conflicts with model User, names are case-insensitive in mysql.",
+            "Field Name conflicts with field name on model User, names are case-insensitive in mysql.",
+        ]
+    );
+
+    let postgres = mysql.replace("mysql", "postgresql");
+    parse_and_validate(&postgres);
+}
+
+#[test]
+fn mapped_names_must_not_collide() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        email: String
+        mail: String @db(name: "email")
+        tags: String[]
+        labels: String[] @db(name: "tags")
+    }
+
+    model Account {
+        id: ID @primary
+    } @db(name: "User")
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+    let messages: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|e| (e.directive_name.as_str(), e.message.as_str()))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "db",
+                "Field mail is mapped to column email, which is already used by field email on model User."
+            ),
+            (
+                "db",
+                "Model Account is mapped to table User, which is already used by model User."
+            ),
+        ]
+    );
+    assert_eq!(&dml[errors[0].span.start..errors[0].span.end], "@db(name: \"email\")");
+}
+
+#[test]
+fn reserved_names_must_error() {
+    let dml = r#"
+    model Query {
+        id: ID @primary
+    }
+
+    enum BatchPayload {
+        A
+    }
+
+    model History {
+        id: ID @primary
+    } @db(name: "_Migration")
+
+    model Order {
+        id: ID @primary
+        group: Group
+    }
+
+    model Group {
+        id: ID @primary
+        orders: Order[]
+    } @db(name: "select")
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+    let messages: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|e| (e.directive_name.as_str(), e.message.as_str()))
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "",
+                "The model name Query is reserved for a type of the generated API. Please choose another name."
+            ),
+            (
+                "",
+                "The enum name BatchPayload is reserved for a type of the generated API. Please choose another name."
+            ),
+            (
+                "db",
+                "The table name _Migration of model History is reserved for the migration history."
+            ),
+        ]
+    );
+}
+
+#[test]
+fn embedded_models_have_no_table_names() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        order: Order
+    }
+
+    model Order {
+        total: Int
+        user: User
+    } @embedded
+    "#;
+
+    parse_and_validate(dml);
+}
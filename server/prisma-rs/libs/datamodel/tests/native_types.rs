@@ -0,0 +1,128 @@
+mod common;
+use common::*;
+use datamodel::dml;
+use datamodel::lowerer::Lowerer;
+use datamodel::renderer::Renderer;
+
+#[test]
+fn parse_native_types() {
+    let dml = r#"
+    datasource pg {
+        provider = "postgresql"
+        url = "postgresql://localhost/prisma"
+    }
+
+    model User {
+        id: String @primary @db.Uuid
+        name: String @db.VarChar(255)
+        balance: Decimal @db.Numeric(10, 2)
+        createdAt: DateTime @db.Timestamptz
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let user_model = schema.assert_has_model("User");
+
+    user_model
+        .assert_has_field("id")
+        .assert_connector_type(&dml::ScalarType::String, "Uuid");
+    user_model
+        .assert_has_field("name")
+        .assert_connector_type(&dml::ScalarType::String, "VarChar(255)");
+    user_model
+        .assert_has_field("balance")
+        .assert_connector_type(&dml::ScalarType::Decimal, "Numeric(10, 2)");
+    user_model
+        .assert_has_field("createdAt")
+        .assert_connector_type(&dml::ScalarType::DateTime, "Timestamptz");
+}
+
+#[test]
+fn fail_on_native_type_of_other_connector() {
+    let dml = r#"
+    datasource db {
+        provider = "sqlite"
+        url = "file:dev.db"
+    }
+
+    model User {
+        id: String @primary @db.Uuid
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].directive_name, "db.Uuid");
+    assert_eq!(
+        errors[0].message,
+        "Native type Uuid is not supported by the sqlite connector."
+    );
+}
+
+#[test]
+fn native_types_of_the_enabled_datasource() {
+    let dml = r#"
+    datasource db {
+        provider = "sqlite"
+        url = "file:dev.db"
+        enabled = false
+    }
+
+    datasource pg {
+        provider = "postgresql"
+        url = "postgresql://localhost/prisma"
+    }
+
+    model User {
+        id: String @primary @db.Uuid
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+
+    schema
+        .assert_has_model("User")
+        .assert_has_field("id")
+        .assert_connector_type(&dml::ScalarType::String, "Uuid");
+}
+
+#[test]
+fn fail_on_invalid_native_types() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        age: Int @db.VarChar(10)
+        name: String @db.VarChar
+        email: String @db.Varchar(10)
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+    let messages: Vec<&str> = errors.iter().map(|err| err.message.as_str()).collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Native type VarChar is not compatible with field type Int.",
+            "Native type VarChar expects 1 arguments, but received 0.",
+            "Unknown native type Varchar.",
+        ]
+    );
+}
+
+#[test]
+fn native_types_round_trip() {
+    let dml = r#"
+    model User {
+        id: Int @primary @db.BigInt
+        name: String @db.VarChar(255)
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let rendered = Renderer::new().render(&Lowerer::new().lower(&schema));
+
+    assert!(rendered.contains("@db.VarChar(255)"));
+    assert_eq!(parse_and_validate(&rendered), schema);
+}
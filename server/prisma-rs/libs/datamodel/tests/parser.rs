@@ -55,3 +55,154 @@ enum CategoryEnum {
 
     datamodel::parser::parse(&String::from(dml));
 }
+
+#[test]
+fn parser_should_recover_from_broken_declarations() {
+    let dml = r#"
+model User {
+    id: ID @primary
+    name: String @default(
+}
+
+/// Blog posts.
+model Post {
+    id: ID @primary
+    title: String
+}
+
+model Comment {
+    id: ID @primary
+    text: String =
+}
+
+enum Role {
+    ADMIN
+    USER
+}
+"#;
+
+    let (schema, errors) = datamodel::parser::parse_with_recovery(dml);
+
+    let names: Vec<&str> = schema
+        .models
+        .iter()
+        .map(|obj| match obj {
+            datamodel::ast::ModelOrEnum::Model(model) => model.name.as_str(),
+            datamodel::ast::ModelOrEnum::Enum(en) => en.name.as_str(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(names, vec!["Post", "Role"]);
+
+    if let datamodel::ast::ModelOrEnum::Model(post) = &schema.models[0] {
+        assert_eq!(post.comments[0].text, "Blog posts.");
+        assert_eq!(&dml[post.span.start..post.span.start + 10], "/// Blog p");
+    }
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].span.start > dml.find("model User").unwrap());
+    assert!(errors[0].span.start < dml.find("/// Blog").unwrap());
+    assert!(errors[1].span.start > dml.find("model Comment").unwrap());
+    assert!(errors[1].span.start < dml.find("enum Role").unwrap());
+}
+
+#[test]
+fn parser_errors_should_list_expected_tokens() {
+    let dml = "model User {\n    id: ID @primary\n    name:\n}\n";
+
+    let error = datamodel::parser::parse(dml).unwrap_err();
+
+    assert_eq!(error.span.start, dml.find("\n}").unwrap() + 1);
+    assert!(error.expected.contains(&String::from("field type")));
+    assert!(error
+        .message
+        .starts_with("Unexpected token. Expected one of: "));
+}
+
+#[test]
+fn parser_with_recovery_should_accept_valid_datamodels() {
+    let dml = "model User {\n    id: ID @primary\n}\n";
+
+    let (schema, errors) = datamodel::parser::parse_with_recovery(dml);
+
+    assert_eq!(schema.models.len(), 1);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn parser_with_recovery_should_not_report_references_to_broken_declarations() {
+    let dml = r#"
+model User {
+    id: ID @primary
+    name:
+}
+
+model Post {
+    id: ID @primary
+    author: User
+}
+"#;
+
+    let (schema, errors) = datamodel::parser::parse_with_recovery(dml);
+
+    assert_eq!(schema.broken_declarations, vec![String::from("User")]);
+    assert_eq!(errors.len(), 1);
+
+    let datamodel = datamodel::Validator::new().validate(&schema).unwrap();
+    let post = datamodel.find_model("Post").unwrap();
+    assert!(post.find_field("author").is_none());
+}
+
+#[test]
+fn parser_with_recovery_should_handle_input_without_valid_declarations() {
+    let (schema, errors) = datamodel::parser::parse_with_recovery("model User {\n    id: ID @primary\n");
+
+    assert!(schema.models.is_empty());
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn parser_should_ignore_dangling_doc_comment_before_closing_brace() {
+    let dml = "model User {\n    id: ID @primary\n    /// Dangling.\n}\n\nenum Role {\n    ADMIN\n    /// Dangling.\n}\n";
+
+    let schema = datamodel::parser::parse(dml).unwrap();
+
+    assert_eq!(schema.models.len(), 2);
+    if let datamodel::ast::ModelOrEnum::Model(model) = &schema.models[0] {
+        assert_eq!(model.fields.len(), 1);
+        assert!(model.comments.is_empty());
+        assert!(model.fields[0].comments.is_empty());
+    } else {
+        panic!("Expected a model.");
+    }
+}
+
+#[test]
+fn parser_should_ignore_dangling_doc_comment_before_model_directive() {
+    let dml = "model User {\n    a: String\n    b: String\n    /// Dangling.\n    @@unique([a, b])\n}\n";
+
+    let schema = datamodel::parser::parse(dml).unwrap();
+
+    if let datamodel::ast::ModelOrEnum::Model(model) = &schema.models[0] {
+        assert_eq!(model.fields.len(), 2);
+        assert_eq!(model.block_directives.len(), 1);
+        assert!(model.comments.is_empty());
+    } else {
+        panic!("Expected a model.");
+    }
+}
+
+#[test]
+fn parser_should_ignore_dangling_doc_comment_at_end_of_file() {
+    let dml = "/// The user.\nmodel User {\n    id: ID @primary\n}\n/// Dangling.\n";
+
+    let schema = datamodel::parser::parse(dml).unwrap();
+
+    assert_eq!(schema.models.len(), 1);
+    if let datamodel::ast::ModelOrEnum::Model(model) = &schema.models[0] {
+        assert_eq!(model.comments.len(), 1);
+        assert_eq!(model.comments[0].text, "The user.");
+    } else {
+        panic!("Expected a model.");
+    }
+}
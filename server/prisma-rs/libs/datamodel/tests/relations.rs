@@ -60,3 +60,217 @@ fn resolve_enum_field() {
     role_enum.assert_has_value("PRO");
     role_enum.assert_has_value("USER");
 }
+
+#[test]
+fn resolve_related_field() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        email: String @unique
+        posts: Post[]
+        reviewed: Post[] @relation(name: "review")
+    }
+
+    model Post {
+        id: ID @primary
+        author: User
+        reviewer: User(email) @relation(name: "review")
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let post_model = schema.assert_has_model("Post");
+    post_model
+        .assert_has_field("author")
+        .assert_relation_to("User")
+        .assert_relation_to_field("id");
+    post_model
+        .assert_has_field("reviewer")
+        .assert_relation_to("User")
+        .assert_relation_to_field("email");
+    schema
+        .assert_has_model("User")
+        .assert_has_field("posts")
+        .assert_relation_to_field("id");
+
+    assert_eq!(schema.related_field("Post", "author").unwrap().name, "posts");
+    assert_eq!(schema.related_field("Post", "reviewer").unwrap().name, "reviewed");
+    assert_eq!(schema.related_field("User", "reviewed").unwrap().name, "reviewer");
+}
+
+#[test]
+fn resolve_self_relation() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        invitedBy: User?
+        invited: User[]
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    assert_eq!(schema.related_field("User", "invitedBy").unwrap().name, "invited");
+    assert_eq!(schema.related_field("User", "invited").unwrap().name, "invitedBy");
+}
+
+#[test]
+fn fail_on_unknown_related_field() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        posts: Post[]
+    }
+
+    model Post {
+        id: ID @primary
+        author: User(mail)
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "Field mail referenced in relation does not exist on model User."
+    );
+}
+
+#[test]
+fn fail_on_missing_back_relation() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        posts: Post[] @relation(name: "author")
+    }
+
+    model Post {
+        id: ID @primary
+        author: User
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].message,
+        "The relation field posts on model User is missing an opposite relation field named author on model Post."
+    );
+    assert_eq!(
+        errors[1].message,
+        "The relation field author on model Post is missing an opposite relation field on model User."
+    );
+}
+
+#[test]
+fn fail_on_ambiguous_relations() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        posts: Post[]
+        reviewed: Post[]
+    }
+
+    model Post {
+        id: ID @primary
+        author: User
+        reviewer: User
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 4);
+    assert_eq!(
+        errors[0].message,
+        "Ambiguous relation detected. There is more than one unnamed relation between User and Post. Please name the relations using @relation(name: ...)."
+    );
+    assert_eq!(errors[0].directive_name, "relation");
+}
+
+#[test]
+fn fail_on_ambiguous_self_relations() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        invitedBy: User? @relation(name: "invite")
+        invited: User[] @relation(name: "invite")
+        referredBy: User? @relation(name: "invite")
+    }
+    "#;
+
+    let errors = parse_and_validate_error(dml);
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(
+        errors[0].message,
+        "Ambiguous relation detected. The relation name invite is used by more than one relation between User and User."
+    );
+}
+
+#[test]
+fn relations_are_named_and_stored_consistently() {
+    let dml = r#"
+    model User {
+        id: ID @primary
+        posts: Post[]
+        groups: Group[]
+    }
+
+    model Post {
+        id: ID @primary
+        author: User @db(name: "authorId")
+        tags: Tag[] @relation(name: "PostTags")
+    }
+
+    model Tag {
+        id: ID @primary
+        posts: Post[] @relation(name: "PostTags")
+    }
+
+    model Group {
+        id: ID @primary
+        members: User[]
+    }
+    "#;
+
+    let schema = parse_and_validate(dml);
+    let relations = schema.relations();
+    let find = |name: &str| {
+        relations
+            .iter()
+            .find(|relation| relation.name() == name)
+            .unwrap_or_else(|| panic!("Relation {} not found.", name))
+    };
+
+    assert_eq!(relations.len(), 3);
+
+    let post_to_user = find("PostToUser");
+    assert_eq!(post_to_user.model_a.name, "Post");
+    assert_eq!(
+        post_to_user.manifestation(),
+        Some(dml::RelationManifestation::Inline {
+            in_table_of_model: String::from("Post"),
+            column: String::from("authorId"),
+        })
+    );
+
+    assert_eq!(
+        find("PostTags").manifestation(),
+        Some(dml::RelationManifestation::Table {
+            table: String::from("_PostTags"),
+            model_a_column: String::from("A"),
+            model_b_column: String::from("B"),
+        })
+    );
+
+    assert_eq!(
+        find("GroupToUser").manifestation(),
+        Some(dml::RelationManifestation::Table {
+            table: String::from("_GroupToUser"),
+            model_a_column: String::from("A"),
+            model_b_column: String::from("B"),
+        })
+    );
+}
@@ -0,0 +1,196 @@
+mod common;
+use common::*;
+use datamodel::lowerer::Lowerer;
+use datamodel::renderer::Renderer;
+
+const DATAMODEL: &str = r#"
+/// A user of the blog.
+model User {
+    id: ID @primary
+    // This line comment is kept.
+    firstName: String @db("first_name") @unique
+    role: Role
+    isPro: Boolean = false
+    posts: Post[] @onDelete(strategy: CASCADE) @relation(name: "author")
+    tags: String[] @scalarList(strategy: RELATION)
+}
+@db(name: "user")
+
+model Post { text: String?  author: User @relation(name: "author") }
+
+/// Roles a user can have.
+enum Role {
+  /// Can do everything.
+  ADMIN
+  USER }
+"#;
+
+fn format(input: &str) -> String {
+    let ast = datamodel::parser::parse(input).expect("Unable to parse datamodel.");
+    Renderer::new().render(&ast)
+}
+
+#[test]
+fn render_canonical_form() {
+    let expected = r#"/// A user of the blog.
+model User {
+    id:        ID               @primary
+    // This line comment is kept.
+    firstName: String           @unique @db("first_name")
+    role:      Role
+    isPro:     Boolean  = false
+    posts:     Post[]           @relation(name: "author") @onDelete(strategy: CASCADE)
+    tags:      String[]         @scalarList(strategy: RELATION)
+}
+@db(name: "user")
+
+model Post {
+    text:   String?
+    author: User    @relation(name: "author")
+}
+
+/// Roles a user can have.
+enum Role {
+    /// Can do everything.
+    ADMIN
+    USER
+}
+"#;
+
+    assert_eq!(format(DATAMODEL), expected);
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    let formatted = format(DATAMODEL);
+
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn formatting_round_trips() {
+    let formatted = format(DATAMODEL);
+
+    assert_eq!(parse_and_validate(&formatted), parse_and_validate(DATAMODEL));
+}
+
+#[test]
+fn lowering_round_trips() {
+    let schema = parse_and_validate(DATAMODEL);
+    let rendered = Renderer::new().render(&Lowerer::new().lower(&schema));
+
+    assert_eq!(parse_and_validate(&rendered), schema);
+}
+
+#[test]
+fn render_datasources_and_generators() {
+    let input = r#"
+/// The main database.
+datasource pg { provider = "postgresql" url = env("DATABASE_URL")
+  enabled = true }
+
+generator js {
+    provider = "javascript"
+}
+"#;
+
+    let expected = r#"/// The main database.
+datasource pg {
+    provider = "postgresql"
+    url      = env("DATABASE_URL")
+    enabled  = true
+}
+
+generator js {
+    provider = "javascript"
+}
+"#;
+
+    assert_eq!(format(input), expected);
+}
+
+#[test]
+fn line_comments_round_trip() {
+    let input = r#"// The blog.
+datasource pg {
+    # The database.
+    provider = "postgresql" // Only postgres.
+    // No more properties.
+}
+
+/// A user of the blog.
+// Line comment after the doc comment.
+model User { // Opening brace.
+    id: ID @primary # Hash comment.
+    // Before the name.
+    name: String @db("name // not a comment")
+    email: String
+
+    // Before the index.
+    @@unique([name, email]) // Trailing the index.
+    // Last in the model.
+} // Closing brace.
+
+enum Role {
+    ADMIN // Admins.
+    # Last in the enum.
+}
+// End of file.
+"#;
+
+    let expected = r#"// The blog.
+datasource pg {
+    # The database.
+    provider = "postgresql" // Only postgres.
+    // No more properties.
+}
+
+// Line comment after the doc comment.
+/// A user of the blog.
+model User {
+    // Opening brace.
+    id:    ID     @primary # Hash comment.
+    // Before the name.
+    name:  String @db("name // not a comment")
+    email: String
+
+    // Before the index.
+    @@unique([name, email]) // Trailing the index.
+    // Last in the model.
+} // Closing brace.
+
+enum Role {
+    ADMIN // Admins.
+    # Last in the enum.
+}
+
+// End of file.
+"#;
+
+    let formatted = format(input);
+
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), formatted);
+    assert_eq!(parse_and_validate(&formatted), parse_and_validate(input));
+}
+
+#[test]
+fn line_comments_before_model_directives_are_kept() {
+    let input = "model User {\n    id: ID @primary\n}\n// Before the directive.\n@db(name: \"user\") // Trailing.\n";
+
+    assert_eq!(format(input), input);
+}
+
+#[test]
+fn sequences_round_trip() {
+    let input = r#"model User {
+    id: Int @primary @sequence(name: "user_ids", initialValue: 100, allocationSize: 10)
+}
+"#;
+
+    let schema = parse_and_validate(input);
+    let rendered = Renderer::new().render(&Lowerer::new().lower(&schema));
+
+    assert_eq!(rendered, input);
+    assert_eq!(parse_and_validate(&rendered), schema);
+}
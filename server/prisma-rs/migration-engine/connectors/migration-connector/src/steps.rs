@@ -135,6 +135,7 @@ pub struct UpdateField {
 impl UpdateField {
     pub fn is_any_option_set(&self) -> bool {
         self.new_name.is_some()
+            || self.tpe.is_some()
             || self.arity.is_some()
             || self.db_name.is_some()
             || self.is_created_at.is_some()
@@ -25,27 +25,56 @@ impl<'a> DatabaseSchemaCalculator<'a> {
         DatabaseSchema { tables }
     }
 
+    // Embedded models have no table, their records are stored as json in a column of the parent table.
     fn calculate_model_tables(&self) -> Vec<Table> {
         self.data_model
             .models()
+            .filter(|model| !model.is_embedded)
             .map(|model| {
-                let columns = model
+                let mut columns: Vec<Column> = model
                     .fields()
-                    .flat_map(|f| match (&f.field_type, &f.arity) {
-                        (FieldType::Base(scalar), arity) if arity != &FieldArity::List => Some(Column {
-                            name: f.name.clone(),
-                            tpe: column_type(scalar),
-                            is_required: arity == &FieldArity::Required,
-                            foreign_key: None,
-                            sequence: None,
-                        }),
-                        _ => None,
+                    .filter(|f| is_scalar(f) && f.arity != FieldArity::List)
+                    .map(|f| Column {
+                        name: f.name.clone(),
+                        tpe: field_column_type(f),
+                        is_required: f.arity == FieldArity::Required,
+                        foreign_key: None,
+                        sequence: None,
+                        default: column_default(f),
+                    })
+                    .collect();
+                let mut embedded_columns: Vec<Column> = model
+                    .fields()
+                    .filter(|f| self.is_embedded_relation(f))
+                    .map(|f| Column::new(f.name.clone(), ColumnType::Json, f.arity == FieldArity::Required))
+                    .collect();
+                columns.append(&mut embedded_columns);
+                columns.append(&mut self.calculate_inline_relation_columns(model));
+                let primary_columns = if model.has_compound_id() {
+                    model.id_fields.clone()
+                } else {
+                    model
+                        .id_field()
+                        .filter(|f| is_scalar(f))
+                        .map(|f| vec![f.name.clone()])
+                        .unwrap_or(Vec::new())
+                };
+                let indexes = model
+                    .indexes
+                    .iter()
+                    // Enum fields are not backed by columns yet, so indexes on them are skipped.
+                    .filter(|index| index.fields.iter().all(|f| columns.iter().any(|c| &c.name == f)))
+                    .map(|index| Index {
+                        name: index.database_name(&model.name),
+                        columns: index.fields.clone(),
+                        unique: index.is_unique(),
                     })
                     .collect();
                 Table {
                     name: model.name.clone(),
                     columns: columns,
-                    indexes: Vec::new(),
+                    indexes: indexes,
+                    primary_columns: primary_columns,
                 }
             })
             .collect()
@@ -54,7 +83,7 @@ impl<'a> DatabaseSchemaCalculator<'a> {
     fn calculate_scalar_list_tables(&self) -> Vec<Table> {
         let mut result = Vec::new();
 
-        for model in self.data_model.models() {
+        for model in self.data_model.models().filter(|model| !model.is_embedded) {
             let list_fields: Vec<&Field> = model
                 .fields()
                 .filter(|f| f.arity == FieldArity::List && is_scalar(f))
@@ -64,11 +93,12 @@ impl<'a> DatabaseSchemaCalculator<'a> {
                 let table = Table {
                     name: format!("{}_{}", model.name.clone(), field.name.clone()),
                     columns: vec![
-                        Column::new("nodeId".to_string(), column_type(&scalar_type(&id_field)), true),
+                        Column::new("nodeId".to_string(), field_column_type(&id_field), true),
                         Column::new("position".to_string(), ColumnType::Int, true),
-                        Column::new("value".to_string(), column_type(&scalar_type(&field)), true),
+                        Column::new("value".to_string(), field_column_type(&field), true),
                     ],
                     indexes: Vec::new(),
+                    primary_columns: Vec::new(),
                 };
                 result.push(table);
             }
@@ -77,137 +107,99 @@ impl<'a> DatabaseSchemaCalculator<'a> {
         result
     }
 
+    // The relation tables and columns follow `Relation::manifestation`, like the query engine.
     fn calculate_relation_tables(&self) -> Vec<Table> {
         let mut result = Vec::new();
-        for relation in self.calculate_relations().iter() {
-            match &relation.manifestation {
-                RelationManifestation::Table {
-                    model_a_column,
-                    model_b_column,
-                } if relation.is_many_to_many() => {
-                    let table = Table {
-                        name: relation.table_name(),
-                        columns: vec![
-                            Column::with_foreign_key(
-                                model_a_column.to_string(),
-                                column_type(&scalar_type(id_field(&relation.model_a))),
-                                true,
-                                ForeignKey {
-                                    table: relation.model_a.name.to_string(),
-                                    column: id_field(&relation.model_a).name.to_string(),
-                                },
-                            ),
-                            Column::with_foreign_key(
-                                model_b_column.to_string(),
-                                column_type(&scalar_type(id_field(&relation.model_b))),
-                                true,
-                                ForeignKey {
-                                    table: relation.model_b.name.to_string(),
-                                    column: id_field(&relation.model_b).name.to_string(),
-                                },
-                            ),
-                        ],
-                        indexes: Vec::new(),
-                    };
-                    result.push(table);
-                }
-                _ => {}
+        for relation in self.data_model.relations() {
+            if let Some(RelationManifestation::Table {
+                table,
+                model_a_column,
+                model_b_column,
+            }) = relation.manifestation()
+            {
+                let mut columns = relation_columns(relation.model_a, &model_a_column, true);
+                columns.append(&mut relation_columns(relation.model_b, &model_b_column, true));
+
+                result.push(Table {
+                    name: table,
+                    columns: columns,
+                    indexes: Vec::new(),
+                    primary_columns: Vec::new(),
+                });
             }
         }
         result
     }
 
-    #[allow(unused)]
-    fn calculate_relations(&self) -> Vec<Relation> {
+    // Records are linked after they are created, so inline relation columns are always nullable.
+    fn calculate_inline_relation_columns(&self, model: &Model) -> Vec<Column> {
         let mut result = Vec::new();
-        for model in self.data_model.models() {
-            for field in model.fields() {
-                match &field.field_type {
-                    FieldType::Relation(relation_info) => {
-                        let RelationInfo {
-                            to,
-                            to_field,
-                            name,
-                            on_delete,
-                        } = relation_info;
-                        let related_model = self.data_model.find_model(&to).unwrap();
-                        // TODO: handle case of implicit back relation field
-                        let related_field = related_model
-                            .fields()
-                            .find(|f| related_type(f) == Some(model.name.to_string()))
-                            .unwrap()
-                            .clone();
-                        let manifestation = RelationManifestation::Table {
-                            model_a_column: "A".to_string(),
-                            model_b_column: "B".to_string(),
-                        };
-                        let (model_a, model_b, field_a, field_b) = match () {
-                            _ if &model.name < &related_model.name => {
-                                (model.clone(), related_model.clone(), field.clone(), related_field)
-                            }
-                            _ if &related_model.name < &model.name => {
-                                (related_model.clone(), model.clone(), related_field, field.clone())
-                            }
-                            _ => (model.clone(), related_model.clone(), field.clone(), related_field),
-                        };
-
-                        result.push(Relation {
-                            model_a: model_a,
-                            model_b: model_b,
-                            field_a: field_a,
-                            field_b: field_b,
-                            manifestation,
-                        })
-                    }
-                    _ => {}
+        for relation in self.data_model.relations() {
+            if let Some(RelationManifestation::Inline {
+                in_table_of_model,
+                column,
+            }) = relation.manifestation()
+            {
+                if in_table_of_model != model.name {
+                    continue;
                 }
+
+                let referenced_model = if relation.model_a.name == model.name {
+                    relation.model_b
+                } else {
+                    relation.model_a
+                };
+
+                result.append(&mut relation_columns(referenced_model, &column, false));
             }
         }
-        result.dedup_by(|rel1, rel2| rel1 == rel2);
         result
     }
-}
-
-#[derive(PartialEq)]
-struct Relation {
-    model_a: Model,
-    model_b: Model,
-    field_a: Field,
-    field_b: Field,
-    manifestation: RelationManifestation,
-}
 
-impl Relation {
-    fn name(&self) -> String {
-        // TODO: must replicate behaviour of `generateRelationName` from `SchemaInferrer`
-        format!("{}To{}", &self.model_a.name, &self.model_b.name)
-    }
-
-    fn table_name(&self) -> String {
-        format!("_{}", self.name())
-    }
-
-    fn is_many_to_many(&self) -> bool {
-        self.field_a.arity == FieldArity::List && self.field_b.arity == FieldArity::List
+    fn is_embedded_relation(&self, field: &Field) -> bool {
+        match related_type(field) {
+            Some(name) => self.data_model.find_model(&name).map_or(false, |m| m.is_embedded),
+            None => false,
+        }
     }
 }
 
-#[derive(PartialEq)]
-enum RelationManifestation {
-    Inline {
-        in_table_of_model: String,
-        column: String,
-    },
-    Table {
-        model_a_column: String,
-        model_b_column: String,
-    },
-}
-
 fn id_field(model: &Model) -> &Field {
     model.fields().next().clone().unwrap()
 }
 
+// A model with a compound id is referenced by one column per id field, e.g. `A_firstName` and `A_lastName`.
+fn relation_columns(model: &Model, column_name: &str, is_required: bool) -> Vec<Column> {
+    if model.has_compound_id() {
+        model
+            .id_fields
+            .iter()
+            .map(|name| {
+                let field = model.find_field(name).unwrap();
+                Column::with_foreign_key(
+                    format!("{}_{}", column_name, field.name),
+                    field_column_type(field),
+                    is_required,
+                    ForeignKey {
+                        table: model.name.to_string(),
+                        column: field.name.to_string(),
+                    },
+                )
+            })
+            .collect()
+    } else {
+        vec![Column::with_foreign_key(
+            column_name.to_string(),
+            field_column_type(id_field(model)),
+            is_required,
+            ForeignKey {
+                table: model.name.to_string(),
+                column: id_field(model).name.to_string(),
+            },
+        )]
+    }
+}
+
 fn related_type(field: &Field) -> Option<String> {
     match &field.field_type {
         FieldType::Relation(relation_info) => {
@@ -226,13 +218,20 @@ fn related_type(field: &Field) -> Option<String> {
 fn is_scalar(field: &Field) -> bool {
     match field.field_type {
         FieldType::Base(_) => true,
+        FieldType::ConnectorSpecific { .. } => true,
         _ => false,
     }
 }
 
-fn scalar_type(field: &Field) -> &ScalarType {
+// Fields with a native type, e.g. @db.VarChar(255), get a column of exactly that type.
+fn field_column_type(field: &Field) -> ColumnType {
     match &field.field_type {
-        FieldType::Base(ref scalar) => scalar,
+        FieldType::Base(ref scalar) => column_type(scalar),
+        FieldType::ConnectorSpecific {
+            connector_type: Some(connector_type),
+            ..
+        } => ColumnType::Native(connector_type_to_sql(connector_type)),
+        FieldType::ConnectorSpecific { base_type, .. } => column_type(base_type),
         x => panic!(format!(
             "only scalar types are suported here. Type is {:?} on field {}",
             x, field.name
@@ -240,6 +239,18 @@ fn scalar_type(field: &Field) -> &ScalarType {
     }
 }
 
+// Only defaults which the database can generate itself end up in the schema. Ids with `cuid()` or
+// `uuid()` are generated by the query engine and `autoincrement()` ids are rowid aliases in SQLite.
+fn column_default(field: &Field) -> Option<String> {
+    match field.default_value {
+        // DateTimes are stored as milliseconds since the epoch.
+        Some(Value::Function(DefaultFunction::Now)) => {
+            Some(String::from("(CAST(strftime('%s', 'now') AS INTEGER) * 1000)"))
+        }
+        _ => None,
+    }
+}
+
 fn column_type(scalar_type: &ScalarType) -> ColumnType {
     match scalar_type {
         ScalarType::Int => ColumnType::Int,
@@ -248,6 +259,10 @@ fn column_type(scalar_type: &ScalarType) -> ColumnType {
         ScalarType::Enum => ColumnType::String,
         ScalarType::String => ColumnType::String,
         ScalarType::DateTime => ColumnType::DateTime,
+        ScalarType::Json => ColumnType::Json,
+        ScalarType::Bytes => ColumnType::Bytes,
+        ScalarType::BigInt => ColumnType::BigInt,
+        ScalarType::Uuid => ColumnType::Uuid,
         ScalarType::Decimal => unimplemented!(),
     }
 }
@@ -1,6 +1,6 @@
 use crate::sql_database_migration_steps_inferrer::wrap_as_step;
 use crate::sql_migration_step::*;
-use database_inspector::{Column, DatabaseSchema, Table};
+use database_inspector::{Column, DatabaseSchema, Index, Table};
 
 pub struct DatabaseSchemaDiffer {
     previous: DatabaseSchema,
@@ -15,6 +15,10 @@ impl DatabaseSchemaDiffer {
 
     fn diff_internal(&self) -> Vec<SqlMigrationStep> {
         let mut result = Vec::new();
+        // Indexes are dropped before and created after the tables they belong to are changed.
+        result.append(&mut wrap_as_step(self.drop_indexes(), |x| {
+            SqlMigrationStep::DropIndex(x)
+        }));
         result.append(&mut wrap_as_step(self.create_tables(), |x| {
             SqlMigrationStep::CreateTable(x)
         }));
@@ -24,6 +28,9 @@ impl DatabaseSchemaDiffer {
         result.append(&mut wrap_as_step(self.alter_tables(), |x| {
             SqlMigrationStep::AlterTable(x)
         }));
+        result.append(&mut wrap_as_step(self.create_indexes(), |x| {
+            SqlMigrationStep::CreateIndex(x)
+        }));
         result
     }
 
@@ -31,17 +38,10 @@ impl DatabaseSchemaDiffer {
         let mut result = Vec::new();
         for next_table in &self.next.tables {
             if !self.previous.has_table(&next_table.name) {
-                let primary_columns = next_table
-                    .indexes
-                    .iter()
-                    .find(|i| i.unique)
-                    .map(|i| i.columns.clone())
-                    .unwrap_or(Vec::new());
-
                 let create = CreateTable {
                     name: next_table.name.clone(),
                     columns: Self::column_descriptions(&next_table.columns),
-                    primary_columns: primary_columns,
+                    primary_columns: next_table.primary_columns.clone(),
                 };
                 result.push(create);
             }
@@ -83,6 +83,46 @@ impl DatabaseSchemaDiffer {
         result
     }
 
+    fn create_indexes(&self) -> Vec<CreateIndex> {
+        let mut result = Vec::new();
+        for next_table in &self.next.tables {
+            for index in &next_table.indexes {
+                let previous_index = self.previous.table(&next_table.name).and_then(|t| t.index(&index.name));
+                if previous_index != Some(index) {
+                    result.push(Self::create_index(&next_table.name, index));
+                }
+            }
+        }
+        result
+    }
+
+    // Changed indexes are dropped and created again.
+    fn drop_indexes(&self) -> Vec<DropIndex> {
+        let mut result = Vec::new();
+        for previous_table in &self.previous.tables {
+            if let Some(next_table) = self.next.table(&previous_table.name) {
+                for index in &previous_table.indexes {
+                    if next_table.index(&index.name) != Some(index) {
+                        result.push(DropIndex {
+                            table: previous_table.name.clone(),
+                            name: index.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn create_index(table: &str, index: &Index) -> CreateIndex {
+        CreateIndex {
+            table: table.to_string(),
+            name: index.name.clone(),
+            columns: index.columns.clone(),
+            unique: index.unique,
+        }
+    }
+
     fn drop_columns(previous: &Table, next: &Table) -> Vec<TableChange> {
         let mut result = Vec::new();
         for previous_column in &previous.columns {
@@ -113,7 +153,7 @@ impl DatabaseSchemaDiffer {
         let mut result = Vec::new();
         for next_column in &next.columns {
             if let Some(previous_column) = previous.column(&next_column.name) {
-                if previous_column != next_column {
+                if !Self::column_unchanged(previous_column, next_column) {
                     let change = AlterColumn {
                         name: previous_column.name.clone(),
                         column: Self::column_description(next_column),
@@ -125,6 +165,13 @@ impl DatabaseSchemaDiffer {
         result
     }
 
+    fn column_unchanged(previous: &Column, next: &Column) -> bool {
+        previous.tpe.is_compatible_with(&next.tpe)
+            && previous.is_required == next.is_required
+            && previous.foreign_key == next.foreign_key
+            && previous.sequence == next.sequence
+    }
+
     fn column_descriptions(columns: &Vec<Column>) -> Vec<ColumnDescription> {
         columns.iter().map(Self::column_description).collect()
     }
@@ -132,18 +179,24 @@ impl DatabaseSchemaDiffer {
     fn column_description(column: &Column) -> ColumnDescription {
         ColumnDescription {
             name: column.name.clone(),
-            tpe: Self::convert_column_type(column.tpe),
+            tpe: Self::convert_column_type(&column.tpe),
             required: column.is_required,
+            default: column.default.clone(),
         }
     }
 
-    fn convert_column_type(inspector_type: database_inspector::ColumnType) -> ColumnType {
+    fn convert_column_type(inspector_type: &database_inspector::ColumnType) -> ColumnType {
         match inspector_type {
             database_inspector::ColumnType::Boolean => ColumnType::Boolean,
             database_inspector::ColumnType::Int => ColumnType::Int,
             database_inspector::ColumnType::Float => ColumnType::Float,
             database_inspector::ColumnType::String => ColumnType::String,
             database_inspector::ColumnType::DateTime => ColumnType::DateTime,
+            database_inspector::ColumnType::Json => ColumnType::Json,
+            database_inspector::ColumnType::Bytes => ColumnType::Bytes,
+            database_inspector::ColumnType::BigInt => ColumnType::BigInt,
+            database_inspector::ColumnType::Uuid => ColumnType::Uuid,
+            database_inspector::ColumnType::Native(tpe) => ColumnType::Native(tpe.clone()),
         }
     }
 }
@@ -80,6 +80,10 @@ fn column_type(ft: FieldType) -> ColumnType {
             ScalarType::Int => ColumnType::Int,
             ScalarType::Float => ColumnType::Float,
             ScalarType::DateTime => ColumnType::DateTime,
+            ScalarType::Json => ColumnType::Json,
+            ScalarType::Bytes => ColumnType::Bytes,
+            ScalarType::BigInt => ColumnType::BigInt,
+            ScalarType::Uuid => ColumnType::Uuid,
             _ => unimplemented!(),
         },
         _ => panic!("Only scalar types are supported here"),
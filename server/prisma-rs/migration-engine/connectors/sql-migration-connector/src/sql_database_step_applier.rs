@@ -23,7 +23,7 @@ impl DatabaseMigrationStepApplier<SqlMigrationStep> for SqlDatabaseStepApplier {
     fn apply(&self, step: SqlMigrationStep) {
         let mut migration = BarrelMigration::new().schema(self.schema_name.clone());
 
-        match dbg!(step) {
+        let sql_string = match dbg!(step) {
             SqlMigrationStep::CreateTable(CreateTable {
                 name,
                 columns,
@@ -31,8 +31,16 @@ impl DatabaseMigrationStepApplier<SqlMigrationStep> for SqlDatabaseStepApplier {
             }) => {
                 migration.create_table(name, move |t| {
                     for column in columns.clone() {
-                        let tpe = column_description_to_barrel_type(&column);
-                        t.add_column(column.name, tpe);
+                        // Barrel only knows some types and no defaults, all other columns are written by hand.
+                        match column_description_to_barrel_type(&column) {
+                            Some(tpe) if column.default.is_none() => {
+                                t.add_column(column.name, tpe);
+                            }
+                            _ => {
+                                let tpe = sql_column_type(&column.tpe);
+                                t.inject_custom(native_column_definition(&column, &tpe));
+                            }
+                        }
                     }
                     if primary_columns.len() > 0 {
                         let column_names: Vec<String> = primary_columns
@@ -43,10 +51,31 @@ impl DatabaseMigrationStepApplier<SqlMigrationStep> for SqlDatabaseStepApplier {
                         t.inject_custom(format!("PRIMARY KEY ({})", column_names.join(",")));
                     }
                 });
+                self.make_sql_string(migration)
+            }
+            // Barrel has no support for indexes, so the statements are written by hand.
+            SqlMigrationStep::CreateIndex(CreateIndex {
+                table,
+                name,
+                columns,
+                unique,
+            }) => {
+                let column_names: Vec<String> = columns.into_iter().map(|col| format!("\"{}\"", col)).collect();
+                format!(
+                    "CREATE {}INDEX \"{}\".\"{}\" ON \"{}\"({})",
+                    if unique { "UNIQUE " } else { "" },
+                    self.schema_name,
+                    name,
+                    table,
+                    column_names.join(",")
+                )
+            }
+            SqlMigrationStep::DropIndex(DropIndex { name, .. }) => {
+                format!("DROP INDEX \"{}\".\"{}\"", self.schema_name, name)
             }
             x => panic!(format!("{:?} not implemented yet here", x)),
         };
-        let sql_string = dbg!(self.make_sql_string(migration));
+        let sql_string = dbg!(sql_string);
         dbg!(self.connection.execute(&sql_string, NO_PARAMS)).unwrap();
     }
 }
@@ -58,13 +87,44 @@ impl SqlDatabaseStepApplier {
     }
 }
 
-fn column_description_to_barrel_type(column_description: &ColumnDescription) -> barrel::types::Type {
+fn native_column_definition(column_description: &ColumnDescription, tpe: &str) -> String {
+    format!(
+        "\"{}\" {}{}{}",
+        column_description.name,
+        tpe,
+        if column_description.required { " NOT NULL" } else { "" },
+        match column_description.default {
+            Some(ref default) => format!(" DEFAULT {}", default),
+            None => String::new(),
+        }
+    )
+}
+
+// The SQLite type of a column. The types barrel knows are the ones barrel renders.
+fn sql_column_type(tpe: &ColumnType) -> String {
+    let tpe = match tpe {
+        ColumnType::Native(tpe) => return tpe.clone(),
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::DateTime => "DATE",
+        ColumnType::Float => "REAL",
+        ColumnType::Int => "INTEGER",
+        ColumnType::String => "TEXT",
+        ColumnType::BigInt => "BIGINT",
+        ColumnType::Uuid => "UUID",
+        ColumnType::Json => "JSON",
+        ColumnType::Bytes => "BLOB",
+    };
+    String::from(tpe)
+}
+
+fn column_description_to_barrel_type(column_description: &ColumnDescription) -> Option<barrel::types::Type> {
     let tpe = match column_description.tpe {
         ColumnType::Boolean => barrel::types::boolean(),
         ColumnType::DateTime => barrel::types::date(),
         ColumnType::Float => barrel::types::float(),
         ColumnType::Int => barrel::types::integer(),
         ColumnType::String => barrel::types::text(),
+        _ => return None,
     };
-    tpe.nullable(!column_description.required)
+    Some(tpe.nullable(!column_description.required))
 }
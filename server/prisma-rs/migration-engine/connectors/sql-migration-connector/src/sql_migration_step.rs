@@ -9,6 +9,8 @@ pub enum SqlMigrationStep {
     CreateTable(CreateTable),
     AlterTable(AlterTable),
     DropTable(DropTable),
+    CreateIndex(CreateIndex),
+    DropIndex(DropIndex),
 }
 
 #[derive(Debug, Serialize)]
@@ -23,6 +25,20 @@ pub struct DropTable {
     pub name: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct CreateIndex {
+    pub table: String,
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DropIndex {
+    pub table: String,
+    pub name: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AlterTable {
     pub table: String,
@@ -57,13 +73,20 @@ pub struct ColumnDescription {
     pub name: String,
     pub tpe: ColumnType,
     pub required: bool,
+    pub default: Option<String>,
 }
 
-#[derive(Debug, Copy, PartialEq, Eq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub enum ColumnType {
     Int,
     Float,
     Boolean,
     String,
     DateTime,
+    Json,
+    Bytes,
+    BigInt,
+    Uuid,
+    // A native database type in SQL form, e.g. VARCHAR(255).
+    Native(String),
 }
@@ -24,7 +24,18 @@ impl MigrationCommand for InferMigrationStepsCommand {
             .map(|m| m.datamodel)
             .unwrap_or(Schema::empty());
 
-        let next_data_model = engine.parse_datamodel(&self.input.data_model);
+        let next_data_model = match engine.parse_datamodel(&self.input.data_model) {
+            Ok(data_model) => data_model,
+            Err(errors) => {
+                return InferMigrationStepsOutput {
+                    datamodel_steps: vec![],
+                    database_steps: serde_json::Value::Array(vec![]),
+                    errors: vec![],
+                    warnings: vec![],
+                    general_errors: errors,
+                }
+            }
+        };
 
         let model_migration_steps = engine
             .datamodel_migration_steps_inferrer()
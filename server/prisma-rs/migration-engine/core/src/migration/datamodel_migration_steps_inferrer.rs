@@ -1,3 +1,4 @@
+use datamodel::diff::*;
 use datamodel::*;
 use migration_connector::steps::*;
 use nullable::Nullable;
@@ -15,163 +16,159 @@ impl DataModelMigrationStepsInferrer for DataModelMigrationStepsInferrerImplWrap
     }
 }
 
-#[allow(dead_code)]
 pub struct DataModelMigrationStepsInferrerImpl {
     previous: Schema,
     next: Schema,
 }
 
-// TODO: this does not deal with renames yet
+// Translates the changes of datamodel::diff into migration steps. Renames detected by the
+// diff become updates with a new name, so no data is lost.
 impl DataModelMigrationStepsInferrerImpl {
     fn infer_internal(&self) -> Vec<MigrationStep> {
-        let mut result: Vec<MigrationStep> = Vec::new();
-        let models_to_create = self.models_to_create();
-        let models_to_delete = self.models_to_delete();
-        let fields_to_create = self.fields_to_create();
-        let fields_to_delete = self.fields_to_delete(&models_to_delete);
-        let fields_to_update = self.fields_to_update();
-
-        result.append(&mut Self::wrap_as_step(models_to_create, MigrationStep::CreateModel));
-        result.append(&mut Self::wrap_as_step(models_to_delete, MigrationStep::DeleteModel));
-        result.append(&mut Self::wrap_as_step(fields_to_create, MigrationStep::CreateField));
-        result.append(&mut Self::wrap_as_step(fields_to_delete, MigrationStep::DeleteField));
-        result.append(&mut Self::wrap_as_step(fields_to_update, MigrationStep::UpdateField));
-        result
-    }
-
-    fn models_to_create(&self) -> Vec<CreateModel> {
-        let mut result = Vec::new();
-        for next_model in self.next.models() {
-            if !self.previous.has_model(&next_model.name()) {
-                let step = CreateModel {
-                    name: next_model.name().to_string(),
-                    db_name: next_model.database_name.as_ref().cloned(),
-                    embedded: next_model.is_embedded,
-                };
-                result.push(step);
-            }
-        }
-
-        result
-    }
-
-    fn models_to_delete(&self) -> Vec<DeleteModel> {
-        let mut result = Vec::new();
-        for previous_model in self.previous.models() {
-            if !self.next.has_model(&previous_model.name) {
-                let step = DeleteModel {
-                    name: previous_model.name().to_string(),
-                };
-                result.push(step);
-            }
-        }
-
-        result
-    }
-
-    fn fields_to_create(&self) -> Vec<CreateField> {
-        let mut result = Vec::new();
-        for next_model in self.next.models() {
-            for next_field in next_model.fields() {
-                let must_create_field = match self.previous.find_model(&next_model.name) {
-                    None => true,
-                    Some(previous_model) => previous_model.find_field(&next_field.name).is_none(),
-                };
-                if must_create_field {
-                    let step = CreateField {
-                        model: next_model.name.clone(),
-                        name: next_field.name.clone(),
-                        tpe: next_field.field_type.clone(),
-                        arity: next_field.arity,
-                        db_name: next_field.database_name.clone(),
-                        default: next_field.default_value.clone(),
-                        id: None, //field.id_behaviour_clone(),
-                        is_created_at: None,
-                        is_updated_at: None,
-                        scalar_list: next_field.scalar_list_strategy,
-                    };
-                    result.push(step);
+        let mut models_to_create = Vec::new();
+        let mut models_to_delete = Vec::new();
+        let mut models_to_update = Vec::new();
+        let mut fields_to_create = Vec::new();
+        let mut fields_to_delete = Vec::new();
+        let mut fields_to_update = Vec::new();
+        let mut enums_to_create = Vec::new();
+        let mut enums_to_delete = Vec::new();
+        let mut enums_to_update = Vec::new();
+
+        for change in datamodel::diff(&self.previous, &self.next) {
+            match change {
+                Change::CreateModel(model) => {
+                    models_to_create.push(CreateModel {
+                        name: model.name.clone(),
+                        db_name: model.database_name.clone(),
+                        embedded: model.is_embedded,
+                    });
+                    for field in model.fields() {
+                        fields_to_create.push(Self::create_field(&model.name, field));
+                    }
                 }
-            }
-        }
-        result
-    }
-
-    fn fields_to_delete(&self, models_to_delete: &Vec<DeleteModel>) -> Vec<DeleteField> {
-        let mut result = Vec::new();
-        for previous_model in self.previous.models() {
-            let model_is_deleted = models_to_delete
-                .iter()
-                .find(|dm| dm.name == previous_model.name)
-                .is_none();
-            if model_is_deleted {
-                for previous_field in previous_model.fields() {
-                    let must_delete_field = match self.next.find_model(&previous_model.name) {
-                        None => true,
-                        Some(next_model) => next_model.find_field(&previous_field.name).is_none(),
+                // The fields of a deleted model are deleted along with it.
+                Change::DeleteModel(model) => models_to_delete.push(DeleteModel { name: model.name }),
+                Change::UpdateModel(update) => {
+                    let mut step = UpdateModel {
+                        name: update.name,
+                        new_name: update.rename.map(|rename| rename.new_name),
+                        db_name: None,
+                        embedded: None,
                     };
-                    if must_delete_field {
-                        let step = DeleteField {
-                            model: previous_model.name.clone(),
-                            name: previous_field.name.clone(),
-                        };
-                        result.push(step);
+                    for change in update.changes {
+                        match change {
+                            ModelChange::DatabaseName(changed) => step.db_name = Some(nullable(changed.next)),
+                            ModelChange::Embedded(changed) => step.embedded = Some(changed.next),
+                            _ => {}
+                        }
+                    }
+                    if step.new_name.is_some() || step.db_name.is_some() || step.embedded.is_some() {
+                        models_to_update.push(step);
                     }
                 }
-            }
-        }
-        result
-    }
-
-    fn fields_to_update(&self) -> Vec<UpdateField> {
-        let mut result = Vec::new();
-        for previous_model in self.previous.models() {
-            for previous_field in previous_model.fields() {
-                if let Some(next_field) = self
-                    .next
-                    .find_model(&previous_model.name)
-                    .and_then(|m| m.find_field(&previous_field.name))
-                {
-                    let (p, n) = (previous_field, next_field);
-                    let step = UpdateField {
-                        model: previous_model.name.clone(),
-                        name: p.name.clone(),
-                        new_name: None,
-                        tpe: Self::diff(&p.field_type, &n.field_type),
-                        arity: Self::diff(&p.arity, &n.arity),
-                        db_name: Self::diff_nullable(&p.database_name, &n.database_name),
-                        is_created_at: None,
-                        is_updated_at: None,
-                        id: None,
-                        default: Self::diff_nullable(&p.default_value, &n.default_value),
-                        scalar_list: Self::diff_nullable(&p.scalar_list_strategy, &n.scalar_list_strategy),
-                    };
+                Change::CreateField { model, field } => fields_to_create.push(Self::create_field(&model, &field)),
+                Change::DeleteField { model, field } => fields_to_delete.push(DeleteField {
+                    model,
+                    name: field.name,
+                }),
+                Change::UpdateField(update) => {
+                    let step = Self::update_field(update);
                     if step.is_any_option_set() {
-                        result.push(step);
+                        fields_to_update.push(step);
+                    }
+                }
+                Change::CreateEnum(en) => enums_to_create.push(CreateEnum {
+                    values: en.value_names(),
+                    name: en.name,
+                }),
+                Change::DeleteEnum(en) => enums_to_delete.push(DeleteEnum { name: en.name }),
+                Change::UpdateEnum(update) => {
+                    let values_changed = update.changes.iter().any(|change| match change {
+                        EnumChange::AddValue(_) | EnumChange::RemoveValue(_) => true,
+                        EnumChange::CustomDirectives(_) => false,
+                    });
+                    let new_name = update.rename.map(|rename| rename.new_name);
+                    let values = if values_changed {
+                        let name = new_name.as_ref().unwrap_or(&update.name);
+                        self.next.find_enum(name).map(|en| en.value_names())
+                    } else {
+                        None
+                    };
+
+                    if new_name.is_some() || values.is_some() {
+                        enums_to_update.push(UpdateEnum {
+                            name: update.name,
+                            new_name,
+                            values,
+                        });
                     }
                 }
             }
         }
+
+        let mut result: Vec<MigrationStep> = Vec::new();
+        result.append(&mut Self::wrap_as_step(enums_to_create, MigrationStep::CreateEnum));
+        result.append(&mut Self::wrap_as_step(enums_to_update, MigrationStep::UpdateEnum));
+        result.append(&mut Self::wrap_as_step(models_to_create, MigrationStep::CreateModel));
+        result.append(&mut Self::wrap_as_step(models_to_delete, MigrationStep::DeleteModel));
+        result.append(&mut Self::wrap_as_step(models_to_update, MigrationStep::UpdateModel));
+        result.append(&mut Self::wrap_as_step(fields_to_create, MigrationStep::CreateField));
+        result.append(&mut Self::wrap_as_step(fields_to_delete, MigrationStep::DeleteField));
+        result.append(&mut Self::wrap_as_step(fields_to_update, MigrationStep::UpdateField));
+        result.append(&mut Self::wrap_as_step(enums_to_delete, MigrationStep::DeleteEnum));
         result
     }
 
-    fn diff<T: PartialEq + Clone>(current: &T, updated: &T) -> Option<T> {
-        if current == updated {
-            None
-        } else {
-            Some(updated.clone())
+    fn create_field(model: &str, field: &Field) -> CreateField {
+        CreateField {
+            model: model.to_string(),
+            name: field.name.clone(),
+            tpe: field.field_type.clone(),
+            arity: field.arity,
+            db_name: field.database_name.clone(),
+            default: field.default_value.clone(),
+            id: None, //field.id_behaviour_clone(),
+            is_created_at: Some(true).filter(|_| is_created_at(field)),
+            is_updated_at: Some(true).filter(|_| field.is_updated_at),
+            scalar_list: field.scalar_list_strategy,
         }
     }
 
-    fn diff_nullable<T: PartialEq + Clone>(current: &Option<T>, updated: &Option<T>) -> Option<Nullable<T>> {
-        if current == updated {
-            None
-        } else {
-            match updated {
-                None => Some(Nullable::Null),
-                Some(x) => Some(Nullable::NotNull(x.clone())),
+    fn update_field(update: FieldUpdate) -> UpdateField {
+        let mut step = UpdateField {
+            model: update.model,
+            name: update.name,
+            new_name: update.rename.map(|rename| rename.new_name),
+            tpe: None,
+            arity: None,
+            db_name: None,
+            is_created_at: None,
+            is_updated_at: None,
+            id: None,
+            default: None,
+            scalar_list: None,
+        };
+
+        for change in update.changes {
+            match change {
+                FieldChange::Type(changed) => step.tpe = Some(changed.next),
+                FieldChange::Arity(changed) => step.arity = Some(changed.next),
+                FieldChange::DatabaseName(changed) => step.db_name = Some(nullable(changed.next)),
+                FieldChange::Default(changed) => {
+                    let created_at = |value: &Option<Value>| *value == Some(Value::Function(DefaultFunction::Now));
+                    if created_at(&changed.previous) != created_at(&changed.next) {
+                        step.is_created_at = Some(created_at(&changed.next));
+                    }
+                    step.default = Some(nullable(changed.next));
+                }
+                FieldChange::UpdatedAt(changed) => step.is_updated_at = Some(changed.next),
+                FieldChange::ScalarList(changed) => step.scalar_list = Some(nullable(changed.next)),
+                FieldChange::Unique(_) | FieldChange::Id(_) | FieldChange::CustomDirectives(_) => {}
             }
         }
+
+        step
     }
 
     fn wrap_as_step<T, F>(steps: Vec<T>, mut wrap_fn: F) -> Vec<MigrationStep>
@@ -181,3 +178,15 @@ impl DataModelMigrationStepsInferrerImpl {
         steps.into_iter().map(|x| wrap_fn(x)).collect()
     }
 }
+
+fn nullable<T>(value: Option<T>) -> Nullable<T> {
+    match value {
+        None => Nullable::Null,
+        Some(x) => Nullable::NotNull(x),
+    }
+}
+
+// Fields defaulting to now() are set to the creation time of the record.
+fn is_created_at(field: &Field) -> bool {
+    field.default_value == Some(Value::Function(DefaultFunction::Now))
+}
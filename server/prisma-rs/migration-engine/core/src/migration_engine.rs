@@ -38,11 +38,35 @@ impl MigrationEngine {
         Arc::new(SqlMigrationConnector::new(file_name.to_string()))
     }
 
-    pub fn parse_datamodel(&self, datamodel_string: &String) -> Schema {
-        let ast = datamodel::parser::parse(datamodel_string).unwrap();
+    // Syntax errors do not hide validation errors in the rest of the datamodel, all errors are
+    // returned as messages with their position, e.g. `3:5: Unexpected token.`
+    pub fn parse_datamodel(&self, datamodel_string: &String) -> Result<Schema, Vec<String>> {
+        let (ast, parser_errors) = datamodel::parser::parse_with_recovery(datamodel_string);
+        let mut errors: Vec<String> = parser_errors
+            .iter()
+            .map(|err| error_message(datamodel_string, &err.span, &err.message))
+            .collect();
+
         // TODO: this would need capabilities
         // TODO: Special directives are injected via EmptyAttachmentValidator.
         let validator = Validator::new();
-        validator.validate(&ast).unwrap()
+
+        match validator.validate(&ast) {
+            Ok(schema) if errors.is_empty() => Ok(schema),
+            Ok(_) => Err(errors),
+            Err(validation_errors) => {
+                errors.extend(
+                    validation_errors
+                        .iter()
+                        .map(|err| error_message(datamodel_string, &err.span, &err.message)),
+                );
+                Err(errors)
+            }
+        }
     }
 }
+
+fn error_message(datamodel_string: &str, span: &datamodel::ast::Span, message: &str) -> String {
+    let (line, column) = datamodel::errors::line_and_column(datamodel_string, span.start);
+    format!("{}:{}: {}", line, column, message)
+}
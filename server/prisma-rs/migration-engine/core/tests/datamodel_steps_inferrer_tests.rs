@@ -59,7 +59,6 @@ fn infer_DeleteModel() {
 }
 
 #[test]
-#[ignore]
 fn infer_UpdateModel() {
     // TODO: add tests for other properties as well
     let dm1 = parse(
@@ -71,15 +70,15 @@ fn infer_UpdateModel() {
     );
     let dm2 = parse(
         r#"
-        embed Post {
+        model Post {
             id: String
-        }
+        } @embedded
     "#,
     );
 
     let steps = infer(dm1, dm2);
     let expected = vec![MigrationStep::UpdateModel(UpdateModel {
-        name: "Test".to_string(),
+        name: "Post".to_string(),
         new_name: None,
         db_name: None,
         embedded: Some(true),
@@ -249,13 +248,12 @@ fn infer_UpdateField_simple() {
 }
 
 #[test]
-#[ignore]
 fn infer_CreateEnum() {
     let dm1 = Schema::empty();
     let dm2 = parse(
         r#"
         enum Test {
-            A,
+            A
             B
         }
     "#,
@@ -269,6 +267,78 @@ fn infer_CreateEnum() {
     assert_eq!(steps, expected);
 }
 
+#[test]
+fn infer_UpdateModel_and_UpdateField_for_renames() {
+    let dm1 = parse(
+        r#"
+        model User {
+            id: String
+            email: String
+        }
+    "#,
+    );
+    let dm2 = parse(
+        r#"
+        model Account {
+            id: String
+            mail: String @renamedFrom("email")
+        } @db(name: "User")
+    "#,
+    );
+
+    let steps = infer(dm1, dm2);
+    let expected = vec![
+        MigrationStep::UpdateModel(UpdateModel {
+            name: "User".to_string(),
+            new_name: Some("Account".to_string()),
+            db_name: Some(Nullable::NotNull("User".to_string())),
+            embedded: None,
+        }),
+        MigrationStep::UpdateField(UpdateField {
+            model: "Account".to_string(),
+            name: "email".to_string(),
+            new_name: Some("mail".to_string()),
+            tpe: None,
+            arity: None,
+            db_name: None,
+            is_created_at: None,
+            is_updated_at: None,
+            id: None,
+            default: None,
+            scalar_list: None,
+        }),
+    ];
+    assert_eq!(steps, expected);
+}
+
+#[test]
+fn infer_UpdateEnum() {
+    let dm1 = parse(
+        r#"
+        enum Role {
+            ADMIN
+            USER
+        }
+    "#,
+    );
+    let dm2 = parse(
+        r#"
+        enum Permission {
+            ADMIN
+            EDITOR
+        } @renamedFrom("Role")
+    "#,
+    );
+
+    let steps = infer(dm1, dm2);
+    let expected = vec![MigrationStep::UpdateEnum(UpdateEnum {
+        name: "Role".to_string(),
+        new_name: Some("Permission".to_string()),
+        values: Some(vec!["ADMIN".to_string(), "EDITOR".to_string()]),
+    })];
+    assert_eq!(steps, expected);
+}
+
 // TODO: we will need this in a lot of test files. Extract it.
 fn parse(datamodel_string: &'static str) -> Schema {
     let ast = datamodel::parser::parse(datamodel_string).unwrap();
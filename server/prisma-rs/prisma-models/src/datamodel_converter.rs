@@ -0,0 +1,437 @@
+use crate::prelude::*;
+use datamodel::dml;
+
+/// Converts a validated datamodel into the template of the internal data model.
+///
+/// Relations get the manifestation of `dml::Relation::manifestation`, which the
+/// migration engine uses as well:
+///
+/// - Relations to embedded models have none, the records are stored on the parent.
+/// - Many-to-many relations use a relation table `_<RelationName>` with the columns `A` and `B`.
+/// - All other relations are inlined as a column in the table of the model holding the
+///   to-one field. The column is named after the field, or its `@db` name.
+pub struct DatamodelConverter<'a> {
+    datamodel: &'a dml::Schema,
+    relations: Vec<dml::Relation<'a>>,
+}
+
+impl<'a> DatamodelConverter<'a> {
+    /// The version marks the internal data model as non-legacy, ids and timestamps
+    /// are then taken from the field behaviours instead of the field names.
+    pub const VERSION: &'static str = "v2";
+
+    pub fn convert(datamodel: &dml::Schema) -> InternalDataModelTemplate {
+        let converter = DatamodelConverter {
+            datamodel,
+            relations: datamodel.relations(),
+        };
+
+        converter.convert_internal()
+    }
+
+    fn convert_internal(&self) -> InternalDataModelTemplate {
+        InternalDataModelTemplate {
+            models: self.convert_models(),
+            relations: self.convert_relations(),
+            enums: self.convert_enums(),
+            version: Some(Self::VERSION.to_string()),
+        }
+    }
+
+    fn convert_enums(&self) -> Vec<InternalEnum> {
+        self.datamodel
+            .enums()
+            .map(|en| InternalEnum {
+                name: en.name.clone(),
+                values: en.value_names(),
+            })
+            .collect()
+    }
+
+    fn convert_models(&self) -> Vec<ModelTemplate> {
+        self.datamodel
+            .models()
+            .map(|model| ModelTemplate {
+                name: model.name.clone(),
+                stable_identifier: model.name.clone(),
+                is_embedded: model.is_embedded,
+                fields: self.convert_fields(model),
+                manifestation: model.database_name.as_ref().map(|db_name| ModelManifestation {
+                    db_name: db_name.clone(),
+                }),
+                indexes: model
+                    .indexes
+                    .iter()
+                    .map(|index| Index {
+                        name: index.name.clone(),
+                        field_names: index.fields.clone(),
+                        is_unique: index.is_unique(),
+                    })
+                    .collect(),
+                id_field_names: model.id_fields.clone(),
+            })
+            .collect()
+    }
+
+    fn convert_fields(&self, model: &dml::Model) -> Vec<FieldTemplate> {
+        model
+            .fields()
+            .map(|field| match field.field_type {
+                dml::FieldType::Relation(_) => {
+                    let relation = self.find_relation(model, field);
+
+                    FieldTemplate::Relation(RelationFieldTemplate {
+                        name: field.name.clone(),
+                        type_identifier: TypeIdentifier::Relation,
+                        is_required: field.arity == dml::FieldArity::Required,
+                        is_list: field.arity == dml::FieldArity::List,
+                        is_unique: field.is_unique,
+                        is_hidden: false,
+                        is_auto_generated: false,
+                        manifestation: None,
+                        relation_name: relation.name(),
+                        relation_side: side_of(relation, model, field),
+                    })
+                }
+                _ => FieldTemplate::Scalar(ScalarFieldTemplate {
+                    name: field.name.clone(),
+                    type_identifier: type_identifier(field),
+                    is_required: field.arity == dml::FieldArity::Required,
+                    is_list: field.arity == dml::FieldArity::List,
+                    is_unique: field.is_unique,
+                    is_hidden: false,
+                    is_auto_generated: is_auto_generated(field),
+                    manifestation: field.database_name.as_ref().map(|db_name| FieldManifestation {
+                        db_name: db_name.clone(),
+                    }),
+                    behaviour: FieldBehaviour::from_dml(field),
+                    enum_name: match &field.field_type {
+                        dml::FieldType::Enum(name) => Some(name.clone()),
+                        _ => None,
+                    },
+                }),
+            })
+            .collect()
+    }
+
+    fn convert_relations(&self) -> Vec<RelationTemplate> {
+        self.relations
+            .iter()
+            .map(|relation| RelationTemplate {
+                name: relation.name(),
+                model_a_on_delete: on_delete(relation.field_a),
+                model_b_on_delete: on_delete(relation.field_b),
+                manifestation: manifestation(relation),
+                model_a_name: relation.model_a.name.clone(),
+                model_b_name: relation.model_b.name.clone(),
+            })
+            .collect()
+    }
+
+    fn find_relation(&self, model: &dml::Model, field: &dml::Field) -> &dml::Relation<'a> {
+        self.relations
+            .iter()
+            .find(|relation| relation.contains(model, field))
+            .unwrap_or_else(|| panic!("No relation found for field {} on model {}.", field.name, model.name))
+    }
+}
+
+fn side_of(relation: &dml::Relation, model: &dml::Model, field: &dml::Field) -> RelationSide {
+    if relation.model_a.name == model.name && relation.field_a.name == field.name {
+        RelationSide::A
+    } else {
+        RelationSide::B
+    }
+}
+
+fn manifestation(relation: &dml::Relation) -> Option<RelationLinkManifestation> {
+    relation.manifestation().map(|manifestation| match manifestation {
+        dml::RelationManifestation::Table {
+            table,
+            model_a_column,
+            model_b_column,
+        } => RelationLinkManifestation::RelationTable(RelationTable {
+            table,
+            model_a_column,
+            model_b_column,
+            id_column: None,
+        }),
+        dml::RelationManifestation::Inline {
+            in_table_of_model,
+            column,
+        } => RelationLinkManifestation::Inline(InlineRelation {
+            in_table_of_model_name: in_table_of_model,
+            referencing_column: column,
+        }),
+    })
+}
+
+fn on_delete(field: &dml::Field) -> OnDelete {
+    match &field.field_type {
+        dml::FieldType::Relation(info) if info.on_delete == dml::OnDeleteStrategy::Cascade => OnDelete::Cascade,
+        _ => OnDelete::SetNull,
+    }
+}
+
+// String ids are cuids generated by the query engine, see `Model::generate_id`.
+fn type_identifier(field: &dml::Field) -> TypeIdentifier {
+    let scalar_type = match &field.field_type {
+        dml::FieldType::Enum(_) => return TypeIdentifier::Enum,
+        dml::FieldType::Relation(_) => return TypeIdentifier::Relation,
+        dml::FieldType::Base(scalar_type) => *scalar_type,
+        dml::FieldType::ConnectorSpecific { base_type, .. } => *base_type,
+    };
+
+    let generates_uuid = field.default_value == Some(dml::Value::Function(dml::DefaultFunction::Uuid));
+
+    match scalar_type {
+        dml::ScalarType::String if field.id_info.is_some() && generates_uuid => TypeIdentifier::UUID,
+        dml::ScalarType::String if field.id_info.is_some() => TypeIdentifier::GraphQLID,
+        dml::ScalarType::Int => TypeIdentifier::Int,
+        dml::ScalarType::Float | dml::ScalarType::Decimal => TypeIdentifier::Float,
+        dml::ScalarType::Boolean => TypeIdentifier::Boolean,
+        dml::ScalarType::String => TypeIdentifier::String,
+        dml::ScalarType::DateTime => TypeIdentifier::DateTime,
+        dml::ScalarType::Json => TypeIdentifier::Json,
+        dml::ScalarType::Bytes => TypeIdentifier::Bytes,
+        dml::ScalarType::BigInt => TypeIdentifier::BigInt,
+        dml::ScalarType::Uuid => TypeIdentifier::UUID,
+        dml::ScalarType::Enum => TypeIdentifier::Enum,
+    }
+}
+
+// Integer ids are generated by the database.
+fn is_auto_generated(field: &dml::Field) -> bool {
+    let is_integer = match &field.field_type {
+        dml::FieldType::Base(scalar_type)
+        | dml::FieldType::ConnectorSpecific {
+            base_type: scalar_type, ..
+        } => *scalar_type == dml::ScalarType::Int || *scalar_type == dml::ScalarType::BigInt,
+        _ => false,
+    };
+
+    match field.id_info {
+        Some(ref id_info) => {
+            is_integer
+                && (id_info.strategy == dml::IdStrategy::Auto
+                    || field.default_value == Some(dml::Value::Function(dml::DefaultFunction::Autoincrement)))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(datamodel_string: &str) -> InternalDataModelTemplate {
+        let ast = datamodel::parser::parse(datamodel_string).unwrap();
+        let datamodel = datamodel::Validator::new().validate(&ast).unwrap();
+
+        DatamodelConverter::convert(&datamodel)
+    }
+
+    fn relation<'a>(template: &'a InternalDataModelTemplate, name: &str) -> &'a RelationTemplate {
+        template.relations.iter().find(|r| r.name == name).unwrap()
+    }
+
+    fn field<'a>(template: &'a InternalDataModelTemplate, model: &str, name: &str) -> &'a FieldTemplate {
+        template
+            .models
+            .iter()
+            .find(|m| m.name == model)
+            .and_then(|m| {
+                m.fields.iter().find(|f| match f {
+                    FieldTemplate::Relation(rf) => rf.name == name,
+                    FieldTemplate::Scalar(sf) => sf.name == name,
+                })
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_models_fields_and_enums() {
+        let template = convert(
+            r#"
+            model User {
+                id: String @primary @default(cuid())
+                email: String @unique @db(name: "mail")
+                role: Role
+                tags: String[] @scalarList(strategy: RELATION)
+                updatedAt: DateTime @updatedAt
+            } @db(name: "users")
+
+            enum Role {
+                ADMIN
+                USER
+            }
+            "#,
+        );
+
+        assert_eq!(template.version, Some("v2".to_string()));
+        assert_eq!(template.enums[0].values, vec!["ADMIN".to_string(), "USER".to_string()]);
+        assert_eq!(template.models[0].manifestation.as_ref().unwrap().db_name, "users");
+
+        match field(&template, "User", "id") {
+            FieldTemplate::Scalar(sf) => {
+                assert_eq!(sf.type_identifier, TypeIdentifier::GraphQLID);
+                assert!(!sf.is_auto_generated);
+                match sf.behaviour {
+                    Some(FieldBehaviour::Id { strategy, .. }) => assert_eq!(strategy, IdStrategy::Auto),
+                    ref behaviour => panic!("Expected an id behaviour, got {:?}", behaviour),
+                }
+            }
+            f => panic!("Expected a scalar field, got {:?}", f),
+        }
+
+        match field(&template, "User", "email") {
+            FieldTemplate::Scalar(sf) => {
+                assert!(sf.is_unique);
+                assert_eq!(sf.manifestation.as_ref().unwrap().db_name, "mail");
+            }
+            f => panic!("Expected a scalar field, got {:?}", f),
+        }
+
+        match field(&template, "User", "tags") {
+            FieldTemplate::Scalar(sf) => {
+                assert!(sf.is_list);
+                assert_eq!(
+                    sf.behaviour,
+                    Some(FieldBehaviour::ScalarList {
+                        strategy: ScalarListStrategy::Relation
+                    })
+                );
+            }
+            f => panic!("Expected a scalar field, got {:?}", f),
+        }
+
+        match field(&template, "User", "role") {
+            FieldTemplate::Scalar(sf) => {
+                assert_eq!(sf.type_identifier, TypeIdentifier::Enum);
+                assert_eq!(sf.enum_name, Some(String::from("Role")));
+            }
+            f => panic!("Expected a scalar field, got {:?}", f),
+        }
+    }
+
+    #[test]
+    fn generates_ids_with_function_defaults() {
+        let template = convert(
+            r#"
+            model User {
+                id: UUID @primary @default(uuid())
+            }
+
+            model Post {
+                id: String @primary @default(cuid())
+            }
+            "#,
+        );
+
+        let internal_data_model = template.build(String::from("db"));
+
+        match internal_data_model.find_model("User").unwrap().generate_id() {
+            Ok(GraphqlId::UUID(_)) => (),
+            id => panic!("Expected a generated UUID, got {:?}", id),
+        }
+
+        match internal_data_model.find_model("Post").unwrap().generate_id() {
+            Ok(GraphqlId::String(ref id)) if id.starts_with('c') => (),
+            id => panic!("Expected a generated cuid, got {:?}", id),
+        }
+    }
+
+    #[test]
+    fn converts_relations() {
+        let template = convert(
+            r#"
+            model User {
+                id: Int @primary
+                posts: Post[]
+                profile: Profile?
+            }
+
+            model Post {
+                id: Int @primary
+                author: User @db(name: "authorId") @onDelete(strategy: CASCADE)
+                categories: Category[]
+            }
+
+            model Category {
+                id: Int @primary
+                posts: Post[]
+            }
+
+            model Profile {
+                id: Int @primary
+                user: User
+            }
+            "#,
+        );
+
+        let post_to_user = relation(&template, "PostToUser");
+        assert_eq!(post_to_user.model_a_name, "Post");
+        assert_eq!(post_to_user.model_a_on_delete, OnDelete::Cascade);
+        assert_eq!(post_to_user.model_b_on_delete, OnDelete::SetNull);
+        match post_to_user.manifestation {
+            Some(RelationLinkManifestation::Inline(ref m)) => {
+                assert_eq!(m.in_table_of_model_name, "Post");
+                assert_eq!(m.referencing_column, "authorId");
+            }
+            ref m => panic!("Expected an inline relation, got {:?}", m),
+        }
+
+        match relation(&template, "CategoryToPost").manifestation {
+            Some(RelationLinkManifestation::RelationTable(ref m)) => {
+                assert_eq!(m.table, "_CategoryToPost");
+                assert_eq!((m.model_a_column.as_str(), m.model_b_column.as_str()), ("A", "B"));
+            }
+            ref m => panic!("Expected a relation table, got {:?}", m),
+        }
+
+        match relation(&template, "ProfileToUser").manifestation {
+            Some(RelationLinkManifestation::Inline(ref m)) => {
+                assert_eq!(m.in_table_of_model_name, "Profile");
+                assert_eq!(m.referencing_column, "user");
+            }
+            ref m => panic!("Expected an inline relation, got {:?}", m),
+        }
+
+        match field(&template, "User", "posts") {
+            FieldTemplate::Relation(rf) => {
+                assert_eq!(rf.relation_name, "PostToUser");
+                assert_eq!(rf.relation_side, RelationSide::B);
+                assert!(rf.is_list);
+            }
+            f => panic!("Expected a relation field, got {:?}", f),
+        }
+
+        match field(&template, "User", "id") {
+            FieldTemplate::Scalar(sf) => assert!(sf.is_auto_generated),
+            f => panic!("Expected a scalar field, got {:?}", f),
+        }
+    }
+
+    #[test]
+    fn inlines_self_relations_on_the_to_one_side() {
+        let template = convert(
+            r#"
+            model Employee {
+                id: Int @primary
+                reports: Employee[] @relation(name: "ReportsTo")
+                reportsTo: Employee? @relation(name: "ReportsTo")
+            }
+            "#,
+        );
+
+        match relation(&template, "ReportsTo").manifestation {
+            Some(RelationLinkManifestation::Inline(ref m)) => assert_eq!(m.referencing_column, "reportsTo"),
+            ref m => panic!("Expected an inline relation, got {:?}", m),
+        }
+
+        match field(&template, "Employee", "reportsTo") {
+            FieldTemplate::Relation(rf) => assert_eq!(rf.relation_side, RelationSide::B),
+            f => panic!("Expected a relation field, got {:?}", f),
+        }
+    }
+}
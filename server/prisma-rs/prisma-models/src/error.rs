@@ -1,3 +1,4 @@
+use crate::TypeIdentifier;
 use failure::Fail;
 
 #[derive(Debug, Fail)]
@@ -25,4 +26,13 @@ pub enum DomainError {
 
     #[fail(display = "Conversion from `{}` to `{}` failed.", _0, _1)]
     ConversionFailure(&'static str, &'static str),
+
+    #[fail(display = "Compound ids are not supported when {}.", _0)]
+    CompoundIdNotSupported(&'static str),
+
+    #[fail(display = "Model `{}` has no id field", model)]
+    IdFieldNotFound { model: String },
+
+    #[fail(display = "Ids of type `{:?}` cannot be generated.", _0)]
+    IdGenerationNotSupported(TypeIdentifier),
 }
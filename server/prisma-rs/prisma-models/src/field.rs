@@ -39,6 +39,8 @@ pub enum TypeIdentifier {
     GraphQLID,
     UUID,
     Int,
+    BigInt,
+    Bytes,
     Relation,
 }
 
@@ -88,6 +90,7 @@ impl FieldTemplate {
                     is_auto_generated: st.is_auto_generated,
                     manifestation: st.manifestation,
                     behaviour: st.behaviour,
+                    enum_name: st.enum_name,
                     model,
                 };
 
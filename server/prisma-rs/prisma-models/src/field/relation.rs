@@ -125,18 +125,14 @@ impl RelationField {
         }
     }
 
-    pub fn opposite_column(&self) -> Column {
-        match self.relation_side {
-            RelationSide::A => self.relation().model_b_column(),
-            RelationSide::B => self.relation().model_a_column(),
-        }
+    /// See `Relation::columns_for_relation_side`.
+    pub fn opposite_columns(&self) -> Vec<Column> {
+        self.relation().columns_for_relation_side(self.relation_side.opposite())
     }
 
-    pub fn relation_column(&self) -> Column {
-        match self.relation_side {
-            RelationSide::A => self.relation().model_a_column(),
-            RelationSide::B => self.relation().model_b_column(),
-        }
+    /// See `Relation::columns_for_relation_side`.
+    pub fn relation_columns(&self) -> Vec<Column> {
+        self.relation().columns_for_relation_side(self.relation_side)
     }
 
     pub fn as_column(&self) -> Column {
@@ -162,6 +158,13 @@ impl RelationField {
         }
     }
 
+    /// True if the field points to an embedded model. Embedded records have no
+    /// table of their own, they are stored as a json document in the column of
+    /// this field.
+    pub fn is_embedded(&self) -> bool {
+        self.related_model().is_embedded
+    }
+
     pub fn is_relation_with_name_and_side(&self, relation_name: &str, side: RelationSide) -> bool {
         self.relation().name == relation_name && self.relation_side == side
     }
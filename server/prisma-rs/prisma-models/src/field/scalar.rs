@@ -24,6 +24,8 @@ pub struct ScalarFieldTemplate {
     pub is_auto_generated: bool,
     pub manifestation: Option<FieldManifestation>,
     pub behaviour: Option<FieldBehaviour>,
+    #[serde(default)]
+    pub enum_name: Option<String>,
 }
 
 #[derive(DebugStub)]
@@ -37,6 +39,8 @@ pub struct ScalarField {
     pub is_auto_generated: bool,
     pub manifestation: Option<FieldManifestation>,
     pub behaviour: Option<FieldBehaviour>,
+    /// The name of the enum of fields with the type identifier `Enum`.
+    pub enum_name: Option<String>,
     #[debug_stub = "#ModelWeakRef#"]
     pub model: ModelWeakRef,
 }
@@ -76,6 +80,48 @@ pub struct Sequence {
     pub allocation_size: i32,
 }
 
+impl FieldBehaviour {
+    /// Derives the behaviour of a scalar field from the datamodel, e.g. an
+    /// `@updatedAt` field or a field defaulting to `now()`.
+    pub fn from_dml(field: &datamodel::Field) -> Option<FieldBehaviour> {
+        let default_function = match field.default_value {
+            Some(datamodel::Value::Function(ref function)) => Some(*function),
+            _ => None,
+        };
+
+        if let Some(ref id_info) = field.id_info {
+            // Ids with a generated default are always generated by us or the database.
+            let strategy = match (default_function, id_info.strategy) {
+                (Some(_), _) | (None, datamodel::IdStrategy::Auto) => IdStrategy::Auto,
+                (None, datamodel::IdStrategy::None) => IdStrategy::None,
+            };
+
+            let sequence = id_info.sequence.as_ref().map(|seq| Sequence {
+                name: seq.name.clone(),
+                initial_value: seq.initial_value,
+                allocation_size: seq.allocation_size,
+            });
+
+            return Some(FieldBehaviour::Id { strategy, sequence });
+        }
+
+        if field.is_updated_at {
+            return Some(FieldBehaviour::UpdatedAt);
+        }
+
+        if default_function == Some(datamodel::DefaultFunction::Now) {
+            return Some(FieldBehaviour::CreatedAt);
+        }
+
+        field.scalar_list_strategy.map(|strategy| FieldBehaviour::ScalarList {
+            strategy: match strategy {
+                datamodel::ScalarListStrategy::Embedded => ScalarListStrategy::Embedded,
+                datamodel::ScalarListStrategy::Relation => ScalarListStrategy::Relation,
+            },
+        })
+    }
+}
+
 impl ScalarField {
     pub fn model(&self) -> ModelRef {
         self.model
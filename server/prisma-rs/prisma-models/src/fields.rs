@@ -8,7 +8,7 @@ use std::{
 #[derive(Debug)]
 pub struct Fields {
     pub all: Vec<Field>,
-    id: OnceCell<Weak<ScalarField>>,
+    id: OnceCell<Option<Weak<ScalarField>>>,
     scalar: OnceCell<Vec<Weak<ScalarField>>>,
     relation: OnceCell<Vec<Weak<RelationField>>>,
     model: ModelWeakRef,
@@ -29,19 +29,47 @@ impl Fields {
         }
     }
 
-    pub fn id(&self) -> Arc<ScalarField> {
+    /// The single id field of the model. Models identified by a compound id
+    /// have none, use `id_fields` for those.
+    pub fn id(&self) -> DomainResult<Arc<ScalarField>> {
+        let model = self.model();
+
+        if model.has_compound_id() {
+            return Err(DomainError::CompoundIdNotSupported("selecting the id field"));
+        }
+
         self.id
             .get_or_init(|| {
-                self.all
-                    .iter()
-                    .fold(None, |acc, field| match field {
-                        Field::Scalar(sf) if sf.is_id() => Some(Arc::downgrade(sf)),
-                        _ => acc,
-                    })
-                    .expect("No id field defined!")
+                self.all.iter().fold(None, |acc, field| match field {
+                    Field::Scalar(sf) if sf.is_id() => Some(Arc::downgrade(sf)),
+                    _ => acc,
+                })
+            })
+            .as_ref()
+            .map(|field| field.upgrade().unwrap())
+            .ok_or_else(|| DomainError::IdFieldNotFound {
+                model: model.name.clone(),
             })
-            .upgrade()
-            .unwrap()
+    }
+
+    /// The fields identifying a record: The fields of a compound id in the
+    /// order of the `@@id` directive, or the single id field otherwise. The
+    /// datamodel validation rejects compound ids on unknown fields.
+    pub fn id_fields(&self) -> Vec<Arc<ScalarField>> {
+        let model = self.model();
+
+        if model.has_compound_id() {
+            let mut fields: Vec<Arc<ScalarField>> = self
+                .scalar()
+                .into_iter()
+                .filter(|field| model.id_field_names.contains(&field.name))
+                .collect();
+
+            fields.sort_by_key(|field| model.id_field_names.iter().position(|name| *name == field.name));
+            fields
+        } else {
+            vec![self.id().expect("A model without a compound id has an id field.")]
+        }
     }
 
     pub fn created_at(&self) -> &Option<Arc<ScalarField>> {
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use std::sync::Arc;
+
+/// An index spanning one or more scalar fields of a model, declared in the
+/// datamodel via `@@unique([a, b])` or `@@index([a, b])`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Index {
+    pub name: Option<String>,
+    #[serde(rename = "fields")]
+    pub field_names: Vec<String>,
+    pub is_unique: bool,
+}
+
+impl Index {
+    /// The explicit name of the index, or the names of its fields joined by `_`,
+    /// e.g. `firstName_lastName`. Compound selectors are addressed by this name.
+    pub fn name(&self) -> String {
+        datamodel::index_name(&self.name, &self.field_names)
+    }
+
+    /// Resolves the fields of the index on the given model.
+    pub fn fields(&self, model: &Model) -> DomainResult<Vec<Arc<ScalarField>>> {
+        self.field_names
+            .iter()
+            .map(|name| model.fields().find_from_scalar(name))
+            .collect()
+    }
+}
@@ -95,6 +95,7 @@ impl InternalDataModel {
         self.relation_fields()
             .into_iter()
             .filter(|rf| rf.related_model() == model)
+            .filter(|rf| !rf.relation().is_embedded())
             .filter(|f| f.is_required && !f.is_list)
             .map(|f| Arc::clone(f))
             .collect()
@@ -6,9 +6,11 @@ extern crate serde_derive;
 #[macro_use]
 extern crate debug_stub_derive;
 
+mod datamodel_converter;
 mod error;
 mod field;
 mod fields;
+mod index;
 mod model;
 mod node;
 mod order_by;
@@ -22,9 +24,11 @@ mod selected_fields;
 
 pub mod prelude;
 
+pub use datamodel_converter::*;
 pub use error::*;
 pub use field::*;
 pub use fields::*;
+pub use index::*;
 pub use model::*;
 pub use node::*;
 pub use order_by::*;
@@ -15,6 +15,10 @@ pub struct ModelTemplate {
     pub is_embedded: bool,
     pub fields: Vec<FieldTemplate>,
     pub manifestation: Option<ModelManifestation>,
+    #[serde(default)]
+    pub indexes: Vec<Index>,
+    #[serde(default, rename = "idFields")]
+    pub id_field_names: Vec<String>,
 }
 
 #[derive(DebugStub)]
@@ -23,6 +27,8 @@ pub struct Model {
     pub stable_identifier: String,
     pub is_embedded: bool,
     pub manifestation: Option<ModelManifestation>,
+    pub indexes: Vec<Index>,
+    pub id_field_names: Vec<String>,
 
     fields: OnceCell<Fields>,
 
@@ -44,6 +50,8 @@ impl ModelTemplate {
             is_embedded: self.is_embedded,
             fields: OnceCell::new(),
             manifestation: self.manifestation,
+            indexes: self.indexes,
+            id_field_names: self.id_field_names,
             internal_data_model: internal_data_model,
         });
 
@@ -69,8 +77,12 @@ impl PartialEq for Model {
 }
 
 impl Model {
-    pub fn generate_id(&self) -> GraphqlId {
-        match self.fields().id().type_identifier {
+    pub fn generate_id(&self) -> DomainResult<GraphqlId> {
+        if self.has_compound_id() {
+            return Err(DomainError::CompoundIdNotSupported("generating ids"));
+        }
+
+        let id = match self.fields().id()?.type_identifier {
             // This will panic when:
             //
             // - System time goes backwards
@@ -80,9 +92,10 @@ impl Model {
             // Panic is a better choice than bubbling this up
             TypeIdentifier::GraphQLID => GraphqlId::String(cuid::cuid().unwrap()),
             TypeIdentifier::UUID => GraphqlId::UUID(Uuid::new_v4()),
-            TypeIdentifier::Int => panic!("Cannot generate integer ids."),
-            t => panic!("You shouldn't even use ids of type {:?}", t),
-        }
+            t => return Err(DomainError::IdGenerationNotSupported(t)),
+        };
+
+        Ok(id)
     }
 
     pub fn table(&self) -> Table {
@@ -114,7 +127,27 @@ impl Model {
             .expect("InternalDataModel does not exist anymore. Parent internal_data_model is deleted without deleting the child internal_data_model.")
     }
 
-    pub fn id_column(&self) -> Column {
-        self.fields().id().as_column()
+    /// True if the model is identified by a compound id, declared in the
+    /// datamodel via `@@id([a, b])`.
+    pub fn has_compound_id(&self) -> bool {
+        !self.id_field_names.is_empty()
+    }
+
+    /// The columns of the id in the order of the id fields, see `Fields::id_fields`.
+    pub fn id_columns(&self) -> Vec<Column> {
+        self.fields()
+            .id_fields()
+            .iter()
+            .map(|field| field.as_column())
+            .collect()
+    }
+
+    pub fn unique_indexes(&self) -> Vec<&Index> {
+        self.indexes.iter().filter(|index| index.is_unique).collect()
+    }
+
+    /// Finds a unique index by its name, see `Index::name`.
+    pub fn find_unique_index(&self, name: &str) -> Option<&Index> {
+        self.unique_indexes().into_iter().find(|index| index.name() == name)
     }
 }
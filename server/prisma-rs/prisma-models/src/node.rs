@@ -88,19 +88,24 @@ impl Node {
     }
 
     pub fn get_id_value(&self, field_names: &Vec<String>, model: ModelRef) -> DomainResult<GraphqlId> {
-        let id_field = model.fields().id();
-        let index = field_names
-            .iter()
-            .position(|r| r == &id_field.name)
-            .map(|i| Ok(i))
-            .unwrap_or_else(|| {
-                Err(Error::FieldNotFound {
-                    name: id_field.name.clone(),
-                    model: model.name.clone(),
-                })
-            })?;
+        let mut parts = Vec::new();
+
+        for id_field in model.fields().id_fields() {
+            let index = field_names
+                .iter()
+                .position(|r| r == &id_field.name)
+                .map(|i| Ok(i))
+                .unwrap_or_else(|| {
+                    Err(Error::FieldNotFound {
+                        name: id_field.name.clone(),
+                        model: model.name.clone(),
+                    })
+                })?;
+
+            parts.push(GraphqlId::try_from(&self.values[index])?);
+        }
 
-        Ok(GraphqlId::try_from(&self.values[index])?)
+        Ok(GraphqlId::from(parts))
     }
 
     pub fn get_field_value(&self, field_names: &Vec<String>, field: &str) -> DomainResult<&PrismaValue> {
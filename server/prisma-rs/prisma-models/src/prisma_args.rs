@@ -42,23 +42,17 @@ impl PrismaArgs {
         self.args.keys()
     }
 
+    /// Sets the `createdAt` and `updatedAt` fields of a new record to the
+    /// current time, also for records created without any arguments.
     pub fn add_datetimes(&mut self, model: ModelRef) {
-        if !self.args.is_empty() {
-            let now = PrismaValue::DateTime(Utc::now());
+        let now = PrismaValue::DateTime(Utc::now());
 
-            match (model.fields().created_at(), model.fields().updated_at()) {
-                (Some(created_at), Some(updated_at)) => {
-                    self.args.insert(created_at.name.clone(), now.clone());
-                    self.args.insert(updated_at.name.clone(), now);
-                }
-                (Some(created_at), None) => {
-                    self.args.insert(created_at.name.clone(), now);
-                }
-                (None, Some(updated_at)) => {
-                    self.args.insert(updated_at.name.clone(), now);
-                }
-                (None, None) => (),
-            }
+        if let Some(created_at) = model.fields().created_at() {
+            self.args.insert(created_at.name.clone(), now.clone());
+        }
+
+        if let Some(updated_at) = model.fields().updated_at() {
+            self.args.insert(updated_at.name.clone(), now);
         }
     }
 
@@ -16,6 +16,8 @@ pub enum GraphqlId {
     String(String),
     Int(usize),
     UUID(Uuid),
+    /// The id of a model with a compound id, one part per id field.
+    Compound(Vec<GraphqlId>),
 }
 
 impl GraphqlId {
@@ -24,6 +26,16 @@ impl GraphqlId {
             GraphqlId::String(s) => GraphqlValue::String(s.clone()),
             GraphqlId::Int(i) => GraphqlValue::Int(Number::from((*i) as i32)), // This could cause issues!
             GraphqlId::UUID(u) => GraphqlValue::String(u.to_string()),
+            GraphqlId::Compound(parts) => GraphqlValue::List(parts.iter().map(|part| part.to_value()).collect()),
+        }
+    }
+
+    /// The parts of the id in the order of the id fields. Only compound ids
+    /// consist of more than one part.
+    pub fn parts(&self) -> Vec<&GraphqlId> {
+        match self {
+            GraphqlId::Compound(parts) => parts.iter().collect(),
+            id => vec![id],
         }
     }
 }
@@ -40,6 +52,7 @@ pub enum PrismaValue {
     Relation(usize),
     Null,
     Uuid(Uuid),
+    Bytes(Vec<u8>),
     GraphqlId(GraphqlId),
     List(PrismaListValue),
 }
@@ -95,10 +108,15 @@ impl fmt::Display for PrismaValue {
             PrismaValue::Relation(x) => x.fmt(f),
             PrismaValue::Null => "null".fmt(f),
             PrismaValue::Uuid(x) => x.fmt(f),
+            PrismaValue::Bytes(x) => base64::encode(x).fmt(f),
             PrismaValue::GraphqlId(x) => match x {
                 GraphqlId::String(x) => x.fmt(f),
                 GraphqlId::Int(x) => x.fmt(f),
                 GraphqlId::UUID(x) => x.fmt(f),
+                GraphqlId::Compound(parts) => {
+                    let parts: Vec<String> = parts.iter().map(|part| PrismaValue::from(part).to_string()).collect();
+                    parts.join(",").fmt(f)
+                }
             },
             PrismaValue::List(x) => {
                 let as_string = format!("{:?}", x);
@@ -156,6 +174,12 @@ impl From<Uuid> for PrismaValue {
     }
 }
 
+impl From<Vec<u8>> for PrismaValue {
+    fn from(s: Vec<u8>) -> Self {
+        PrismaValue::Bytes(s)
+    }
+}
+
 impl From<PrismaListValue> for PrismaValue {
     fn from(s: PrismaListValue) -> Self {
         PrismaValue::List(s)
@@ -225,6 +249,41 @@ impl TryFrom<PrismaValue> for i64 {
     }
 }
 
+/// The value as part of a json document, e.g. of an embedded record. The
+/// format matches the json responses of the query engine.
+impl From<PrismaValue> for Value {
+    fn from(pv: PrismaValue) -> Value {
+        match pv {
+            PrismaValue::String(s) => Value::String(s),
+            PrismaValue::Float(f) => serde_json::Number::from_f64(f).map(Value::Number).unwrap_or(Value::Null),
+            PrismaValue::Boolean(b) => Value::Bool(b),
+            PrismaValue::DateTime(d) => Value::String(d.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()),
+            PrismaValue::Enum(e) => Value::String(e),
+            PrismaValue::Json(j) => j,
+            PrismaValue::Int(i) => Value::from(i),
+            PrismaValue::Relation(i) => Value::from(i),
+            PrismaValue::Null => Value::Null,
+            PrismaValue::Uuid(u) => Value::String(u.to_hyphenated().to_string()),
+            PrismaValue::Bytes(b) => Value::String(base64::encode(&b)),
+            PrismaValue::GraphqlId(id) => Value::from(id),
+            PrismaValue::List(l) => Value::Array(l.unwrap_or_default().into_iter().map(Value::from).collect()),
+        }
+    }
+}
+
+impl From<GraphqlId> for Value {
+    fn from(id: GraphqlId) -> Value {
+        match id {
+            GraphqlId::String(s) => Value::String(s),
+            GraphqlId::Int(i) => Value::from(i),
+            GraphqlId::UUID(u) => Value::String(u.to_hyphenated().to_string()),
+            GraphqlId::Compound(parts) => Value::Array(parts.into_iter().map(Value::from).collect()),
+        }
+    }
+}
+
+/// A compound id is a row of its parts, to be compared with the id columns,
+/// e.g. `(firstName, lastName) = ('Alice', 'Smith')`.
 #[cfg(feature = "sql")]
 impl From<GraphqlId> for DatabaseValue {
     fn from(id: GraphqlId) -> DatabaseValue {
@@ -232,6 +291,10 @@ impl From<GraphqlId> for DatabaseValue {
             GraphqlId::String(s) => s.into(),
             GraphqlId::Int(i) => (i as i64).into(),
             GraphqlId::UUID(u) => u.into(),
+            GraphqlId::Compound(parts) => {
+                let parts: Vec<DatabaseValue> = parts.into_iter().map(DatabaseValue::from).collect();
+                parts.into()
+            }
         }
     }
 }
@@ -257,6 +320,9 @@ impl From<PrismaValue> for DatabaseValue {
             PrismaValue::Relation(i) => (i as i64).into(),
             PrismaValue::Null => DatabaseValue::Parameterized(ParameterizedValue::Null),
             PrismaValue::Uuid(u) => u.into(),
+            // There are no binary parameters yet, so bytes are written base64 encoded. The Postgres
+            // connector decodes them again for BYTEA columns, as it parses json for JSON(B) columns.
+            PrismaValue::Bytes(b) => base64::encode(&b).into(),
             PrismaValue::GraphqlId(id) => id.into(),
             PrismaValue::List(Some(l)) => l.into(),
             PrismaValue::List(_) => panic!("List values are not supported here"),
@@ -293,3 +359,14 @@ impl From<Uuid> for GraphqlId {
         GraphqlId::UUID(uuid)
     }
 }
+
+/// Builds an id from its parts. A single part is the id itself.
+impl From<Vec<GraphqlId>> for GraphqlId {
+    fn from(mut parts: Vec<GraphqlId>) -> Self {
+        if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            GraphqlId::Compound(parts)
+        }
+    }
+}
@@ -142,6 +142,12 @@ impl Relation {
         !self.is_inline_relation()
     }
 
+    /// A relation to an embedded model. It has neither a relation table nor an
+    /// inline column, the embedded records are stored on the parent record.
+    pub fn is_embedded(&self) -> bool {
+        self.model_a().is_embedded || self.model_b().is_embedded
+    }
+
     /// A model that relates to itself. For example a `Person` that is a parent
     /// can relate to people that are children.
     pub fn is_self_relation(&self) -> bool {
@@ -159,6 +165,34 @@ impl Relation {
         }
     }
 
+    /// Like `inline_relation_column`, but with one column per id field if the
+    /// referenced model has a compound id.
+    pub fn inline_relation_columns(&self) -> Option<Vec<Column>> {
+        let column = self.inline_relation_column()?;
+        let mani = self.inline_manifestation()?;
+
+        let referenced_model = if self.model_a().name == mani.in_table_of_model_name {
+            self.model_b()
+        } else {
+            self.model_a()
+        };
+
+        if !referenced_model.has_compound_id() {
+            return Some(vec![column]);
+        }
+
+        let columns = referenced_model
+            .fields()
+            .id_fields()
+            .iter()
+            .map(|field| {
+                Column::from(format!("{}_{}", mani.referencing_column, field.db_name())).table(self.relation_table())
+            })
+            .collect();
+
+        Some(columns)
+    }
+
     /// A pointer to the first `Model` in the `Relation`.
     pub fn model_a(&self) -> ModelRef {
         self.model_a
@@ -213,52 +247,52 @@ impl Relation {
             .expect("Field B deleted without deleting the relations in internal_data_model.")
     }
 
-    pub fn model_a_column(&self) -> Column {
+    fn model_a_columns(&self) -> Vec<Column> {
         use RelationLinkManifestation::*;
 
         match self.manifestation {
-            Some(RelationTable(ref m)) => m.model_a_column.clone().into(),
+            Some(RelationTable(ref m)) => vec![m.model_a_column.clone().into()],
             Some(Inline(ref m)) => {
                 let model_a = self.model_a();
                 let model_b = self.model_b();
 
                 if self.is_self_relation() && self.field_a().is_hidden {
-                    model_a.fields().id().as_column()
+                    model_a.id_columns()
                 } else if self.is_self_relation() && self.field_b().is_hidden {
-                    model_b.fields().id().as_column()
+                    model_b.id_columns()
                 } else if self.is_self_relation() {
-                    m.referencing_column(self.relation_table())
+                    vec![m.referencing_column(self.relation_table())]
                 } else if m.in_table_of_model_name == model_a.name && !self.is_self_relation() {
-                    model_a.fields().id().as_column()
+                    model_a.id_columns()
                 } else {
-                    m.referencing_column(self.relation_table())
+                    vec![m.referencing_column(self.relation_table())]
                 }
             }
-            None => Self::MODEL_A_DEFAULT_COLUMN.into(),
+            None => vec![Self::MODEL_A_DEFAULT_COLUMN.into()],
         }
     }
 
-    pub fn model_b_column(&self) -> Column {
+    fn model_b_columns(&self) -> Vec<Column> {
         use RelationLinkManifestation::*;
 
         match self.manifestation {
-            Some(RelationTable(ref m)) => m.model_b_column.clone().into(),
+            Some(RelationTable(ref m)) => vec![m.model_b_column.clone().into()],
             Some(Inline(ref m)) => {
                 let model_b = self.model_b();
 
                 if self.is_self_relation() && self.field_a().is_hidden {
-                    m.referencing_column(self.relation_table())
+                    vec![m.referencing_column(self.relation_table())]
                 } else if self.is_self_relation() && self.field_b().is_hidden {
-                    m.referencing_column(self.relation_table())
+                    vec![m.referencing_column(self.relation_table())]
                 } else if self.is_self_relation() {
-                    model_b.fields().id().as_column()
+                    model_b.id_columns()
                 } else if m.in_table_of_model_name == model_b.name && !self.is_self_relation() {
-                    model_b.fields().id().as_column()
+                    model_b.id_columns()
                 } else {
-                    m.referencing_column(self.relation_table())
+                    vec![m.referencing_column(self.relation_table())]
                 }
             }
-            None => Self::MODEL_B_DEFAULT_COLUMN.into(),
+            None => vec![Self::MODEL_B_DEFAULT_COLUMN.into()],
         }
     }
 
@@ -309,11 +343,46 @@ impl Relation {
         self.id_column().is_some()
     }
 
-    pub fn column_for_relation_side(&self, side: RelationSide) -> Column {
-        match side {
-            RelationSide::A => self.model_a_column(),
-            RelationSide::B => self.model_b_column(),
+    /// The columns referencing the model on the given side. A model with a
+    /// compound id is referenced by one column per id field, named after the
+    /// relation column and the field, e.g. `A_firstName`.
+    pub fn columns_for_relation_side(&self, side: RelationSide) -> Vec<Column> {
+        use RelationLinkManifestation::*;
+
+        let model = match side {
+            RelationSide::A => self.model_a(),
+            RelationSide::B => self.model_b(),
+        };
+
+        if !model.has_compound_id() {
+            return match side {
+                RelationSide::A => self.model_a_columns(),
+                RelationSide::B => self.model_b_columns(),
+            };
         }
+
+        let (column_name, table) = match (&self.manifestation, side) {
+            (Some(Inline(m)), _) if m.in_table_of_model_name == model.name => return model.id_columns(),
+            (Some(Inline(m)), _) => (m.referencing_column.clone(), Some(self.relation_table())),
+            (Some(RelationTable(m)), RelationSide::A) => (m.model_a_column.clone(), None),
+            (Some(RelationTable(m)), RelationSide::B) => (m.model_b_column.clone(), None),
+            (None, RelationSide::A) => (Self::MODEL_A_DEFAULT_COLUMN.to_string(), None),
+            (None, RelationSide::B) => (Self::MODEL_B_DEFAULT_COLUMN.to_string(), None),
+        };
+
+        model
+            .fields()
+            .id_fields()
+            .iter()
+            .map(|field| {
+                let column = Column::from(format!("{}_{}", column_name, field.db_name()));
+
+                match table {
+                    Some(ref table) => column.table(table.clone()),
+                    None => column,
+                }
+            })
+            .collect()
     }
 
     pub fn contains_the_model(&self, model: ModelRef) -> bool {
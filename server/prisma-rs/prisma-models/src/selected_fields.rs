@@ -99,7 +99,7 @@ impl SelectedFields {
     }
 
     pub fn id(model: ModelRef) -> Self {
-        Self::from(model.fields().id())
+        Self::from(model.fields().id_fields())
     }
 
     #[deprecated]
@@ -121,22 +121,24 @@ impl SelectedFields {
                     result.push(rf.as_column());
                 }
 
+                for rf in self.relation_embedded().iter() {
+                    result.push(rf.as_column());
+                }
+
                 if let Some(ref from_field) = self.from_field {
-                    let relation = from_field.relation();
-
-                    result.push(
-                        relation
-                            .column_for_relation_side(from_field.relation_side.opposite())
-                            .alias(Self::RELATED_MODEL_ALIAS)
-                            .table(Relation::TABLE_ALIAS),
-                    );
-
-                    result.push(
-                        relation
-                            .column_for_relation_side(from_field.relation_side)
-                            .alias(Self::PARENT_MODEL_ALIAS)
-                            .table(Relation::TABLE_ALIAS),
-                    );
+                    let related_columns = from_field.opposite_columns();
+                    let related_aliases = Self::id_aliases(Self::RELATED_MODEL_ALIAS, related_columns.len());
+
+                    for (column, alias) in related_columns.into_iter().zip(related_aliases) {
+                        result.push(column.alias(alias).table(Relation::TABLE_ALIAS));
+                    }
+
+                    let parent_columns = from_field.relation_columns();
+                    let parent_aliases = Self::id_aliases(Self::PARENT_MODEL_ALIAS, parent_columns.len());
+
+                    for (column, alias) in parent_columns.into_iter().zip(parent_aliases) {
+                        result.push(column.alias(alias).table(Relation::TABLE_ALIAS));
+                    }
                 };
 
                 result
@@ -151,6 +153,10 @@ impl SelectedFields {
             result.push(rf.name.clone());
         }
 
+        for rf in self.relation_embedded().iter() {
+            result.push(rf.name.clone());
+        }
+
         if let Some(ref from_field) = self.from_field {
             result.push(from_field.related_field().name.clone());
             result.push(from_field.name.clone());
@@ -166,15 +172,39 @@ impl SelectedFields {
             result.push(rf.type_identifier);
         }
 
+        // Embedded records are read as the json document of the parent.
+        for _ in self.relation_embedded().iter() {
+            result.push(TypeIdentifier::Json);
+        }
+
         // Related and parent id.
-        if self.from_field.is_some() {
-            result.push(TypeIdentifier::GraphQLID);
-            result.push(TypeIdentifier::GraphQLID);
+        if let Some(ref from_field) = self.from_field {
+            result.append(&mut Self::id_type_identifiers(from_field.related_model()));
+            result.append(&mut Self::id_type_identifiers(from_field.model()));
         };
 
         result
     }
 
+    /// The aliases of the related and parent id columns in related node
+    /// queries. A compound id is selected column by column, e.g.
+    /// `__ParentModel__0` and `__ParentModel__1`.
+    pub fn id_aliases(alias: &str, count: usize) -> Vec<String> {
+        if count == 1 {
+            vec![alias.to_string()]
+        } else {
+            (0..count).map(|i| format!("{}{}", alias, i)).collect()
+        }
+    }
+
+    fn id_type_identifiers(model: ModelRef) -> Vec<TypeIdentifier> {
+        if model.has_compound_id() {
+            model.fields().id_fields().iter().map(|sf| sf.type_identifier).collect()
+        } else {
+            vec![TypeIdentifier::GraphQLID]
+        }
+    }
+
     pub fn model(&self) -> ModelRef {
         self.scalar
             .first()
@@ -205,6 +235,16 @@ impl SelectedFields {
             .collect()
     }
 
+    /// Relation fields to embedded models, stored in a json column of the
+    /// parent table.
+    pub fn relation_embedded(&self) -> Vec<Arc<RelationField>> {
+        self.relation
+            .iter()
+            .map(|rf| Arc::clone(&rf.field))
+            .filter(|rf| rf.is_embedded())
+            .collect()
+    }
+
     pub fn scalar_non_list(&self) -> Vec<Arc<ScalarField>> {
         self.scalar
             .iter()
@@ -1,4 +1,8 @@
-use crate::{filter::NodeSelector, query_arguments::QueryArguments, ConnectorResult};
+use crate::{
+    filter::{CompoundNodeSelector, NodeSelector},
+    query_arguments::QueryArguments,
+    ConnectorResult,
+};
 use prisma_models::prelude::*;
 use prisma_models::ScalarFieldRef;
 
@@ -11,6 +15,13 @@ pub trait DataResolver {
         selected_fields: &SelectedFields,
     ) -> ConnectorResult<Option<SingleNode>>;
 
+    /// Find one record by the values of a compound unique index.
+    fn get_node_by_compound_where(
+        &self,
+        compound_selector: &CompoundNodeSelector,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<Option<SingleNode>>;
+
     /// Filter many records.
     fn get_nodes(
         &self,
@@ -12,9 +12,11 @@ pub struct NodeSelectorInfo {
 
 impl NodeSelectorInfo {
     pub fn for_id(model: ModelRef, value: &GraphqlId) -> Self {
+        let id_field_names: Vec<String> = model.fields().id_fields().iter().map(|f| f.name.clone()).collect();
+
         Self {
             model: model.name.clone(),
-            field: model.fields().id().name.clone(),
+            field: id_field_names.join(","),
             value: PrismaValue::from(value.clone()),
         }
     }
@@ -79,3 +79,9 @@ impl From<NodeSelector> for Filter {
         })
     }
 }
+
+impl From<CompoundNodeSelector> for Filter {
+    fn from(compound_selector: CompoundNodeSelector) -> Self {
+        Filter::and(compound_selector.selectors.into_iter().map(Filter::from).collect())
+    }
+}
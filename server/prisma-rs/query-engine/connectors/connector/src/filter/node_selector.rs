@@ -30,3 +30,43 @@ impl NodeSelector {
         }
     }
 }
+
+/// Selects a node by the values of all fields of a compound unique index,
+/// e.g. `@@unique([firstName, lastName])`, or of a compound id.
+#[derive(Debug, Clone)]
+pub struct CompoundNodeSelector {
+    pub selectors: Vec<NodeSelector>,
+}
+
+impl CompoundNodeSelector {
+    pub fn new(selectors: Vec<NodeSelector>) -> Self {
+        Self { selectors }
+    }
+
+    /// Selects the node with the given id, one selector per id field.
+    pub fn for_id(model: ModelRef, id: &GraphqlId) -> Self {
+        let selectors = model
+            .fields()
+            .id_fields()
+            .into_iter()
+            .zip(id.parts())
+            .map(|(field, part)| NodeSelector::new(field, part))
+            .collect();
+
+        Self::new(selectors)
+    }
+
+    pub fn model(&self) -> ModelRef {
+        self.selectors
+            .first()
+            .expect("A compound node selector needs at least one field.")
+            .field
+            .model()
+    }
+}
+
+impl From<NodeSelector> for CompoundNodeSelector {
+    fn from(selector: NodeSelector) -> Self {
+        Self::new(vec![selector])
+    }
+}
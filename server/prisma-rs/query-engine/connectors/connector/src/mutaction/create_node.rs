@@ -8,6 +8,7 @@ pub struct CreateNode {
     pub non_list_args: PrismaArgs,
     pub list_args: Vec<(String, PrismaListValue)>,
     pub nested_mutactions: NestedMutactions,
+    pub embedded_writes: Vec<EmbeddedWrite>,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,84 @@
+//! Nested writes of embedded records
+//!
+//! Embedded records have no table of their own, they are stored as a json
+//! document on their parent record. A nested `create` or `update` of an
+//! embedded field therefore always results in a new document for the field,
+//! based on the document that is currently stored.
+
+use prisma_models::prelude::*;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+/// A nested write to a relation field pointing to an embedded model
+#[derive(Debug, Clone)]
+pub struct EmbeddedWrite {
+    pub field: RelationFieldRef,
+    pub operation: EmbeddedOperation,
+}
+
+#[derive(Debug, Clone)]
+pub enum EmbeddedOperation {
+    /// Replaces a single record, appends to a list of records
+    Create(Vec<EmbeddedDocument>),
+    /// Sets the given values on the record, or on all records of a list
+    Update(EmbeddedDocument),
+    /// Removes the record, or all records of a list
+    Delete,
+}
+
+/// The values of an embedded record, including nested writes to its own embedded fields
+#[derive(Debug, Clone, Default)]
+pub struct EmbeddedDocument {
+    pub values: JsonMap<String, JsonValue>,
+    pub nested: Vec<EmbeddedWrite>,
+}
+
+impl EmbeddedWrite {
+    /// The new document of the field, based on the currently stored one
+    pub fn apply(&self, current: JsonValue) -> JsonValue {
+        match &self.operation {
+            EmbeddedOperation::Create(documents) if self.field.is_list => {
+                let mut records = match current {
+                    JsonValue::Array(records) => records,
+                    _ => vec![],
+                };
+
+                records.extend(documents.iter().map(|document| document.apply(JsonMap::new())));
+                JsonValue::Array(records)
+            }
+            EmbeddedOperation::Create(documents) => documents
+                .first()
+                .map(|document| document.apply(JsonMap::new()))
+                .unwrap_or(JsonValue::Null),
+            EmbeddedOperation::Update(document) => match current {
+                JsonValue::Array(records) => JsonValue::Array(
+                    records
+                        .into_iter()
+                        .map(|record| match record {
+                            JsonValue::Object(values) => document.apply(values),
+                            record => record,
+                        })
+                        .collect(),
+                ),
+                JsonValue::Object(values) => document.apply(values),
+                current => current,
+            },
+            EmbeddedOperation::Delete if self.field.is_list => JsonValue::Array(vec![]),
+            EmbeddedOperation::Delete => JsonValue::Null,
+        }
+    }
+}
+
+impl EmbeddedDocument {
+    fn apply(&self, mut values: JsonMap<String, JsonValue>) -> JsonValue {
+        for (name, value) in self.values.iter() {
+            values.insert(name.clone(), value.clone());
+        }
+
+        for write in self.nested.iter() {
+            let current = values.remove(&write.field.name).unwrap_or(JsonValue::Null);
+            values.insert(write.field.name.clone(), write.apply(current));
+        }
+
+        JsonValue::Object(values)
+    }
+}
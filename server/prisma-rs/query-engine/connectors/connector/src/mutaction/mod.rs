@@ -1,6 +1,7 @@
 //! Combined mutation executions
 mod create_node;
 mod delete_node;
+mod embedded_write;
 mod node_address;
 mod path;
 mod result;
@@ -9,6 +10,7 @@ mod upsert_node;
 
 pub use create_node::*;
 pub use delete_node::*;
+pub use embedded_write::*;
 pub use node_address::*;
 pub use path::*;
 pub use result::*;
@@ -1,4 +1,4 @@
-use crate::filter::{Filter, NodeSelector};
+use crate::filter::{CompoundNodeSelector, Filter, NodeSelector};
 use prisma_models::*;
 
 #[derive(Debug, Default, Clone)]
@@ -33,6 +33,12 @@ impl From<NodeSelector> for QueryArguments {
     }
 }
 
+impl From<CompoundNodeSelector> for QueryArguments {
+    fn from(compound_selector: CompoundNodeSelector) -> Self {
+        QueryArguments::from(Filter::from(compound_selector))
+    }
+}
+
 impl From<Filter> for QueryArguments {
     fn from(filter: Filter) -> Self {
         let mut query_arguments = Self::default();
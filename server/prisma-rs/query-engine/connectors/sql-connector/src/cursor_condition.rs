@@ -1,7 +1,7 @@
+use crate::IdCondition;
 use connector::QueryArguments;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
-use std::sync::Arc;
 
 #[derive(Clone, Copy)]
 enum CursorType {
@@ -20,42 +20,45 @@ impl CursorCondition {
         ) {
             (None, None, _) => ConditionTree::NoCondition,
             (before, after, order_by) => {
+                let id_columns = model.id_columns();
+
+                // Without an explicit ordering, records are ordered by their id.
                 let field = match order_by {
-                    Some(order) => Arc::clone(&order.field),
-                    None => model.fields().id(),
+                    Some(order) => order.field.as_column(),
+                    None => id_columns[0].clone(),
                 };
 
                 let sort_order: SortOrder = order_by.map(|order| order.sort_order).unwrap_or(SortOrder::Ascending);
 
                 let cursor_for = |cursor_type: CursorType, id: GraphqlId| {
-                    let model_id = model.fields().id();
-                    let where_condition = model_id.as_column().equals(id.clone());
+                    let where_condition = id_columns.clone().equals_id(&id);
+                    let id_condition = Self::compare_ids(id_columns.clone(), &id, cursor_type);
 
                     let select_query = Select::from_table(model.table())
-                        .column(field.as_column())
-                        .so_that(ConditionTree::single(where_condition));
+                        .column(field.clone())
+                        .so_that(where_condition);
 
                     let compare = match (cursor_type, sort_order) {
                         (CursorType::Before, SortOrder::Ascending) => field
-                            .as_column()
+                            .clone()
                             .equals(select_query.clone())
-                            .and(model_id.as_column().less_than(id))
-                            .or(field.as_column().less_than(select_query)),
+                            .and(id_condition)
+                            .or(field.clone().less_than(select_query)),
                         (CursorType::Before, SortOrder::Descending) => field
-                            .as_column()
+                            .clone()
                             .equals(select_query.clone())
-                            .and(model_id.as_column().less_than(id))
-                            .or(field.as_column().greater_than(select_query)),
+                            .and(id_condition)
+                            .or(field.clone().greater_than(select_query)),
                         (CursorType::After, SortOrder::Ascending) => field
-                            .as_column()
+                            .clone()
                             .equals(select_query.clone())
-                            .and(model_id.as_column().greater_than(id))
-                            .or(field.as_column().greater_than(select_query)),
+                            .and(id_condition)
+                            .or(field.clone().greater_than(select_query)),
                         (CursorType::After, SortOrder::Descending) => field
-                            .as_column()
+                            .clone()
                             .equals(select_query.clone())
-                            .and(model_id.as_column().greater_than(id))
-                            .or(field.as_column().less_than(select_query)),
+                            .and(id_condition)
+                            .or(field.clone().less_than(select_query)),
                     };
 
                     ConditionTree::single(compare)
@@ -79,4 +82,25 @@ impl CursorCondition {
             }
         }
     }
+
+    /// Records before or after the cursor id. A compound id is compared
+    /// column by column, in the order of the id fields.
+    fn compare_ids(columns: Vec<Column>, id: &GraphqlId, cursor_type: CursorType) -> ConditionTree {
+        let parts: Vec<(Column, GraphqlId)> = columns.into_iter().zip(id.parts().into_iter().cloned()).collect();
+
+        parts
+            .into_iter()
+            .rev()
+            .fold(ConditionTree::NoCondition, |acc, (column, part)| {
+                let compare = match cursor_type {
+                    CursorType::Before => column.clone().less_than(part.clone()),
+                    CursorType::After => column.clone().greater_than(part.clone()),
+                };
+
+                match acc {
+                    ConditionTree::NoCondition => compare.into(),
+                    acc => ConditionTree::from(compare).or(column.equals(part).and(acc)),
+                }
+            })
+    }
 }
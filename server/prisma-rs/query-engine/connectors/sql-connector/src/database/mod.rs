@@ -5,6 +5,14 @@ use crate::Transactional;
 pub use postgresql::*;
 pub use sqlite::*;
 
+/// The SQL dialect of a database, for the statements the query AST can't
+/// express.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SqlFamily {
+    Sqlite,
+    Postgres,
+}
+
 /// A wrapper for relational databases due to trait restrictions. Implements the
 /// needed traits.
 pub struct SqlDatabase<T>
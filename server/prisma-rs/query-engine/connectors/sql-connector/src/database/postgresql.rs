@@ -1,17 +1,18 @@
 use crate::{
-    error::SqlError, MutationBuilder, RawQuery, SqlId, SqlResult, SqlRow, ToSqlRow, Transaction, Transactional,
+    error::SqlError, MutationBuilder, RawQuery, SqlFamily, SqlId, SqlResult, SqlRow, ToSqlRow, Transaction,
+    Transactional,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
 use connector::{error::*, ConnectorResult};
 use native_tls::TlsConnector;
 use postgres::{
     types::{FromSql, ToSql, Type as PostgresType},
-    Client, Config, Row as PostgresRow, Transaction as PostgresTransaction,
+    Client, Config, Row as PostgresRow, Statement, Transaction as PostgresTransaction,
 };
 use prisma_common::config::{ConnectionLimit, ConnectionStringConfig, ExplicitConfig, PrismaDatabase};
 use prisma_models::{GraphqlId, PrismaValue, ProjectRef, TypeIdentifier};
 use prisma_query::{
-    ast::{Query, Select},
+    ast::{ParameterizedValue, Query, Select},
     visitor::{self, Visitor},
 };
 use r2d2_postgres::PostgresConnectionManager;
@@ -118,14 +119,54 @@ impl<'a> FromSql<'a> for SqlId {
     }
 }
 
+/// Bytes and Json values are passed as text, base64 encoded and serialized,
+/// as SQLite stores them. Postgres expects parameters for BYTEA and JSON(B)
+/// columns in their own representation, so the text is decoded for these.
+enum PostgresParam<'a> {
+    Value(&'a ParameterizedValue),
+    Bytes(Vec<u8>),
+    Json(Value),
+}
+
+impl<'a> PostgresParam<'a> {
+    fn bind(stmt: &Statement, params: &'a [ParameterizedValue]) -> SqlResult<Vec<Self>> {
+        params
+            .iter()
+            .zip(stmt.params())
+            .map(|(param, tpe)| {
+                let param = match param {
+                    ParameterizedValue::Text(text) if *tpe == PostgresType::BYTEA => {
+                        PostgresParam::Bytes(base64::decode(text)?)
+                    }
+                    ParameterizedValue::Text(text) if *tpe == PostgresType::JSON || *tpe == PostgresType::JSONB => {
+                        PostgresParam::Json(serde_json::from_str(text)?)
+                    }
+                    param => PostgresParam::Value(param),
+                };
+
+                Ok(param)
+            })
+            .collect()
+    }
+
+    fn to_sql(&self) -> &ToSql {
+        match self {
+            PostgresParam::Value(value) => *value as &ToSql,
+            PostgresParam::Bytes(bytes) => bytes as &ToSql,
+            PostgresParam::Json(json) => json as &ToSql,
+        }
+    }
+}
+
 impl<'a> Transaction for PostgresTransaction<'a> {
     fn write(&mut self, q: Query) -> SqlResult<Option<GraphqlId>> {
         let id = match q {
             insert @ Query::Insert(_) => {
                 let (sql, params) = dbg!(visitor::Postgres::build(insert));
 
-                let params: Vec<&ToSql> = params.iter().map(|pv| pv as &ToSql).collect();
                 let stmt = self.prepare(&sql)?;
+                let params = PostgresParam::bind(&stmt, &params)?;
+                let params: Vec<&ToSql> = params.iter().map(PostgresParam::to_sql).collect();
                 let rows = self.query(&stmt, params.as_slice())?;
 
                 rows.into_iter().rev().next().map(|row| {
@@ -135,9 +176,10 @@ impl<'a> Transaction for PostgresTransaction<'a> {
             }
             query => {
                 let (sql, params) = dbg!(visitor::Postgres::build(query));
-                let params: Vec<&ToSql> = params.iter().map(|pv| pv as &ToSql).collect();
 
                 let stmt = self.prepare(&sql)?;
+                let params = PostgresParam::bind(&stmt, &params)?;
+                let params: Vec<&ToSql> = params.iter().map(PostgresParam::to_sql).collect();
                 self.execute(&stmt, params.as_slice())?;
 
                 None
@@ -149,9 +191,33 @@ impl<'a> Transaction for PostgresTransaction<'a> {
 
     fn filter(&mut self, q: Select, idents: &[TypeIdentifier]) -> SqlResult<Vec<SqlRow>> {
         let (sql, params) = dbg!(visitor::Postgres::build(q));
-        let params: Vec<&ToSql> = params.iter().map(|pv| pv as &ToSql).collect();
 
         let stmt = self.prepare(&sql)?;
+        let params = PostgresParam::bind(&stmt, &params)?;
+        let params: Vec<&ToSql> = params.iter().map(PostgresParam::to_sql).collect();
+        let rows = self.query(&stmt, params.as_slice())?;
+        let mut result = Vec::new();
+
+        for row in rows {
+            result.push(row.to_prisma_row(idents)?);
+        }
+
+        Ok(result)
+    }
+
+    fn sql_family(&self) -> SqlFamily {
+        SqlFamily::Postgres
+    }
+
+    fn query_sql(
+        &mut self,
+        sql: &str,
+        params: Vec<ParameterizedValue>,
+        idents: &[TypeIdentifier],
+    ) -> SqlResult<Vec<SqlRow>> {
+        let stmt = self.prepare(sql)?;
+        let params = PostgresParam::bind(&stmt, &params)?;
+        let params: Vec<&ToSql> = params.iter().map(PostgresParam::to_sql).collect();
         let rows = self.query(&stmt, params.as_slice())?;
         let mut result = Vec::new();
 
@@ -414,13 +480,37 @@ impl ToSqlRow for PostgresRow {
                     Some(val) => PrismaValue::Enum(val),
                     None => PrismaValue::Null,
                 },
-                TypeIdentifier::Json => match row.try_get(i)? {
-                    Some(val) => {
-                        let j_str: &str = val;
-                        PrismaValue::Json(serde_json::from_str(j_str)?)
-                    }
+                TypeIdentifier::BigInt => match row.try_get(i)? {
+                    Some(val) => PrismaValue::Int(val),
                     None => PrismaValue::Null,
                 },
+                TypeIdentifier::Bytes => match *row.columns()[i].type_() {
+                    PostgresType::BYTEA => match row.try_get(i)? {
+                        Some(val) => PrismaValue::Bytes(val),
+                        None => PrismaValue::Null,
+                    },
+                    // Bytes written by the connector are stored base64 encoded.
+                    _ => match row.try_get(i)? {
+                        Some(val) => {
+                            let b64_str: &str = val;
+                            PrismaValue::Bytes(base64::decode(b64_str)?)
+                        }
+                        None => PrismaValue::Null,
+                    },
+                },
+                TypeIdentifier::Json => match *row.columns()[i].type_() {
+                    PostgresType::JSON | PostgresType::JSONB => match row.try_get(i)? {
+                        Some(val) => PrismaValue::Json(val),
+                        None => PrismaValue::Null,
+                    },
+                    _ => match row.try_get(i)? {
+                        Some(val) => {
+                            let j_str: &str = val;
+                            PrismaValue::Json(serde_json::from_str(j_str)?)
+                        }
+                        None => PrismaValue::Null,
+                    },
+                },
                 TypeIdentifier::UUID => match row.try_get(i)? {
                     Some(val) => PrismaValue::Uuid(val),
                     None => PrismaValue::Null,
@@ -469,3 +559,47 @@ impl PostgreSql {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use postgres::NoTls;
+    use prisma_query::ast::Insert;
+    use serde_json::json;
+
+    // The database of dev-configs/postgres.yml, started with `make dev-postgres`.
+    fn client() -> Client {
+        Client::connect(
+            "host=127.0.0.1 port=5432 user=postgres password=prisma dbname=prisma",
+            NoTls,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[ignore]
+    fn bytes_and_json_round_trip() {
+        let mut client = client();
+        let mut tx = client.transaction().unwrap();
+
+        tx.batch_execute(r#"CREATE TEMPORARY TABLE "Blob" ("data" BYTEA, "meta" JSONB)"#)
+            .unwrap();
+
+        let bytes = PrismaValue::Bytes(vec![0, 159, 146, 150]);
+        let json = PrismaValue::Json(json!({ "tags": ["a", "b"], "count": 2 }));
+
+        let insert = Insert::single_into("Blob")
+            .value("data", bytes.clone())
+            .value("meta", json.clone());
+
+        tx.insert(insert.into()).unwrap();
+
+        let select = Select::from_table("Blob").column("data").column("meta");
+        let rows = tx
+            .filter(select, &[TypeIdentifier::Bytes, TypeIdentifier::Json])
+            .unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values, vec![bytes, json]);
+    }
+}
@@ -1,13 +1,13 @@
-use crate::{MutationBuilder, RawQuery, SqlId, SqlResult, SqlRow, ToSqlRow, Transaction, Transactional};
+use crate::{MutationBuilder, RawQuery, SqlFamily, SqlId, SqlResult, SqlRow, ToSqlRow, Transaction, Transactional};
 use chrono::{DateTime, Utc};
 use prisma_models::{GraphqlId, PrismaValue, ProjectRef, TypeIdentifier};
 use prisma_query::{
-    ast::{Query, Select},
+    ast::{ParameterizedValue, Query, Select},
     visitor::{self, Visitor},
 };
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{
-    types::{FromSql, FromSqlResult, Type as SqliteType, ValueRef},
+    types::{FromSql, FromSqlResult, Type as SqliteType, Value as SqliteValue, ValueRef},
     Connection, Error as SqliteError, Row as SqliteRow, Transaction as SqliteTransaction, NO_PARAMS,
 };
 use serde_json::{Map, Number, Value};
@@ -68,6 +68,27 @@ impl<'a> Transaction for SqliteTransaction<'a> {
         Ok(result)
     }
 
+    fn sql_family(&self) -> SqlFamily {
+        SqlFamily::Sqlite
+    }
+
+    fn query_sql(
+        &mut self,
+        sql: &str,
+        params: Vec<ParameterizedValue>,
+        idents: &[TypeIdentifier],
+    ) -> SqlResult<Vec<SqlRow>> {
+        let mut stmt = self.prepare_cached(sql)?;
+        let mut rows = stmt.query(params)?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next() {
+            result.push(row?.to_prisma_row(idents)?);
+        }
+
+        Ok(result)
+    }
+
     fn truncate(&mut self, project: ProjectRef) -> SqlResult<()> {
         self.write(Query::from("PRAGMA foreign_keys = OFF"))?;
 
@@ -151,6 +172,16 @@ impl<'a, 'stmt> ToSqlRow for SqliteRow<'a, 'stmt> {
                 }),
                 TypeIdentifier::Float => row.get_checked(i).map(|val| PrismaValue::Float(val)),
                 TypeIdentifier::Int => row.get_checked(i).map(|val| PrismaValue::Int(val)),
+                TypeIdentifier::BigInt => row.get_checked(i).map(|val| PrismaValue::Int(val)),
+                TypeIdentifier::Bytes => row.get_checked(i).and_then(|val| match val {
+                    SqliteValue::Blob(bytes) => Ok(PrismaValue::Bytes(bytes)),
+                    // Bytes written by the connector are stored base64 encoded.
+                    SqliteValue::Text(text) => base64::decode(&text).map(PrismaValue::Bytes).map_err(|err| {
+                        SqliteError::FromSqlConversionFailure(i as usize, SqliteType::Text, Box::new(err))
+                    }),
+                    SqliteValue::Null => Ok(PrismaValue::Null),
+                    other => Err(SqliteError::InvalidColumnType(i, other.data_type())),
+                }),
                 TypeIdentifier::Boolean => row.get_checked(i).map(|val| PrismaValue::Boolean(val)),
                 TypeIdentifier::Enum => row.get_checked(i).map(|val| PrismaValue::Enum(val)),
                 TypeIdentifier::Json => row.get_checked(i).and_then(|val| {
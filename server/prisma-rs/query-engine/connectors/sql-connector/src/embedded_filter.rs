@@ -0,0 +1,560 @@
+//! Embedded records are stored as json documents in a column of their parent.
+//! Conditions on embedded fields are rendered with the json functions of the
+//! database, `json_extract` on SQLite and `->>` on PostgreSQL, which the query
+//! AST can't express. The ids of the matching parents are therefore selected
+//! first in the transaction of the query, and the condition is replaced by a
+//! condition on these ids in the filter.
+
+use crate::{SqlFamily, SqlResult};
+use connector::{filter::*, ScalarCompare};
+use prisma_models::prelude::*;
+use prisma_query::ast::ParameterizedValue;
+use serde_json::Value as JsonValue;
+
+const TRUE: &str = "1 = 1";
+const FALSE: &str = "1 = 0";
+const PARENT_ALIAS: &str = "prismaEmbeddedParent";
+
+/// A condition on an embedded field of a model.
+#[derive(Clone, Copy)]
+pub enum EmbeddedCondition<'a> {
+    Relation(&'a RelationFilter),
+    IsNull(&'a OneRelationIsNullFilter),
+}
+
+impl<'a> EmbeddedCondition<'a> {
+    pub fn field(&self) -> &'a RelationFieldRef {
+        match *self {
+            EmbeddedCondition::Relation(rf) => &rf.field,
+            EmbeddedCondition::IsNull(rf) => &rf.field,
+        }
+    }
+
+    /// The query selecting the ids of the records matching the condition,
+    /// with its parameters.
+    pub fn query(&self, family: SqlFamily) -> (String, Vec<ParameterizedValue>) {
+        let (predicate, params) = self.predicate(family);
+        let params = params.into_iter().map(parameterize).collect();
+
+        (self.select_ids(&predicate), params)
+    }
+
+    /// The condition on the document of the field, with its parameters.
+    fn predicate(&self, family: SqlFamily) -> (String, Vec<JsonValue>) {
+        let mut renderer = Renderer::new(family);
+        let document = renderer.column(self.field());
+
+        let predicate = match *self {
+            EmbeddedCondition::Relation(rf) => renderer.relation(rf, &document),
+            EmbeddedCondition::IsNull(_) => renderer.is_null(&document),
+        };
+
+        (predicate, renderer.params)
+    }
+
+    /// Selects the ids of the parents matching the condition.
+    fn select_ids(&self, condition: &str) -> String {
+        let model = self.field().model();
+
+        let columns: Vec<String> = model
+            .fields()
+            .id_fields()
+            .iter()
+            .map(|field| format!("{}.{}", quote(PARENT_ALIAS), quote(field.db_name())))
+            .collect();
+
+        format!(
+            "SELECT {} FROM {}.{} AS {} WHERE {}",
+            columns.join(", "),
+            quote(&model.internal_data_model().db_name),
+            quote(model.db_name()),
+            quote(PARENT_ALIAS),
+            condition
+        )
+    }
+}
+
+/// Replaces all conditions on embedded fields in the filter, including the
+/// ones nested in relation filters, with a condition on the ids of the
+/// matching records, as returned by `select_ids`.
+pub fn resolve<F>(filter: Filter, select_ids: &mut F) -> SqlResult<Filter>
+where
+    F: FnMut(EmbeddedCondition) -> SqlResult<Vec<GraphqlId>>,
+{
+    let resolved = match filter {
+        Filter::And(filters) => Filter::And(resolve_all(filters, select_ids)?),
+        Filter::Or(filters) => Filter::Or(resolve_all(filters, select_ids)?),
+        Filter::Not(filters) => Filter::Not(resolve_all(filters, select_ids)?),
+        Filter::Relation(mut rf) => {
+            if rf.field.is_embedded() {
+                let ids = select_ids(EmbeddedCondition::Relation(&rf))?;
+                ids_filter(rf.field.model(), ids)?
+            } else {
+                rf.nested_filter = Box::new(resolve(*rf.nested_filter, select_ids)?);
+                Filter::Relation(rf)
+            }
+        }
+        Filter::OneRelationIsNull(rf) => {
+            if rf.field.is_embedded() {
+                let ids = select_ids(EmbeddedCondition::IsNull(&rf))?;
+                ids_filter(rf.field.model(), ids)?
+            } else {
+                Filter::OneRelationIsNull(rf)
+            }
+        }
+        filter => filter,
+    };
+
+    Ok(resolved)
+}
+
+fn resolve_all<F>(filters: Vec<Box<Filter>>, select_ids: &mut F) -> SqlResult<Vec<Box<Filter>>>
+where
+    F: FnMut(EmbeddedCondition) -> SqlResult<Vec<GraphqlId>>,
+{
+    filters
+        .into_iter()
+        .map(|filter| resolve(*filter, select_ids).map(Box::new))
+        .collect()
+}
+
+/// Matches the records of the model with one of the ids.
+fn ids_filter(model: ModelRef, ids: Vec<GraphqlId>) -> SqlResult<Filter> {
+    if ids.is_empty() {
+        return Ok(Filter::BoolFilter(false));
+    }
+
+    Ok(model.fields().id()?.is_in(Some(ids)))
+}
+
+/// Renders conditions against json documents, collecting the parameters.
+struct Renderer {
+    family: SqlFamily,
+    params: Vec<JsonValue>,
+    elements: usize,
+}
+
+impl Renderer {
+    fn new(family: SqlFamily) -> Self {
+        Self {
+            family,
+            params: Vec::new(),
+            elements: 0,
+        }
+    }
+
+    /// The document of an embedded field of the parent.
+    fn column(&self, field: &RelationFieldRef) -> String {
+        let column = format!("{}.{}", quote(PARENT_ALIAS), quote(&field.db_name()));
+
+        match self.family {
+            SqlFamily::Sqlite => column,
+            SqlFamily::Postgres => format!("{}::jsonb", column),
+        }
+    }
+
+    /// A condition on the records of the nested filter, the record given as a
+    /// json document.
+    fn filter(&mut self, filter: &Filter, record: &str) -> String {
+        match filter {
+            Filter::And(filters) => self.join(filters, record, " AND ", TRUE),
+            Filter::Or(filters) => self.join(filters, record, " OR ", FALSE),
+            Filter::Not(filters) if filters.is_empty() => TRUE.to_string(),
+            Filter::Not(filters) => format!("NOT {}", self.join(filters, record, " OR ", FALSE)),
+            Filter::Scalar(sf) => self.scalar(sf, record),
+            Filter::OneRelationIsNull(rf) => self.is_null(&self.document(record, &rf.field.name)),
+            Filter::Relation(rf) => {
+                let document = self.document(record, &rf.field.name);
+                self.relation(rf, &document)
+            }
+            Filter::BoolFilter(true) => TRUE.to_string(),
+            Filter::BoolFilter(false) => FALSE.to_string(),
+            Filter::ScalarList(_) | Filter::NodeSubscription => FALSE.to_string(),
+        }
+    }
+
+    fn join(&mut self, filters: &[Box<Filter>], record: &str, separator: &str, empty: &str) -> String {
+        if filters.is_empty() {
+            return empty.to_string();
+        }
+
+        let conditions: Vec<String> = filters.iter().map(|filter| self.filter(filter, record)).collect();
+
+        format!("({})", conditions.join(separator))
+    }
+
+    /// A condition on the document of an embedded field.
+    fn relation(&mut self, filter: &RelationFilter, document: &str) -> String {
+        let nested = filter.nested_filter.as_ref();
+
+        match filter.condition {
+            RelationCondition::ToOneRelatedNode => {
+                let type_of = self.type_of(document);
+                format!("({} = 'object' AND {})", type_of, self.filter(nested, document))
+            }
+            RelationCondition::AtLeastOneRelatedNode => format!("EXISTS ({})", self.elements(document, nested, false)),
+            RelationCondition::EveryRelatedNode => format!("NOT EXISTS ({})", self.elements(document, nested, true)),
+            RelationCondition::NoRelatedNode => format!("NOT EXISTS ({})", self.elements(document, nested, false)),
+        }
+    }
+
+    /// Selects the records of a list document matching the nested filter, or
+    /// the ones not matching it if `negate` is set. Anything but an array is
+    /// treated as an empty list.
+    fn elements(&mut self, document: &str, filter: &Filter, negate: bool) -> String {
+        self.elements += 1;
+
+        let alias = quote(&format!("prismaElement{}", self.elements));
+        let element = format!("{}.value", alias);
+        let array = format!(
+            "CASE WHEN {} = 'array' THEN {} ELSE {} END",
+            self.type_of(document),
+            document,
+            self.empty_array()
+        );
+
+        let elements = match self.family {
+            SqlFamily::Sqlite => format!("json_each({}) AS {}", array, alias),
+            SqlFamily::Postgres => format!("jsonb_array_elements({}) AS {}(value)", array, alias),
+        };
+
+        let condition = self.filter(filter, &element);
+        let condition = if negate {
+            format!("({}) IS NOT TRUE", condition)
+        } else {
+            condition
+        };
+
+        format!("SELECT 1 FROM {} WHERE {}", elements, condition)
+    }
+
+    fn scalar(&mut self, filter: &ScalarFilter, record: &str) -> String {
+        let value = self.value(record, &filter.field);
+        let field = &filter.field;
+
+        match &filter.condition {
+            ScalarCondition::Equals(PrismaValue::Null) => format!("{} IS NULL", value),
+            ScalarCondition::NotEquals(PrismaValue::Null) => format!("{} IS NOT NULL", value),
+            ScalarCondition::Equals(expected) => format!("{} = {}", value, self.param(field, expected)),
+            ScalarCondition::NotEquals(expected) => format!("{} <> {}", value, self.param(field, expected)),
+            ScalarCondition::Contains(expected) => self.like(&value, "LIKE", format!("%{}%", expected)),
+            ScalarCondition::NotContains(expected) => self.like(&value, "NOT LIKE", format!("%{}%", expected)),
+            ScalarCondition::StartsWith(expected) => self.like(&value, "LIKE", format!("{}%", expected)),
+            ScalarCondition::NotStartsWith(expected) => self.like(&value, "NOT LIKE", format!("{}%", expected)),
+            ScalarCondition::EndsWith(expected) => self.like(&value, "LIKE", format!("%{}", expected)),
+            ScalarCondition::NotEndsWith(expected) => self.like(&value, "NOT LIKE", format!("%{}", expected)),
+            ScalarCondition::LessThan(expected) => format!("{} < {}", value, self.param(field, expected)),
+            ScalarCondition::LessThanOrEquals(expected) => format!("{} <= {}", value, self.param(field, expected)),
+            ScalarCondition::GreaterThan(expected) => format!("{} > {}", value, self.param(field, expected)),
+            ScalarCondition::GreaterThanOrEquals(expected) => format!("{} >= {}", value, self.param(field, expected)),
+            ScalarCondition::In(Some(values)) => match values.split_first() {
+                Some((PrismaValue::Null, tail)) if tail.is_empty() => format!("{} IS NULL", value),
+                Some(_) => format!("{} IN ({})", value, self.params(field, values)),
+                None => FALSE.to_string(),
+            },
+            ScalarCondition::NotIn(Some(values)) => match values.split_first() {
+                Some((PrismaValue::Null, tail)) if tail.is_empty() => format!("{} IS NOT NULL", value),
+                Some(_) => format!("{} NOT IN ({})", value, self.params(field, values)),
+                None => TRUE.to_string(),
+            },
+            ScalarCondition::In(None) => format!("{} IS NULL", value),
+            ScalarCondition::NotIn(None) => format!("{} IS NOT NULL", value),
+        }
+    }
+
+    fn like(&mut self, value: &str, operator: &str, pattern: String) -> String {
+        self.params.push(JsonValue::String(pattern));
+        format!("{} {} {}", value, operator, self.placeholder())
+    }
+
+    fn is_null(&self, document: &str) -> String {
+        format!("COALESCE({}, 'null') = 'null'", self.type_of(document))
+    }
+
+    /// The document of a field of the record, a json value.
+    fn document(&self, record: &str, name: &str) -> String {
+        match self.family {
+            SqlFamily::Sqlite => format!("json_extract({}, '$.\"{}\"')", record, escape(name)),
+            SqlFamily::Postgres => format!("({} -> '{}')", record, escape(name)),
+        }
+    }
+
+    /// The value of a scalar field of the record. PostgreSQL reads values as
+    /// text, so numbers and booleans are cast back to their type.
+    fn value(&self, record: &str, field: &ScalarFieldRef) -> String {
+        match self.family {
+            SqlFamily::Sqlite => format!("json_extract({}, '$.\"{}\"')", record, escape(&field.name)),
+            SqlFamily::Postgres => {
+                let value = format!("({} ->> '{}')", record, escape(&field.name));
+
+                match field.type_identifier {
+                    TypeIdentifier::Int | TypeIdentifier::BigInt | TypeIdentifier::Relation => {
+                        format!("{}::bigint", value)
+                    }
+                    TypeIdentifier::Float => format!("{}::float8", value),
+                    TypeIdentifier::Boolean => format!("{}::boolean", value),
+                    _ => value,
+                }
+            }
+        }
+    }
+
+    fn type_of(&self, document: &str) -> String {
+        match self.family {
+            SqlFamily::Sqlite => format!("json_type({})", document),
+            SqlFamily::Postgres => format!("jsonb_typeof({})", document),
+        }
+    }
+
+    fn empty_array(&self) -> &'static str {
+        match self.family {
+            SqlFamily::Sqlite => "'[]'",
+            SqlFamily::Postgres => "'[]'::jsonb",
+        }
+    }
+
+    fn params(&mut self, field: &ScalarFieldRef, values: &[PrismaValue]) -> String {
+        let placeholders: Vec<String> = values.iter().map(|value| self.param(field, value)).collect();
+        placeholders.join(", ")
+    }
+
+    /// Adds a parameter compared with a value of the field, in the format the
+    /// value has in the document.
+    fn param(&mut self, field: &ScalarFieldRef, value: &PrismaValue) -> String {
+        let value = JsonValue::from(value.clone());
+
+        let value = match (self.family, field.type_identifier, value) {
+            (SqlFamily::Sqlite, _, value) => value,
+            (_, _, JsonValue::Null) => JsonValue::Null,
+            (_, TypeIdentifier::Int, value)
+            | (_, TypeIdentifier::BigInt, value)
+            | (_, TypeIdentifier::Relation, value)
+            | (_, TypeIdentifier::Boolean, value) => value,
+            (_, TypeIdentifier::Float, value) => value.as_f64().map(JsonValue::from).unwrap_or(value),
+            (_, _, JsonValue::String(s)) => JsonValue::String(s),
+            (_, _, value) => JsonValue::String(value.to_string()),
+        };
+
+        self.params.push(value);
+        self.placeholder()
+    }
+
+    /// The placeholder of the last added parameter. Placeholders are numbered,
+    /// the parameters being bound in the order they were added.
+    fn placeholder(&self) -> String {
+        match self.family {
+            SqlFamily::Sqlite => format!("?{}", self.params.len()),
+            SqlFamily::Postgres => format!("${}", self.params.len()),
+        }
+    }
+}
+
+fn parameterize(value: JsonValue) -> ParameterizedValue {
+    match value {
+        JsonValue::Null => ParameterizedValue::Null,
+        JsonValue::Bool(b) => ParameterizedValue::from(b),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => ParameterizedValue::from(i),
+            None => ParameterizedValue::from(n.as_f64().unwrap_or_default()),
+        },
+        JsonValue::String(s) => ParameterizedValue::from(s),
+        value => ParameterizedValue::from(value.to_string()),
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn escape(name: &str) -> String {
+    name.replace('\'', "''")
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use crate::{query_builder::QueryBuilder, Transaction};
+    use connector::{filter::Filter, QueryArguments, RelationCompare, ScalarCompare};
+    use prisma_models::*;
+    use rusqlite::{Connection, NO_PARAMS};
+    use std::{fs::File, sync::Arc};
+
+    fn internal_data_model() -> InternalDataModelRef {
+        let file = File::open("embedded_test_schema.json").unwrap();
+        let template: InternalDataModelTemplate = serde_json::from_reader(file).unwrap();
+
+        template.build(String::from("test"))
+    }
+
+    fn database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            r#"
+            ATTACH DATABASE ':memory:' AS "test";
+            CREATE TABLE "test"."User" ("id" TEXT PRIMARY KEY, "name" TEXT, "address" TEXT, "posts" TEXT);
+            INSERT INTO "test"."User" VALUES
+                ('alice', 'Alice', '{"street": "Main St", "city": "Berlin"}',
+                    '[{"title": "Hello", "likes": 10, "comments": [{"text": "First"}]}, {"title": "Bye", "likes": 2}]'),
+                ('bob', 'Bob', '{"street": "Side St", "city": "Paris"}', '[{"title": "Howdy", "likes": 7}]'),
+                ('carol', 'Carol', NULL, '[]'),
+                ('dave', 'Dave', NULL, NULL);
+            "#,
+        )
+        .unwrap();
+
+        conn
+    }
+
+    fn filter_ids(filter: Filter) -> Vec<GraphqlId> {
+        let internal_data_model = internal_data_model();
+        let model = internal_data_model.find_model("User").unwrap();
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+
+        let mut ids = tx.filter_ids(model, filter).unwrap();
+        ids.sort_by_key(|id| format!("{:?}", id));
+
+        ids
+    }
+
+    fn ids(names: &[&str]) -> Vec<GraphqlId> {
+        names.iter().map(|name| GraphqlId::String(name.to_string())).collect()
+    }
+
+    #[test]
+    fn filters_on_a_scalar_of_an_embedded_record() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let address = user.fields().find_from_relation_fields("address").unwrap();
+        let city = address.related_model().fields().find_from_scalar("city").unwrap();
+
+        let filter = address.to_one_related(city.equals("Paris"));
+        assert_eq!(ids(&["bob"]), filter_ids(filter));
+
+        let filter = address.to_one_related(city.is_in(Some(vec!["Berlin", "Paris"])));
+        assert_eq!(ids(&["alice", "bob"]), filter_ids(filter));
+    }
+
+    #[test]
+    fn filters_on_missing_embedded_records() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let address = user.fields().find_from_relation_fields("address").unwrap();
+
+        assert_eq!(ids(&["carol", "dave"]), filter_ids(address.one_relation_is_null()));
+    }
+
+    #[test]
+    fn filters_on_embedded_lists() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let posts = user.fields().find_from_relation_fields("posts").unwrap();
+        let likes = posts.related_model().fields().find_from_scalar("likes").unwrap();
+
+        let filter = posts.at_least_one_related(likes.greater_than(5));
+        assert_eq!(ids(&["alice", "bob"]), filter_ids(filter));
+
+        let filter = posts.every_related(likes.greater_than(5));
+        assert_eq!(ids(&["bob", "carol", "dave"]), filter_ids(filter));
+
+        let filter = posts.no_related(likes.greater_than(5));
+        assert_eq!(ids(&["carol", "dave"]), filter_ids(filter));
+    }
+
+    #[test]
+    fn filters_on_records_embedded_in_embedded_records() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let posts = user.fields().find_from_relation_fields("posts").unwrap();
+        let comments = posts
+            .related_model()
+            .fields()
+            .find_from_relation_fields("comments")
+            .unwrap();
+        let text = comments.related_model().fields().find_from_scalar("text").unwrap();
+
+        let filter = posts.at_least_one_related(comments.at_least_one_related(text.starts_with("Fir")));
+        assert_eq!(ids(&["alice"]), filter_ids(filter));
+    }
+
+    #[test]
+    fn combines_embedded_and_scalar_filters() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let name = user.fields().find_from_scalar("name").unwrap();
+        let posts = user.fields().find_from_relation_fields("posts").unwrap();
+        let title = posts.related_model().fields().find_from_scalar("title").unwrap();
+
+        let filter = Filter::or(vec![
+            posts.at_least_one_related(title.contains("ow")),
+            name.equals("Carol"),
+        ]);
+
+        assert_eq!(ids(&["bob", "carol"]), filter_ids(filter));
+    }
+
+    #[test]
+    fn orders_records_filtered_on_embedded_fields() {
+        let internal_data_model = internal_data_model();
+        let model = internal_data_model.find_model("User").unwrap();
+        let name = model.fields().find_from_scalar("name").unwrap();
+        let posts = model.fields().find_from_relation_fields("posts").unwrap();
+        let likes = posts.related_model().fields().find_from_scalar("likes").unwrap();
+
+        let query_arguments = QueryArguments {
+            filter: Some(posts.every_related(likes.greater_than(5))),
+            order_by: Some(OrderBy {
+                field: Arc::clone(&name),
+                sort_order: SortOrder::Descending,
+            }),
+            ..Default::default()
+        };
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+        let query_arguments = tx.resolve_embedded_arguments(query_arguments).unwrap();
+
+        let selected_fields = SelectedFields::from(Arc::clone(&model));
+        let select = QueryBuilder::get_nodes(Arc::clone(&model), &selected_fields, query_arguments);
+        let rows = tx.filter(select, &selected_fields.type_identifiers()).unwrap();
+
+        let names: Vec<PrismaValue> = rows
+            .into_iter()
+            .map(|row| {
+                let node = SingleNode::new(Node::from(row), selected_fields.names());
+                node.get_field_value("name").unwrap().clone()
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                PrismaValue::from("Dave"),
+                PrismaValue::from("Carol"),
+                PrismaValue::from("Bob")
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn filters_without_changing_the_schema() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let posts = user.fields().find_from_relation_fields("posts").unwrap();
+        let likes = posts.related_model().fields().find_from_scalar("likes").unwrap();
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+
+        let ids = tx
+            .filter_ids(user, posts.at_least_one_related(likes.greater_than(5)))
+            .unwrap();
+        assert_eq!(2, ids.len());
+
+        let tables: i64 = tx
+            .query_row("SELECT COUNT(*) FROM sqlite_temp_master", NO_PARAMS, |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(0, tables);
+    }
+}
@@ -124,6 +124,12 @@ impl From<serde_json::error::Error> for SqlError {
     }
 }
 
+impl From<base64::DecodeError> for SqlError {
+    fn from(e: base64::DecodeError) -> SqlError {
+        SqlError::ColumnReadFailure(e.into())
+    }
+}
+
 impl From<r2d2::Error> for SqlError {
     fn from(e: r2d2::Error) -> SqlError {
         SqlError::ConnectionError(e.into())
@@ -1,3 +1,4 @@
+use crate::IdCondition;
 use connector::filter::*;
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
@@ -178,21 +179,29 @@ impl AliasedCondition for ScalarFilter {
 impl AliasedCondition for RelationFilter {
     /// Conversion from a `RelationFilter` to a query condition tree. Aliased when in a nested `SELECT`.
     fn aliased_cond(self, alias: Option<Alias>) -> ConditionTree {
-        let id = self.field.model().id_column();
+        if self.field.is_embedded() {
+            unreachable!("Conditions on embedded fields are resolved by the transaction, see `embedded_filter`.");
+        }
 
-        let column = match alias {
-            Some(ref alias) => id.table(alias.dec().to_string(None)),
-            None => id,
+        let ids = self.field.model().id_columns();
+
+        let columns: Vec<Column> = match alias {
+            Some(ref alias) => ids
+                .into_iter()
+                .map(|id| id.table(alias.dec().to_string(None)))
+                .collect(),
+            None => ids,
         };
 
         let condition = self.condition.clone();
+        let selected = self.field.relation_columns();
         let sub_select = self.aliased_sel(alias.map(|a| a.inc(AliasMode::Table)));
 
         let comparison = match condition {
-            RelationCondition::EveryRelatedNode => column.not_in_selection(sub_select),
-            RelationCondition::NoRelatedNode => column.not_in_selection(sub_select),
-            RelationCondition::AtLeastOneRelatedNode => column.in_selection(sub_select),
-            RelationCondition::ToOneRelatedNode => column.in_selection(sub_select),
+            RelationCondition::EveryRelatedNode => columns.not_in_select(sub_select, selected),
+            RelationCondition::NoRelatedNode => columns.not_in_select(sub_select, selected),
+            RelationCondition::AtLeastOneRelatedNode => columns.in_select(sub_select, selected),
+            RelationCondition::ToOneRelatedNode => columns.in_select(sub_select, selected),
         };
 
         comparison.into()
@@ -206,8 +215,15 @@ impl AliasedSelect for RelationFilter {
         let condition = self.condition.clone();
         let relation = self.field.relation();
 
-        let this_column = self.field.relation_column().table(alias.to_string(None));
-        let other_column = self.field.opposite_column().table(alias.to_string(None));
+        let aliased = |columns: Vec<Column>| -> Vec<Column> {
+            columns
+                .into_iter()
+                .map(|column| column.table(alias.to_string(None)))
+                .collect()
+        };
+
+        let this_columns = aliased(self.field.relation_columns());
+        let other_columns = aliased(self.field.opposite_columns());
 
         // Normalize filter tree
         let compacted = match *self.nested_filter {
@@ -229,44 +245,53 @@ impl AliasedSelect for RelationFilter {
         };
 
         match compacted {
-            Filter::Relation(filter) => {
+            // Embedded records have no relation table, they are matched as a nested filter.
+            Filter::Relation(ref filter) if !filter.field.is_embedded() => {
                 let sub_condition = filter.condition.clone();
-                let sub_select = filter.aliased_sel(Some(alias.inc(AliasMode::Table)));
+                let selected = filter.field.relation_columns();
+                let sub_select = filter.clone().aliased_sel(Some(alias.inc(AliasMode::Table)));
 
                 let tree: ConditionTree = match sub_condition {
-                    RelationCondition::EveryRelatedNode => other_column.not_in_selection(sub_select),
-                    RelationCondition::NoRelatedNode => other_column.not_in_selection(sub_select),
-                    RelationCondition::AtLeastOneRelatedNode => other_column.in_selection(sub_select),
-                    RelationCondition::ToOneRelatedNode => other_column.in_selection(sub_select),
+                    RelationCondition::EveryRelatedNode => other_columns.not_in_select(sub_select, selected),
+                    RelationCondition::NoRelatedNode => other_columns.not_in_select(sub_select, selected),
+                    RelationCondition::AtLeastOneRelatedNode => other_columns.in_select(sub_select, selected),
+                    RelationCondition::ToOneRelatedNode => other_columns.in_select(sub_select, selected),
                 }
                 .into();
 
                 let conditions = tree.invert_if(condition.invert_of_subselect());
 
-                Select::from_table(relation.relation_table().alias(alias.to_string(None)))
-                    .column(this_column)
+                this_columns
+                    .into_iter()
+                    .fold(
+                        Select::from_table(relation.relation_table().alias(alias.to_string(None))),
+                        |acc, column| acc.column(column),
+                    )
                     .so_that(conditions)
             }
             nested_filter => {
                 let tree = nested_filter.aliased_cond(Some(alias.flip(AliasMode::Join)));
 
-                let id_column = self
+                let id_columns: Vec<Column> = self
                     .field
                     .related_model()
-                    .id_column()
-                    .table(alias.to_string(Some(AliasMode::Join)));
+                    .id_columns()
+                    .into_iter()
+                    .map(|column| column.table(alias.to_string(Some(AliasMode::Join))))
+                    .collect();
 
                 let join = self
                     .field
                     .related_model()
                     .table()
                     .alias(alias.to_string(Some(AliasMode::Join)))
-                    .on(id_column.equals(other_column));
+                    .on(id_columns.equals_columns(other_columns));
 
                 let table = relation.relation_table().alias(alias.to_string(Some(AliasMode::Table)));
 
-                Select::from_table(table)
-                    .column(this_column)
+                this_columns
+                    .into_iter()
+                    .fold(Select::from_table(table), |acc, column| acc.column(column))
                     .inner_join(join)
                     .so_that(tree.invert_if(condition.invert_of_subselect()))
             }
@@ -277,16 +302,34 @@ impl AliasedSelect for RelationFilter {
 impl AliasedCondition for OneRelationIsNullFilter {
     /// Conversion from a `OneRelationIsNullFilter` to a query condition tree. Aliased when in a nested `SELECT`.
     fn aliased_cond(self, alias: Option<Alias>) -> ConditionTree {
+        if self.field.is_embedded() {
+            unreachable!("Conditions on embedded fields are resolved by the transaction, see `embedded_filter`.");
+        }
+
         let alias = alias.map(|a| a.to_string(None));
 
         let condition = if self.field.relation_is_inlined_in_parent() {
-            self.field.as_column().opt_table(alias.clone()).is_null()
+            // A compound id is referenced by one column per id field, all of them are null.
+            self.field
+                .opposite_columns()
+                .into_iter()
+                .fold(ConditionTree::NoCondition, |acc, column| {
+                    let is_null = column.opt_table(alias.clone()).is_null();
+
+                    match acc {
+                        ConditionTree::NoCondition => is_null.into(),
+                        acc => acc.and(is_null),
+                    }
+                })
         } else {
             let relation = self.field.relation();
 
-            let column = relation
-                .column_for_relation_side(self.field.relation_side)
-                .opt_table(alias.clone());
+            let columns: Vec<Column> = self
+                .field
+                .relation_columns()
+                .into_iter()
+                .map(|column| column.opt_table(alias.clone()))
+                .collect();
 
             let table = Table::from(relation.relation_table());
             let relation_table = match alias {
@@ -294,12 +337,22 @@ impl AliasedCondition for OneRelationIsNullFilter {
                 None => table,
             };
 
-            let select = Select::from_table(relation_table).column(column);
-            let id_column = self.field.model().id_column().opt_table(alias.clone());
+            let select = columns
+                .iter()
+                .cloned()
+                .fold(Select::from_table(relation_table), |acc, column| acc.column(column));
 
-            id_column.not_in_selection(select)
+            let id_columns: Vec<Column> = self
+                .field
+                .model()
+                .id_columns()
+                .into_iter()
+                .map(|column| column.opt_table(alias.clone()))
+                .collect();
+
+            id_columns.not_in_select(select, columns).into()
         };
 
-        ConditionTree::single(condition)
+        condition
     }
 }
@@ -0,0 +1,118 @@
+use prisma_models::prelude::*;
+use prisma_query::ast::*;
+
+/// Conditions matching records by their ids. The columns are given in the
+/// order of the id fields, a compound id being compared column by column.
+pub trait IdCondition {
+    /// Matches the record with the given id.
+    fn equals_id(self, id: &GraphqlId) -> ConditionTree;
+
+    /// Matches all records having one of the given ids.
+    fn in_ids<'a, I>(self, ids: I) -> ConditionTree
+    where
+        I: IntoIterator<Item = &'a GraphqlId>;
+
+    /// Matches the id columns against columns referencing the id, e.g. the
+    /// columns of a relation table.
+    fn equals_columns(self, columns: Vec<Column>) -> ConditionTree;
+
+    /// Matches the rows having a value in all of the columns, e.g. in the
+    /// columns of a relation table referencing a compound id.
+    fn all_not_null(self) -> ConditionTree;
+
+    /// Matches all records whose id is returned by the sub-select, which
+    /// selects the given columns, one per id column.
+    fn in_select(self, select: Select, selected: Vec<Column>) -> Compare;
+
+    /// Matches all records whose id is not returned by the sub-select, which
+    /// selects the given columns, one per id column.
+    fn not_in_select(self, select: Select, selected: Vec<Column>) -> Compare;
+}
+
+impl IdCondition for Vec<Column> {
+    fn equals_id(self, id: &GraphqlId) -> ConditionTree {
+        self.into_iter()
+            .zip(id.parts())
+            .fold(ConditionTree::NoCondition, |acc, (column, part)| match acc {
+                ConditionTree::NoCondition => column.equals(part).into(),
+                acc => acc.and(column.equals(part)),
+            })
+    }
+
+    fn in_ids<'a, I>(self, ids: I) -> ConditionTree
+    where
+        I: IntoIterator<Item = &'a GraphqlId>,
+    {
+        let ids: Vec<&GraphqlId> = ids.into_iter().collect();
+
+        if self.len() == 1 || ids.is_empty() {
+            // UNWRAP: An id has at least one column.
+            return self.into_iter().next().unwrap().in_selection(ids).into();
+        }
+
+        ids.into_iter().fold(ConditionTree::NoCondition, |acc, id| {
+            let condition = self.clone().equals_id(id);
+
+            match acc {
+                ConditionTree::NoCondition => condition,
+                acc => acc.or(condition),
+            }
+        })
+    }
+
+    fn equals_columns(self, columns: Vec<Column>) -> ConditionTree {
+        self.into_iter()
+            .zip(columns)
+            .fold(ConditionTree::NoCondition, |acc, (column, other)| match acc {
+                ConditionTree::NoCondition => column.equals(other).into(),
+                acc => acc.and(column.equals(other)),
+            })
+    }
+
+    fn all_not_null(self) -> ConditionTree {
+        self.into_iter()
+            .fold(ConditionTree::NoCondition, |acc, column| match acc {
+                ConditionTree::NoCondition => column.is_not_null().into(),
+                acc => acc.and(column.is_not_null()),
+            })
+    }
+
+    fn in_select(self, select: Select, selected: Vec<Column>) -> Compare {
+        let (column, select) = correlate(self, select, selected);
+        column.in_selection(select)
+    }
+
+    fn not_in_select(self, select: Select, selected: Vec<Column>) -> Compare {
+        let (column, select) = correlate(self, select, selected);
+        column.not_in_selection(select)
+    }
+}
+
+/// Rows can't be compared to a sub-select, so a compound id is matched by
+/// its first column while the other columns are compared to the rows of the
+/// sub-select in a correlated sub-select around it.
+fn correlate(columns: Vec<Column>, select: Select, selected: Vec<Column>) -> (Column, Select) {
+    const SUB_ALIAS: &str = "id_sub";
+
+    let mut columns = columns.into_iter();
+    // UNWRAP: An id has at least one column.
+    let first = columns.next().unwrap();
+
+    if selected.len() == 1 {
+        return (first, select);
+    }
+
+    let mut selected = selected
+        .into_iter()
+        .map(|column| Column::from(column.name.to_string()).table(SUB_ALIAS));
+
+    // UNWRAP: The sub-select selects one column per id column.
+    let first_selected = selected.next().unwrap();
+    let others: Vec<Column> = columns.collect();
+
+    let correlated = Select::from_table(Table::from(select).alias(SUB_ALIAS))
+        .column(first_selected)
+        .so_that(others.equals_columns(selected.collect()));
+
+    (first, correlated)
+}
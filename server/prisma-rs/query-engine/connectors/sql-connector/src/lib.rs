@@ -8,8 +8,10 @@
 
 mod cursor_condition;
 mod database;
+mod embedded_filter;
 mod error;
 mod filter_conversion;
+mod id_condition;
 mod mutaction;
 mod ordering;
 mod query_builder;
@@ -18,6 +20,7 @@ mod row;
 mod transactional;
 
 use filter_conversion::*;
+use id_condition::*;
 use mutaction::*;
 use raw_query::*;
 use row::*;
@@ -1,25 +1,38 @@
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
 
-use crate::{error::SqlError, SqlResult};
+use crate::{error::SqlError, IdCondition, SqlResult};
+use std::convert::TryFrom;
 
 pub struct MutationBuilder;
 
 impl MutationBuilder {
     const PARAMETER_LIMIT: usize = 10000;
 
-    pub fn create_node(model: ModelRef, mut args: PrismaArgs) -> (Insert, Option<GraphqlId>) {
-        let model_id = model.fields().id();
-
-        let return_id = match args.get_field_value(&model_id.name) {
-            _ if model_id.is_auto_generated => None,
-            Some(PrismaValue::Null) | None => {
-                let id = model.generate_id();
-                args.insert(model_id.name.as_ref(), id.clone());
-                Some(id)
+    pub fn create_node(model: ModelRef, mut args: PrismaArgs) -> SqlResult<(Insert, Option<GraphqlId>)> {
+        let return_id = if model.has_compound_id() {
+            // Compound ids are never generated, all parts are given in the arguments.
+            let parts: Option<Vec<GraphqlId>> = model
+                .fields()
+                .id_fields()
+                .iter()
+                .map(|field| args.get_field_value(&field.name).and_then(|v| GraphqlId::try_from(v).ok()))
+                .collect();
+
+            parts.map(GraphqlId::from)
+        } else {
+            let model_id = model.fields().id()?;
+
+            match args.get_field_value(&model_id.name) {
+                _ if model_id.is_auto_generated => None,
+                Some(PrismaValue::Null) | None => {
+                    let id = model.generate_id()?;
+                    args.insert(model_id.name.as_ref(), id.clone());
+                    Some(id)
+                }
+                Some(PrismaValue::GraphqlId(id)) => Some(id.clone()),
+                _ => None,
             }
-            Some(PrismaValue::GraphqlId(id)) => Some(id.clone()),
-            _ => None,
         };
 
         let fields: Vec<&Field> = model
@@ -39,7 +52,7 @@ impl MutationBuilder {
             .into_iter()
             .fold(base, |acc, (name, value)| acc.value(name.into_owned(), value));
 
-        (Insert::from(insert).returning(vec![model_id.as_column()]), return_id)
+        Ok((Insert::from(insert).returning(model.id_columns()), return_id))
     }
 
     pub fn create_relation(field: RelationFieldRef, parent_id: &GraphqlId, child_id: &GraphqlId) -> Query {
@@ -54,24 +67,42 @@ impl MutationBuilder {
                     false => (child_id, parent_id),
                 };
 
-                let update_condition = match field.relation_is_inlined_in_parent() {
-                    true => field.model().fields().id().as_column().equals(update_id),
-                    false => field.related_model().fields().id().as_column().equals(update_id),
+                let (update_model, link_columns) = match field.relation_is_inlined_in_parent() {
+                    true => (field.model(), field.opposite_columns()),
+                    false => (field.related_model(), field.relation_columns()),
+                };
+
+                let update_condition = update_model.id_columns().equals_id(update_id);
+
+                // A compound id is linked by one referencing column per id field.
+                let link_values: Vec<(String, GraphqlId)> = if link_columns.len() > 1 {
+                    link_columns
+                        .into_iter()
+                        .zip(link_id.parts())
+                        .map(|(column, part)| (column.name.to_string(), part.clone()))
+                        .collect()
+                } else {
+                    vec![(referencing_column.to_string(), link_id.clone())]
                 };
 
-                Update::table(relation.relation_table())
-                    .set(referencing_column, link_id.clone())
+                link_values
+                    .into_iter()
+                    .fold(Update::table(relation.relation_table()), |acc, (column, value)| {
+                        acc.set(column, value)
+                    })
                     .so_that(update_condition)
                     .into()
             }
             None => {
                 let relation = field.relation();
-                let parent_column = field.relation_column();
-                let child_column = field.opposite_column();
+                let parent_columns = field.relation_columns().into_iter().zip(parent_id.parts());
+                let child_columns = field.opposite_columns().into_iter().zip(child_id.parts());
 
-                let insert = Insert::single_into(relation.relation_table())
-                    .value(parent_column.name, parent_id.clone())
-                    .value(child_column.name, child_id.clone());
+                let insert = parent_columns
+                    .chain(child_columns)
+                    .fold(Insert::single_into(relation.relation_table()), |acc, (column, part)| {
+                        acc.value(column.name, part.clone())
+                    });
 
                 let insert: Insert = match relation.id_column() {
                     Some(id_column) => insert.value(id_column, cuid::cuid().unwrap()).into(),
@@ -127,24 +158,27 @@ impl MutationBuilder {
         let mut query = Update::table(model.table());
 
         for (name, value) in args.args.iter() {
-            let field = fields.find_from_scalar(&name).unwrap();
-
-            if field.is_required && value.is_null() {
-                return Err(SqlError::FieldCannotBeNull {
-                    field: field.name.clone(),
-                });
+            // Embedded records are written as a whole document into the column of their relation field.
+            let (db_name, is_required) = match fields.find_from_all(&name).unwrap() {
+                Field::Relation(rf) if rf.is_embedded() => (rf.db_name(), rf.is_required),
+                _ => {
+                    let field = fields.find_from_scalar(&name).unwrap();
+                    (field.db_name().to_string(), field.is_required)
+                }
+            };
+
+            if is_required && value.is_null() {
+                return Err(SqlError::FieldCannotBeNull { field: name.clone() });
             }
 
-            query = query.set(field.db_name(), value.clone());
+            query = query.set(db_name, value.clone());
         }
 
         let result: Vec<Update> = ids
             .chunks(Self::PARAMETER_LIMIT)
             .into_iter()
             .map(|ids| {
-                query
-                    .clone()
-                    .so_that(fields.id().as_column().in_selection(ids.to_vec()))
+                query.clone().so_that(model.id_columns().in_ids(ids.iter().cloned()))
             })
             .collect();
 
@@ -161,7 +195,7 @@ impl MutationBuilder {
                 deletes.push(Delete::from_table(scalar_list_table.table()).so_that(condition));
             }
 
-            let condition = model.fields().id().as_column().in_selection(chunk.to_vec());
+            let condition = model.id_columns().in_ids(chunk.iter().cloned());
             deletes.push(Delete::from_table(model.table()).so_that(condition));
         }
 
@@ -207,12 +241,16 @@ impl MutationBuilder {
             .internal_data_model()
             .relations()
             .iter()
+            .filter(|r| !r.is_embedded())
             .map(|r| r.relation_table())
             .fold(deletes, |mut acc, table| {
                 acc.push(Delete::from_table(table));
                 acc
             });
 
+        // Embedded models are stored on their parents and have no tables of their own.
+        let models: Vec<&ModelRef> = models.iter().filter(|m| !m.is_embedded).collect();
+
         deletes = models.iter().map(|m| m.table()).fold(deletes, |mut acc, table| {
             acc.push(Delete::from_table(table));
             acc
@@ -1,4 +1,4 @@
-use crate::{error::SqlError, SqlResult};
+use crate::{error::SqlError, IdCondition, SqlResult};
 use prisma_models::prelude::*;
 use prisma_query::ast::*;
 
@@ -21,13 +21,18 @@ impl DeleteActions {
         for rf in model.internal_data_model().fields_requiring_model(model) {
             let relation = rf.relation();
 
-            let condition = rf
-                .opposite_column()
-                .in_selection(ids.to_vec())
-                .and(rf.relation_column().is_not_null());
+            let opposite_columns = rf.opposite_columns();
 
-            let select = Select::from_table(relation.relation_table())
-                .column(rf.opposite_column())
+            let condition = opposite_columns
+                .clone()
+                .in_ids(ids.iter().cloned())
+                .and(rf.relation_columns().all_not_null());
+
+            let select = opposite_columns
+                .into_iter()
+                .fold(Select::from_table(relation.relation_table()), |acc, column| {
+                    acc.column(column)
+                })
                 .so_that(condition);
 
             if let Some(_) = f(select)? {
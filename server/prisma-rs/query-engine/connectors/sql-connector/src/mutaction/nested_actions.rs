@@ -11,7 +11,7 @@ pub use nested_disconnect::*;
 pub use nested_set::*;
 
 use crate::query_builder::QueryBuilder;
-use crate::{error::*, SqlResult};
+use crate::{error::*, IdCondition, SqlResult};
 use connector::{error::NodeSelectorInfo, filter::NodeSelector};
 use prisma_models::*;
 use prisma_query::ast::*;
@@ -71,67 +71,41 @@ pub trait NestedActions {
 
     fn removal_by_parent(&self, id: &GraphqlId) -> Query {
         let rf = self.relation_field();
-        let relation = self.relation();
-        let relation_column = relation.column_for_relation_side(rf.relation_side);
-
-        let condition = relation_column.equals(id.clone());
+        let condition = rf.relation_columns().equals_id(id);
 
-        match relation.inline_relation_column() {
-            Some(column) => Update::table(relation.relation_table())
-                .set(column.name.as_ref(), PrismaValue::Null)
-                .so_that(condition)
-                .into(),
-            None => Delete::from_table(relation.relation_table()).so_that(condition).into(),
-        }
+        unlink(&self.relation(), condition)
     }
 
     fn removal_by_child(&self, id: &GraphqlId) -> Query {
         let rf = self.relation_field();
         assert!(!rf.related_field().is_list);
 
-        let relation = self.relation();
-
-        let condition = relation
-            .column_for_relation_side(rf.relation_side.opposite())
-            .equals(id.clone());
+        let condition = rf.opposite_columns().equals_id(id);
 
-        match relation.inline_relation_column() {
-            Some(column) => Update::table(relation.relation_table())
-                .set(column.name.as_ref(), PrismaValue::Null)
-                .so_that(condition)
-                .into(),
-            None => Delete::from_table(relation.relation_table()).so_that(condition).into(),
-        }
+        unlink(&self.relation(), condition)
     }
 
     fn removal_by_parent_and_child(&self, parent_id: &GraphqlId, child_id: &GraphqlId) -> Query {
-        let relation = self.relation();
         let rf = self.relation_field();
 
-        let is_child = rf.opposite_column().equals(child_id.clone());
-        let is_parent = rf.relation_column().equals(parent_id.clone());
-
-        let table = relation.relation_table();
+        let is_child = rf.opposite_columns().equals_id(child_id);
+        let is_parent = rf.relation_columns().equals_id(parent_id);
 
-        match relation.inline_relation_column() {
-            Some(column) => Update::table(table)
-                .set(column.name.as_ref(), PrismaValue::Null)
-                .so_that(is_child.and(is_parent))
-                .into(),
-            None => Delete::from_table(table).so_that(is_child.and(is_parent)).into(),
-        }
+        unlink(&self.relation(), is_child.and(is_parent))
     }
 
     fn check_for_old_child(&self, id: &GraphqlId) -> (Select, ResultCheck) {
         let relation = self.relation();
         let rf = self.relation_field().related_field();
 
-        let relation_column = relation.column_for_relation_side(rf.relation_side);
-        let opposite_column = relation.column_for_relation_side(rf.relation_side.opposite());
+        let relation_columns = rf.relation_columns();
+        let opposite_columns = rf.opposite_columns();
 
-        let query = Select::from_table(relation.relation_table())
-            .column(opposite_column.clone())
-            .so_that(opposite_column.equals(id.clone()).and(relation_column.is_not_null()));
+        let query = opposite_columns
+            .iter()
+            .cloned()
+            .fold(Select::from_table(relation.relation_table()), |acc, column| acc.column(column))
+            .so_that(opposite_columns.equals_id(id).and(relation_columns.all_not_null()));
 
         let error = self.relation_violation();
 
@@ -150,22 +124,22 @@ pub trait NestedActions {
         let relation = self.relation();
         let rf = self.relation_field().related_field();
 
-        let relation_column = relation.column_for_relation_side(rf.relation_side);
-        let opposite_column = relation.column_for_relation_side(rf.relation_side.opposite());
+        let relation_columns = rf.relation_columns();
+        let opposite_columns = rf.opposite_columns();
 
         let sub_select = QueryBuilder::get_nodes(
             rf.model(),
-            &SelectedFields::from(rf.model().fields().id()),
+            &SelectedFields::from(rf.model().fields().id_fields()),
             node_selector.clone(),
         );
 
-        let condition = relation_column
-            .clone()
-            .in_selection(sub_select)
-            .and(opposite_column.clone().is_not_null());
+        let condition = relation_columns
+            .in_select(sub_select, rf.model().id_columns())
+            .and(opposite_columns.clone().all_not_null());
 
-        let query = Select::from_table(relation.relation_table())
-            .column(opposite_column)
+        let query = opposite_columns
+            .into_iter()
+            .fold(Select::from_table(relation.relation_table()), |acc, column| acc.column(column))
             .so_that(condition);
 
         let error = self.relation_violation();
@@ -185,12 +159,14 @@ pub trait NestedActions {
         let relation = self.relation();
         let rf = self.relation_field();
 
-        let is_parent = rf.relation_column().equals(parent_id);
-        let child_exists = rf.opposite_column().is_not_null();
+        let relation_columns = rf.relation_columns();
+        let child_exists = rf.opposite_columns().all_not_null();
 
-        let query = Select::from_table(relation.relation_table())
-            .column(rf.relation_column())
-            .so_that(is_parent.and(child_exists));
+        let query = relation_columns
+            .iter()
+            .cloned()
+            .fold(Select::from_table(relation.relation_table()), |acc, column| acc.column(column))
+            .so_that(relation_columns.equals_id(parent_id).and(child_exists));
 
         let error = self.nodes_not_connected(Some(parent_id.clone()), None);
 
@@ -209,13 +185,16 @@ pub trait NestedActions {
         let relation = self.relation();
         let rf = self.relation_field();
 
-        let condition = rf
-            .opposite_column()
-            .equals(parent_id)
-            .and(rf.relation_column().equals(child_id));
+        let opposite_columns = rf.opposite_columns();
+
+        let condition = opposite_columns
+            .clone()
+            .equals_id(parent_id)
+            .and(rf.relation_columns().equals_id(child_id));
 
-        let query = Select::from_table(relation.relation_table())
-            .column(rf.opposite_column())
+        let query = opposite_columns
+            .into_iter()
+            .fold(Select::from_table(relation.relation_table()), |acc, column| acc.column(column))
             .so_that(condition);
 
         let error = self.nodes_not_connected(Some(parent_id.clone()), Some(child_id.clone()));
@@ -231,3 +210,18 @@ pub trait NestedActions {
         (query, Box::new(check))
     }
 }
+
+// Unlinks related records: An inline relation sets its referencing columns to
+// null, a relation table deletes the matching rows.
+fn unlink(relation: &Relation, condition: ConditionTree) -> Query {
+    match relation.inline_relation_columns() {
+        Some(columns) => columns
+            .into_iter()
+            .fold(Update::table(relation.relation_table()), |acc, column| {
+                acc.set(column.name.to_string(), PrismaValue::Null)
+            })
+            .so_that(condition)
+            .into(),
+        None => Delete::from_table(relation.relation_table()).so_that(condition).into(),
+    }
+}
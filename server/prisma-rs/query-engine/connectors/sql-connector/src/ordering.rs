@@ -10,19 +10,16 @@ impl Ordering {
     pub fn for_model(model: ModelRef, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec {
         Self::by_fields(
             order_by.map(|oby| oby.field.as_column()),
-            model.fields().id().as_column(),
+            model.id_columns(),
             order_by,
             reverse,
         )
     }
 
-    pub fn internal<C>(second_field: C, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec
-    where
-        C: Into<Column>,
-    {
+    pub fn internal(second_fields: Vec<Column>, order_by: Option<&OrderBy>, reverse: bool) -> OrderVec {
         Self::by_fields(
             order_by.map(|oby| oby.field.as_column()),
-            second_field.into(),
+            second_fields,
             order_by,
             reverse,
         )
@@ -31,21 +28,26 @@ impl Ordering {
     pub fn aliased_internal(
         alias: &str,
         secondary_alias: &str,
-        secondary_field: &str,
+        secondary_fields: &[String],
         order_by: Option<&OrderBy>,
         reverse: bool,
     ) -> OrderVec {
         Self::by_fields(
             order_by.map(|oby| (alias, oby.field.db_name()).into()),
-            (secondary_alias, secondary_field).into(),
+            secondary_fields
+                .iter()
+                .map(|field| (secondary_alias, field.as_str()).into())
+                .collect(),
             order_by,
             reverse,
         )
     }
 
+    /// The secondary columns, usually the id, make the ordering stable. A
+    /// compound id has more than one column.
     fn by_fields(
         first_column: Option<Column>,
-        second_column: Column,
+        second_columns: Vec<Column>,
         order_by: Option<&OrderBy>,
         reverse: bool,
     ) -> OrderVec {
@@ -54,32 +56,32 @@ impl Ordering {
             .map(|order| order.sort_order)
             .unwrap_or(SortOrder::Ascending);
 
-        let ordering = match first_column {
-            Some(first) => {
-                if first != second_column {
-                    match (default_order, reverse) {
-                        (SortOrder::Ascending, true) => vec![first.descend(), second_column.descend()],
-                        (SortOrder::Descending, true) => vec![first.ascend(), second_column.descend()],
-                        (SortOrder::Ascending, false) => vec![first.ascend(), second_column.ascend()],
-                        (SortOrder::Descending, false) => vec![first.descend(), second_column.ascend()],
-                    }
-                } else {
-                    match (default_order, reverse) {
-                        (SortOrder::Ascending, true) => vec![second_column.descend()],
-                        (SortOrder::Descending, true) => vec![second_column.ascend()],
-                        (SortOrder::Ascending, false) => vec![second_column.ascend()],
-                        (SortOrder::Descending, false) => vec![second_column.descend()],
-                    }
-                }
+        let descending = match (default_order, reverse) {
+            (SortOrder::Ascending, true) => true,
+            (SortOrder::Descending, true) => false,
+            (SortOrder::Ascending, false) => false,
+            (SortOrder::Descending, false) => true,
+        };
+
+        let order = |column: Column, descending: bool| {
+            if descending {
+                column.descend()
+            } else {
+                column.ascend()
             }
-            _ => match (default_order, reverse) {
-                (SortOrder::Ascending, true) => vec![second_column.descend()],
-                (SortOrder::Descending, true) => vec![second_column.ascend()],
-                (SortOrder::Ascending, false) => vec![second_column.ascend()],
-                (SortOrder::Descending, false) => vec![second_column.descend()],
-            },
         };
 
-        ordering
+        match first_column {
+            Some(ref first) if !second_columns.contains(first) => {
+                let mut ordering = vec![order(first.clone(), descending)];
+
+                for column in second_columns {
+                    ordering.push(order(column, reverse));
+                }
+
+                ordering
+            }
+            _ => second_columns.into_iter().map(|column| order(column, descending)).collect(),
+        }
     }
 }
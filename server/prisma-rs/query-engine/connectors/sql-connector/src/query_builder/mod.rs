@@ -2,7 +2,7 @@ mod related_nodes;
 
 use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering};
 use connector::{
-    filter::{Filter, NodeSelector},
+    filter::{CompoundNodeSelector, Filter, NodeSelector},
     QueryArguments,
 };
 use prisma_models::prelude::*;
@@ -34,6 +34,19 @@ impl SelectDefinition for &NodeSelector {
     }
 }
 
+impl SelectDefinition for CompoundNodeSelector {
+    fn into_select(self, model: ModelRef) -> Select {
+        let args = QueryArguments::from(self);
+        args.into_select(model)
+    }
+}
+
+impl SelectDefinition for &CompoundNodeSelector {
+    fn into_select(self, model: ModelRef) -> Select {
+        self.clone().into_select(model)
+    }
+}
+
 impl SelectDefinition for Select {
     fn into_select(self, _: ModelRef) -> Select {
         self
@@ -121,16 +134,13 @@ impl QueryBuilder {
     }
 
     pub fn count_by_model(model: ModelRef, query_arguments: QueryArguments) -> Select {
-        let id_field = model.fields().id();
-
-        let mut selected_fields = SelectedFields::default();
-        selected_fields.add_scalar(id_field.clone(), false);
+        let id_fields = model.fields().id_fields();
+        let selected_fields = SelectedFields::from(id_fields.clone());
 
         let base_query = Self::get_nodes(model, &selected_fields, query_arguments);
 
         let table = Table::from(base_query).alias("sub");
-        let column = Column::from(("sub", id_field.db_name()));
-        let select_ast = Select::from_table(table).value(count(column));
+        let select_ast = Select::from_table(table).value(count(asterisk()));
 
         select_ast
     }
@@ -1,4 +1,4 @@
-use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering};
+use crate::{cursor_condition::CursorCondition, filter_conversion::AliasedCondition, ordering::Ordering, IdCondition};
 use connector::QueryArguments;
 use prisma_models::prelude::*;
 use prisma_query::ast::{
@@ -58,21 +58,26 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
     }
 
     pub fn with_pagination(self) -> Select {
-        let relation_side_column = self.relation_side_column();
+        let relation_side_columns = self.relation_side_columns();
         let base_query = self.base_query();
         let cursor_condition = self.cursor_condition;
 
+        let related_aliases =
+            SelectedFields::id_aliases(SelectedFields::RELATED_MODEL_ALIAS, self.related_model.id_columns().len());
+        let parent_aliases =
+            SelectedFields::id_aliases(SelectedFields::PARENT_MODEL_ALIAS, relation_side_columns.len());
+
         let ordering = Ordering::aliased_internal(
             Self::BASE_TABLE_ALIAS,
             Self::BASE_TABLE_ALIAS,
-            SelectedFields::RELATED_MODEL_ALIAS,
+            &related_aliases,
             self.order_by.as_ref(),
             self.reverse_order,
         );
 
         // TODO: prisma query crate slice handling
-        let conditions = relation_side_column
-            .in_selection(self.from_node_ids.to_owned())
+        let conditions = relation_side_columns
+            .in_ids(self.from_node_ids)
             .and(self.conditions)
             .and(cursor_condition);
 
@@ -89,10 +94,11 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
             None => base_query.so_that(conditions),
         };
 
-        let row_number_part: Function = ordering
+        let row_number = ordering.into_iter().fold(row_number(), |acc, ord| acc.order_by(ord));
+
+        let row_number_part: Function = parent_aliases
             .into_iter()
-            .fold(row_number(), |acc, ord| acc.order_by(ord))
-            .partition_by((Self::BASE_TABLE_ALIAS, SelectedFields::PARENT_MODEL_ALIAS))
+            .fold(row_number, |acc, alias| acc.partition_by((Self::BASE_TABLE_ALIAS, alias.as_str())))
             .into();
 
         let with_row_numbers = Select::from_table(Table::from(base_with_conditions).alias(Self::BASE_TABLE_ALIAS))
@@ -105,20 +111,19 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
     }
 
     pub fn without_pagination(self) -> Select {
-        let relation_side_column = self.relation_side_column();
-        let opposite_relation_side_column = self.opposite_relation_side_column();
+        let relation_side_columns = self.relation_side_columns();
+        let opposite_relation_side_columns = self.opposite_relation_side_columns();
         let base_query = self.base_query();
         let cursor_condition = self.cursor_condition;
 
         // TODO: prisma query crate slice handling
-        let conditions = relation_side_column
-            .clone()
-            .in_selection(self.from_node_ids.to_owned())
+        let conditions = relation_side_columns
+            .in_ids(self.from_node_ids)
             .and(cursor_condition)
             .and(self.conditions);
 
         Ordering::internal(
-            opposite_relation_side_column,
+            opposite_relation_side_columns,
             self.order_by.as_ref(),
             self.reverse_order,
         )
@@ -135,24 +140,28 @@ impl<'a> RelatedNodesQueryBuilder<'a> {
             .fold(select, |acc, col| acc.column(col.clone()))
             .inner_join(
                 self.relation_table()
-                    .on(self.id_column().equals(self.opposite_relation_side_column())),
+                    .on(self.id_columns().equals_columns(self.opposite_relation_side_columns())),
             )
     }
 
-    fn id_column(&self) -> Column {
-        self.related_model.id_column()
+    fn id_columns(&self) -> Vec<Column> {
+        self.related_model.id_columns()
     }
 
-    fn relation_side_column(&self) -> Column {
+    fn relation_side_columns(&self) -> Vec<Column> {
         self.relation
-            .column_for_relation_side(self.from_field.relation_side)
-            .table(Relation::TABLE_ALIAS)
+            .columns_for_relation_side(self.from_field.relation_side)
+            .into_iter()
+            .map(|column| column.table(Relation::TABLE_ALIAS))
+            .collect()
     }
 
-    fn opposite_relation_side_column(&self) -> Column {
+    fn opposite_relation_side_columns(&self) -> Vec<Column> {
         self.relation
-            .column_for_relation_side(self.from_field.relation_side.opposite())
-            .table(Relation::TABLE_ALIAS)
+            .columns_for_relation_side(self.from_field.relation_side.opposite())
+            .into_iter()
+            .map(|column| column.table(Relation::TABLE_ALIAS))
+            .collect()
     }
 
     fn relation_table(&self) -> Table {
@@ -1,8 +1,12 @@
 use crate::{database::SqlDatabase, error::SqlError, query_builder::QueryBuilder, Transactional};
-use connector::{error::ConnectorError, filter::NodeSelector, *};
+use connector::{
+    error::ConnectorError,
+    filter::{CompoundNodeSelector, NodeSelector},
+    *,
+};
 use itertools::Itertools;
 use prisma_models::*;
-use std::convert::TryFrom;
+use std::{convert::TryFrom, sync::Arc};
 
 struct ScalarListElement {
     node_id: GraphqlId,
@@ -36,6 +40,30 @@ where
         Ok(node)
     }
 
+    fn get_node_by_compound_where(
+        &self,
+        compound_selector: &CompoundNodeSelector,
+        selected_fields: &SelectedFields,
+    ) -> ConnectorResult<Option<SingleNode>> {
+        let model = compound_selector.model();
+        let db_name = &model.internal_data_model().db_name;
+        let query = QueryBuilder::get_nodes(Arc::clone(&model), selected_fields, compound_selector);
+        let field_names = selected_fields.names();
+        let idents = selected_fields.type_identifiers();
+
+        let node = self
+            .executor
+            .with_transaction(db_name, |conn| match conn.find(query, idents.as_slice()) {
+                Ok(result) => Ok(Some(result)),
+                Err(_e @ SqlError::NodeNotFoundForWhere(_)) => Ok(None),
+                Err(e) => Err(e),
+            })?
+            .map(Node::from)
+            .map(|node| SingleNode { node, field_names });
+
+        Ok(node)
+    }
+
     fn get_nodes(
         &self,
         model: ModelRef,
@@ -45,11 +73,15 @@ where
         let db_name = &model.internal_data_model().db_name;
         let field_names = selected_fields.names();
         let idents = selected_fields.type_identifiers();
-        let query = QueryBuilder::get_nodes(model, selected_fields, query_arguments);
 
         let nodes = self
             .executor
-            .with_transaction(db_name, |conn| conn.filter(query, idents.as_slice()))?
+            .with_transaction(db_name, |conn| {
+                let query_arguments = conn.resolve_embedded_arguments(query_arguments)?;
+                let query = QueryBuilder::get_nodes(model, selected_fields, query_arguments);
+
+                conn.filter(query, idents.as_slice())
+            })?
             .into_iter()
             .map(Node::from)
             .collect();
@@ -67,21 +99,42 @@ where
         let db_name = &from_field.model().internal_data_model().db_name;
         let idents = selected_fields.type_identifiers();
         let field_names = selected_fields.names();
-        let query = QueryBuilder::get_related_nodes(from_field, from_node_ids, query_arguments, selected_fields);
+        let parent_id_len = from_field.model().fields().id_fields().len();
+        let related_id_len = from_field.related_model().fields().id_fields().len();
 
         let nodes: ConnectorResult<Vec<Node>> = self
             .executor
-            .with_transaction(db_name, |conn| conn.filter(query, idents.as_slice()))?
+            .with_transaction(db_name, |conn| {
+                let query_arguments = conn.resolve_embedded_arguments(query_arguments)?;
+                let query =
+                    QueryBuilder::get_related_nodes(from_field, from_node_ids, query_arguments, selected_fields);
+
+                conn.filter(query, idents.as_slice())
+            })?
             .into_iter()
             .map(|mut row| {
-                let parent_id = row.values.pop().ok_or(ConnectorError::ColumnDoesNotExist)?;
-
-                // Relation id is always the second last value. We don't need it
+                // The parent id is always selected last, one value per id field.
+                let split_at = row
+                    .values
+                    .len()
+                    .checked_sub(parent_id_len)
+                    .ok_or(ConnectorError::ColumnDoesNotExist)?;
+
+                let parent_id = row
+                    .values
+                    .split_off(split_at)
+                    .into_iter()
+                    .map(GraphqlId::try_from)
+                    .collect::<Result<Vec<GraphqlId>, _>>()?;
+
+                // Relation id is always before the parent id. We don't need it
                 // here and we don't need it in the node.
-                let _ = row.values.pop();
+                for _ in 0..related_id_len {
+                    let _ = row.values.pop();
+                }
 
                 let mut node = Node::from(row);
-                node.add_parent_id(GraphqlId::try_from(parent_id)?);
+                node.add_parent_id(GraphqlId::from(parent_id));
 
                 Ok(node)
             })
@@ -95,11 +148,13 @@ where
 
     fn count_by_model(&self, model: ModelRef, query_arguments: QueryArguments) -> ConnectorResult<usize> {
         let db_name = &model.internal_data_model().db_name;
-        let query = QueryBuilder::count_by_model(model, query_arguments);
 
         let result = self
             .executor
-            .with_transaction(db_name, |conn| conn.find_int(query))
+            .with_transaction(db_name, |conn| {
+                let query_arguments = conn.resolve_embedded_arguments(query_arguments)?;
+                conn.find_int(QueryBuilder::count_by_model(model, query_arguments))
+            })
             .map(|count| count as usize)?;
 
         Ok(result)
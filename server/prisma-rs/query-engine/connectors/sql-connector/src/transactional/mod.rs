@@ -4,10 +4,14 @@ mod mutaction_executor;
 pub use data_resolver::*;
 pub use mutaction_executor::*;
 
-use crate::{error::*, query_builder::QueryBuilder, AliasedCondition, RawQuery, SqlResult, SqlRow};
+use crate::{
+    embedded_filter, error::*, query_builder::QueryBuilder, AliasedCondition, IdCondition, RawQuery, SqlFamily,
+    SqlResult, SqlRow,
+};
 use connector::{
     error::NodeSelectorInfo,
     filter::{Filter, NodeSelector},
+    QueryArguments,
 };
 use prisma_models::*;
 use prisma_query::ast::*;
@@ -44,6 +48,15 @@ pub trait Transaction {
     /// Provides user an escape hatch for using the database directly.
     fn raw(&mut self, q: RawQuery) -> SqlResult<Value>;
 
+    /// The SQL dialect of the database.
+    fn sql_family(&self) -> SqlFamily;
+
+    /// Selects rows with a parameterized query written in the dialect of the
+    /// database. Only for queries the query AST can't express, see
+    /// `embedded_filter`.
+    fn query_sql(&mut self, sql: &str, params: Vec<ParameterizedValue>, idents: &[TypeIdentifier])
+        -> SqlResult<Vec<SqlRow>>;
+
     /// Insert to the database. On success returns the last insert row id.
     fn insert(&mut self, q: Insert) -> SqlResult<Option<GraphqlId>> {
         Ok(self.write(q.into())?)
@@ -112,11 +125,15 @@ pub trait Transaction {
 
     /// Read the all columns as an `GraphqlId`
     fn filter_ids(&mut self, model: ModelRef, filter: Filter) -> SqlResult<Vec<GraphqlId>> {
-        let select = Select::from_table(model.table())
-            .column(model.fields().id().as_column())
+        let filter = self.resolve_embedded_filters(filter)?;
+
+        let select = model
+            .id_columns()
+            .into_iter()
+            .fold(Select::from_table(model.table()), |acc, column| acc.column(column))
             .so_that(filter.aliased_cond(None));
 
-        self.select_ids(select)
+        self.select_model_ids(model, select)
     }
 
     fn select_ids(&mut self, select: Select) -> SqlResult<Vec<GraphqlId>> {
@@ -132,6 +149,31 @@ pub trait Transaction {
         Ok(result)
     }
 
+    /// Read the ids of the given model. The select must return the id
+    /// columns of the model in the order of the id fields, a compound id
+    /// being read from all of its columns.
+    fn select_model_ids(&mut self, model: ModelRef, select: Select) -> SqlResult<Vec<GraphqlId>> {
+        if !model.has_compound_id() {
+            return self.select_ids(select);
+        }
+
+        let idents: Vec<TypeIdentifier> = model.fields().id_fields().iter().map(|f| f.type_identifier).collect();
+        let mut rows = self.filter(select, idents.as_slice())?;
+        let mut result = Vec::new();
+
+        for mut row in rows.drain(0..) {
+            let parts = row
+                .values
+                .drain(0..)
+                .map(GraphqlId::try_from)
+                .collect::<Result<Vec<GraphqlId>, _>>()?;
+
+            result.push(GraphqlId::from(parts));
+        }
+
+        Ok(result)
+    }
+
     /// Find a child of a parent. Will return an error if no child found with
     /// the given parameters. A more restrictive version of `get_ids_by_parents`.
     fn find_id_by_parent(
@@ -167,27 +209,85 @@ pub trait Transaction {
     ) -> SqlResult<Vec<GraphqlId>> {
         let related_model = parent_field.related_model();
         let relation = parent_field.relation();
-        let child_id_field = relation.column_for_relation_side(parent_field.relation_side.opposite());
-        let parent_id_field = relation.column_for_relation_side(parent_field.relation_side);
-
-        let subselect = Select::from_table(relation.relation_table())
-            .column(child_id_field)
-            .so_that(parent_id_field.in_selection(parent_ids));
+        let child_id_columns = parent_field.opposite_columns();
+        let parent_id_columns = parent_field.relation_columns();
 
-        let conditions = related_model.fields().id().db_name().in_selection(subselect);
+        let base = related_model
+            .id_columns()
+            .into_iter()
+            .fold(Select::from_table(related_model.table()), |acc, column| acc.column(column));
+
+        // A compound id cannot be compared against a subselect, so the
+        // relation table is joined instead.
+        let (base, conditions) = if related_model.has_compound_id() {
+            let alias = |columns: Vec<Column>| -> Vec<Column> {
+                columns.into_iter().map(|c| c.table(Relation::TABLE_ALIAS)).collect()
+            };
+
+            let join = relation
+                .relation_table()
+                .alias(Relation::TABLE_ALIAS)
+                .on(related_model.id_columns().equals_columns(alias(child_id_columns)));
+
+            (base.inner_join(join), alias(parent_id_columns).in_ids(parent_ids))
+        } else {
+            let subselect = child_id_columns
+                .iter()
+                .cloned()
+                .fold(Select::from_table(relation.relation_table()), |acc, column| acc.column(column))
+                .so_that(parent_id_columns.in_ids(parent_ids));
+
+            let conditions: ConditionTree = related_model
+                .id_columns()
+                .in_select(subselect, child_id_columns)
+                .into();
+
+            (base, conditions)
+        };
 
         let conditions = match selector {
-            Some(into_cond) => {
-                let filter: Filter = into_cond.into();
+            Some(filter) => {
+                let filter = self.resolve_embedded_filters(filter)?;
                 conditions.and(filter.aliased_cond(None))
             }
-            None => conditions.into(),
+            None => conditions,
         };
 
-        let select = Select::from_table(related_model.table())
-            .column(related_model.fields().id().as_column())
-            .so_that(conditions);
+        self.select_model_ids(related_model, base.so_that(conditions))
+    }
+
+    /// Replaces the conditions on embedded fields with conditions on the ids
+    /// of the matching records, which are selected first. See
+    /// `embedded_filter` for the reasoning.
+    fn resolve_embedded_filters(&mut self, filter: Filter) -> SqlResult<Filter> {
+        let family = self.sql_family();
+
+        embedded_filter::resolve(filter, &mut |condition| {
+            if condition.field().model().has_compound_id() {
+                return Err(DomainError::CompoundIdNotSupported("filtering on embedded fields").into());
+            }
+
+            let (sql, params) = condition.query(family);
+            let mut rows = self.query_sql(&sql, params, &[TypeIdentifier::GraphQLID])?;
+            let mut ids = Vec::new();
+
+            for mut row in rows.drain(0..) {
+                for value in row.values.drain(0..) {
+                    ids.push(GraphqlId::try_from(value)?)
+                }
+            }
+
+            Ok(ids)
+        })
+    }
+
+    /// Resolves the embedded conditions of the filter in the arguments.
+    fn resolve_embedded_arguments(&mut self, query_arguments: QueryArguments) -> SqlResult<QueryArguments> {
+        let filter = match query_arguments.filter {
+            Some(filter) => Some(self.resolve_embedded_filters(filter)?),
+            None => None,
+        };
 
-        self.select_ids(select)
+        Ok(QueryArguments { filter, ..query_arguments })
     }
 }
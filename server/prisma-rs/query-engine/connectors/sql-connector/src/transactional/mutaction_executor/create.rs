@@ -15,7 +15,10 @@ pub fn execute<S>(
 where
     S: AsRef<str>,
 {
-    let (insert, returned_id) = MutationBuilder::create_node(Arc::clone(&model), non_list_args.clone());
+    let mut args = non_list_args.clone();
+    args.add_datetimes(Arc::clone(&model));
+
+    let (insert, returned_id) = MutationBuilder::create_node(Arc::clone(&model), args)?;
     let last_id = conn.insert(insert)?;
 
     let id = match returned_id {
@@ -0,0 +1,293 @@
+use crate::{error::SqlError, query_builder::QueryBuilder, SqlResult, Transaction};
+use connector::{error::NodeSelectorInfo, filter::NodeSelector, mutaction::EmbeddedWrite};
+use prisma_models::*;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+
+/// Embedded records are stored as json on their parent, so nested writes to
+/// them are turned into arguments holding the new document. A created record
+/// has no documents yet.
+pub fn create_args(non_list_args: &PrismaArgs, writes: &[EmbeddedWrite]) -> PrismaArgs {
+    let mut args = non_list_args.clone();
+
+    for write in writes {
+        let document = write.apply(JsonValue::Null);
+        args.insert(write.field.name.clone(), PrismaValue::Json(document));
+    }
+
+    args
+}
+
+/// The arguments of an update, including the new documents of the embedded
+/// fields. The stored documents are read in the transaction of the update,
+/// so the change is written based on the current state of the record.
+pub fn update_args(
+    conn: &mut Transaction,
+    node_selector: &NodeSelector,
+    non_list_args: &PrismaArgs,
+    writes: &[EmbeddedWrite],
+) -> SqlResult<PrismaArgs> {
+    let mut args = non_list_args.clone();
+
+    if writes.is_empty() {
+        return Ok(args);
+    }
+
+    let fields = writes
+        .iter()
+        .map(|write| {
+            SelectedField::Relation(SelectedRelationField {
+                field: Arc::clone(&write.field),
+                selected_fields: SelectedFields::new(vec![], None),
+            })
+        })
+        .collect();
+
+    let selected_fields = SelectedFields::new(fields, None);
+    let select = QueryBuilder::get_nodes(node_selector.field.model(), &selected_fields, node_selector);
+    let idents = selected_fields.type_identifiers();
+
+    let row = conn.find(select, idents.as_slice()).map_err(|e| match e {
+        SqlError::NodeDoesNotExist => SqlError::NodeNotFoundForWhere(NodeSelectorInfo::from(node_selector)),
+        e => e,
+    })?;
+
+    let node = SingleNode::new(Node::from(row), selected_fields.names());
+
+    for write in writes {
+        let current = match node.get_field_value(&write.field.name) {
+            Ok(PrismaValue::Json(document)) => document.clone(),
+            _ => JsonValue::Null,
+        };
+
+        args.insert(write.field.name.clone(), PrismaValue::Json(write.apply(current)));
+    }
+
+    Ok(args)
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::transactional::mutaction_executor::{create, update};
+    use connector::mutaction::{EmbeddedDocument, EmbeddedOperation};
+    use rusqlite::{Connection, Transaction as SqliteTransaction};
+    use serde_json::json;
+    use std::fs::File;
+
+    fn internal_data_model() -> InternalDataModelRef {
+        let file = File::open("embedded_test_schema.json").unwrap();
+        let template: InternalDataModelTemplate = serde_json::from_reader(file).unwrap();
+
+        template.build(String::from("test"))
+    }
+
+    fn database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            r#"
+            ATTACH DATABASE ':memory:' AS "test";
+            CREATE TABLE "test"."User" ("id" TEXT PRIMARY KEY, "name" TEXT, "address" TEXT, "posts" TEXT);
+            INSERT INTO "test"."User" VALUES
+                ('alice', 'Alice', '{"street": "Main St", "city": "Berlin"}',
+                    '[{"title": "Hello", "comments": [{"text": "First"}]}, {"title": "Bye", "comments": []}]');
+            "#,
+        )
+        .unwrap();
+
+        conn
+    }
+
+    fn document(values: JsonValue, nested: Vec<EmbeddedWrite>) -> EmbeddedDocument {
+        match values {
+            JsonValue::Object(values) => EmbeddedDocument { values, nested },
+            _ => unreachable!(),
+        }
+    }
+
+    fn write(model: &ModelRef, field: &str, operation: EmbeddedOperation) -> EmbeddedWrite {
+        EmbeddedWrite {
+            field: model.fields().find_from_relation_fields(field).unwrap(),
+            operation,
+        }
+    }
+
+    fn documents(tx: &SqliteTransaction, id: &str) -> (JsonValue, JsonValue) {
+        let (address, posts): (Option<String>, Option<String>) = tx
+            .query_row(
+                r#"SELECT "address", "posts" FROM "test"."User" WHERE "id" = ?1"#,
+                &[id],
+                |row| (row.get(0), row.get(1)),
+            )
+            .unwrap();
+
+        let parse = |document: Option<String>| {
+            document
+                .map(|document| serde_json::from_str(&document).unwrap())
+                .unwrap_or(JsonValue::Null)
+        };
+
+        (parse(address), parse(posts))
+    }
+
+    fn selector(model: &ModelRef, id: &str) -> NodeSelector {
+        NodeSelector::new(model.fields().id().unwrap(), GraphqlId::String(id.to_string()))
+    }
+
+    #[test]
+    fn creates_embedded_records() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let post = internal_data_model.find_model("Post").unwrap();
+
+        let writes = vec![
+            write(
+                &user,
+                "address",
+                EmbeddedOperation::Create(vec![document(json!({ "city": "Paris" }), vec![])]),
+            ),
+            write(
+                &user,
+                "posts",
+                EmbeddedOperation::Create(vec![
+                    document(
+                        json!({ "title": "Hello" }),
+                        vec![write(
+                            &post,
+                            "comments",
+                            EmbeddedOperation::Create(vec![document(json!({ "text": "First" }), vec![])]),
+                        )],
+                    ),
+                    document(json!({ "title": "Bye" }), vec![]),
+                ]),
+            ),
+        ];
+
+        let mut non_list_args = PrismaArgs::new();
+        non_list_args.insert("id", GraphqlId::String(String::from("bob")));
+        non_list_args.insert("name", "Bob");
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+
+        let args = create_args(&non_list_args, &writes);
+        let no_list_args: &[(String, PrismaListValue)] = &[];
+        create::execute(&mut tx, Arc::clone(&user), &args, no_list_args).unwrap();
+
+        let (address, posts) = documents(&tx, "bob");
+
+        assert_eq!(json!({ "city": "Paris" }), address);
+        assert_eq!(
+            json!([{ "title": "Hello", "comments": [{ "text": "First" }] }, { "title": "Bye" }]),
+            posts
+        );
+    }
+
+    #[test]
+    fn updates_the_stored_embedded_records() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let post = internal_data_model.find_model("Post").unwrap();
+
+        let writes = vec![
+            write(
+                &user,
+                "address",
+                EmbeddedOperation::Update(document(json!({ "city": "Paris" }), vec![])),
+            ),
+            write(
+                &user,
+                "posts",
+                EmbeddedOperation::Update(document(
+                    json!({}),
+                    vec![write(
+                        &post,
+                        "comments",
+                        EmbeddedOperation::Create(vec![document(json!({ "text": "Again" }), vec![])]),
+                    )],
+                )),
+            ),
+        ];
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+        let selector = selector(&user, "alice");
+
+        let args = update_args(&mut tx, &selector, &PrismaArgs::new(), &writes).unwrap();
+        let no_list_args: &[(String, PrismaListValue)] = &[];
+        update::execute(&mut tx, &selector, &args, no_list_args).unwrap();
+
+        let (address, posts) = documents(&tx, "alice");
+
+        assert_eq!(json!({ "street": "Main St", "city": "Paris" }), address);
+        assert_eq!(
+            json!([
+                { "title": "Hello", "comments": [{ "text": "First" }, { "text": "Again" }] },
+                { "title": "Bye", "comments": [{ "text": "Again" }] }
+            ]),
+            posts
+        );
+    }
+
+    #[test]
+    fn appends_created_records_to_embedded_lists() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+
+        let writes = vec![write(
+            &user,
+            "posts",
+            EmbeddedOperation::Create(vec![document(json!({ "title": "New" }), vec![])]),
+        )];
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+        let selector = selector(&user, "alice");
+
+        let args = update_args(&mut tx, &selector, &PrismaArgs::new(), &writes).unwrap();
+        let no_list_args: &[(String, PrismaListValue)] = &[];
+        update::execute(&mut tx, &selector, &args, no_list_args).unwrap();
+
+        let (_, posts) = documents(&tx, "alice");
+        let titles: Vec<&JsonValue> = posts.as_array().unwrap().iter().map(|post| &post["title"]).collect();
+
+        assert_eq!(vec![&json!("Hello"), &json!("Bye"), &json!("New")], titles);
+    }
+
+    #[test]
+    fn deletes_embedded_records() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+
+        let writes = vec![
+            write(&user, "address", EmbeddedOperation::Delete),
+            write(&user, "posts", EmbeddedOperation::Delete),
+        ];
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+        let selector = selector(&user, "alice");
+
+        let args = update_args(&mut tx, &selector, &PrismaArgs::new(), &writes).unwrap();
+        let no_list_args: &[(String, PrismaListValue)] = &[];
+        update::execute(&mut tx, &selector, &args, no_list_args).unwrap();
+
+        assert_eq!((JsonValue::Null, json!([])), documents(&tx, "alice"));
+    }
+
+    #[test]
+    fn fails_to_update_the_embedded_records_of_a_missing_record() {
+        let internal_data_model = internal_data_model();
+        let user = internal_data_model.find_model("User").unwrap();
+        let writes = vec![write(&user, "address", EmbeddedOperation::Delete)];
+
+        let mut conn = database();
+        let mut tx = conn.transaction().unwrap();
+
+        match update_args(&mut tx, &selector(&user, "nobody"), &PrismaArgs::new(), &writes) {
+            Err(SqlError::NodeNotFoundForWhere(_)) => (),
+            result => panic!("Expected the record not to be found, got {:?}", result),
+        }
+    }
+}
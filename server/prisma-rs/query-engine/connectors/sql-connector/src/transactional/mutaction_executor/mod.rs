@@ -1,6 +1,7 @@
 mod create;
 mod delete;
 mod delete_many;
+mod embedded;
 mod nested;
 mod relation;
 mod update;
@@ -22,7 +23,8 @@ where
     ) -> ConnectorResult<DatabaseMutactionResult> {
         let result = self.executor.with_transaction(&db_name, |conn: &mut Transaction| {
             fn create(conn: &mut Transaction, cn: &CreateNode) -> SqlResult<DatabaseMutactionResult> {
-                let parent_id = create::execute(conn, Arc::clone(&cn.model), &cn.non_list_args, &cn.list_args)?;
+                let args = embedded::create_args(&cn.non_list_args, &cn.embedded_writes);
+                let parent_id = create::execute(conn, Arc::clone(&cn.model), &args, &cn.list_args)?;
                 nested::execute(conn, &cn.nested_mutactions, &parent_id)?;
 
                 Ok(DatabaseMutactionResult {
@@ -32,7 +34,8 @@ where
             }
 
             fn update(conn: &mut Transaction, un: &UpdateNode) -> SqlResult<DatabaseMutactionResult> {
-                let parent_id = update::execute(conn, &un.where_, &un.non_list_args, &un.list_args)?;
+                let args = embedded::update_args(conn, &un.where_, &un.non_list_args, &un.embedded_writes)?;
+                let parent_id = update::execute(conn, &un.where_, &args, &un.list_args)?;
                 nested::execute(conn, &un.nested_mutactions, &parent_id)?;
 
                 Ok(DatabaseMutactionResult {
@@ -16,7 +16,24 @@ where
     let model = node_selector.field.model();
     let id = conn.find_id(node_selector)?;
 
-    if let Some(update) = MutationBuilder::update_one(Arc::clone(&model), &id, non_list_args)? {
+    update_by_id(conn, model, id, non_list_args, list_args)
+}
+
+/// Updates the record with the given id, which is known to exist.
+fn update_by_id<S>(
+    conn: &mut Transaction,
+    model: ModelRef,
+    id: GraphqlId,
+    non_list_args: &PrismaArgs,
+    list_args: &[(S, PrismaListValue)],
+) -> SqlResult<GraphqlId>
+where
+    S: AsRef<str>,
+{
+    let mut args = non_list_args.clone();
+    args.update_datetimes(Arc::clone(&model), !list_args.is_empty());
+
+    if let Some(update) = MutationBuilder::update_one(Arc::clone(&model), &id, &args)? {
         conn.update(update)?;
     }
 
@@ -43,9 +60,8 @@ where
     };
 
     let id = conn.find_id_by_parent(Arc::clone(&relation_field), parent_id, node_selector)?;
-    let node_selector = NodeSelector::from((relation_field.related_model().fields().id(), id));
 
-    execute(conn, &node_selector, non_list_args, list_args)
+    update_by_id(conn, relation_field.related_model(), id, non_list_args, list_args)
 }
 
 /// Updates list args related to the given records.
@@ -25,9 +25,12 @@ where
         return Ok(count);
     }
 
+    let mut args = non_list_args.clone();
+    args.update_datetimes(Arc::clone(&model), !list_args.is_empty());
+
     let updates = {
         let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-        MutationBuilder::update_many(Arc::clone(&model), ids.as_slice(), non_list_args)?
+        MutationBuilder::update_many(Arc::clone(&model), ids.as_slice(), &args)?
     };
 
     for update in updates {
@@ -59,9 +62,12 @@ where
         return Ok(count);
     }
 
+    let mut args = non_list_args.clone();
+    args.update_datetimes(relation_field.related_model(), !list_args.is_empty());
+
     let updates = {
         let ids: Vec<&GraphqlId> = ids.iter().map(|id| &*id).collect();
-        MutationBuilder::update_many(relation_field.related_model(), ids.as_slice(), non_list_args)?
+        MutationBuilder::update_many(relation_field.related_model(), ids.as_slice(), &args)?
     };
 
     for update in updates {
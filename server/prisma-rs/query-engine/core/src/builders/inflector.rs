@@ -24,8 +24,6 @@ impl Inflector {
         }
     }
 
-    // TODO: Figure out why and how to use this
-    #[allow(dead_code)]
     pub fn pluralize(word: &str) -> String {
         match PLURALIZE_EXCEPTIONS.get(word).cloned() {
             Some(exception) => exception.to_owned(),
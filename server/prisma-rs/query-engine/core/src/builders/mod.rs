@@ -5,6 +5,7 @@ mod inflector;
 mod many;
 mod many_rel;
 mod one_rel;
+mod operation;
 mod root;
 mod single;
 mod mutations;
@@ -14,13 +15,14 @@ pub(crate) mod utils;
 pub use many::*;
 pub use many_rel::*;
 pub use one_rel::*;
+pub use operation::*;
 pub use root::*;
 pub use single::*;
 pub use mutations::*;
 
 pub use self::inflector::Inflector;
 
-use crate::{CoreError, CoreResult, ReadQuery};
+use crate::{CoreError, CoreResult, ReadQuery, ResponseKey};
 use connector::QueryArguments;
 use graphql_parser::query::{Field, Selection, Value};
 use prisma_models::{
@@ -201,6 +203,11 @@ pub trait BuilderExt {
             .iter()
             .filter_map(|i| {
                 if let Selection::Field(f) = i {
+                    // `__typename` is resolved from the model when building the result.
+                    if f.name == "__typename" {
+                        return None;
+                    }
+
                     // We have to make sure the selected field exists in some form.
                     let field = model.fields().find_from_all(&f.name);
                     match field {
@@ -225,7 +232,17 @@ pub trait BuilderExt {
                 }
             })
             .collect::<CoreResult<Vec<_>>>()
-            .map(|sf| SelectedFields::new(sf, parent.into()))
+            .map(|mut sf| {
+                // A selection of only `__typename` still needs a field to query the records with.
+                if sf.is_empty() {
+                    sf.push(SelectedField::Scalar(SelectedScalarField {
+                        field: Arc::clone(&model.fields().id_fields()[0]),
+                        implicit: true,
+                    }));
+                }
+
+                SelectedFields::new(sf, parent.into())
+            })
     }
 
     fn collect_nested_queries<'field>(
@@ -239,6 +256,10 @@ pub trait BuilderExt {
             .iter()
             .filter_map(|i| {
                 if let Selection::Field(x) = i {
+                    if x.name == "__typename" {
+                        return None;
+                    }
+
                     let field = &model.fields().find_from_all(&x.name);
                     match &field {
                         Ok(ModelField::Scalar(_f)) => None,
@@ -273,14 +294,17 @@ pub trait BuilderExt {
             .collect()
     }
 
-    fn collect_selection_order(field: &Field) -> Vec<String> {
+    fn collect_selection_order(field: &Field) -> Vec<ResponseKey> {
         field
             .selection_set
             .items
             .iter()
             .filter_map(|select| {
                 if let Selection::Field(field) = select {
-                    Some(field.alias.clone().unwrap_or_else(|| field.name.clone()))
+                    Some(ResponseKey {
+                        key: field.alias.clone().unwrap_or_else(|| field.name.clone()),
+                        field_name: field.name.clone(),
+                    })
                 } else {
                     None
                 }
@@ -1,7 +1,10 @@
 //! Providing an interface to build WriteQueries
 
+use super::parse_embedded_write;
 use crate::{builders::utils, CoreError, CoreResult, WriteQuery};
-use connector::mutaction::{CreateNode, DeleteNode, DeleteNodes, TopLevelDatabaseMutaction, UpdateNode, UpsertNode};
+use connector::mutaction::{
+    CreateNode, DeleteNode, DeleteNodes, EmbeddedWrite, TopLevelDatabaseMutaction, UpdateNode, UpsertNode,
+};
 use graphql_parser::query::{Field, Value};
 use prisma_models::{InternalDataModelRef, ModelRef, PrismaArgs, PrismaValue};
 
@@ -32,11 +35,8 @@ impl<'field> MutationBuilder<'field> {
     }
 
     pub fn build(self) -> CoreResult<WriteQuery> {
-        let (non_list_args, list_args) = get_mutation_args(&self.field.arguments);
-        let (op, model) = parse_model_action(
-            self.field.alias.as_ref().unwrap_or_else(|| &self.field.name),
-            Arc::clone(&self.internal_data_model),
-        )?;
+        let (op, model) = parse_model_action(&self.field.name, Arc::clone(&self.internal_data_model))?;
+        let (non_list_args, list_args, embedded_writes) = get_mutation_args(&self.field.arguments, &model)?;
 
         let inner = match op {
             Operation::Create => TopLevelDatabaseMutaction::CreateNode(CreateNode {
@@ -44,12 +44,14 @@ impl<'field> MutationBuilder<'field> {
                 non_list_args,
                 list_args,
                 nested_mutactions: Default::default(),
+                embedded_writes,
             }),
             Operation::Update => TopLevelDatabaseMutaction::UpdateNode(UpdateNode {
                 where_: utils::extract_node_selector(self.field, Arc::clone(&model))?,
                 non_list_args,
                 list_args,
                 nested_mutactions: Default::default(),
+                embedded_writes,
             }),
             Operation::Delete => TopLevelDatabaseMutaction::DeleteNode(DeleteNode {
                 where_: utils::extract_node_selector(self.field, Arc::clone(&model))?,
@@ -65,12 +67,14 @@ impl<'field> MutationBuilder<'field> {
                     non_list_args: non_list_args.clone(),
                     list_args: list_args.clone(),
                     nested_mutactions: Default::default(),
+                    embedded_writes: embedded_writes.clone(),
                 },
                 update: UpdateNode {
                     where_: utils::extract_node_selector(self.field, Arc::clone(&model))?,
                     non_list_args,
                     list_args,
                     nested_mutactions: Default::default(),
+                    embedded_writes,
                 },
             }),
             _ => unimplemented!(),
@@ -86,15 +90,33 @@ impl<'field> MutationBuilder<'field> {
 }
 
 /// Extract String-Value pairs into usable mutation arguments
-fn get_mutation_args(args: &Vec<(String, Value)>) -> (PrismaArgs, PrismaListArgs) {
-    let (args, lists) = args
-        .iter()
-        .fold((BTreeMap::new(), vec![]), |(mut map, mut vec), (_, v)| {
-            match v {
-                Value::Object(o) => o.iter().for_each(|(k, v)| {
-                    // If the child is an object, we are probably dealing with ScalarList values
-                    match v {
-                        Value::Object(o) if o.contains_key("set") => {
+///
+/// Arguments of embedded fields are not stored in columns of their own,
+/// they are returned as `EmbeddedWrite`s instead.
+fn get_mutation_args(
+    args: &Vec<(String, Value)>,
+    model: &ModelRef,
+) -> CoreResult<(PrismaArgs, PrismaListArgs, Vec<EmbeddedWrite>)> {
+    let mut map = BTreeMap::new();
+    let mut vec = vec![];
+    let mut embedded = vec![];
+
+    for (_, v) in args {
+        match v {
+            Value::Object(o) => {
+                for (k, v) in o {
+                    let embedded_field = model
+                        .fields()
+                        .find_from_relation_fields(k)
+                        .ok()
+                        .filter(|rf| rf.is_embedded());
+
+                    match (embedded_field, v) {
+                        (Some(rf), v) => {
+                            embedded.push(parse_embedded_write(rf, v)?);
+                        }
+                        // If the child is an object, we are probably dealing with ScalarList values
+                        (_, Value::Object(o)) if o.contains_key("set") => {
                             vec.push((
                                 k.clone(),
                                 match o.get("set") {
@@ -108,17 +130,17 @@ fn get_mutation_args(args: &Vec<(String, Value)>) -> (PrismaArgs, PrismaListArgs
                                 },
                             ));
                         }
-                        v => {
+                        (_, v) => {
                             map.insert(k.clone(), PrismaValue::from_value(v));
                         }
                     }
-                }),
-                _ => panic!("Unknown argument structure!"),
+                }
             }
+            _ => panic!("Unknown argument structure!"),
+        }
+    }
 
-            (map, vec)
-        });
-    (args.into(), lists)
+    Ok((map.into(), vec, embedded))
 }
 
 /// A simple enum to discriminate top-level actions
@@ -0,0 +1,75 @@
+//! Parsing of nested writes to embedded records, see `EmbeddedWrite`.
+
+use crate::{CoreError, CoreResult};
+use connector::mutaction::{EmbeddedDocument, EmbeddedOperation, EmbeddedWrite};
+use graphql_parser::query::Value;
+use prisma_models::{Field, ModelRef, PrismaValue, RelationFieldRef};
+use serde_json::Value as JsonValue;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Parses the argument of an embedded field, e.g. `{ create: { street: "Main St" } }`
+pub fn parse_embedded_write(field: RelationFieldRef, value: &Value) -> CoreResult<EmbeddedWrite> {
+    let model = field.related_model();
+    let object = match value {
+        Value::Object(object) if object.len() == 1 => object,
+        _ => {
+            return Err(invalid(
+                &field,
+                "Expected exactly one of `create`, `update` or `delete`",
+            ))
+        }
+    };
+
+    let operation = match object.iter().next().unwrap() {
+        (op, Value::Object(values)) if op == "create" => {
+            EmbeddedOperation::Create(vec![parse_embedded_document(model, values)?])
+        }
+        (op, Value::List(list)) if op == "create" && field.is_list => {
+            let documents = list
+                .iter()
+                .map(|value| match value {
+                    Value::Object(values) => parse_embedded_document(Arc::clone(&model), values),
+                    _ => Err(invalid(&field, "Expected a list of objects for `create`")),
+                })
+                .collect::<CoreResult<Vec<EmbeddedDocument>>>()?;
+
+            EmbeddedOperation::Create(documents)
+        }
+        (op, Value::Object(values)) if op == "update" => {
+            EmbeddedOperation::Update(parse_embedded_document(model, values)?)
+        }
+        (op, Value::Boolean(true)) if op == "delete" => EmbeddedOperation::Delete,
+        (op, _) => return Err(invalid(&field, &format!("Invalid nested operation `{}`", op))),
+    };
+
+    Ok(EmbeddedWrite { field, operation })
+}
+
+fn parse_embedded_document(model: ModelRef, values: &BTreeMap<String, Value>) -> CoreResult<EmbeddedDocument> {
+    let mut document = EmbeddedDocument::default();
+
+    for (name, value) in values {
+        match model.fields().find_from_all(name) {
+            Ok(Field::Scalar(_)) => {
+                document
+                    .values
+                    .insert(name.clone(), JsonValue::from(PrismaValue::from_value(value)));
+            }
+            Ok(Field::Relation(rf)) if rf.is_embedded() => {
+                document.nested.push(parse_embedded_write(Arc::clone(rf), value)?);
+            }
+            _ => {
+                return Err(CoreError::QueryValidationError(format!(
+                    "Field {} not found on embedded model {}",
+                    name, model.name
+                )))
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+fn invalid(field: &RelationFieldRef, message: &str) -> CoreError {
+    CoreError::QueryValidationError(format!("{} on embedded field {}", message, field.name))
+}
@@ -2,8 +2,10 @@
 
 mod ast;
 mod builder;
+mod embedded;
 mod results;
 
 pub use ast::*;
 pub use builder::*;
+pub use embedded::*;
 pub use results::*;
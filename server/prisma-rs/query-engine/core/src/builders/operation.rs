@@ -0,0 +1,477 @@
+//! Preparation of the executed operation of a query document
+
+use crate::{InputType, QuerySchema, ScalarType, ValidationError};
+use graphql_parser::{query::*, Pos};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+
+/// Prepares a query document for query building: The operation is selected by
+/// `operation_name`, variables are substituted, fields excluded by `@skip` and
+/// `@include` are removed and fragments are expanded. The resulting document
+/// has a single operation of plain fields.
+#[derive(Debug)]
+pub struct OperationBuilder<'a> {
+    pub query: &'a Document,
+    pub query_schema: &'a QuerySchema,
+    pub operation_name: Option<String>,
+    pub variables: Map<String, JsonValue>,
+}
+
+impl<'a> OperationBuilder<'a> {
+    pub fn build(self) -> Result<Document, Vec<ValidationError>> {
+        let operation = self.find_operation()?;
+
+        let variable_definitions = match operation {
+            OperationDefinition::Query(query) => query.variable_definitions.as_slice(),
+            OperationDefinition::Mutation(mutation) => mutation.variable_definitions.as_slice(),
+            _ => &[],
+        };
+
+        let expansion = Expansion {
+            fragments: self
+                .query
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                    _ => None,
+                })
+                .collect(),
+            variables: self.coerce_variables(variable_definitions)?,
+        };
+
+        // Variables are substituted, the prepared operation has no variable definitions.
+        let operation = match operation {
+            OperationDefinition::SelectionSet(selection_set) => {
+                OperationDefinition::SelectionSet(expansion.selection_set(selection_set))
+            }
+            OperationDefinition::Query(query) => OperationDefinition::Query(Query {
+                variable_definitions: vec![],
+                selection_set: expansion.selection_set(&query.selection_set),
+                ..query.clone()
+            }),
+            OperationDefinition::Mutation(mutation) => OperationDefinition::Mutation(Mutation {
+                variable_definitions: vec![],
+                selection_set: expansion.selection_set(&mutation.selection_set),
+                ..mutation.clone()
+            }),
+            OperationDefinition::Subscription(subscription) => {
+                return Err(vec![error(
+                    "Subscriptions are not supported.".into(),
+                    Some(subscription.position),
+                )])
+            }
+        };
+
+        Ok(Document {
+            definitions: vec![Definition::Operation(operation)],
+        })
+    }
+
+    fn find_operation(&self) -> Result<&'a OperationDefinition, Vec<ValidationError>> {
+        let operations: Vec<&OperationDefinition> = self
+            .query
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Operation(operation) => Some(operation),
+                _ => None,
+            })
+            .collect();
+
+        let operation = match self.operation_name {
+            Some(ref name) => operations
+                .into_iter()
+                .find(|operation| operation_name(operation) == Some(name))
+                .ok_or_else(|| format!("Unknown operation named \"{}\".", name)),
+            None if operations.len() == 1 => Ok(operations[0]),
+            None if operations.is_empty() => Err(String::from("Must provide an operation.")),
+            None => Err(String::from(
+                "Must provide operation name if query contains multiple operations.",
+            )),
+        };
+
+        operation.map_err(|message| vec![error(message, None)])
+    }
+
+    /// The values of the defined variables, taken from the request or the defaults of the definitions.
+    /// Variables without either are left out, which leaves out the arguments using them.
+    fn coerce_variables(
+        &self,
+        definitions: &[VariableDefinition],
+    ) -> Result<BTreeMap<String, Value>, Vec<ValidationError>> {
+        let mut values = BTreeMap::new();
+        let mut errors = vec![];
+
+        for definition in definitions {
+            let tpe = self.input_type(&definition.var_type);
+            let required = tpe.as_ref().map_or(false, InputType::is_required);
+            let type_name = tpe.as_ref().map(|tpe| tpe.to_string()).unwrap_or_default();
+
+            match (self.variables.get(&definition.name), &definition.default_value) {
+                (Some(JsonValue::Null), _) if required => errors.push(error(
+                    format!(
+                        "Variable \"${}\" of non-null type \"{}\" must not be null.",
+                        definition.name, type_name
+                    ),
+                    Some(definition.position),
+                )),
+                (Some(json), _) => {
+                    values.insert(definition.name.clone(), self.coerce(json, tpe.as_ref()));
+                }
+                (None, Some(default_value)) => {
+                    values.insert(definition.name.clone(), default_value.clone());
+                }
+                (None, None) if required => errors.push(error(
+                    format!(
+                        "Variable \"${}\" of required type \"{}\" was not provided.",
+                        definition.name, type_name
+                    ),
+                    Some(definition.position),
+                )),
+                (None, None) => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The schema type of a variable, `None` for types that are not input types of the schema.
+    fn input_type(&self, tpe: &Type) -> Option<InputType> {
+        match tpe {
+            Type::NonNullType(inner) => match self.input_type(inner)? {
+                InputType::Opt(inner) => Some(*inner),
+                tpe => Some(tpe),
+            },
+            Type::ListType(inner) => Some(InputType::opt(InputType::list(self.input_type(inner)?))),
+            Type::NamedType(name) => {
+                let tpe = if let Some(scalar) = ScalarType::all().into_iter().find(|scalar| scalar.name() == name) {
+                    InputType::Scalar(scalar)
+                } else if self.query_schema.find_enum_type(name).is_some() {
+                    InputType::Enum(name.clone())
+                } else if self.query_schema.find_input_type(name).is_some() {
+                    InputType::object(name.as_str())
+                } else {
+                    return None;
+                };
+
+                Some(InputType::opt(tpe))
+            }
+        }
+    }
+
+    /// Converts a JSON value into a GraphQL value of the given type. Values not
+    /// matching the type are converted as they are and rejected by the validation.
+    fn coerce(&self, json: &JsonValue, tpe: Option<&InputType>) -> Value {
+        match (tpe, json) {
+            (_, JsonValue::Null) => Value::Null,
+            (Some(InputType::Opt(inner)), json) => self.coerce(json, Some(inner)),
+            (Some(InputType::List(inner)), JsonValue::Array(items)) => {
+                Value::List(items.iter().map(|item| self.coerce(item, Some(inner))).collect())
+            }
+            // Single values are coerced into lists with one element.
+            (Some(InputType::List(inner)), json) => Value::List(vec![self.coerce(json, Some(inner))]),
+            (Some(InputType::Enum(_)), JsonValue::String(value)) => Value::Enum(value.clone()),
+            (Some(InputType::Scalar(ScalarType::Float)), JsonValue::Number(number)) => {
+                Value::Float(number.as_f64().unwrap_or_default())
+            }
+            (Some(InputType::Scalar(ScalarType::Json)), JsonValue::Object(_))
+            | (Some(InputType::Scalar(ScalarType::Json)), JsonValue::Array(_)) => Value::String(json.to_string()),
+            (Some(InputType::Object(name)), JsonValue::Object(fields)) => {
+                let input_type = self.query_schema.find_input_type(name);
+
+                Value::Object(
+                    fields
+                        .iter()
+                        .map(|(field_name, value)| {
+                            let field_type = input_type
+                                .and_then(|input_type| input_type.find_field(field_name))
+                                .map(|field| &field.field_type);
+
+                            (field_name.clone(), self.coerce(value, field_type))
+                        })
+                        .collect(),
+                )
+            }
+            (_, JsonValue::Bool(b)) => Value::Boolean(*b),
+            (_, JsonValue::String(s)) => Value::String(s.clone()),
+            // GraphQL numbers are 32 bit integers, larger ones are passed on as floats.
+            (_, JsonValue::Number(number)) => match number.as_i64() {
+                Some(i) if i >= i64::from(std::i32::MIN) && i <= i64::from(std::i32::MAX) => {
+                    Value::Int(Number::from(i as i32))
+                }
+                _ => Value::Float(number.as_f64().unwrap_or_default()),
+            },
+            (_, JsonValue::Array(items)) => Value::List(items.iter().map(|item| self.coerce(item, None)).collect()),
+            (_, JsonValue::Object(fields)) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(field_name, value)| (field_name.clone(), self.coerce(value, None)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Expands the selection sets of the operation with the coerced variable values.
+struct Expansion<'a> {
+    fragments: BTreeMap<&'a str, &'a FragmentDefinition>,
+    variables: BTreeMap<String, Value>,
+}
+
+impl<'a> Expansion<'a> {
+    fn selection_set(&self, selection_set: &SelectionSet) -> SelectionSet {
+        let mut fields = vec![];
+        self.collect_fields(selection_set, &mut fields);
+
+        SelectionSet {
+            span: selection_set.span,
+            items: fields
+                .into_iter()
+                .map(|field| {
+                    let selection_set = self.selection_set(&field.selection_set);
+                    Selection::Field(Field { selection_set, ..field })
+                })
+                .collect(),
+        }
+    }
+
+    /// Collects the included fields of a selection set, with fragments spread in place.
+    /// Fields with the same response key are merged into one field.
+    fn collect_fields(&self, selection_set: &SelectionSet, fields: &mut Vec<Field>) {
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) if self.is_included(&field.directives) => {
+                    let field = Field {
+                        arguments: self.arguments(&field.arguments),
+                        directives: vec![],
+                        ..field.clone()
+                    };
+
+                    match fields.iter_mut().find(|f| response_key(f) == response_key(&field)) {
+                        Some(existing) => existing.selection_set.items.extend(field.selection_set.items),
+                        None => fields.push(field),
+                    }
+                }
+                // Fragments are validated to exist and to be on the selected type.
+                Selection::FragmentSpread(spread) if self.is_included(&spread.directives) => {
+                    if let Some(fragment) = self.fragments.get(spread.fragment_name.as_str()) {
+                        self.collect_fields(&fragment.selection_set, fields);
+                    }
+                }
+                Selection::InlineFragment(fragment) if self.is_included(&fragment.directives) => {
+                    self.collect_fields(&fragment.selection_set, fields);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn is_included(&self, directives: &[Directive]) -> bool {
+        directives.iter().all(|directive| {
+            let condition = self
+                .arguments(&directive.arguments)
+                .into_iter()
+                .find(|(name, _)| name == "if")
+                .map(|(_, value)| value);
+
+            match (directive.name.as_str(), condition) {
+                ("skip", Some(Value::Boolean(true))) => false,
+                ("include", Some(Value::Boolean(false))) => false,
+                _ => true,
+            }
+        })
+    }
+
+    fn arguments(&self, arguments: &[(String, Value)]) -> Vec<(String, Value)> {
+        arguments
+            .iter()
+            .filter_map(|(name, value)| self.substitute(value).map(|value| (name.clone(), value)))
+            .collect()
+    }
+
+    /// Replaces variables with their values, `None` for variables without a value.
+    fn substitute(&self, value: &Value) -> Option<Value> {
+        match value {
+            Value::Variable(name) => self.variables.get(name).cloned(),
+            Value::List(items) => Some(Value::List(
+                items
+                    .iter()
+                    .map(|item| self.substitute(item).unwrap_or(Value::Null))
+                    .collect(),
+            )),
+            Value::Object(fields) => Some(Value::Object(
+                fields
+                    .iter()
+                    .filter_map(|(name, value)| self.substitute(value).map(|value| (name.clone(), value)))
+                    .collect(),
+            )),
+            value => Some(value.clone()),
+        }
+    }
+}
+
+fn operation_name(operation: &OperationDefinition) -> Option<&String> {
+    match operation {
+        OperationDefinition::Query(query) => query.name.as_ref(),
+        OperationDefinition::Mutation(mutation) => mutation.name.as_ref(),
+        OperationDefinition::Subscription(subscription) => subscription.name.as_ref(),
+        OperationDefinition::SelectionSet(_) => None,
+    }
+}
+
+fn response_key(field: &Field) -> &str {
+    field.alias.as_ref().unwrap_or(&field.name)
+}
+
+fn error(message: String, location: Option<Pos>) -> ValidationError {
+    ValidationError {
+        message,
+        locations: location.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::test_utils::query_schema;
+    use serde_json::json;
+
+    fn build(
+        query: &str,
+        operation_name: Option<&str>,
+        variables: JsonValue,
+    ) -> Result<Document, Vec<ValidationError>> {
+        let schema = query_schema();
+        let document = graphql_parser::parse_query(query).unwrap();
+
+        OperationBuilder {
+            query: &document,
+            query_schema: &schema,
+            operation_name: operation_name.map(String::from),
+            variables: variables.as_object().cloned().unwrap_or_default(),
+        }
+        .build()
+    }
+
+    fn operation(document: &Document) -> &OperationDefinition {
+        match document.definitions.as_slice() {
+            [Definition::Operation(operation)] => operation,
+            definitions => panic!("Expected a single operation, got {:?}", definitions),
+        }
+    }
+
+    fn selection_set(document: &Document) -> &SelectionSet {
+        match operation(document) {
+            OperationDefinition::SelectionSet(selection_set) => selection_set,
+            OperationDefinition::Query(query) => &query.selection_set,
+            OperationDefinition::Mutation(mutation) => &mutation.selection_set,
+            OperationDefinition::Subscription(subscription) => &subscription.selection_set,
+        }
+    }
+
+    /// Renders the response keys of a prepared selection set, e.g. `users { id posts { id } }`.
+    fn render(selection_set: &SelectionSet) -> String {
+        let fields: Vec<String> = selection_set
+            .items
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field(field) if field.selection_set.items.is_empty() => response_key(field).to_owned(),
+                Selection::Field(field) => format!("{} {{ {} }}", response_key(field), render(&field.selection_set)),
+                selection => panic!("Unexpected selection {:?}", selection),
+            })
+            .collect();
+
+        fields.join(" ")
+    }
+
+    fn first_field(document: &Document) -> &Field {
+        match selection_set(document).items.first() {
+            Some(Selection::Field(field)) => field,
+            selection => panic!("Expected a field, got {:?}", selection),
+        }
+    }
+
+    #[test]
+    fn requires_an_operation_name_for_several_operations() {
+        let query = "query Users { users { id } } query Posts { posts { id } }";
+
+        assert_eq!(
+            Err(vec![error(
+                String::from("Must provide operation name if query contains multiple operations."),
+                None
+            )]),
+            build(query, None, json!({}))
+        );
+
+        let document = build(query, Some("Posts"), json!({})).unwrap();
+
+        assert_eq!(Some(&String::from("Posts")), operation_name(operation(&document)));
+        assert_eq!("posts { id }", render(selection_set(&document)));
+
+        assert_eq!(
+            Err(vec![error(String::from("Unknown operation named \"Nope\"."), None)]),
+            build(query, Some("Nope"), json!({}))
+        );
+    }
+
+    #[test]
+    fn uses_default_values_of_variables() {
+        let query = "query($first: Int = 10, $skip: Int) { users(first: $first, skip: $skip) { id } }";
+
+        let document = build(query, None, json!({})).unwrap();
+        assert_eq!(
+            vec![(String::from("first"), Value::Int(Number::from(10)))],
+            first_field(&document).arguments
+        );
+
+        let document = build(query, None, json!({ "first": 2, "skip": 1 })).unwrap();
+        assert_eq!(
+            vec![
+                (String::from("first"), Value::Int(Number::from(2))),
+                (String::from("skip"), Value::Int(Number::from(1))),
+            ],
+            first_field(&document).arguments
+        );
+
+        match operation(&document) {
+            OperationDefinition::Query(query) => assert!(query.variable_definitions.is_empty()),
+            operation => panic!("Expected a query, got {:?}", operation),
+        }
+    }
+
+    #[test]
+    fn expands_nested_fragment_spreads_with_skip() {
+        let query = r#"
+            query($withEmail: Boolean!) {
+              users { ...UserFields }
+              all: users @skip(if: true) { id }
+            }
+
+            fragment UserFields on User {
+              id
+              email @include(if: $withEmail)
+              posts { ...PostFields }
+              ...AddressFields @skip(if: true)
+            }
+
+            fragment PostFields on Post {
+              title @skip(if: true)
+              ... on Post { id }
+              ... on Post @skip(if: true) { author { id } }
+            }
+
+            fragment AddressFields on User { address { street } }
+        "#;
+
+        let document = build(query, None, json!({ "withEmail": false })).unwrap();
+        assert_eq!("users { id posts { id } }", render(selection_set(&document)));
+
+        let document = build(query, None, json!({ "withEmail": true })).unwrap();
+        assert_eq!("users { id email posts { id } }", render(selection_set(&document)));
+    }
+}
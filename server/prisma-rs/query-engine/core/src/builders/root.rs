@@ -1,18 +1,18 @@
 use super::Builder;
-use crate::{CoreResult, Query as PrismaQuery, MutationBuilder};
+use crate::{is_introspection_field, CoreResult, Query as PrismaQuery, MutationBuilder};
 use graphql_parser::query::*;
 use prisma_models::InternalDataModelRef;
 use std::sync::Arc;
 
+/// Builds the queries of a document prepared by the `OperationBuilder`,
+/// which has a single operation without fragments.
 #[derive(Debug)]
 pub struct RootBuilder {
     pub query: Document,
     pub internal_data_model: InternalDataModelRef,
-    pub operation_name: Option<String>,
 }
 
 impl RootBuilder {
-    // FIXME: Find op name and only execute op!
     pub fn build(self) -> CoreResult<Vec<PrismaQuery>> {
         self.query
             .definitions
@@ -48,6 +48,7 @@ impl RootBuilder {
     fn build_query(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<PrismaQuery>> {
         root_fields
             .iter()
+            .filter(|item| !is_introspection(item))
             .map(|item| {
                 // First query-level fields map to a model in our internal_data_model, either a plural or singular
                 match item {
@@ -62,6 +63,7 @@ impl RootBuilder {
     fn build_mutation(&self, root_fields: &Vec<Selection>) -> CoreResult<Vec<PrismaQuery>> {
         root_fields
             .iter()
+            .filter(|item| !is_introspection(item))
             .map(|item| {
                 match item {
                     Selection::Field(root_field) => MutationBuilder::new(Arc::clone(&self.internal_data_model), root_field).build().map(|q| PrismaQuery::Write(q)),
@@ -72,6 +74,14 @@ impl RootBuilder {
     }
 }
 
+/// Introspection fields are resolved from the query schema, see `IntrospectionResolver`.
+fn is_introspection(item: &Selection) -> bool {
+    match item {
+        Selection::Field(field) => is_introspection_field(field),
+        _ => false,
+    }
+}
+
 trait UuidCheck {
     fn is_uuid(&self) -> bool;
 }
@@ -2,27 +2,72 @@
 
 use graphql_parser::query::{Field, Value};
 use prisma_models::{ModelRef, PrismaValue, GraphqlId};
-use connector::filter::NodeSelector;
-use crate::CoreResult;
+use connector::filter::{CompoundNodeSelector, NodeSelector};
+use crate::{CoreError, CoreResult, RecordSelector};
 
 use std::sync::Arc;
 use std::collections::BTreeMap;
 
 /// Get node selector from field and model
 pub(crate) fn extract_node_selector(field: &Field, model: ModelRef) -> CoreResult<NodeSelector> {
+    match extract_record_selector(field, model)? {
+        RecordSelector::Single(selector) => Ok(selector),
+        RecordSelector::Compound(_) => Err(CoreError::UnsupportedFeatureError(
+            "Compound unique indexes can only be used to read single records.".into(),
+        )),
+    }
+}
+
+/// Get record selector from field and model
+///
+/// Records are either selected by a unique field, e.g. `where: { email: "a@b.c" }`,
+/// or by all fields of a compound unique index or compound id, addressed by the name of
+/// the index or the id field names joined by `_`,
+/// e.g. `where: { firstName_lastName: { firstName: "Alice", lastName: "Smith" } }`
+pub(crate) fn extract_record_selector(field: &Field, model: ModelRef) -> CoreResult<RecordSelector> {
 
     // FIXME: this expects at least one query arg...
     let (_, value) = field.arguments.first().expect("no arguments found");
     match value {
         Value::Object(obj) => {
-            let (field_name, value) = obj.iter().next().expect("object was empty");
-            let field = model.fields().find_from_scalar(field_name).unwrap();
-            let value = PrismaValue::from_value(value);
-
-            Ok(NodeSelector {
-                field: Arc::clone(&field),
-                value: value,
-            })
+            let (name, value) = obj.iter().next().expect("object was empty");
+
+            if let Ok(field) = model.fields().find_from_scalar(name) {
+                return Ok(RecordSelector::Single(NodeSelector {
+                    field: Arc::clone(&field),
+                    value: PrismaValue::from_value(value),
+                }));
+            }
+
+            let compound_fields = if model.has_compound_id() && name == &model.id_field_names.join("_") {
+                Some(model.fields().id_fields())
+            } else {
+                match model.find_unique_index(name) {
+                    Some(index) => Some(index.fields(&model)?),
+                    None => None,
+                }
+            };
+
+            match (compound_fields, value) {
+                (Some(fields), Value::Object(values)) => {
+                    let selectors = fields
+                        .into_iter()
+                        .map(|field| match values.get(&field.name) {
+                            Some(value) => Ok(NodeSelector::new(Arc::clone(&field), PrismaValue::from_value(value))),
+                            None => Err(CoreError::QueryValidationError(format!(
+                                "Missing value for field {} of unique index {}",
+                                field.name, name
+                            ))),
+                        })
+                        .collect::<CoreResult<Vec<NodeSelector>>>()?;
+
+                    Ok(RecordSelector::Compound(CompoundNodeSelector::new(selectors)))
+                }
+                _ => Err(CoreError::QueryValidationError(format!(
+                    "Neither a unique field nor a unique index named {} exists on model {}",
+                    name, model.name
+                ))),
+            }
         }
         _ => unimplemented!(),
     }
@@ -34,14 +79,25 @@ pub(crate) fn extract_node_selector(field: &Field, model: ModelRef) -> CoreResul
 pub(crate) fn derive_field(field: &Field, model: ModelRef, id: GraphqlId) -> Field {
     let mut new = field.clone();
 
-    // Remove alias and override Name
+    // Override Name, the result is still returned under the alias if there is one
     new.name = model.name.to_lowercase();
-    new.alias = None;
 
     // Create a selection set for this ID
-    let id_name = model.fields().id().name.clone();
     let mut map = BTreeMap::new();
-    map.insert(id_name, id.to_value());
+
+    let id_fields = model.fields().id_fields();
+
+    if model.has_compound_id() {
+        let values = id_fields
+            .iter()
+            .zip(id.parts())
+            .map(|(field, part)| (field.name.clone(), part.to_value()))
+            .collect();
+
+        map.insert(model.id_field_names.join("_"), Value::Object(values));
+    } else {
+        map.insert(id_fields[0].name.clone(), id.to_value());
+    }
 
     // Then override the existing arguments
     new.arguments = vec![
@@ -1,7 +1,8 @@
-use crate::{query_ast, query_results::*, CoreResult};
+use crate::{query_ast, query_results::*, CoreError, CoreResult};
 use connector::{ConnectorResult, DataResolver, ScalarListValues};
-use prisma_models::{GraphqlId, ScalarField, SelectedFields, SingleNode};
+use prisma_models::{GraphqlId, Node, PrismaValue, RelationFieldRef, ScalarField, SelectedFields, SingleNode};
 use query_ast::*;
+use serde_json::{Map as JsonMap, Value as JsonValue};
 use std::{convert::TryFrom, sync::Arc};
 
 // Todo We could eliminate the trait object with enums.
@@ -18,18 +19,32 @@ impl ReadQueryExecutor {
     fn execute_internal(&self, queries: &[ReadQuery], parent_ids: Vec<GraphqlId>) -> CoreResult<Vec<ReadQueryResult>> {
         let mut results = vec![];
 
-        for query in queries {
+        // Embedded records are resolved together with their parent, see `resolve_embedded`.
+        for query in queries.iter().filter(|query| !query.is_embedded()) {
             match query {
                 ReadQuery::RecordQuery(query) => {
                     let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
 
-                    let scalars = self
-                        .data_resolver
-                        .get_node_by_where(&query.selector, &selected_fields)?;
+                    let mut scalars = match &query.selector {
+                        RecordSelector::Single(selector) => {
+                            self.data_resolver.get_node_by_where(selector, &selected_fields)?
+                        }
+                        RecordSelector::Compound(selector) => self
+                            .data_resolver
+                            .get_node_by_compound_where(selector, &selected_fields)?,
+                    };
+
+                    if let Some(ref mut record) = scalars {
+                        Self::resolve_embedded(
+                            &query.nested,
+                            std::slice::from_mut(&mut record.node),
+                            &mut record.field_names,
+                        )?;
+                    }
 
                     match scalars {
                         Some(ref record) => {
-                            let model = Arc::clone(&query.selector.field.model());
+                            let model = query.selector.model();
                             let ids = vec![record.get_id_value(model)?.clone()];
                             let list_fields = selected_fields.scalar_lists();
                             let lists = self.resolve_scalar_list_fields(ids.clone(), list_fields)?;
@@ -50,10 +65,12 @@ impl ReadQueryExecutor {
                 }
                 ReadQuery::ManyRecordsQuery(query) => {
                     let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
-                    let scalars =
+                    let mut scalars =
                         self.data_resolver
                             .get_nodes(Arc::clone(&query.model), query.args.clone(), &selected_fields)?;
 
+                    Self::resolve_embedded(&query.nested, &mut scalars.nodes, &mut scalars.field_names)?;
+
                     let ids = scalars.get_id_values(Arc::clone(&query.model))?;
                     let list_fields = selected_fields.scalar_lists();
                     let lists = self.resolve_scalar_list_fields(ids.clone(), list_fields)?;
@@ -72,13 +89,15 @@ impl ReadQueryExecutor {
                 ReadQuery::RelatedRecordQuery(query) => {
                     let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
 
-                    let result = self.data_resolver.get_related_nodes(
+                    let mut result = self.data_resolver.get_related_nodes(
                         Arc::clone(&query.parent_field),
                         &parent_ids,
                         query.args.clone(),
                         &selected_fields,
                     )?;
 
+                    Self::resolve_embedded(&query.nested, &mut result.nodes, &mut result.field_names)?;
+
                     // If our result set contains more than one entry
                     // we need to handle all of them!
                     if result.nodes.len() > 1 {
@@ -134,13 +153,15 @@ impl ReadQueryExecutor {
                 ReadQuery::ManyRelatedRecordsQuery(query) => {
                     let selected_fields = Self::inject_required_fields(query.selected_fields.clone());
 
-                    let scalars = self.data_resolver.get_related_nodes(
+                    let mut scalars = self.data_resolver.get_related_nodes(
                         Arc::clone(&query.parent_field),
                         &parent_ids,
                         query.args.clone(),
                         &selected_fields,
                     )?;
 
+                    Self::resolve_embedded(&query.nested, &mut scalars.nodes, &mut scalars.field_names)?;
+
                     // FIXME: Rewrite to not panic and also in a more functional way!
                     let ids = scalars.get_id_values(Arc::clone(&query.parent_field.related_model()))?;
                     let list_fields = selected_fields.scalar_lists();
@@ -183,14 +204,105 @@ impl ReadQueryExecutor {
         }
     }
 
+    /// Embedded records are stored as json documents on their parent. Instead of
+    /// being queried, the documents are read from the parent records, reduced to
+    /// the selected fields and added to the parent records as an additional value,
+    /// named like the nested query.
+    fn resolve_embedded(queries: &[ReadQuery], nodes: &mut [Node], field_names: &mut Vec<String>) -> CoreResult<()> {
+        for query in queries.iter().filter(|query| query.is_embedded()) {
+            let field_name = &Self::embedded_field(query)?.name;
+
+            for node in nodes.iter_mut() {
+                let document = match node.get_field_value(field_names, field_name) {
+                    Ok(PrismaValue::Json(document)) => Self::select_embedded(document, query)?,
+                    _ => JsonValue::Null,
+                };
+
+                node.values.push(PrismaValue::Json(document));
+            }
+
+            field_names.push(query.name().to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Reduces an embedded document, or a list of them, to the fields selected by the query.
+    fn select_embedded(document: &JsonValue, query: &ReadQuery) -> CoreResult<JsonValue> {
+        let field = Self::embedded_field(query)?;
+
+        let selected = match document {
+            JsonValue::Array(documents) => JsonValue::Array(
+                documents
+                    .iter()
+                    .map(|document| Self::select_embedded(document, query))
+                    .collect::<CoreResult<Vec<JsonValue>>>()?,
+            ),
+            JsonValue::Object(values) => {
+                let mut selected = JsonMap::new();
+
+                for response_key in query.fields() {
+                    let value = match query.nested().iter().find(|nested| nested.name() == response_key.key) {
+                        Some(nested) => match values.get(&Self::embedded_field(nested)?.name) {
+                            Some(document) => Self::select_embedded(document, nested)?,
+                            None => JsonValue::Null,
+                        },
+                        None if response_key.field_name == "__typename" => {
+                            JsonValue::String(field.related_model().name.clone())
+                        }
+                        None => values.get(&response_key.field_name).cloned().unwrap_or(JsonValue::Null),
+                    };
+
+                    selected.insert(response_key.key.clone(), value);
+                }
+
+                JsonValue::Object(selected)
+            }
+            _ => JsonValue::Null,
+        };
+
+        Ok(selected)
+    }
+
+    /// The embedded field read by a nested query. Embedded records are always
+    /// read as a whole, so filtering, ordering or paginating them is rejected.
+    fn embedded_field(query: &ReadQuery) -> CoreResult<&RelationFieldRef> {
+        let (field, args) = match query {
+            ReadQuery::RelatedRecordQuery(q) => (&q.parent_field, &q.args),
+            ReadQuery::ManyRelatedRecordsQuery(q) => (&q.parent_field, &q.args),
+            _ => {
+                return Err(CoreError::QueryValidationError(format!(
+                    "`{}` is not a nested selection of an embedded field",
+                    query.name()
+                )))
+            }
+        };
+
+        let has_arguments = args.filter.is_some()
+            || args.order_by.is_some()
+            || args.is_with_pagination()
+            || args.after.is_some()
+            || args.before.is_some();
+
+        if has_arguments {
+            return Err(CoreError::QueryValidationError(format!(
+                "Arguments are not supported on embedded field `{}`, its records are always read as a whole",
+                field.name
+            )));
+        }
+
+        Ok(field)
+    }
+
     /// Injects fields required for querying, if they're not already in the selection set.
     /// Currently, required fields for every query are:
-    /// - ID field
+    /// - ID fields (more than one for compound ids)
     fn inject_required_fields(mut selected_fields: SelectedFields) -> SelectedFields {
-        let id_field = selected_fields.model().fields().id();
-        if let None = selected_fields.scalar.iter().find(|f| f.field.name == id_field.name) {
-            selected_fields.add_scalar(id_field.into(), true);
-        };
+        for id_field in selected_fields.model().fields().id_fields() {
+            if let None = selected_fields.scalar.iter().find(|f| f.field.name == id_field.name) {
+                selected_fields.add_scalar(id_field, true);
+            };
+        }
 
         selected_fields
     }
@@ -1,6 +1,6 @@
 //! Process a set of records into an IR List
 
-use super::{maps::build_map, trim_records, Item, List, Map};
+use super::{maps::build_map, take_field, trim_records, Item, List, Map};
 use crate::{ManyReadQueryResults, ReadQueryResult};
 use prisma_models::{GraphqlId, PrismaValue};
 use std::{
@@ -148,6 +148,7 @@ pub fn build_list(mut result: ManyReadQueryResults) -> List {
 
     let model = Arc::clone(&result.selected_fields.model());
     let final_field_order = result.fields.clone();
+    let selected_fields = result.selected_fields.clone();
 
     // There is always at least one scalar selected (id), making scalars the perfect entry point.
     result
@@ -206,8 +207,9 @@ pub fn build_list(mut result: ManyReadQueryResults) -> List {
             Item::Map(
                 record.parent_id,
                 final_field_order.iter().fold(Map::new(), |mut new, field| {
-                    let item = base_map.remove(field).expect("Missing field for serialization.");
-                    new.insert(field.clone(), item);
+                    let item =
+                        take_field(&mut base_map, field, &selected_fields).expect("Missing field for serialization.");
+                    new.insert(field.key.clone(), item);
                     new
                 }),
             )
@@ -1,6 +1,6 @@
 //! Process a record into an IR Map
 
-use super::{lists::build_list, trim_records, Item, Map};
+use super::{lists::build_list, take_field, trim_records, Item, Map};
 use crate::{ReadQueryResult, SingleReadQueryResult};
 use prisma_models::PrismaValue;
 
@@ -55,12 +55,14 @@ pub fn build_map(result: SingleReadQueryResult) -> Option<Map> {
         );
     });
 
+    let selected_fields = &result.selected_fields;
+
     // Re-order fields to be in-line with what the query specified
     // This also removes implicit fields
     Some(result.fields.iter().fold(Map::new(), |mut map, field| {
         map.insert(
-            field.clone(),
-            outer.remove(field).expect("[Map]: Missing required field"),
+            field.key.clone(),
+            take_field(&mut outer, field, selected_fields).expect("[Map]: Missing required field"),
         );
         map
     }))
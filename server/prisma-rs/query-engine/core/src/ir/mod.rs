@@ -11,11 +11,12 @@
 mod lists;
 mod maps;
 
-use crate::ReadQueryResult;
+use crate::{ReadQueryResult, ResponseKey};
 use connector::QueryArguments;
 use indexmap::IndexMap;
 use prisma_models::GraphqlId;
 use prisma_models::PrismaValue;
+use prisma_models::SelectedFields;
 
 /// A response set maps to a Vec<PrismaQueryResponse>
 /// where each represents the result of a query
@@ -37,7 +38,7 @@ pub type Map = IndexMap<String, Item>;
 pub type List = Vec<Item>;
 
 /// An IR item that either expands to a subtype or leaf-record
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Item {
     /// (Parent ID, transformed record as map)
     Map(Option<GraphqlId>, Map),
@@ -86,6 +87,21 @@ impl Builder {
     }
 }
 
+/// Takes the item of a selected field from a record map. Scalars are stored by
+/// field name, as they can be selected more than once under different aliases,
+/// relations are stored by response key.
+fn take_field(record: &mut Map, field: &ResponseKey, selected_fields: &SelectedFields) -> Option<Item> {
+    if field.field_name == "__typename" {
+        return Some(Item::Value(PrismaValue::String(selected_fields.model().name.clone())));
+    }
+
+    if selected_fields.scalar.iter().any(|sf| sf.field.name == field.field_name) {
+        record.get(&field.field_name).cloned()
+    } else {
+        record.remove(&field.key)
+    }
+}
+
 /// Removes the excess records added to by the database query layer based on the query arguments
 /// This would be the right place to add pagination markers (has next page, etc.).
 pub fn trim_records(data: &mut Vec<Item>, query_args: &QueryArguments) {
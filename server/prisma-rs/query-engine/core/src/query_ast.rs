@@ -1,6 +1,9 @@
 //! Prisma read query AST module
 
-use connector::{filter::NodeSelector, QueryArguments};
+use connector::{
+    filter::{CompoundNodeSelector, NodeSelector},
+    QueryArguments,
+};
 use prisma_models::prelude::*;
 
 #[derive(Debug, Clone)]
@@ -11,13 +14,81 @@ pub enum ReadQuery {
     ManyRelatedRecordsQuery(ManyRelatedRecordsQuery),
 }
 
+impl ReadQuery {
+    pub fn name(&self) -> &str {
+        match self {
+            ReadQuery::RecordQuery(q) => &q.name,
+            ReadQuery::ManyRecordsQuery(q) => &q.name,
+            ReadQuery::RelatedRecordQuery(q) => &q.name,
+            ReadQuery::ManyRelatedRecordsQuery(q) => &q.name,
+        }
+    }
+
+    /// The relation field of the parent, only present for nested queries.
+    pub fn parent_field(&self) -> Option<&RelationFieldRef> {
+        match self {
+            ReadQuery::RelatedRecordQuery(q) => Some(&q.parent_field),
+            ReadQuery::ManyRelatedRecordsQuery(q) => Some(&q.parent_field),
+            _ => None,
+        }
+    }
+
+    /// The selected fields in the order of the query.
+    pub fn fields(&self) -> &[ResponseKey] {
+        match self {
+            ReadQuery::RecordQuery(q) => &q.fields,
+            ReadQuery::ManyRecordsQuery(q) => &q.fields,
+            ReadQuery::RelatedRecordQuery(q) => &q.fields,
+            ReadQuery::ManyRelatedRecordsQuery(q) => &q.fields,
+        }
+    }
+
+    pub fn nested(&self) -> &[ReadQuery] {
+        match self {
+            ReadQuery::RecordQuery(q) => &q.nested,
+            ReadQuery::ManyRecordsQuery(q) => &q.nested,
+            ReadQuery::RelatedRecordQuery(q) => &q.nested,
+            ReadQuery::ManyRelatedRecordsQuery(q) => &q.nested,
+        }
+    }
+
+    /// Nested queries of embedded records are not executed against the database,
+    /// the records are read from the document stored on the parent.
+    pub fn is_embedded(&self) -> bool {
+        self.parent_field().map_or(false, |field| field.is_embedded())
+    }
+}
+
+/// Selects a single record, either by a unique field or by a compound unique index
+#[derive(Debug, Clone)]
+pub enum RecordSelector {
+    Single(NodeSelector),
+    Compound(CompoundNodeSelector),
+}
+
+impl RecordSelector {
+    pub fn model(&self) -> ModelRef {
+        match self {
+            RecordSelector::Single(selector) => selector.field.model(),
+            RecordSelector::Compound(selector) => selector.model(),
+        }
+    }
+}
+
+/// A selected field, returned under its alias if it has one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseKey {
+    pub key: String,
+    pub field_name: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct RecordQuery {
     pub name: String,
-    pub selector: NodeSelector,
+    pub selector: RecordSelector,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub fields: Vec<String>,
+    pub fields: Vec<ResponseKey>,
 }
 
 #[derive(Debug, Clone)]
@@ -27,7 +98,7 @@ pub struct ManyRecordsQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub fields: Vec<String>,
+    pub fields: Vec<ResponseKey>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,7 +108,7 @@ pub struct RelatedRecordQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub fields: Vec<String>,
+    pub fields: Vec<ResponseKey>,
 }
 
 #[derive(Debug, Clone)]
@@ -47,5 +118,5 @@ pub struct ManyRelatedRecordsQuery {
     pub args: QueryArguments,
     pub selected_fields: SelectedFields,
     pub nested: Vec<ReadQuery>,
-    pub fields: Vec<String>,
+    pub fields: Vec<ResponseKey>,
 }
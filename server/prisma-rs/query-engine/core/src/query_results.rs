@@ -1,3 +1,4 @@
+use crate::ResponseKey;
 use connector::{QueryArguments, ScalarListValues};
 use prisma_models::{GraphqlId, ManyNodes, PrismaValue, SelectedFields, SelectedScalarField, SingleNode};
 
@@ -19,7 +20,7 @@ impl ReadQueryResult {
 #[derive(Debug)]
 pub struct SingleReadQueryResult {
     pub name: String,
-    pub fields: Vec<String>,
+    pub fields: Vec<ResponseKey>,
 
     /// Scalar field results
     pub scalars: Option<SingleNode>,
@@ -37,7 +38,7 @@ pub struct SingleReadQueryResult {
 #[derive(Debug)]
 pub struct ManyReadQueryResults {
     pub name: String,
-    pub fields: Vec<String>,
+    pub fields: Vec<ResponseKey>,
 
     /// Scalar field results
     pub scalars: ManyNodes,
@@ -90,7 +91,7 @@ impl SingleReadQueryResult {
 impl ManyReadQueryResults {
     pub fn new(
         name: String,
-        fields: Vec<String>,
+        fields: Vec<ResponseKey>,
         scalars: ManyNodes,
         nested: Vec<ReadQueryResult>,
         lists: Vec<(String, Vec<ScalarListValues>)>,
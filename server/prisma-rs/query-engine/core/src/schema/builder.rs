@@ -1,13 +1,1005 @@
-use prisma_inflector;
-use prisma_models::InternalDataModelRef;
+use super::*;
+use crate::Inflector;
+use indexmap::IndexMap;
+use prisma_models::{
+    Field as ModelField, InternalDataModelRef, ModelRef, RelationFieldRef, ScalarFieldRef, TypeIdentifier,
+};
+use rust_inflector::Inflector as RustInflector;
 
-pub struct SchemaBuilder;
+/// Builds the GraphQL schema of an internal data model.
+///
+/// Root fields and arguments are named the way the query builders parse them,
+/// e.g. `user` and `users` for reading records of a model `User`, `createUser`
+/// for creating one, or `where: { name_starts_with: "A" }` for filtering.
+pub struct SchemaBuilder {
+    internal_data_model: InternalDataModelRef,
+    input_types: IndexMap<String, InputObjectType>,
+}
 
 impl SchemaBuilder {
-    pub fn build(data_model: InternalDataModelRef) {
-        data_model.models().into_iter().for_each(|m| {
-            let candidate = prisma_inflector::default().pluralize(&m.name);
-            println!("{} -> {:?}", &m.name, candidate);
+    pub fn build(internal_data_model: InternalDataModelRef) -> QuerySchema {
+        let mut builder = SchemaBuilder {
+            internal_data_model,
+            input_types: IndexMap::new(),
+        };
+
+        let query = builder.query_type();
+        let mutation = builder.mutation_type();
+        let object_types = builder.object_types();
+        let enum_types = builder.enum_types();
+
+        QuerySchema {
+            query,
+            mutation,
+            object_types,
+            input_types: builder.input_types.into_iter().map(|(_, t)| t).collect(),
+            enum_types,
+        }
+    }
+
+    fn models(&self) -> Vec<ModelRef> {
+        self.internal_data_model.models().to_vec()
+    }
+
+    /// Embedded models are only reachable through the fields of their parents.
+    fn root_models(&self) -> Vec<ModelRef> {
+        self.models().into_iter().filter(|m| !m.is_embedded).collect()
+    }
+
+    fn query_type(&mut self) -> ObjectType {
+        let mut fields = Vec::new();
+
+        for model in self.root_models() {
+            let where_unique = self.find_one_where_input(&model);
+            let many_arguments = self.many_arguments(&model);
+
+            fields.push(OutputField {
+                name: model.name.to_camel_case(),
+                arguments: vec![argument("where", InputType::object(where_unique))],
+                field_type: OutputType::opt(OutputType::object(model.name.as_str())),
+            });
+
+            fields.push(OutputField {
+                name: plural(&model),
+                arguments: many_arguments.clone(),
+                field_type: OutputType::list(OutputType::opt(OutputType::object(model.name.as_str()))),
+            });
+
+            fields.push(OutputField {
+                name: format!("{}Connection", plural(&model)),
+                arguments: many_arguments,
+                field_type: OutputType::object(format!("{}Connection", model.name)),
+            });
+        }
+
+        ObjectType {
+            name: String::from("Query"),
+            fields,
+        }
+    }
+
+    fn mutation_type(&mut self) -> ObjectType {
+        let mut fields = Vec::new();
+
+        for model in self.root_models() {
+            let record = || OutputType::object(model.name.as_str());
+            let where_unique = self.where_unique_input(&model).map(InputType::object);
+            let where_input = InputType::opt(InputType::object(self.where_input(&model)));
+            let create = self.create_input(&model, None);
+            let update = self.update_input(&model, None);
+            let update_many = self.update_many_input(&model);
+
+            fields.push(OutputField {
+                name: format!("create{}", model.name),
+                arguments: create
+                    .iter()
+                    .map(|create| argument("data", InputType::object(create.as_str())))
+                    .collect(),
+                field_type: record(),
+            });
+
+            if let (Some(update), Some(where_unique)) = (&update, &where_unique) {
+                fields.push(OutputField {
+                    name: format!("update{}", model.name),
+                    arguments: vec![
+                        argument("data", InputType::object(update.as_str())),
+                        argument("where", where_unique.clone()),
+                    ],
+                    field_type: OutputType::opt(record()),
+                });
+            }
+
+            if let (Some(create), Some(update), Some(where_unique)) = (&create, &update, &where_unique) {
+                fields.push(OutputField {
+                    name: format!("upsert{}", model.name),
+                    arguments: vec![
+                        argument("where", where_unique.clone()),
+                        argument("create", InputType::object(create.as_str())),
+                        argument("update", InputType::object(update.as_str())),
+                    ],
+                    field_type: record(),
+                });
+            }
+
+            if let Some(where_unique) = where_unique {
+                fields.push(OutputField {
+                    name: format!("delete{}", model.name),
+                    arguments: vec![argument("where", where_unique)],
+                    field_type: OutputType::opt(record()),
+                });
+            }
+
+            if let Some(update_many) = update_many {
+                fields.push(OutputField {
+                    name: format!("updateMany{}", plural(&model).to_pascal_case()),
+                    arguments: vec![
+                        argument("data", InputType::object(update_many)),
+                        argument("where", where_input.clone()),
+                    ],
+                    field_type: OutputType::object("BatchPayload"),
+                });
+            }
+
+            fields.push(OutputField {
+                name: format!("deleteMany{}", plural(&model).to_pascal_case()),
+                arguments: vec![argument("where", where_input)],
+                field_type: OutputType::object("BatchPayload"),
+            });
+        }
+
+        ObjectType {
+            name: String::from("Mutation"),
+            fields,
+        }
+    }
+
+    fn object_types(&mut self) -> Vec<ObjectType> {
+        let mut types = Vec::new();
+
+        for model in self.models() {
+            let fields = visible_fields(&model)
+                .into_iter()
+                .map(|field| match field {
+                    ModelField::Scalar(sf) => output_field(&sf.name, scalar_output_type(&sf)),
+                    ModelField::Relation(rf) => {
+                        let related = OutputType::object(rf.related_model().name.as_str());
+
+                        // Lists of embedded records are always read as a whole.
+                        if rf.is_list && !rf.is_embedded() {
+                            OutputField {
+                                name: rf.name.clone(),
+                                arguments: self.many_arguments(&rf.related_model()),
+                                field_type: OutputType::opt(OutputType::list(related)),
+                            }
+                        } else if rf.is_list {
+                            output_field(&rf.name, OutputType::opt(OutputType::list(related)))
+                        } else if rf.is_required {
+                            output_field(&rf.name, related)
+                        } else {
+                            output_field(&rf.name, OutputType::opt(related))
+                        }
+                    }
+                })
+                .collect();
+
+            types.push(ObjectType {
+                name: model.name.clone(),
+                fields,
+            });
+        }
+
+        for model in self.root_models() {
+            types.push(ObjectType {
+                name: format!("{}Connection", model.name),
+                fields: vec![
+                    output_field("pageInfo", OutputType::object("PageInfo")),
+                    output_field(
+                        "edges",
+                        OutputType::list(OutputType::opt(OutputType::object(format!("{}Edge", model.name)))),
+                    ),
+                    output_field("aggregate", OutputType::object(format!("Aggregate{}", model.name))),
+                ],
+            });
+
+            types.push(ObjectType {
+                name: format!("{}Edge", model.name),
+                fields: vec![
+                    output_field("node", OutputType::object(model.name.as_str())),
+                    output_field("cursor", OutputType::Scalar(ScalarType::String)),
+                ],
+            });
+
+            types.push(ObjectType {
+                name: format!("Aggregate{}", model.name),
+                fields: vec![output_field("count", OutputType::Scalar(ScalarType::Int))],
+            });
+        }
+
+        types.push(ObjectType {
+            name: String::from("PageInfo"),
+            fields: vec![
+                output_field("hasNextPage", OutputType::Scalar(ScalarType::Boolean)),
+                output_field("hasPreviousPage", OutputType::Scalar(ScalarType::Boolean)),
+                output_field("startCursor", OutputType::opt(OutputType::Scalar(ScalarType::String))),
+                output_field("endCursor", OutputType::opt(OutputType::Scalar(ScalarType::String))),
+            ],
+        });
+
+        types.push(ObjectType {
+            name: String::from("BatchPayload"),
+            fields: vec![output_field("count", OutputType::Scalar(ScalarType::Long))],
         });
+
+        types
+    }
+
+    fn enum_types(&self) -> Vec<EnumType> {
+        let mut types: Vec<EnumType> = self
+            .internal_data_model
+            .enums
+            .iter()
+            .map(|en| EnumType {
+                name: en.name.clone(),
+                values: en.values.clone(),
+            })
+            .collect();
+
+        for model in self.root_models() {
+            let values: Vec<String> = orderable_fields(&model)
+                .iter()
+                .flat_map(|sf| vec![format!("{}_ASC", sf.name), format!("{}_DESC", sf.name)])
+                .collect();
+
+            if !values.is_empty() {
+                types.push(EnumType {
+                    name: order_by_enum(&model),
+                    values,
+                });
+            }
+        }
+
+        types
+    }
+
+    /// The arguments of fields returning many records, e.g. `users` or `User.posts`.
+    fn many_arguments(&mut self, model: &ModelRef) -> Vec<Argument> {
+        let where_input = InputType::object(self.where_input(model));
+        let int = || InputType::opt(InputType::Scalar(ScalarType::Int));
+        let string = || InputType::opt(InputType::Scalar(ScalarType::String));
+        let mut arguments = vec![argument("where", InputType::opt(where_input))];
+
+        if !orderable_fields(model).is_empty() {
+            let order_by = InputType::Enum(order_by_enum(model));
+            arguments.push(argument("orderBy", InputType::opt(order_by)));
+        }
+
+        arguments.push(argument("skip", int()));
+        arguments.push(argument("after", string()));
+        arguments.push(argument("before", string()));
+        arguments.push(argument("first", int()));
+        arguments.push(argument("last", int()));
+        arguments
+    }
+
+    /// Registers an input type under the given name, unless it already exists.
+    /// Types are registered before their fields are built, so recursive
+    /// references resolve to the type in construction. Types without fields are
+    /// not valid GraphQL and are dropped.
+    fn register<F>(&mut self, name: String, build_fields: F) -> Option<String>
+    where
+        F: FnOnce(&mut Self) -> Vec<InputField>,
+    {
+        if self.input_types.contains_key(&name) {
+            return Some(name);
+        }
+
+        self.input_types.insert(
+            name.clone(),
+            InputObjectType {
+                name: name.clone(),
+                fields: vec![],
+            },
+        );
+
+        let fields = build_fields(self);
+
+        if fields.is_empty() {
+            self.input_types.swap_remove(&name);
+            None
+        } else {
+            self.input_types.get_mut(&name).unwrap().fields = fields;
+            Some(name)
+        }
+    }
+
+    fn where_input(&mut self, model: &ModelRef) -> String {
+        let name = format!("{}WhereInput", model.name);
+        let nested = || InputType::opt(InputType::list(InputType::object(format!("{}WhereInput", model.name))));
+
+        self.register(name, |builder| {
+            let mut fields = Vec::new();
+
+            for field in visible_fields(model) {
+                match field {
+                    ModelField::Scalar(ref sf) if sf.is_list => {}
+                    ModelField::Scalar(sf) => {
+                        let tpe = scalar_input_type(&sf);
+
+                        for suffix in filter_suffixes(sf.type_identifier) {
+                            let field_type = match *suffix {
+                                "_in" | "_not_in" => InputType::list(tpe.clone()),
+                                _ => tpe.clone(),
+                            };
+
+                            fields.push(input_field(
+                                format!("{}{}", sf.name, suffix),
+                                InputType::opt(field_type),
+                            ));
+                        }
+                    }
+                    ModelField::Relation(rf) => {
+                        let related = InputType::opt(InputType::object(builder.where_input(&rf.related_model())));
+
+                        if rf.is_list {
+                            for suffix in &["_every", "_some", "_none"] {
+                                fields.push(input_field(format!("{}{}", rf.name, suffix), related.clone()));
+                            }
+                        } else {
+                            fields.push(input_field(rf.name.clone(), related));
+                        }
+                    }
+                }
+            }
+
+            fields.push(input_field("AND", nested()));
+            fields.push(input_field("OR", nested()));
+            fields.push(input_field("NOT", nested()));
+            fields
+        })
+        .unwrap()
+    }
+
+    /// The unique fields of the model. Mutations select records by a single
+    /// unique field only, see `utils::extract_node_selector`. Models with only a
+    /// compound id have no such input.
+    fn where_unique_input(&mut self, model: &ModelRef) -> Option<String> {
+        let name = format!("{}WhereUniqueInput", model.name);
+
+        self.register(name, |_| unique_fields(model))
+    }
+
+    /// Unique fields and compound uniques, see `utils::extract_record_selector`.
+    fn find_one_where_input(&mut self, model: &ModelRef) -> String {
+        let name = format!("{}WhereUniqueQueryInput", model.name);
+
+        self.register(name, |builder| {
+            let mut fields = unique_fields(model);
+
+            let mut compounds: Vec<(String, Vec<String>)> = model
+                .unique_indexes()
+                .into_iter()
+                .map(|index| (index.name(), index.field_names.clone()))
+                .collect();
+
+            if model.has_compound_id() {
+                compounds.insert(0, (model.id_field_names.join("_"), model.id_field_names.clone()));
+            }
+
+            for (compound_name, field_names) in compounds {
+                if fields.iter().any(|f| f.name == compound_name) {
+                    continue;
+                }
+
+                let compound_type = builder.register(
+                    format!("{}{}CompoundUniqueInput", model.name, compound_name.to_pascal_case()),
+                    |_| {
+                        field_names
+                            .iter()
+                            .filter_map(|name| model.fields().find_from_scalar(name).ok())
+                            .map(|sf| input_field(sf.name.clone(), scalar_input_type(&sf)))
+                            .collect()
+                    },
+                );
+
+                if let Some(compound_type) = compound_type {
+                    fields.push(input_field(
+                        compound_name,
+                        InputType::opt(InputType::object(compound_type)),
+                    ));
+                }
+            }
+
+            fields
+        })
+        .unwrap()
+    }
+
+    /// The input to create a record of the model. Nested creates from the other
+    /// side of a relation leave out the field pointing back, e.g.
+    /// `PostCreateWithoutAuthorInput` for `author { create: { posts: { create: ... } } }`.
+    fn create_input(&mut self, model: &ModelRef, without: Option<&RelationFieldRef>) -> Option<String> {
+        let name = match without {
+            Some(rf) => format!("{}CreateWithout{}Input", model.name, rf.name.to_pascal_case()),
+            None => format!("{}CreateInput", model.name),
+        };
+
+        self.register(name, |builder| {
+            let mut fields = Vec::new();
+
+            for field in visible_fields(model) {
+                match field {
+                    ModelField::Scalar(sf) => {
+                        if sf.is_id() && !sf.is_auto_generated {
+                            fields.push(input_field(sf.name.clone(), InputType::opt(scalar_input_type(&sf))));
+                        } else if sf.is_writable() {
+                            fields.push(builder.scalar_write_field(model, &sf, "Create"));
+                        }
+                    }
+                    ModelField::Relation(ref rf) if is_back_relation(rf, without) => {}
+                    ModelField::Relation(rf) => {
+                        if let Some(nested) = builder.nested_create_input(&rf) {
+                            let nested = InputType::object(nested);
+                            let field_type = if rf.is_required && !rf.is_list {
+                                nested
+                            } else {
+                                InputType::opt(nested)
+                            };
+
+                            fields.push(input_field(rf.name.clone(), field_type));
+                        }
+                    }
+                }
+            }
+
+            fields
+        })
+    }
+
+    /// The input to update a record of the model, see `create_input`.
+    fn update_input(&mut self, model: &ModelRef, without: Option<&RelationFieldRef>) -> Option<String> {
+        let name = match without {
+            Some(rf) => format!("{}UpdateWithout{}DataInput", model.name, rf.name.to_pascal_case()),
+            None => format!("{}UpdateInput", model.name),
+        };
+
+        self.register(name, |builder| {
+            let mut fields = Vec::new();
+
+            for field in visible_fields(model) {
+                match field {
+                    ModelField::Scalar(sf) => {
+                        if sf.is_writable() {
+                            let field = builder.scalar_write_field(model, &sf, "Update");
+                            fields.push(input_field(field.name, optional(field.field_type)));
+                        }
+                    }
+                    ModelField::Relation(ref rf) if is_back_relation(rf, without) => {}
+                    ModelField::Relation(rf) => {
+                        if let Some(nested) = builder.nested_update_input(&rf) {
+                            fields.push(input_field(rf.name.clone(), InputType::opt(InputType::object(nested))));
+                        }
+                    }
+                }
+            }
+
+            fields
+        })
+    }
+
+    /// The scalar fields of the model, as updated by `updateMany`.
+    fn update_many_input(&mut self, model: &ModelRef) -> Option<String> {
+        self.register(format!("{}UpdateManyMutationInput", model.name), |builder| {
+            model
+                .fields()
+                .scalar()
+                .into_iter()
+                .filter(|sf| !sf.is_hidden && sf.is_writable())
+                .map(|sf| {
+                    let field = builder.scalar_write_field(model, &sf, "Update");
+                    input_field(field.name, optional(field.field_type))
+                })
+                .collect()
+        })
+    }
+
+    /// Scalar lists are written via `{ set: [...] }`.
+    fn scalar_write_field(&mut self, model: &ModelRef, sf: &ScalarFieldRef, operation: &str) -> InputField {
+        if sf.is_list {
+            let set_type = self
+                .register(
+                    format!("{}{}{}Input", model.name, operation, sf.name.to_pascal_case()),
+                    |_| vec![input_field("set", InputType::opt(scalar_input_type(sf)))],
+                )
+                .unwrap();
+
+            input_field(sf.name.clone(), InputType::opt(InputType::object(set_type)))
+        } else if sf.is_required {
+            input_field(sf.name.clone(), scalar_input_type(sf))
+        } else {
+            input_field(sf.name.clone(), InputType::opt(scalar_input_type(sf)))
+        }
+    }
+
+    fn nested_create_input(&mut self, rf: &RelationFieldRef) -> Option<String> {
+        let related_model = rf.related_model();
+        let arity = if rf.is_list { "Many" } else { "One" };
+        let wrap = |tpe: InputType| {
+            if rf.is_list {
+                InputType::opt(InputType::list(tpe))
+            } else {
+                InputType::opt(tpe)
+            }
+        };
+
+        // Embedded records are only ever created along with their parent.
+        if rf.is_embedded() {
+            let create = self.create_input(&related_model, None)?;
+
+            return self.register(format!("{}Create{}Input", related_model.name, arity), |_| {
+                vec![input_field("create", wrap(InputType::object(create)))]
+            });
+        }
+
+        let back = back_relation(rf);
+        let name = match back {
+            Some(ref back) => format!(
+                "{}Create{}Without{}Input",
+                related_model.name,
+                arity,
+                back.name.to_pascal_case()
+            ),
+            None => format!("{}Create{}Input", related_model.name, arity),
+        };
+
+        self.register(name, |builder| {
+            let mut fields = Vec::new();
+
+            if let Some(create) = builder.create_input(&related_model, back.as_ref()) {
+                fields.push(input_field("create", wrap(InputType::object(create))));
+            }
+
+            if let Some(where_unique) = builder.where_unique_input(&related_model) {
+                fields.push(input_field("connect", wrap(InputType::object(where_unique))));
+            }
+
+            fields
+        })
+    }
+
+    fn nested_update_input(&mut self, rf: &RelationFieldRef) -> Option<String> {
+        let related_model = rf.related_model();
+
+        if rf.is_embedded() {
+            let arity = if rf.is_list { "Many" } else { "One" };
+            let create = self.create_input(&related_model, None);
+            let update = self.update_input(&related_model, None);
+
+            return self.register(format!("{}Update{}Input", related_model.name, arity), |_| {
+                let mut fields = Vec::new();
+
+                if let Some(create) = create {
+                    let create = InputType::object(create);
+                    let create = if rf.is_list { InputType::list(create) } else { create };
+                    fields.push(input_field("create", InputType::opt(create)));
+                }
+
+                if let Some(update) = update {
+                    fields.push(input_field("update", InputType::opt(InputType::object(update))));
+                }
+
+                fields.push(input_field(
+                    "delete",
+                    InputType::opt(InputType::Scalar(ScalarType::Boolean)),
+                ));
+                fields
+            });
+        }
+
+        let back = back_relation(rf);
+        let without = |prefix: &str, suffix: &str| match back {
+            Some(ref back) => format!(
+                "{}{}Without{}{}",
+                related_model.name,
+                prefix,
+                back.name.to_pascal_case(),
+                suffix
+            ),
+            None => format!("{}{}{}", related_model.name, prefix, suffix),
+        };
+
+        let name = if rf.is_list {
+            without("UpdateMany", "Input")
+        } else if rf.is_required {
+            without("UpdateOneRequired", "Input")
+        } else {
+            without("UpdateOne", "Input")
+        };
+
+        self.register(name, |builder| {
+            let mut fields = Vec::new();
+            let create = builder.create_input(&related_model, back.as_ref());
+            let update = builder.update_input(&related_model, back.as_ref());
+            let where_unique = builder.where_unique_input(&related_model).map(InputType::object);
+
+            if rf.is_list {
+                let list = |tpe: InputType| InputType::opt(InputType::list(tpe));
+
+                if let Some(ref create) = create {
+                    fields.push(input_field("create", list(InputType::object(create.as_str()))));
+                }
+
+                // Related records are only addressed by a unique field.
+                let where_unique = match where_unique {
+                    Some(where_unique) => where_unique,
+                    None => return fields,
+                };
+
+                fields.push(input_field("connect", list(where_unique.clone())));
+                fields.push(input_field("set", list(where_unique.clone())));
+                fields.push(input_field("disconnect", list(where_unique.clone())));
+                fields.push(input_field("delete", list(where_unique.clone())));
+
+                if let Some(ref update) = update {
+                    let update_with_where = builder.register(without("UpdateWithWhereUnique", "Input"), |_| {
+                        vec![
+                            input_field("where", where_unique.clone()),
+                            input_field("data", InputType::object(update.as_str())),
+                        ]
+                    });
+
+                    if let Some(update_with_where) = update_with_where {
+                        fields.push(input_field("update", list(InputType::object(update_with_where))));
+                    }
+                }
+
+                if let (Some(create), Some(update)) = (&create, &update) {
+                    let upsert_with_where = builder.register(without("UpsertWithWhereUnique", "Input"), |_| {
+                        vec![
+                            input_field("where", where_unique.clone()),
+                            input_field("update", InputType::object(update.as_str())),
+                            input_field("create", InputType::object(create.as_str())),
+                        ]
+                    });
+
+                    if let Some(upsert_with_where) = upsert_with_where {
+                        fields.push(input_field("upsert", list(InputType::object(upsert_with_where))));
+                    }
+                }
+            } else {
+                if let Some(ref create) = create {
+                    fields.push(input_field(
+                        "create",
+                        InputType::opt(InputType::object(create.as_str())),
+                    ));
+                }
+
+                if let Some(where_unique) = where_unique {
+                    fields.push(input_field("connect", InputType::opt(where_unique)));
+                }
+
+                if !rf.is_required {
+                    fields.push(input_field(
+                        "disconnect",
+                        InputType::opt(InputType::Scalar(ScalarType::Boolean)),
+                    ));
+                    fields.push(input_field(
+                        "delete",
+                        InputType::opt(InputType::Scalar(ScalarType::Boolean)),
+                    ));
+                }
+
+                if let Some(ref update) = update {
+                    fields.push(input_field(
+                        "update",
+                        InputType::opt(InputType::object(update.as_str())),
+                    ));
+                }
+
+                if let (Some(create), Some(update)) = (&create, &update) {
+                    let upsert = builder.register(without("Upsert", "Input"), |_| {
+                        vec![
+                            input_field("update", InputType::object(update.as_str())),
+                            input_field("create", InputType::object(create.as_str())),
+                        ]
+                    });
+
+                    if let Some(upsert) = upsert {
+                        fields.push(input_field("upsert", InputType::opt(InputType::object(upsert))));
+                    }
+                }
+            }
+
+            fields
+        })
+    }
+}
+
+fn plural(model: &ModelRef) -> String {
+    Inflector::pluralize(&model.name)
+}
+
+fn order_by_enum(model: &ModelRef) -> String {
+    format!("{}OrderByInput", model.name)
+}
+
+pub(super) fn argument(name: &str, argument_type: InputType) -> Argument {
+    Argument {
+        name: name.to_owned(),
+        argument_type,
+    }
+}
+
+fn input_field(name: impl Into<String>, field_type: InputType) -> InputField {
+    InputField {
+        name: name.into(),
+        field_type,
+    }
+}
+
+pub(super) fn output_field(name: &str, field_type: OutputType) -> OutputField {
+    OutputField {
+        name: name.to_owned(),
+        arguments: vec![],
+        field_type,
+    }
+}
+
+fn optional(tpe: InputType) -> InputType {
+    if tpe.is_required() {
+        InputType::opt(tpe)
+    } else {
+        tpe
+    }
+}
+
+/// The fields of the model in declaration order, without hidden fields.
+fn visible_fields(model: &ModelRef) -> Vec<ModelField> {
+    model
+        .fields()
+        .all
+        .iter()
+        .filter_map(|field| match field {
+            ModelField::Scalar(sf) if !sf.is_hidden => Some(ModelField::Scalar(sf.clone())),
+            ModelField::Relation(rf) if !rf.is_hidden => Some(ModelField::Relation(rf.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The visible unique scalar fields of the model, including a single id field.
+fn unique_fields(model: &ModelRef) -> Vec<InputField> {
+    model
+        .fields()
+        .scalar_non_list()
+        .into_iter()
+        .filter(|sf| !sf.is_hidden && (sf.is_unique || sf.is_id()))
+        .map(|sf| input_field(sf.name.clone(), InputType::opt(scalar_input_type(&sf))))
+        .collect()
+}
+
+fn orderable_fields(model: &ModelRef) -> Vec<ScalarFieldRef> {
+    model
+        .fields()
+        .scalar_non_list()
+        .into_iter()
+        .filter(|sf| !sf.is_hidden && !filter_suffixes(sf.type_identifier).is_empty())
+        .collect()
+}
+
+/// The field on the related model pointing back, unless it is hidden.
+fn back_relation(rf: &RelationFieldRef) -> Option<RelationFieldRef> {
+    Some(rf.related_field()).filter(|back| !back.is_hidden)
+}
+
+fn is_back_relation(rf: &RelationFieldRef, without: Option<&RelationFieldRef>) -> bool {
+    without.map(|without| without.name == rf.name).unwrap_or(false)
+}
+
+/// The filters of `builders::filters` available for a type.
+fn filter_suffixes(type_identifier: TypeIdentifier) -> &'static [&'static str] {
+    match type_identifier {
+        TypeIdentifier::String | TypeIdentifier::GraphQLID | TypeIdentifier::UUID => &[
+            "",
+            "_not",
+            "_in",
+            "_not_in",
+            "_lt",
+            "_lte",
+            "_gt",
+            "_gte",
+            "_contains",
+            "_not_contains",
+            "_starts_with",
+            "_not_starts_with",
+            "_ends_with",
+            "_not_ends_with",
+        ],
+        TypeIdentifier::Int | TypeIdentifier::BigInt | TypeIdentifier::Float | TypeIdentifier::DateTime => {
+            &["", "_not", "_in", "_not_in", "_lt", "_lte", "_gt", "_gte"]
+        }
+        TypeIdentifier::Enum => &["", "_not", "_in", "_not_in"],
+        TypeIdentifier::Boolean => &["", "_not"],
+        TypeIdentifier::Json | TypeIdentifier::Bytes | TypeIdentifier::Relation => &[],
+    }
+}
+
+fn scalar_type(type_identifier: TypeIdentifier) -> ScalarType {
+    match type_identifier {
+        TypeIdentifier::Float => ScalarType::Float,
+        TypeIdentifier::Boolean => ScalarType::Boolean,
+        TypeIdentifier::Json => ScalarType::Json,
+        TypeIdentifier::DateTime => ScalarType::DateTime,
+        TypeIdentifier::GraphQLID => ScalarType::ID,
+        TypeIdentifier::UUID => ScalarType::UUID,
+        TypeIdentifier::Int => ScalarType::Int,
+        TypeIdentifier::BigInt => ScalarType::Long,
+        // Enum fields of internal data models without enum names fall back to strings.
+        TypeIdentifier::String | TypeIdentifier::Bytes | TypeIdentifier::Enum | TypeIdentifier::Relation => {
+            ScalarType::String
+        }
+    }
+}
+
+/// The type of a scalar field as argument, lists are `[T!]`.
+fn scalar_input_type(sf: &ScalarFieldRef) -> InputType {
+    let tpe = match sf.enum_name {
+        Some(ref name) => InputType::Enum(name.clone()),
+        None => InputType::Scalar(scalar_type(sf.type_identifier)),
+    };
+
+    if sf.is_list {
+        InputType::list(tpe)
+    } else {
+        tpe
+    }
+}
+
+/// The type of a scalar field of an object type, lists are `[T!]!`.
+fn scalar_output_type(sf: &ScalarFieldRef) -> OutputType {
+    let tpe = match sf.enum_name {
+        Some(ref name) => OutputType::Enum(name.clone()),
+        None => OutputType::Scalar(scalar_type(sf.type_identifier)),
+    };
+
+    if sf.is_list {
+        OutputType::list(tpe)
+    } else if sf.is_required {
+        tpe
+    } else {
+        OutputType::opt(tpe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils::query_schema;
+    use super::*;
+
+    fn field_names(fields: &[InputField]) -> Vec<&str> {
+        fields.iter().map(|field| field.name.as_str()).collect()
+    }
+
+    fn input_type<'a>(schema: &'a QuerySchema, name: &str) -> &'a InputObjectType {
+        schema
+            .find_input_type(name)
+            .unwrap_or_else(|| panic!("Input type {} not found", name))
+    }
+
+    #[test]
+    fn leaves_embedded_models_out_of_the_root_types() {
+        let schema = query_schema();
+
+        let queries: Vec<&str> = schema.query.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            vec!["user", "users", "usersConnection", "post", "posts", "postsConnection"],
+            queries
+        );
+
+        assert!(schema.mutation.fields.iter().all(|f| !f.name.contains("Address")));
+        assert!(schema.find_object_type("Address").is_some());
+        assert!(schema.find_object_type("AddressConnection").is_none());
+    }
+
+    #[test]
+    fn selects_single_records_by_unique_fields_and_compound_uniques() {
+        let schema = query_schema();
+        let user = schema.query.fields.iter().find(|f| f.name == "user").unwrap();
+
+        assert_eq!(
+            vec![argument("where", InputType::object("UserWhereUniqueQueryInput"))],
+            user.arguments
+        );
+
+        let where_unique = input_type(&schema, "UserWhereUniqueQueryInput");
+        assert_eq!(vec!["id", "email", "name_role"], field_names(&where_unique.fields));
+        assert_eq!(
+            InputType::opt(InputType::object("UserNameRoleCompoundUniqueInput")),
+            where_unique.fields[2].field_type
+        );
+
+        let compound = input_type(&schema, "UserNameRoleCompoundUniqueInput");
+        assert_eq!(
+            vec![
+                input_field("name", InputType::Scalar(ScalarType::String)),
+                input_field("role", InputType::Enum(String::from("Role"))),
+            ],
+            compound.fields
+        );
+
+        // Mutations select records by a single unique field only.
+        let where_unique = input_type(&schema, "UserWhereUniqueInput");
+        assert_eq!(vec!["id", "email"], field_names(&where_unique.fields));
+    }
+
+    #[test]
+    fn reads_lists_of_related_records_with_arguments() {
+        let schema = query_schema();
+        let user = schema.find_object_type("User").unwrap();
+        let posts = user.fields.iter().find(|f| f.name == "posts").unwrap();
+        let arguments: Vec<&str> = posts.arguments.iter().map(|arg| arg.name.as_str()).collect();
+
+        assert_eq!(
+            vec!["where", "orderBy", "skip", "after", "before", "first", "last"],
+            arguments
+        );
+        assert_eq!(
+            OutputType::opt(OutputType::list(OutputType::object("Post"))),
+            posts.field_type
+        );
+
+        let address = user.fields.iter().find(|f| f.name == "address").unwrap();
+        assert!(address.arguments.is_empty());
+    }
+
+    #[test]
+    fn leaves_hidden_fields_out_of_the_object_types() {
+        let schema = query_schema();
+        let address = schema.find_object_type("Address").unwrap();
+
+        assert_eq!(
+            vec![output_field("street", OutputType::Scalar(ScalarType::String))],
+            address.fields
+        );
+    }
+
+    #[test]
+    fn leaves_the_back_relation_out_of_nested_create_inputs() {
+        let schema = query_schema();
+
+        let nested = input_type(&schema, "PostCreateManyWithoutAuthorInput");
+        assert_eq!(vec!["create", "connect"], field_names(&nested.fields));
+
+        let create = input_type(&schema, "PostCreateWithoutAuthorInput");
+        assert_eq!(vec!["id", "title"], field_names(&create.fields));
+
+        let create = input_type(&schema, "UserCreateInput");
+        assert_eq!(
+            vec!["id", "name", "email", "role", "tags", "posts", "address"],
+            field_names(&create.fields)
+        );
+        assert_eq!(
+            InputType::opt(InputType::object("UserCreateTagsInput")),
+            create.fields[4].field_type
+        );
+    }
+
+    #[test]
+    fn writes_embedded_records_only_along_with_their_parent() {
+        let schema = query_schema();
+
+        let create = input_type(&schema, "AddressCreateOneInput");
+        assert_eq!(vec!["create"], field_names(&create.fields));
+
+        let update = input_type(&schema, "AddressUpdateOneInput");
+        assert_eq!(vec!["create", "update", "delete"], field_names(&update.fields));
+    }
+
+    #[test]
+    fn orders_by_the_scalar_fields_of_a_model() {
+        let schema = query_schema();
+        let order_by = schema.find_enum_type("PostOrderByInput").unwrap();
+
+        assert_eq!(vec!["id_ASC", "id_DESC", "title_ASC", "title_DESC"], order_by.values);
+        assert!(schema.find_enum_type("AddressOrderByInput").is_none());
     }
 }
@@ -0,0 +1,636 @@
+use super::{
+    builder::{argument, output_field},
+    *,
+};
+use graphql_parser::query::{
+    self as gql, Definition, Document, Field, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+};
+use lazy_static::lazy_static;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+lazy_static! {
+    static ref META_OBJECT_TYPES: Vec<ObjectType> = meta_object_types();
+    static ref META_ENUM_TYPES: Vec<EnumType> = meta_enum_types();
+    static ref META_FIELDS: Vec<OutputField> = vec![
+        output_field("__schema", OutputType::object("__Schema")),
+        OutputField {
+            name: String::from("__type"),
+            arguments: vec![argument("name", InputType::Scalar(ScalarType::String))],
+            field_type: OutputType::opt(OutputType::object("__Type")),
+        },
+    ];
+}
+
+/// True for the fields of the introspection system, their names start with `__`.
+pub fn is_introspection_field(field: &Field) -> bool {
+    field.name.starts_with("__")
+}
+
+/// Resolves the introspection fields on the root types of a query, `__schema`,
+/// `__type` and `__typename`, from the query schema.
+pub struct IntrospectionResolver<'a> {
+    schema: &'a QuerySchema,
+    document: &'a Document,
+    fragments: BTreeMap<&'a str, &'a FragmentDefinition>,
+}
+
+impl<'a> IntrospectionResolver<'a> {
+    pub fn new(schema: &'a QuerySchema, document: &'a Document) -> Self {
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            schema,
+            document,
+            fragments,
+        }
+    }
+
+    /// The results of the introspection fields of all operations, keyed by alias or name.
+    pub fn resolve(&self) -> Vec<(String, Value)> {
+        let mut results = vec![];
+
+        for definition in &self.document.definitions {
+            let (root, selection_set) = match definition {
+                Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                    (&self.schema.query, selection_set)
+                }
+                Definition::Operation(OperationDefinition::Query(query)) => (&self.schema.query, &query.selection_set),
+                Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+                    (&self.schema.mutation, &mutation.selection_set)
+                }
+                _ => continue,
+            };
+
+            for selection in &selection_set.items {
+                if let Selection::Field(field) = selection {
+                    if is_introspection_field(field) {
+                        let key = field.alias.clone().unwrap_or_else(|| field.name.clone());
+                        results.push((key, self.resolve_root_field(root, field)));
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    fn resolve_root_field(&self, root: &ObjectType, field: &Field) -> Value {
+        match field.name.as_str() {
+            "__typename" => Value::String(root.name.clone()),
+            "__schema" => self.project(self.schema.introspection(), "__Schema", &field.selection_set),
+            "__type" => {
+                let tpe = field
+                    .arguments
+                    .iter()
+                    .find(|(name, _)| name == "name")
+                    .and_then(|(_, value)| match value {
+                        gql::Value::String(name) => self.schema.type_introspection(name),
+                        _ => None,
+                    });
+
+                match tpe {
+                    Some(tpe) => self.project(tpe, "__Type", &field.selection_set),
+                    None => Value::Null,
+                }
+            }
+            _ => Value::Null,
+        }
+    }
+
+    /// Reduces an introspection value of the given type to the selected fields.
+    fn project(&self, value: Value, type_name: &str, selection_set: &SelectionSet) -> Value {
+        match value {
+            Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| self.project(item, type_name, selection_set))
+                    .collect(),
+            ),
+            Value::Object(object) => {
+                let mut result = Map::new();
+                self.project_into(&mut result, &object, type_name, selection_set);
+                Value::Object(result)
+            }
+            value => value,
+        }
+    }
+
+    fn project_into(
+        &self,
+        result: &mut Map<String, Value>,
+        object: &Map<String, Value>,
+        type_name: &str,
+        selection_set: &SelectionSet,
+    ) {
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) => {
+                    let key = field.alias.clone().unwrap_or_else(|| field.name.clone());
+                    let value = if field.name == "__typename" {
+                        Value::String(type_name.to_owned())
+                    } else {
+                        self.field_value(object, type_name, field)
+                    };
+
+                    result.insert(key, value);
+                }
+                // Fragments are validated to be on the selected type.
+                Selection::FragmentSpread(spread) => {
+                    if let Some(fragment) = self.fragments.get(spread.fragment_name.as_str()) {
+                        self.project_into(result, object, type_name, &fragment.selection_set);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.project_into(result, object, type_name, &fragment.selection_set);
+                }
+            }
+        }
+    }
+
+    fn field_value(&self, object: &Map<String, Value>, type_name: &str, field: &Field) -> Value {
+        // Type references only carry `kind`, `name` and `ofType`, other fields are taken from the named type.
+        let value = match object.get(&field.name) {
+            Some(value) => value.clone(),
+            None => object
+                .get("name")
+                .and_then(Value::as_str)
+                .and_then(|name| self.schema.type_introspection(name))
+                .map(|tpe| tpe[field.name.as_str()].clone())
+                .unwrap_or(Value::Null),
+        };
+
+        let field_type = self
+            .schema
+            .find_object_type(type_name)
+            .and_then(|object_type| object_type.find_field(&field.name))
+            .map(|schema_field| schema_field.field_type.name());
+
+        match field_type {
+            Some(ref field_type) if !field.selection_set.items.is_empty() => {
+                self.project(value, field_type, &field.selection_set)
+            }
+            _ => value,
+        }
+    }
+}
+
+impl QuerySchema {
+    /// The `__Schema` of the GraphQL introspection, with all of its fields.
+    pub fn introspection(&self) -> Value {
+        let mut types: Vec<Value> = self.all_object_types().into_iter().map(object_type).collect();
+        types.extend(self.input_types.iter().map(input_object_type));
+        types.extend(self.enum_types.iter().map(enum_type));
+        types.extend(self.scalar_types().into_iter().map(scalar_type));
+        types.extend(META_OBJECT_TYPES.iter().map(object_type));
+        types.extend(META_ENUM_TYPES.iter().map(enum_type));
+
+        json!({
+            "queryType": { "name": self.query.name },
+            "mutationType": { "name": self.mutation.name },
+            "subscriptionType": null,
+            "types": types,
+            "directives": [directive("skip"), directive("include")],
+        })
+    }
+
+    /// The `__Type` of the GraphQL introspection for the named type.
+    pub fn type_introspection(&self, name: &str) -> Option<Value> {
+        if let Some(t) = self.find_object_type(name) {
+            return Some(object_type(t));
+        }
+
+        if let Some(t) = self.find_input_type(name) {
+            return Some(input_object_type(t));
+        }
+
+        if let Some(t) = self.find_enum_type(name) {
+            return Some(enum_type(t));
+        }
+
+        self.scalar_types()
+            .into_iter()
+            .find(|scalar| scalar.name() == name)
+            .map(scalar_type)
+    }
+
+    /// The `__schema` and `__type` fields, which are implicitly defined on the query type.
+    pub fn find_meta_field(&self, object_type: &ObjectType, name: &str) -> Option<&'static OutputField> {
+        if object_type.name == self.query.name {
+            META_FIELDS.iter().find(|field| field.name == name)
+        } else {
+            None
+        }
+    }
+}
+
+pub(super) fn find_meta_object_type(name: &str) -> Option<&'static ObjectType> {
+    META_OBJECT_TYPES.iter().find(|t| t.name == name)
+}
+
+pub(super) fn find_meta_enum_type(name: &str) -> Option<&'static EnumType> {
+    META_ENUM_TYPES.iter().find(|t| t.name == name)
+}
+
+/// The object types of the introspection system, as defined by the GraphQL specification.
+fn meta_object_types() -> Vec<ObjectType> {
+    let string = || OutputType::Scalar(ScalarType::String);
+    let boolean = || OutputType::Scalar(ScalarType::Boolean);
+    let list_of = |name: &str| OutputType::list(OutputType::object(name));
+    let include_deprecated = || {
+        vec![argument(
+            "includeDeprecated",
+            InputType::opt(InputType::Scalar(ScalarType::Boolean)),
+        )]
+    };
+
+    let object = |name: &str, fields: Vec<OutputField>| ObjectType {
+        name: name.to_owned(),
+        fields,
+    };
+
+    let with_arguments = |field: OutputField, arguments: Vec<Argument>| OutputField { arguments, ..field };
+
+    vec![
+        object(
+            "__Schema",
+            vec![
+                output_field("types", list_of("__Type")),
+                output_field("queryType", OutputType::object("__Type")),
+                output_field("mutationType", OutputType::opt(OutputType::object("__Type"))),
+                output_field("subscriptionType", OutputType::opt(OutputType::object("__Type"))),
+                output_field("directives", list_of("__Directive")),
+            ],
+        ),
+        object(
+            "__Type",
+            vec![
+                output_field("kind", OutputType::Enum(String::from("__TypeKind"))),
+                output_field("name", OutputType::opt(string())),
+                output_field("description", OutputType::opt(string())),
+                with_arguments(
+                    output_field("fields", OutputType::opt(list_of("__Field"))),
+                    include_deprecated(),
+                ),
+                output_field("interfaces", OutputType::opt(list_of("__Type"))),
+                output_field("possibleTypes", OutputType::opt(list_of("__Type"))),
+                with_arguments(
+                    output_field("enumValues", OutputType::opt(list_of("__EnumValue"))),
+                    include_deprecated(),
+                ),
+                output_field("inputFields", OutputType::opt(list_of("__InputValue"))),
+                output_field("ofType", OutputType::opt(OutputType::object("__Type"))),
+            ],
+        ),
+        object(
+            "__Field",
+            vec![
+                output_field("name", string()),
+                output_field("description", OutputType::opt(string())),
+                output_field("args", list_of("__InputValue")),
+                output_field("type", OutputType::object("__Type")),
+                output_field("isDeprecated", boolean()),
+                output_field("deprecationReason", OutputType::opt(string())),
+            ],
+        ),
+        object(
+            "__InputValue",
+            vec![
+                output_field("name", string()),
+                output_field("description", OutputType::opt(string())),
+                output_field("type", OutputType::object("__Type")),
+                output_field("defaultValue", OutputType::opt(string())),
+            ],
+        ),
+        object(
+            "__EnumValue",
+            vec![
+                output_field("name", string()),
+                output_field("description", OutputType::opt(string())),
+                output_field("isDeprecated", boolean()),
+                output_field("deprecationReason", OutputType::opt(string())),
+            ],
+        ),
+        object(
+            "__Directive",
+            vec![
+                output_field("name", string()),
+                output_field("description", OutputType::opt(string())),
+                output_field(
+                    "locations",
+                    OutputType::list(OutputType::Enum(String::from("__DirectiveLocation"))),
+                ),
+                output_field("args", list_of("__InputValue")),
+            ],
+        ),
+    ]
+}
+
+fn meta_enum_types() -> Vec<EnumType> {
+    let enum_type = |name: &str, values: &[&str]| EnumType {
+        name: name.to_owned(),
+        values: values.iter().map(|v| v.to_string()).collect(),
+    };
+
+    vec![
+        enum_type(
+            "__TypeKind",
+            &[
+                "SCALAR",
+                "OBJECT",
+                "INTERFACE",
+                "UNION",
+                "ENUM",
+                "INPUT_OBJECT",
+                "LIST",
+                "NON_NULL",
+            ],
+        ),
+        enum_type(
+            "__DirectiveLocation",
+            &[
+                "QUERY",
+                "MUTATION",
+                "SUBSCRIPTION",
+                "FIELD",
+                "FRAGMENT_DEFINITION",
+                "FRAGMENT_SPREAD",
+                "INLINE_FRAGMENT",
+                "SCHEMA",
+                "SCALAR",
+                "OBJECT",
+                "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INTERFACE",
+                "UNION",
+                "ENUM",
+                "ENUM_VALUE",
+                "INPUT_OBJECT",
+                "INPUT_FIELD_DEFINITION",
+            ],
+        ),
+    ]
+}
+
+fn full_type(kind: &str, name: &str) -> Value {
+    json!({
+        "kind": kind,
+        "name": name,
+        "description": null,
+        "fields": null,
+        "inputFields": null,
+        "interfaces": null,
+        "enumValues": null,
+        "possibleTypes": null,
+        "ofType": null,
+    })
+}
+
+fn object_type(t: &ObjectType) -> Value {
+    let fields: Vec<Value> = t
+        .fields
+        .iter()
+        .map(|field| {
+            json!({
+                "name": field.name,
+                "description": null,
+                "args": field.arguments.iter().map(|arg| input_value(&arg.name, &arg.argument_type)).collect::<Vec<Value>>(),
+                "type": output_type_ref(&field.field_type),
+                "isDeprecated": false,
+                "deprecationReason": null,
+            })
+        })
+        .collect();
+
+    let mut value = full_type("OBJECT", &t.name);
+    value["fields"] = json!(fields);
+    value["interfaces"] = json!([]);
+    value
+}
+
+fn input_object_type(t: &InputObjectType) -> Value {
+    let fields: Vec<Value> = t
+        .fields
+        .iter()
+        .map(|field| input_value(&field.name, &field.field_type))
+        .collect();
+
+    let mut value = full_type("INPUT_OBJECT", &t.name);
+    value["inputFields"] = json!(fields);
+    value
+}
+
+fn enum_type(t: &EnumType) -> Value {
+    let values: Vec<Value> = t
+        .values
+        .iter()
+        .map(|v| {
+            json!({
+                "name": v,
+                "description": null,
+                "isDeprecated": false,
+                "deprecationReason": null,
+            })
+        })
+        .collect();
+
+    let mut value = full_type("ENUM", &t.name);
+    value["enumValues"] = json!(values);
+    value
+}
+
+fn scalar_type(scalar: ScalarType) -> Value {
+    full_type("SCALAR", scalar.name())
+}
+
+fn input_value(name: &str, tpe: &InputType) -> Value {
+    json!({
+        "name": name,
+        "description": null,
+        "type": input_type_ref(tpe),
+        "defaultValue": null,
+    })
+}
+
+fn type_ref(kind: &str, name: Option<&str>, of_type: Option<Value>) -> Value {
+    json!({
+        "kind": kind,
+        "name": name,
+        "ofType": of_type,
+    })
+}
+
+fn input_type_ref(tpe: &InputType) -> Value {
+    match tpe {
+        InputType::Opt(inner) => match inner.as_ref() {
+            InputType::List(item) => type_ref("LIST", None, Some(input_type_ref(item))),
+            InputType::Opt(_) => input_type_ref(inner),
+            InputType::Enum(name) => type_ref("ENUM", Some(name), None),
+            InputType::Object(name) => type_ref("INPUT_OBJECT", Some(name), None),
+            InputType::Scalar(scalar) => type_ref("SCALAR", Some(scalar.name()), None),
+        },
+        tpe => type_ref("NON_NULL", None, Some(input_type_ref(&InputType::opt(tpe.clone())))),
+    }
+}
+
+fn output_type_ref(tpe: &OutputType) -> Value {
+    match tpe {
+        OutputType::Opt(inner) => match inner.as_ref() {
+            OutputType::List(item) => type_ref("LIST", None, Some(output_type_ref(item))),
+            OutputType::Opt(_) => output_type_ref(inner),
+            OutputType::Enum(name) => type_ref("ENUM", Some(name), None),
+            OutputType::Object(name) => type_ref("OBJECT", Some(name), None),
+            OutputType::Scalar(scalar) => type_ref("SCALAR", Some(scalar.name()), None),
+        },
+        tpe => type_ref("NON_NULL", None, Some(output_type_ref(&OutputType::opt(tpe.clone())))),
+    }
+}
+
+fn directive(name: &str) -> Value {
+    json!({
+        "name": name,
+        "description": null,
+        "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+        "args": [input_value("if", &InputType::Scalar(ScalarType::Boolean))],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils::query_schema;
+    use super::*;
+
+    fn resolve(query: &str) -> Vec<(String, Value)> {
+        let schema = query_schema();
+        let document = graphql_parser::parse_query(query).unwrap();
+
+        assert_eq!(Ok(()), QueryValidator::new(&schema).validate(&document));
+        IntrospectionResolver::new(&schema, &document).resolve()
+    }
+
+    #[test]
+    fn resolves_the_type_of_a_model() {
+        let query = r#"{ __type(name: "User") { kind name fields { name type { kind name ofType { kind name } } } } }"#;
+        let (key, tpe) = resolve(query).remove(0);
+
+        assert_eq!("__type", key);
+        assert_eq!(json!("OBJECT"), tpe["kind"]);
+        assert_eq!(json!("User"), tpe["name"]);
+
+        let names: Vec<&Value> = tpe["fields"].as_array().unwrap().iter().map(|f| &f["name"]).collect();
+        assert_eq!(
+            vec!["id", "name", "email", "role", "tags", "posts", "address"],
+            names.iter().map(|name| name.as_str().unwrap()).collect::<Vec<&str>>()
+        );
+
+        assert_eq!(
+            json!({
+                "name": "id",
+                "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": "SCALAR", "name": "ID" } },
+            }),
+            tpe["fields"][0]
+        );
+
+        assert_eq!(
+            json!({
+                "name": "address",
+                "type": { "kind": "OBJECT", "name": "Address", "ofType": null },
+            }),
+            tpe["fields"][6]
+        );
+    }
+
+    #[test]
+    fn resolves_the_type_of_an_enum() {
+        let query = r#"{ __type(name: "Role") { kind name enumValues { name } fields { name } } }"#;
+
+        assert_eq!(
+            vec![(
+                String::from("__type"),
+                json!({
+                    "kind": "ENUM",
+                    "name": "Role",
+                    "enumValues": [{ "name": "ADMIN" }, { "name": "USER" }],
+                    "fields": null,
+                })
+            )],
+            resolve(query)
+        );
+    }
+
+    #[test]
+    fn resolves_the_type_of_an_input_object() {
+        let query = r#"
+            {
+              compound: __type(name: "UserNameRoleCompoundUniqueInput") {
+                kind
+                inputFields { name type { kind ofType { kind name } } }
+              }
+            }
+        "#;
+
+        assert_eq!(
+            vec![(
+                String::from("compound"),
+                json!({
+                    "kind": "INPUT_OBJECT",
+                    "inputFields": [
+                        { "name": "name", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "String" } } },
+                        { "name": "role", "type": { "kind": "NON_NULL", "ofType": { "kind": "ENUM", "name": "Role" } } },
+                    ],
+                })
+            )],
+            resolve(query)
+        );
+    }
+
+    #[test]
+    fn resolves_unknown_types_to_null() {
+        assert_eq!(
+            vec![(String::from("__type"), Value::Null)],
+            resolve(r#"{ __type(name: "Nope") { name } }"#)
+        );
+    }
+
+    #[test]
+    fn resolves_typename_on_nested_selections() {
+        let query = r#"
+            {
+              __typename
+              __type(name: "Post") { __typename fields { kind: __typename type { __typename } } }
+              __schema { ...SchemaFields }
+            }
+
+            fragment SchemaFields on __Schema { __typename queryType { __typename name } }
+        "#;
+
+        let field = json!({ "kind": "__Field", "type": { "__typename": "__Type" } });
+
+        assert_eq!(
+            vec![
+                (String::from("__typename"), json!("Query")),
+                (
+                    String::from("__type"),
+                    json!({ "__typename": "__Type", "fields": [field.clone(), field.clone(), field] })
+                ),
+                (
+                    String::from("__schema"),
+                    json!({ "__typename": "__Schema", "queryType": { "__typename": "__Type", "name": "Query" } })
+                ),
+            ],
+            resolve(query)
+        );
+
+        assert_eq!(
+            vec![(String::from("__typename"), json!("Mutation"))],
+            resolve("mutation { __typename }")
+        );
+    }
+}
@@ -1,5 +1,13 @@
 mod builder;
+mod introspection;
 mod schema;
+mod sdl;
+mod validation;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 pub use builder::*;
-pub use schema::*;
\ No newline at end of file
+pub use introspection::*;
+pub use schema::*;
+pub use validation::*;
@@ -1,35 +1,287 @@
-// pub struct QuerySchema {
-//     query: ObjectType,    // read(s)?
-//     mutation: ObjectType, // write(s)?
-// }
-
-// // enum for Optional input types, list types?
-// // Could also be a flag on the structs
-
-// impl QuerySchema {}
-
-// struct ObjectType {}
-
-// // On schema construction checks:
-// // - field name uniqueness
-// // - val NameRegexp = """^[_a-zA-Z][_a-zA-Z0-9]*$""".r match
-// // -
-
-// enum InputType {
-//     EnumType,
-//     InputObjectType,
-//     ListInputType,
-//     OptionInputType,
-//     ScalarType,
-// }
-
-// // enum OutputType {
-// //     EnumType,
-// //     ListType(OutputType),
-// //     ObjectType(ObjectType),
-// //     OptionType(OutputType),
-// //     ScalarType,
-// // }
-
-// // Possible:
-// // InputType(OptionType(StringType))
+use super::introspection::{find_meta_enum_type, find_meta_object_type};
+use std::fmt;
+
+/// The GraphQL schema served by the query engine, see `SchemaBuilder`.
+///
+/// Types reference each other by name. All types are non-null unless wrapped
+/// in an `Opt` type, lists are wrapped in a `List` type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuerySchema {
+    pub query: ObjectType,
+    pub mutation: ObjectType,
+    pub object_types: Vec<ObjectType>,
+    pub input_types: Vec<InputObjectType>,
+    pub enum_types: Vec<EnumType>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectType {
+    pub name: String,
+    pub fields: Vec<OutputField>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputField {
+    pub name: String,
+    pub arguments: Vec<Argument>,
+    pub field_type: OutputType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Argument {
+    pub name: String,
+    pub argument_type: InputType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputObjectType {
+    pub name: String,
+    pub fields: Vec<InputField>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputField {
+    pub name: String,
+    pub field_type: InputType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumType {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputType {
+    Enum(String),
+    Object(String),
+    List(Box<InputType>),
+    Opt(Box<InputType>),
+    Scalar(ScalarType),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputType {
+    Enum(String),
+    Object(String),
+    List(Box<OutputType>),
+    Opt(Box<OutputType>),
+    Scalar(ScalarType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalarType {
+    String,
+    Int,
+    Float,
+    Boolean,
+    ID,
+    DateTime,
+    Json,
+    UUID,
+    Long,
+}
+
+impl QuerySchema {
+    /// Finds an object type by name, including the `Query` and `Mutation` root
+    /// types and the types of the introspection system.
+    pub fn find_object_type(&self, name: &str) -> Option<&ObjectType> {
+        self.all_object_types()
+            .into_iter()
+            .find(|t| t.name == name)
+            .or_else(|| find_meta_object_type(name))
+    }
+
+    pub fn find_input_type(&self, name: &str) -> Option<&InputObjectType> {
+        self.input_types.iter().find(|t| t.name == name)
+    }
+
+    pub fn find_enum_type(&self, name: &str) -> Option<&EnumType> {
+        self.enum_types
+            .iter()
+            .find(|t| t.name == name)
+            .or_else(|| find_meta_enum_type(name))
+    }
+
+    /// The builtin scalar types and the custom scalar types used in the schema,
+    /// in declaration order of `ScalarType`.
+    pub fn scalar_types(&self) -> Vec<ScalarType> {
+        let mut used = Vec::new();
+
+        for object_type in self.all_object_types() {
+            for field in &object_type.fields {
+                used.push(field.field_type.scalar());
+                used.extend(field.arguments.iter().map(|arg| arg.argument_type.scalar()));
+            }
+        }
+
+        for input_type in &self.input_types {
+            used.extend(input_type.fields.iter().map(|field| field.field_type.scalar()));
+        }
+
+        ScalarType::all()
+            .into_iter()
+            .filter(|scalar| scalar.is_builtin() || used.contains(&Some(*scalar)))
+            .collect()
+    }
+
+    /// All object types, starting with the `Query` and `Mutation` root types.
+    pub fn all_object_types(&self) -> Vec<&ObjectType> {
+        vec![&self.query, &self.mutation]
+            .into_iter()
+            .chain(self.object_types.iter())
+            .collect()
+    }
+}
+
+impl ObjectType {
+    pub fn find_field(&self, name: &str) -> Option<&OutputField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+impl OutputField {
+    pub fn find_argument(&self, name: &str) -> Option<&Argument> {
+        self.arguments.iter().find(|arg| arg.name == name)
+    }
+}
+
+impl InputObjectType {
+    pub fn find_field(&self, name: &str) -> Option<&InputField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+impl InputType {
+    pub fn opt(inner: InputType) -> InputType {
+        InputType::Opt(Box::new(inner))
+    }
+
+    pub fn list(inner: InputType) -> InputType {
+        InputType::List(Box::new(inner))
+    }
+
+    pub fn object(name: impl Into<String>) -> InputType {
+        InputType::Object(name.into())
+    }
+
+    pub fn is_required(&self) -> bool {
+        match self {
+            InputType::Opt(_) => false,
+            _ => true,
+        }
+    }
+
+    /// The name of the underlying type, e.g. `String` for `[String!]`.
+    pub fn name(&self) -> String {
+        match self {
+            InputType::Enum(name) | InputType::Object(name) => name.clone(),
+            InputType::List(inner) | InputType::Opt(inner) => inner.name(),
+            InputType::Scalar(scalar) => scalar.name().to_owned(),
+        }
+    }
+
+    fn scalar(&self) -> Option<ScalarType> {
+        match self {
+            InputType::List(inner) | InputType::Opt(inner) => inner.scalar(),
+            InputType::Scalar(scalar) => Some(*scalar),
+            _ => None,
+        }
+    }
+}
+
+impl OutputType {
+    pub fn opt(inner: OutputType) -> OutputType {
+        OutputType::Opt(Box::new(inner))
+    }
+
+    pub fn list(inner: OutputType) -> OutputType {
+        OutputType::List(Box::new(inner))
+    }
+
+    pub fn object(name: impl Into<String>) -> OutputType {
+        OutputType::Object(name.into())
+    }
+
+    /// The name of the underlying type, e.g. `User` for `[User!]!`.
+    pub fn name(&self) -> String {
+        match self {
+            OutputType::Enum(name) | OutputType::Object(name) => name.clone(),
+            OutputType::List(inner) | OutputType::Opt(inner) => inner.name(),
+            OutputType::Scalar(scalar) => scalar.name().to_owned(),
+        }
+    }
+
+    fn scalar(&self) -> Option<ScalarType> {
+        match self {
+            OutputType::List(inner) | OutputType::Opt(inner) => inner.scalar(),
+            OutputType::Scalar(scalar) => Some(*scalar),
+            _ => None,
+        }
+    }
+}
+
+impl ScalarType {
+    pub fn all() -> Vec<ScalarType> {
+        vec![
+            ScalarType::String,
+            ScalarType::Int,
+            ScalarType::Float,
+            ScalarType::Boolean,
+            ScalarType::ID,
+            ScalarType::DateTime,
+            ScalarType::Json,
+            ScalarType::UUID,
+            ScalarType::Long,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ScalarType::String => "String",
+            ScalarType::Int => "Int",
+            ScalarType::Float => "Float",
+            ScalarType::Boolean => "Boolean",
+            ScalarType::ID => "ID",
+            ScalarType::DateTime => "DateTime",
+            ScalarType::Json => "Json",
+            ScalarType::UUID => "UUID",
+            ScalarType::Long => "Long",
+        }
+    }
+
+    /// True for the scalars every GraphQL schema has, the others are declared in the SDL.
+    pub fn is_builtin(self) -> bool {
+        match self {
+            ScalarType::String | ScalarType::Int | ScalarType::Float | ScalarType::Boolean | ScalarType::ID => true,
+            _ => false,
+        }
+    }
+}
+
+/// Renders type references the way they are written in GraphQL, e.g. `[String!]`.
+impl fmt::Display for InputType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputType::Opt(inner) => match inner.as_ref() {
+                InputType::List(item) => write!(f, "[{}]", item),
+                opt @ InputType::Opt(_) => opt.fmt(f),
+                named => write!(f, "{}", named.name()),
+            },
+            tpe => write!(f, "{}!", InputType::opt(tpe.clone())),
+        }
+    }
+}
+
+impl fmt::Display for OutputType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OutputType::Opt(inner) => match inner.as_ref() {
+                OutputType::List(item) => write!(f, "[{}]", item),
+                opt @ OutputType::Opt(_) => opt.fmt(f),
+                named => write!(f, "{}", named.name()),
+            },
+            tpe => write!(f, "{}!", OutputType::opt(tpe.clone())),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::*;
+
+impl QuerySchema {
+    /// Renders the schema as GraphQL SDL.
+    pub fn to_sdl(&self) -> String {
+        let mut blocks = Vec::new();
+
+        for object_type in self.all_object_types() {
+            let fields = object_type.fields.iter().map(|field| {
+                let arguments: Vec<String> = field
+                    .arguments
+                    .iter()
+                    .map(|arg| format!("{}: {}", arg.name, arg.argument_type))
+                    .collect();
+
+                if arguments.is_empty() {
+                    format!("{}: {}", field.name, field.field_type)
+                } else {
+                    format!("{}({}): {}", field.name, arguments.join(", "), field.field_type)
+                }
+            });
+
+            blocks.push(block("type", &object_type.name, fields));
+        }
+
+        for input_type in &self.input_types {
+            let fields = input_type
+                .fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, field.field_type));
+
+            blocks.push(block("input", &input_type.name, fields));
+        }
+
+        for enum_type in &self.enum_types {
+            blocks.push(block("enum", &enum_type.name, enum_type.values.iter().cloned()));
+        }
+
+        for scalar in self.scalar_types().into_iter().filter(|s| !s.is_builtin()) {
+            blocks.push(format!("scalar {}", scalar.name()));
+        }
+
+        let mut sdl = blocks.join("\n\n");
+        sdl.push('\n');
+        sdl
+    }
+}
+
+fn block<I>(keyword: &str, name: &str, lines: I) -> String
+where
+    I: Iterator<Item = String>,
+{
+    let lines: Vec<String> = lines.map(|line| format!("  {}", line)).collect();
+    format!("{} {} {{\n{}\n}}", keyword, name, lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils::query_schema;
+
+    #[test]
+    fn renders_object_types_with_their_arguments() {
+        let sdl = query_schema().to_sdl();
+
+        assert!(sdl.starts_with("type Query {\n  user(where: UserWhereUniqueQueryInput!): User\n"));
+        assert!(sdl.contains("\n\ntype Address {\n  street: String!\n}\n\n"));
+        assert!(sdl.contains(
+            "  posts(where: PostWhereInput, orderBy: PostOrderByInput, skip: Int, after: String, \
+             before: String, first: Int, last: Int): [Post!]\n"
+        ));
+    }
+
+    #[test]
+    fn renders_input_enum_and_custom_scalar_types() {
+        let sdl = query_schema().to_sdl();
+
+        assert!(sdl.contains("\n\ninput UserNameRoleCompoundUniqueInput {\n  name: String!\n  role: Role!\n}\n\n"));
+        assert!(sdl.contains("\n\nenum Role {\n  ADMIN\n  USER\n}\n\n"));
+        assert!(sdl.ends_with("\n\nscalar Long\n"));
+        assert!(!sdl.contains("scalar String"));
+    }
+
+    #[test]
+    fn renders_parseable_sdl() {
+        let sdl = query_schema().to_sdl();
+
+        if let Err(err) = graphql_parser::parse_schema(&sdl) {
+            panic!("Invalid SDL: {}\n{}", err, sdl);
+        }
+    }
+}
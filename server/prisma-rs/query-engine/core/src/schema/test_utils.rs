@@ -0,0 +1,17 @@
+//! Helpers for tests against the schema of `test_schema.json`: users with
+//! posts, an embedded address and a unique index on `name` and `role`.
+
+use super::*;
+use prisma_models::{InternalDataModelRef, InternalDataModelTemplate};
+use std::fs::File;
+
+pub fn internal_data_model() -> InternalDataModelRef {
+    let file = File::open("test_schema.json").unwrap();
+    let template: InternalDataModelTemplate = serde_json::from_reader(file).unwrap();
+
+    template.build(String::from("test"))
+}
+
+pub fn query_schema() -> QuerySchema {
+    SchemaBuilder::build(internal_data_model())
+}
@@ -0,0 +1,686 @@
+//! Validation of queries against the query schema
+//!
+//! The checks follow the GraphQL specification where it applies to the query
+//! schema: Selected fields and arguments exist, required arguments are given,
+//! argument values match their types and selection sets fit the selected types.
+//! All violations of a query are collected instead of stopping at the first one.
+
+use super::*;
+use graphql_parser::{query::*, Pos};
+use std::collections::BTreeMap;
+
+/// A violation of the query schema, located in the query source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub message: String,
+    pub locations: Vec<Pos>,
+}
+
+impl ValidationError {
+    fn new(message: String, location: Pos) -> Self {
+        Self {
+            message,
+            locations: vec![location],
+        }
+    }
+}
+
+pub struct QueryValidator<'a> {
+    schema: &'a QuerySchema,
+    fragments: BTreeMap<&'a str, &'a FragmentDefinition>,
+    variables: Vec<&'a VariableDefinition>,
+    // The fragments currently spread, to stop at cycles.
+    spreads: Vec<&'a str>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> QueryValidator<'a> {
+    pub fn new(schema: &'a QuerySchema) -> Self {
+        Self {
+            schema,
+            fragments: BTreeMap::new(),
+            variables: vec![],
+            spreads: vec![],
+            errors: vec![],
+        }
+    }
+
+    pub fn validate(mut self, document: &'a Document) -> Result<(), Vec<ValidationError>> {
+        for definition in &document.definitions {
+            if let Definition::Fragment(fragment) = definition {
+                if self.fragments.insert(fragment.name.as_str(), fragment).is_some() {
+                    self.error(
+                        format!("There can be only one fragment named \"{}\".", fragment.name),
+                        fragment.position,
+                    );
+                }
+
+                let TypeCondition::On(ref type_name) = fragment.type_condition;
+                if self.schema.find_object_type(type_name).is_none() {
+                    self.error(format!("Unknown type \"{}\".", type_name), fragment.position);
+                }
+            }
+        }
+
+        for definition in &document.definitions {
+            match definition {
+                Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                    self.variables = vec![];
+                    self.validate_selection_set(&self.schema.query, selection_set);
+                }
+                Definition::Operation(OperationDefinition::Query(query)) => {
+                    self.validate_operation(&self.schema.query, &query.variable_definitions, &query.directives);
+                    self.validate_selection_set(&self.schema.query, &query.selection_set);
+                }
+                Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+                    self.validate_operation(
+                        &self.schema.mutation,
+                        &mutation.variable_definitions,
+                        &mutation.directives,
+                    );
+                    self.validate_selection_set(&self.schema.mutation, &mutation.selection_set);
+                }
+                Definition::Operation(OperationDefinition::Subscription(subscription)) => {
+                    self.error("Subscriptions are not supported.".into(), subscription.position);
+                }
+                Definition::Fragment(_) => {}
+            }
+        }
+
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    fn error(&mut self, message: String, location: Pos) {
+        self.errors.push(ValidationError::new(message, location));
+    }
+
+    fn validate_operation(&mut self, root: &ObjectType, variables: &'a [VariableDefinition], directives: &[Directive]) {
+        self.variables = variables.iter().collect();
+
+        for variable in variables {
+            let type_name = variable_type_name(&variable.var_type);
+
+            if !self.is_input_type(type_name) {
+                self.error(
+                    format!(
+                        "Variable \"${}\" cannot be non-input type \"{}\".",
+                        variable.name, type_name
+                    ),
+                    variable.position,
+                );
+            }
+        }
+
+        for directive in directives {
+            self.error(
+                format!("Directive \"{}\" may not be used on {}.", directive.name, root.name),
+                directive.position,
+            );
+        }
+    }
+
+    fn is_input_type(&self, name: &str) -> bool {
+        self.schema.find_input_type(name).is_some()
+            || self.schema.find_enum_type(name).is_some()
+            || self.schema.scalar_types().iter().any(|scalar| scalar.name() == name)
+    }
+
+    fn validate_selection_set(&mut self, object_type: &ObjectType, selection_set: &'a SelectionSet) {
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) => {
+                    self.validate_directives(&field.directives);
+                    self.validate_field(object_type, field);
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.validate_directives(&spread.directives);
+                    self.validate_fragment_spread(object_type, spread);
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.validate_directives(&fragment.directives);
+
+                    if let Some(TypeCondition::On(ref type_name)) = fragment.type_condition {
+                        if !self.validate_type_condition(object_type, type_name, None, fragment.position) {
+                            continue;
+                        }
+                    }
+
+                    self.validate_selection_set(object_type, &fragment.selection_set);
+                }
+            }
+        }
+    }
+
+    fn validate_fragment_spread(&mut self, object_type: &ObjectType, spread: &'a FragmentSpread) {
+        let fragment = match self.fragments.get(spread.fragment_name.as_str()) {
+            Some(fragment) => *fragment,
+            None => {
+                return self.error(
+                    format!("Unknown fragment \"{}\".", spread.fragment_name),
+                    spread.position,
+                )
+            }
+        };
+
+        if self.spreads.contains(&fragment.name.as_str()) {
+            return self.error(
+                format!("Cannot spread fragment \"{}\" within itself.", fragment.name),
+                spread.position,
+            );
+        }
+
+        let TypeCondition::On(ref type_name) = fragment.type_condition;
+        if self.validate_type_condition(object_type, type_name, Some(&fragment.name), spread.position) {
+            self.spreads.push(&fragment.name);
+            self.validate_selection_set(object_type, &fragment.selection_set);
+            self.spreads.pop();
+        }
+    }
+
+    /// Object types are the only composite types, fragments must be on exactly the selected type.
+    fn validate_type_condition(
+        &mut self,
+        object_type: &ObjectType,
+        type_name: &str,
+        fragment_name: Option<&str>,
+        position: Pos,
+    ) -> bool {
+        if object_type.name == type_name {
+            return true;
+        }
+
+        match (self.schema.find_object_type(type_name), fragment_name) {
+            // Unknown types of fragment definitions are reported along with the definition.
+            (None, Some(_)) => {}
+            (None, None) => self.error(format!("Unknown type \"{}\".", type_name), position),
+            (Some(_), Some(name)) => self.error(
+                format!(
+                    "Fragment \"{}\" cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+                    name, object_type.name, type_name
+                ),
+                position,
+            ),
+            (Some(_), None) => self.error(
+                format!(
+                    "Fragment cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+                    object_type.name, type_name
+                ),
+                position,
+            ),
+        }
+
+        false
+    }
+
+    fn validate_directives(&mut self, directives: &[Directive]) {
+        for directive in directives {
+            if directive.name != "skip" && directive.name != "include" {
+                self.error(format!("Unknown directive \"{}\".", directive.name), directive.position);
+                continue;
+            }
+
+            let condition = Argument {
+                name: String::from("if"),
+                argument_type: InputType::Scalar(ScalarType::Boolean),
+            };
+
+            self.validate_arguments(
+                &format!("@{}", directive.name),
+                &[condition],
+                &directive.arguments,
+                directive.position,
+                "Directive",
+            );
+        }
+    }
+
+    fn validate_field(&mut self, object_type: &ObjectType, field: &'a Field) {
+        if field.name == "__typename" {
+            return self.validate_leaf(field, "String!");
+        }
+
+        let schema = self.schema;
+        let schema_field = match object_type
+            .find_field(&field.name)
+            .or_else(|| schema.find_meta_field(object_type, &field.name))
+        {
+            Some(schema_field) => schema_field,
+            None => {
+                return self.error(
+                    format!(
+                        "Cannot query field \"{}\" on type \"{}\".",
+                        field.name, object_type.name
+                    ),
+                    field.position,
+                )
+            }
+        };
+
+        self.validate_arguments(
+            &field.name,
+            &schema_field.arguments,
+            &field.arguments,
+            field.position,
+            "Field",
+        );
+
+        match schema.find_object_type(&schema_field.field_type.name()) {
+            Some(_) if field.selection_set.items.is_empty() => self.error(
+                format!(
+                    "Field \"{}\" of type \"{}\" must have a selection of subfields. Did you mean \"{} {{ ... }}\"?",
+                    field.name, schema_field.field_type, field.name
+                ),
+                field.position,
+            ),
+            Some(field_object_type) => self.validate_selection_set(field_object_type, &field.selection_set),
+            None => self.validate_leaf(field, &schema_field.field_type.to_string()),
+        }
+    }
+
+    fn validate_leaf(&mut self, field: &Field, type_name: &str) {
+        if !field.selection_set.items.is_empty() {
+            self.error(
+                format!(
+                    "Field \"{}\" must not have a selection since type \"{}\" has no subfields.",
+                    field.name, type_name
+                ),
+                field.selection_set.span.0,
+            );
+        }
+    }
+
+    /// Arguments have no location of their own, violations are located at the field or directive.
+    fn validate_arguments(
+        &mut self,
+        name: &str,
+        schema_arguments: &[Argument],
+        arguments: &[(String, Value)],
+        position: Pos,
+        kind: &str,
+    ) {
+        for (arg_name, value) in arguments {
+            match schema_arguments.iter().find(|arg| &arg.name == arg_name) {
+                Some(arg) => {
+                    if let Err(reason) = self.validate_value(&arg.argument_type, value, arg_name) {
+                        self.error(
+                            format!(
+                                "Argument \"{}\" has invalid value {}. {}",
+                                arg_name,
+                                render(value),
+                                reason
+                            ),
+                            position,
+                        );
+                    }
+                }
+                None => self.error(
+                    format!(
+                        "Unknown argument \"{}\" on {} \"{}\".",
+                        arg_name,
+                        kind.to_lowercase(),
+                        name
+                    ),
+                    position,
+                ),
+            }
+        }
+
+        for arg in schema_arguments.iter().filter(|arg| arg.argument_type.is_required()) {
+            if !arguments.iter().any(|(arg_name, _)| arg_name == &arg.name) {
+                self.error(
+                    format!(
+                        "{} \"{}\" argument \"{}\" of type \"{}\" is required, but it was not provided.",
+                        kind, name, arg.name, arg.argument_type
+                    ),
+                    position,
+                );
+            }
+        }
+    }
+
+    /// Checks a value against an input type. The path locates nested violations within the argument.
+    fn validate_value(&self, tpe: &InputType, value: &Value, path: &str) -> Result<(), String> {
+        // Only null values violate the non-null part of a type.
+        let expected = || match value {
+            Value::Null => format!("Expected type \"{}\"{}, found null.", tpe, at(path)),
+            _ => format!(
+                "Expected type \"{}\"{}, found {}.",
+                InputType::opt(tpe.clone()),
+                at(path),
+                render(value)
+            ),
+        };
+
+        match (tpe, value) {
+            (_, Value::Variable(name)) => {
+                if self.variables.iter().any(|variable| &variable.name == name) {
+                    Ok(())
+                } else {
+                    Err(format!("Variable \"${}\" is not defined.", name))
+                }
+            }
+            (InputType::Opt(_), Value::Null) => Ok(()),
+            (InputType::Opt(inner), value) => self.validate_value(inner, value, path),
+            (_, Value::Null) => Err(expected()),
+            (InputType::List(inner), Value::List(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.validate_value(inner, item, &format!("{}[{}]", path, i))?;
+                }
+
+                Ok(())
+            }
+            // Single values are coerced into lists with one element.
+            (InputType::List(inner), value) => self.validate_value(inner, value, path),
+            (InputType::Scalar(scalar), value) => {
+                if is_scalar_value(*scalar, value) {
+                    Ok(())
+                } else {
+                    Err(expected())
+                }
+            }
+            (InputType::Enum(name), Value::Enum(value)) => {
+                let en = self.schema.find_enum_type(name).expect("Enum of the schema must exist");
+
+                if en.values.contains(value) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Value \"{}\" does not exist in \"{}\" enum{}.",
+                        value,
+                        name,
+                        at(path)
+                    ))
+                }
+            }
+            (InputType::Object(name), Value::Object(fields)) => {
+                let input_type = self
+                    .schema
+                    .find_input_type(name)
+                    .expect("Input type of the schema must exist");
+
+                for (field_name, field_value) in fields {
+                    match input_type.find_field(field_name) {
+                        Some(field) => {
+                            self.validate_value(&field.field_type, field_value, &format!("{}.{}", path, field_name))?
+                        }
+                        None => {
+                            return Err(format!(
+                                "Field \"{}\" is not defined by type \"{}\"{}.",
+                                field_name,
+                                name,
+                                at(path)
+                            ))
+                        }
+                    }
+                }
+
+                for field in input_type.fields.iter().filter(|f| f.field_type.is_required()) {
+                    if !fields.contains_key(&field.name) {
+                        return Err(format!(
+                            "Field \"{}.{}\" of required type \"{}\" was not provided{}.",
+                            name,
+                            field.name,
+                            field.field_type,
+                            at(path)
+                        ));
+                    }
+                }
+
+                Ok(())
+            }
+            _ => Err(expected()),
+        }
+    }
+}
+
+/// Locates nested violations within an argument, e.g. ` at "data.posts.create[0]"`.
+fn at(path: &str) -> String {
+    if path.contains('.') || path.contains('[') {
+        format!(" at \"{}\"", path)
+    } else {
+        String::new()
+    }
+}
+
+fn variable_type_name(tpe: &Type) -> &str {
+    match tpe {
+        Type::NamedType(name) => name,
+        Type::ListType(inner) | Type::NonNullType(inner) => variable_type_name(inner),
+    }
+}
+
+fn is_scalar_value(scalar: ScalarType, value: &Value) -> bool {
+    match (scalar, value) {
+        (ScalarType::Int, Value::Int(_)) | (ScalarType::Long, Value::Int(_)) => true,
+        (ScalarType::Float, Value::Int(_)) | (ScalarType::Float, Value::Float(_)) => true,
+        (ScalarType::Boolean, Value::Boolean(_)) => true,
+        (ScalarType::ID, Value::String(_)) | (ScalarType::ID, Value::Int(_)) => true,
+        (ScalarType::String, Value::String(_))
+        | (ScalarType::DateTime, Value::String(_))
+        | (ScalarType::Json, Value::String(_))
+        | (ScalarType::UUID, Value::String(_)) => true,
+        _ => false,
+    }
+}
+
+/// Renders a value the way it is written in the query.
+fn render(value: &Value) -> String {
+    match value {
+        Value::Variable(name) => format!("${}", name),
+        Value::Int(number) => number.as_i64().map(|i| i.to_string()).unwrap_or_default(),
+        Value::Float(f) => f.to_string(),
+        Value::String(s) => format!("{:?}", s),
+        Value::Boolean(b) => b.to_string(),
+        Value::Null => String::from("null"),
+        Value::Enum(name) => name.clone(),
+        Value::List(items) => format!("[{}]", items.iter().map(render).collect::<Vec<String>>().join(", ")),
+        Value::Object(fields) => {
+            let fields: Vec<String> = fields.iter().map(|(k, v)| format!("{}: {}", k, render(v))).collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils::query_schema;
+    use super::*;
+
+    fn validate(query: &str) -> Result<(), Vec<ValidationError>> {
+        let schema = query_schema();
+        let document = graphql_parser::parse_query(query).unwrap();
+
+        QueryValidator::new(&schema).validate(&document)
+    }
+
+    fn error(message: &str, line: usize, column: usize) -> ValidationError {
+        ValidationError::new(message.to_owned(), Pos { line, column })
+    }
+
+    #[test]
+    fn accepts_valid_queries() {
+        let query = r#"
+            query ($where: UserWhereUniqueQueryInput!, $first: Int) {
+              user(where: $where) { id name posts(first: $first) { title } }
+              users(where: { role_in: [ADMIN], address: { street_starts_with: "Main" } }) { ...UserFields }
+            }
+
+            fragment UserFields on User { __typename email address { street } }
+        "#;
+
+        assert_eq!(Ok(()), validate(query));
+    }
+
+    #[test]
+    fn reports_unknown_fields() {
+        assert_eq!(
+            Err(vec![error("Cannot query field \"nope\" on type \"User\".", 1, 14)]),
+            validate("{ users { id nope } }")
+        );
+
+        assert_eq!(
+            Err(vec![error("Cannot query field \"nope\" on type \"Query\".", 1, 3)]),
+            validate("{ nope }")
+        );
+    }
+
+    #[test]
+    fn reports_unknown_arguments() {
+        assert_eq!(
+            Err(vec![error("Unknown argument \"foo\" on field \"users\".", 1, 3)]),
+            validate("{ users(foo: 1) { id } }")
+        );
+    }
+
+    #[test]
+    fn reports_missing_required_arguments() {
+        assert_eq!(
+            Err(vec![error(
+                "Field \"user\" argument \"where\" of type \"UserWhereUniqueQueryInput!\" is required, but it was not provided.",
+                1,
+                3
+            )]),
+            validate("{ user { id } }")
+        );
+
+        assert_eq!(
+            Err(vec![error(
+                "Field \"createPost\" argument \"data\" of type \"PostCreateInput!\" is required, but it was not provided.",
+                1,
+                12
+            )]),
+            validate("mutation { createPost { id } }")
+        );
+    }
+
+    #[test]
+    fn reports_enum_mismatches_at_their_path() {
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"orderBy\" has invalid value nope_ASC. Value \"nope_ASC\" does not exist in \"UserOrderByInput\" enum.",
+                1,
+                3
+            )]),
+            validate("{ users(orderBy: nope_ASC) { id } }")
+        );
+
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"where\" has invalid value {posts_some: {author: {role_in: [ADMIN, NOPE]}}}. \
+                 Value \"NOPE\" does not exist in \"Role\" enum at \"where.posts_some.author.role_in[1]\".",
+                1,
+                3
+            )]),
+            validate("{ users(where: { posts_some: { author: { role_in: [ADMIN, NOPE] } } }) { id } }")
+        );
+    }
+
+    #[test]
+    fn reports_input_object_mismatches_at_their_path() {
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"data\" has invalid value {author: {connect: {nope: 1}}, title: \"a\"}. \
+                 Field \"nope\" is not defined by type \"UserWhereUniqueInput\" at \"data.author.connect\".",
+                1,
+                12
+            )]),
+            validate(r#"mutation { createPost(data: { title: "a", author: { connect: { nope: 1 } } }) { id } }"#)
+        );
+
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"data\" has invalid value {author: {create: {name: \"n\"}}, title: \"a\"}. \
+                 Field \"UserCreateWithoutPostsInput.role\" of required type \"Role!\" was not provided at \"data.author.create\".",
+                1,
+                12
+            )]),
+            validate(r#"mutation { createPost(data: { title: "a", author: { create: { name: "n" } } }) { id } }"#)
+        );
+
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"data\" has invalid value {title: null}. Expected type \"String!\" at \"data.title\", found null.",
+                1,
+                12
+            )]),
+            validate("mutation { createPost(data: { title: null }) { id } }")
+        );
+    }
+
+    #[test]
+    fn reports_scalar_mismatches() {
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"first\" has invalid value \"x\". Expected type \"Int\", found \"x\".",
+                1,
+                3
+            )]),
+            validate(r#"{ users(first: "x") { id } }"#)
+        );
+    }
+
+    #[test]
+    fn reports_undefined_variables() {
+        let query = "query ($where: UserWhereUniqueQueryInput!) \
+                     { user(where: $where) { id } users(where: { name: $name }) { id } }";
+
+        assert_eq!(
+            Err(vec![error(
+                "Argument \"where\" has invalid value {name: $name}. Variable \"$name\" is not defined.",
+                1,
+                73
+            )]),
+            validate(query)
+        );
+    }
+
+    #[test]
+    fn reports_fragment_cycles_and_unknown_fragments() {
+        let query = r#"
+            query {
+              users { ...A ...Missing }
+            }
+
+            fragment A on User { posts { author { ...B } } }
+            fragment B on User { ...A }
+        "#;
+
+        assert_eq!(
+            Err(vec![
+                error("Cannot spread fragment \"A\" within itself.", 7, 34),
+                error("Unknown fragment \"Missing\".", 3, 28),
+            ]),
+            validate(query)
+        );
+    }
+
+    #[test]
+    fn locates_errors_in_multiline_queries() {
+        let query = r#"
+            {
+              all: users {
+                id { x }
+              }
+              mine: user(where: { id: "1" }) {
+                nah
+              }
+            }
+        "#;
+
+        assert_eq!(
+            Err(vec![
+                error(
+                    "Field \"id\" must not have a selection since type \"ID!\" has no subfields.",
+                    4,
+                    20
+                ),
+                error("Cannot query field \"nah\" on type \"User\".", 7, 17),
+            ]),
+            validate(query)
+        );
+    }
+}
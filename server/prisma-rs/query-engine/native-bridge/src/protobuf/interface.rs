@@ -95,7 +95,7 @@ impl ExternalInterface for ProtoBufInterface {
             let query_result = self.data_resolver.get_node_by_where(&node_selector, &selected_fields)?;
 
             let (nodes, fields) = match query_result {
-                Some(node) => (vec![node.node.into()], node.field_names),
+                Some(node) => (vec![prisma::Node::try_from(node.node)?], node.field_names),
                 _ => (Vec::new(), Vec::new()),
             };
 
@@ -122,7 +122,10 @@ impl ExternalInterface for ProtoBufInterface {
 
             let query_result = self.data_resolver.get_nodes(model, query_arguments, &selected_fields)?;
             let (nodes, fields) = (query_result.nodes, query_result.field_names);
-            let proto_nodes = nodes.into_iter().map(|n| n.into()).collect();
+            let proto_nodes = nodes
+                .into_iter()
+                .map(prisma::Node::try_from)
+                .collect::<DomainResult<Vec<prisma::Node>>>()?;
 
             let response = RpcResponse::ok(prisma::NodesResult {
                 nodes: proto_nodes,
@@ -162,7 +165,10 @@ impl ExternalInterface for ProtoBufInterface {
             )?;
 
             let (nodes, fields) = (query_result.nodes, query_result.field_names);
-            let proto_nodes = nodes.into_iter().map(|n| n.into()).collect();
+            let proto_nodes = nodes
+                .into_iter()
+                .map(prisma::Node::try_from)
+                .collect::<DomainResult<Vec<prisma::Node>>>()?;
 
             let response = RpcResponse::ok(prisma::NodesResult {
                 nodes: proto_nodes,
@@ -195,11 +201,17 @@ impl ExternalInterface for ProtoBufInterface {
 
             let proto_values = query_result
                 .into_iter()
-                .map(|vals| prisma::ScalarListValues {
-                    node_id: vals.node_id.into(),
-                    values: vals.values.into_iter().map(|n| n.into()).collect(),
+                .map(|vals| {
+                    Ok(prisma::ScalarListValues {
+                        node_id: prisma::GraphqlId::try_from(vals.node_id)?,
+                        values: vals
+                            .values
+                            .into_iter()
+                            .map(ValueContainer::try_from)
+                            .collect::<DomainResult<Vec<ValueContainer>>>()?,
+                    })
                 })
-                .collect();
+                .collect::<DomainResult<Vec<prisma::ScalarListValues>>>()?;
 
             let response = RpcResponse::ok(prisma::ScalarListValuesResult { values: proto_values });
 
@@ -286,7 +298,7 @@ impl ExternalInterface for ProtoBufInterface {
             let db_name = project.internal_data_model().db_name.to_string();
 
             let result = self.database_mutaction_executor.execute(db_name, mutaction)?;
-            let response = RpcResponse::ok_mutaction(convert_mutaction_result(result));
+            let response = RpcResponse::ok_mutaction(convert_mutaction_result(result)?);
 
             let mut response_payload = Vec::new();
 
@@ -17,7 +17,7 @@ use crate::{BridgeError, BridgeResult};
 use chrono::prelude::*;
 use prelude::*;
 use prisma_models::prelude::*;
-use std::sync::Arc;
+use std::{convert::TryFrom, sync::Arc};
 use uuid::Uuid;
 
 pub mod prisma {
@@ -160,8 +160,10 @@ impl From<prisma::GraphqlId> for GraphqlId {
     }
 }
 
-impl From<PrismaValue> for ValueContainer {
-    fn from(pv: PrismaValue) -> ValueContainer {
+impl TryFrom<PrismaValue> for ValueContainer {
+    type Error = DomainError;
+
+    fn try_from(pv: PrismaValue) -> DomainResult<ValueContainer> {
         use prisma::value_container as vc;
 
         let prisma_value = match pv {
@@ -175,71 +177,82 @@ impl From<PrismaValue> for ValueContainer {
             PrismaValue::Relation(v) => vc::PrismaValue::Relation(v as i64),
             PrismaValue::Null => vc::PrismaValue::Null(true),
             PrismaValue::Uuid(v) => vc::PrismaValue::Uuid(v.to_hyphenated().to_string()),
-            PrismaValue::GraphqlId(v) => vc::PrismaValue::GraphqlId(v.into()),
+            // The protocol has no binary values, so bytes are sent base64 encoded.
+            PrismaValue::Bytes(v) => vc::PrismaValue::String(base64::encode(&v)),
+            PrismaValue::GraphqlId(v) => vc::PrismaValue::GraphqlId(prisma::GraphqlId::try_from(v)?),
             PrismaValue::List(v) => {
-                let values: Vec<ValueContainer> = v
+                let values = v
                     .expect("Proto bridge should not deal with list NULLs")
                     .into_iter()
-                    .map(|x| x.into())
-                    .collect();
+                    .map(ValueContainer::try_from)
+                    .collect::<DomainResult<Vec<ValueContainer>>>()?;
                 let list_value = crate::protobuf::prisma::PrismaListValue { values };
                 vc::PrismaValue::List(list_value)
             }
         };
 
-        ValueContainer {
+        Ok(ValueContainer {
             prisma_value: Some(prisma_value),
-        }
+        })
     }
 }
 
-impl From<GraphqlId> for prisma::GraphqlId {
-    fn from(id: GraphqlId) -> prisma::GraphqlId {
+/// The protocol has no compound ids, they are rejected.
+impl TryFrom<GraphqlId> for prisma::GraphqlId {
+    type Error = DomainError;
+
+    fn try_from(id: GraphqlId) -> DomainResult<prisma::GraphqlId> {
         use prisma::graphql_id as id;
 
         let id_value = match id {
             GraphqlId::String(s) => id::IdValue::String(s),
             GraphqlId::Int(i) => id::IdValue::Int(i as i64),
             GraphqlId::UUID(s) => id::IdValue::Uuid(s.to_hyphenated().to_string()),
+            GraphqlId::Compound(_) => return Err(DomainError::CompoundIdNotSupported("using the protobuf interface")),
         };
 
-        prisma::GraphqlId {
+        Ok(prisma::GraphqlId {
             id_value: Some(id_value),
-        }
+        })
     }
 }
 
-impl From<&GraphqlId> for prisma::GraphqlId {
-    fn from(id: &GraphqlId) -> prisma::GraphqlId {
-        use prisma::graphql_id as id;
-
-        let id_value = match id {
-            GraphqlId::String(s) => id::IdValue::String(s.clone()),
-            GraphqlId::Int(i) => id::IdValue::Int(*i as i64),
-            GraphqlId::UUID(s) => id::IdValue::Uuid(s.to_hyphenated().to_string()),
-        };
+impl TryFrom<&GraphqlId> for prisma::GraphqlId {
+    type Error = DomainError;
 
-        prisma::GraphqlId {
-            id_value: Some(id_value),
-        }
+    fn try_from(id: &GraphqlId) -> DomainResult<prisma::GraphqlId> {
+        prisma::GraphqlId::try_from(id.clone())
     }
 }
 
-impl From<Node> for prisma::Node {
-    fn from(node: Node) -> prisma::Node {
-        prisma::Node {
-            values: node.values.into_iter().map(ValueContainer::from).collect(),
-            parent_id: node.parent_id.map(prisma::GraphqlId::from),
-        }
+impl TryFrom<Node> for prisma::Node {
+    type Error = DomainError;
+
+    fn try_from(node: Node) -> DomainResult<prisma::Node> {
+        let parent_id = match node.parent_id {
+            Some(id) => Some(prisma::GraphqlId::try_from(id)?),
+            None => None,
+        };
+
+        Ok(prisma::Node {
+            values: node
+                .values
+                .into_iter()
+                .map(ValueContainer::try_from)
+                .collect::<DomainResult<Vec<ValueContainer>>>()?,
+            parent_id,
+        })
     }
 }
 
-impl From<SingleNode> for prisma::NodeResult {
-    fn from(node: SingleNode) -> prisma::NodeResult {
-        prisma::NodeResult {
-            node: prisma::Node::from(node.node),
+impl TryFrom<SingleNode> for prisma::NodeResult {
+    type Error = DomainError;
+
+    fn try_from(node: SingleNode) -> DomainResult<prisma::NodeResult> {
+        Ok(prisma::NodeResult {
+            node: prisma::Node::try_from(node.node)?,
             fields: node.field_names,
-        }
+        })
     }
 }
 
@@ -1,7 +1,7 @@
 use super::filter::IntoFilter;
 use connector::{filter::NodeSelector, mutaction::*};
 use prisma_models::prelude::*;
-use std::sync::Arc;
+use std::{convert::TryFrom, sync::Arc};
 
 pub fn convert_mutaction(
     m: crate::protobuf::prisma::DatabaseMutaction,
@@ -33,6 +33,7 @@ pub fn convert_create(m: crate::protobuf::prisma::CreateNode, project: ProjectRe
         non_list_args: convert_prisma_args(m.non_list_args),
         list_args: convert_list_args(m.list_args),
         nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
+        embedded_writes: Vec::new(),
     }
 }
 
@@ -114,6 +115,7 @@ pub fn convert_update(m: crate::protobuf::prisma::UpdateNode, project: ProjectRe
         non_list_args: convert_prisma_args(m.non_list_args),
         list_args: convert_list_args(m.list_args),
         nested_mutactions: convert_nested_mutactions(m.nested, Arc::clone(&project)),
+        embedded_writes: Vec::new(),
     }
 }
 
@@ -298,24 +300,30 @@ pub fn find_relation_field(project: ProjectRef, model: String, field: String) ->
         .unwrap()
 }
 
-pub fn convert_mutaction_result(result: DatabaseMutactionResult) -> crate::protobuf::prisma::DatabaseMutactionResult {
+pub fn convert_mutaction_result(
+    result: DatabaseMutactionResult,
+) -> DomainResult<crate::protobuf::prisma::DatabaseMutactionResult> {
     use crate::protobuf::prisma::database_mutaction_result;
 
-    match result.typ {
+    let converted = match result.typ {
         DatabaseMutactionResultType::Create => {
-            let result = crate::protobuf::prisma::IdResult { id: result.id().into() };
+            let result = crate::protobuf::prisma::IdResult {
+                id: crate::protobuf::prisma::GraphqlId::try_from(result.id())?,
+            };
             let typ = database_mutaction_result::Type::Create(result);
 
             crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
         }
         DatabaseMutactionResultType::Update => {
-            let result = crate::protobuf::prisma::IdResult { id: result.id().into() };
+            let result = crate::protobuf::prisma::IdResult {
+                id: crate::protobuf::prisma::GraphqlId::try_from(result.id())?,
+            };
             let typ = database_mutaction_result::Type::Update(result);
 
             crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
         }
         DatabaseMutactionResultType::Delete => {
-            let result = crate::protobuf::prisma::NodeResult::from(result.node().clone());
+            let result = crate::protobuf::prisma::NodeResult::try_from(result.node().clone())?;
             let typ = database_mutaction_result::Type::Delete(result);
 
             crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
@@ -332,7 +340,7 @@ pub fn convert_mutaction_result(result: DatabaseMutactionResult) -> crate::proto
             let typ = database_mutaction_result::Type::Unit(result);
             crate::protobuf::prisma::DatabaseMutactionResult { type_: Some(typ) }
         }
+    };
 
-        // x => panic!("can't handle result type {:?}", x),
-    }
+    Ok(converted)
 }
@@ -1,12 +1,15 @@
-use crate::{data_model, exec_loader, PrismaResult};
-use core::{Executor, SchemaBuilder};
+use crate::{data_model, exec_loader, PrismaError, PrismaResult};
+use core::{Executor, QuerySchema, SchemaBuilder};
+use datamodel::Source;
 use prisma_common::config::{self, PrismaConfig};
 use prisma_models::InternalDataModelRef;
+use serde_json::{json, Map, Value};
 
 #[derive(DebugStub)]
 pub struct PrismaContext {
     pub config: PrismaConfig,
     pub internal_data_model: InternalDataModelRef,
+    pub query_schema: QuerySchema,
 
     #[debug_stub = "#Executor#"]
     pub executor: Executor,
@@ -14,8 +17,19 @@ pub struct PrismaContext {
 
 impl PrismaContext {
     pub fn new() -> PrismaResult<Self> {
-        // Load config and executors
-        let config = config::load().unwrap();
+        // Load config and executors. A datasource in the datamodel file takes precedence over prisma.yml.
+        let config = match data_model::load_configuration()? {
+            Some(configuration) => match configuration.default_source() {
+                Some(source) => config_from_source(source)?,
+                None => {
+                    return Err(PrismaError::ConfigurationError(
+                        "The datamodel file does not declare an enabled datasource.".into(),
+                    ))
+                }
+            },
+            None => config::load().unwrap(),
+        };
+
         let executor = exec_loader::load(&config);
 
         // Find db name. This right here influences how
@@ -24,12 +38,56 @@ impl PrismaContext {
 
         // Load internal data model
         let internal_data_model = data_model::load(db_name)?;
-        // let _ = SchemaBuilder::build(internal_data_model.clone());
+        let query_schema = SchemaBuilder::build(internal_data_model.clone());
 
         Ok(Self {
             config,
             internal_data_model,
+            query_schema,
             executor,
         })
     }
 }
+
+/// Builds the prisma.yml equivalent of a datamodel datasource, which becomes the default database.
+/// All additional datasource properties are passed on as database options, e.g. `connectionLimit = 10`.
+fn config_from_source(source: &Source) -> PrismaResult<PrismaConfig> {
+    let mut database = Map::new();
+
+    match source.provider.as_str() {
+        "sqlite" => {
+            database.insert("connector".into(), json!("sqlite-native"));
+            database.insert("databaseFile".into(), json!(source.url.trim_start_matches("file:")));
+        }
+        "postgresql" => {
+            database.insert("connector".into(), json!("postgres-native"));
+            database.insert("uri".into(), json!(source.url));
+        }
+        provider => {
+            return Err(PrismaError::ConfigurationError(format!(
+                "Datasource provider {} is not supported.",
+                provider
+            )))
+        }
+    }
+
+    for (key, value) in source.config.iter().filter(|(key, _)| key.as_str() != "port") {
+        // Datasource values are strings, numbers and booleans are recovered for typed options.
+        let value = serde_json::from_str::<Value>(value).unwrap_or_else(|_| json!(value));
+        database.insert(key.clone(), value);
+    }
+
+    let port = match source.config.get("port") {
+        Some(port) => port
+            .parse::<u16>()
+            .map_err(|_| PrismaError::ConfigurationError(format!("Invalid port: {}", port)))?,
+        None => 4466,
+    };
+
+    let config = json!({
+        "port": port,
+        "databases": { "default": Value::Object(database) },
+    });
+
+    Ok(serde_json::from_value(config)?)
+}
@@ -1,51 +1,50 @@
 use crate::{utilities, PrismaError, PrismaResult};
+use core::{QuerySchema, QueryValidator, ValidationError};
 use graphql_parser::query;
-use prisma_models::{InternalDataModelRef, InternalDataModelTemplate};
-use serde::Serialize;
+use prisma_models::{DatamodelConverter, InternalDataModelRef, InternalDataModelTemplate};
 use serde_json;
-use std::{
-    fs::File,
-    io::{Read, Write},
-    process::{Command, Stdio},
-};
-
-pub enum ValidationError {
-    #[allow(dead_code)]
-    EverythingIsBroken,
-    #[allow(dead_code)]
-    Problematic(String),
-    #[allow(dead_code)]
-    Duplicate(String),
-}
+use std::{fs::File, io::Read};
 
-// todo: Return crate::error errors, removing the above?
 pub trait Validatable {
-    fn validate(&self, doc: &query::Document) -> Result<(), ValidationError>;
+    fn validate(&self, doc: &query::Document) -> Result<(), Vec<ValidationError>>;
 }
 
-impl Validatable for InternalDataModelRef {
-    fn validate(&self, _: &query::Document) -> Result<(), ValidationError> {
-        // It's not really ok 😭
-        Ok(())
+impl Validatable for QuerySchema {
+    fn validate(&self, doc: &query::Document) -> Result<(), Vec<ValidationError>> {
+        QueryValidator::new(self).validate(doc)
     }
 }
 
-/// Loads and builds the internal data model from the data model JSON.
+/// Loads and builds the internal data model, either from the data model JSON
+/// or converted from the datamodel.
 pub fn load(db_name: String) -> PrismaResult<InternalDataModelRef> {
-    let data_model_json = load_string()?;
-    Ok(serde_json::from_str::<InternalDataModelTemplate>(&data_model_json)?.build(db_name))
+    let template = load_template().map_err(|err| {
+        PrismaError::ConfigurationError(format!(
+            "Unable to construct internal Prisma data model from any source. Last error: {}",
+            err
+        ))
+    })?;
+
+    Ok(template.build(db_name))
 }
 
-/// Attempts to load the config as unparsed JSON string.
-pub fn load_string() -> PrismaResult<String> {
-    load_internal_from_env()
-        .or_else(|_| load_sdl_string().and_then(|sdl| resolve_internal_data_model_json(sdl)))
-        .map_err(|err| {
-            PrismaError::ConfigurationError(format!(
-                "Unable to construct internal Prisma data model from any source. Last error: {}",
-                err
-            ))
-        })
+fn load_template() -> PrismaResult<InternalDataModelTemplate> {
+    match load_internal_from_env() {
+        Ok(data_model_json) => Ok(serde_json::from_str::<InternalDataModelTemplate>(&data_model_json)?),
+        Err(_) => load_datamodel().map(|datamodel| DatamodelConverter::convert(&datamodel)),
+    }
+}
+
+/// Parses and validates the datamodel loaded by `load_sdl_string`.
+pub fn load_datamodel() -> PrismaResult<datamodel::Schema> {
+    let sdl = load_sdl_string()?;
+    let ast = datamodel::parser::parse(&sdl)
+        .map_err(|err| PrismaError::ConfigurationError(format!("Unable to parse datamodel: {}", err)))?;
+
+    datamodel::Validator::new().validate(&ast).map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+        PrismaError::ConfigurationError(format!("Invalid datamodel: {}", messages.join(", ")))
+    })
 }
 
 /// Attempts to resolve the internal data model from an env var.
@@ -63,13 +62,15 @@ pub fn load_internal_from_env() -> PrismaResult<String> {
     })
 }
 
-// let inferrer = resolve_internal_data_model_json(sdl)?;
-
 /// Attempts to load a Prisma SDL string from either env or file.
+/// The datamodel file at `PRISMA_DATAMODEL_PATH` is used last.
 pub fn load_sdl_string() -> PrismaResult<String> {
-    load_sdl_from_env().or_else(|_| load_sdl_from_file()).map_err(|err| {
-        PrismaError::ConfigurationError(format!("Unable to load SDL from any source. Last error: {}", err))
-    })
+    load_sdl_from_env()
+        .or_else(|_| load_sdl_from_file())
+        .or_else(|_| load_sdl_from_datamodel_file())
+        .map_err(|err| {
+            PrismaError::ConfigurationError(format!("Unable to load SDL from any source. Last error: {}", err))
+        })
 }
 
 /// Attempts to load a Prisma SDL string from env.
@@ -113,28 +114,72 @@ pub fn load_sdl_from_file() -> PrismaResult<String> {
     Ok(sdl)
 }
 
-/// Transforms an SDL string into stringified JSON of the internal data model.
-fn resolve_internal_data_model_json(sdl: String) -> PrismaResult<String> {
-    #[derive(Serialize)]
-    #[serde(rename_all = "camelCase")]
-    struct InternalDataModelInferrerJson {
-        data_model: String,
-    }
+/// Attempts to load a Prisma SDL string from the datamodel file, which may also declare datasources.
+fn load_sdl_from_datamodel_file() -> PrismaResult<String> {
+    let path = utilities::get_env("PRISMA_DATAMODEL_PATH")?;
+    let mut f = File::open(&path)?;
+    let mut sdl = String::new();
 
-    let internal_data_model_inferrer = utilities::get_env("SCHEMA_INFERRER_PATH")?;
-    let mut child = Command::new(internal_data_model_inferrer)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()?;
+    f.read_to_string(&mut sdl)?;
+    debug!("Loaded Prisma SDL from datamodel file: {}.", path);
 
-    let compacted = sdl.replace('\n', " ");
-    let child_in = child.stdin.as_mut().unwrap();
-    let json = serde_json::to_string(&InternalDataModelInferrerJson { data_model: compacted })?;
+    Ok(sdl)
+}
+
+/// Attempts to load the datasources and generators of the datamodel file at `PRISMA_DATAMODEL_PATH`.
+/// Returns: `None` if no datamodel file is configured.
+pub fn load_configuration() -> PrismaResult<Option<datamodel::Configuration>> {
+    let path = match utilities::get_env("PRISMA_DATAMODEL_PATH") {
+        Ok(path) => path,
+        Err(_) => return Ok(None),
+    };
 
-    child_in.write_all(json.as_bytes()).expect("Failed to write to stdin");
+    debug!("Loading datamodel configuration from file: {}.", path);
 
-    let output = child.wait_with_output()?;
-    let inferred = String::from_utf8(output.stdout)?;
+    let mut f = File::open(&path)?;
+    let mut datamodel_string = String::new();
+    f.read_to_string(&mut datamodel_string)?;
 
-    Ok(inferred)
+    let ast = datamodel::parser::parse(&datamodel_string)
+        .map_err(|err| PrismaError::ConfigurationError(format!("Unable to parse datamodel file {}: {}", path, err)))?;
+
+    datamodel::configuration::load(&ast).map(Some).map_err(|errors| {
+        let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+        PrismaError::ConfigurationError(format!("Invalid datamodel configuration: {}", messages.join(", ")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prisma_models::RelationLinkManifestation;
+    use std::env;
+
+    #[test]
+    fn loads_the_schema_of_the_repository() {
+        env::remove_var("PRISMA_INTERNAL_DATA_MODEL_JSON");
+        env::remove_var("PRISMA_SDL");
+        env::set_var(
+            "PRISMA_SDL_PATH",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../schema.prisma"),
+        );
+
+        let template = load_template().unwrap();
+
+        assert!(template.models.iter().any(|model| model.name == "Album"));
+
+        let relation = template
+            .relations
+            .iter()
+            .find(|relation| relation.name == "AlbumToArtist")
+            .expect("Relation AlbumToArtist not found");
+
+        match relation.manifestation {
+            Some(RelationLinkManifestation::Inline(ref inline)) => {
+                assert_eq!(inline.in_table_of_model_name, "Album");
+                assert_eq!(inline.referencing_column, "ArtistId");
+            }
+            _ => panic!("Expected AlbumToArtist to be stored inline in the Album table."),
+        }
+    }
 }
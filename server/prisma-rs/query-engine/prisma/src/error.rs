@@ -7,9 +7,6 @@ pub enum PrismaError {
     #[fail(display = "{}", _0)]
     QueryParsingError(String),
 
-    #[fail(display = "{}", _0)]
-    QueryValidationError(String),
-
     #[fail(display = "{}", _0)]
     SerializationError(String),
 
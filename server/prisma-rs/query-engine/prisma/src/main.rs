@@ -17,9 +17,10 @@ mod utilities;
 
 use actix_web::{http::Method, server, App, HttpRequest, HttpResponse, Json, Responder};
 use context::PrismaContext;
+use core::QuerySchema;
 use error::PrismaError;
 use req_handlers::{GraphQlBody, GraphQlRequestHandler, PrismaRequest, RequestHandler};
-use serde_json;
+use serde_json::{self, json};
 use std::sync::Arc;
 
 pub type PrismaResult<T> = Result<T, PrismaError>;
@@ -56,6 +57,7 @@ fn main() {
                 r.method(Method::GET).with(playground_handler);
             })
             .resource("/datamodel", |r| r.method(Method::GET).with(data_model_handler))
+            .resource("/schema", |r| r.method(Method::GET).with(schema_handler))
     })
     .bind(address)
     .unwrap()
@@ -88,6 +90,25 @@ fn data_model_handler<T>(_: HttpRequest<T>) -> impl Responder {
     data_model::load_sdl_string().unwrap()
 }
 
+/// Serves the GraphQL schema as SDL, or as introspection result with `?format=json`.
+fn schema_handler(req: HttpRequest<Arc<RequestContext>>) -> impl Responder {
+    let query_schema = &req.state().context.query_schema;
+
+    match req.query().get("format").map(|format| format.as_str()) {
+        Some("json") => match introspection_json(query_schema) {
+            Ok(body) => HttpResponse::Ok().content_type("application/json").body(body),
+            Err(err) => HttpResponse::InternalServerError().body(format!("{}", err)),
+        },
+        _ => HttpResponse::Ok().content_type("text/plain").body(query_schema.to_sdl()),
+    }
+}
+
+fn introspection_json(query_schema: &QuerySchema) -> PrismaResult<String> {
+    let result = json!({ "data": { "__schema": query_schema.introspection() } });
+
+    serde_json::to_string(&result).map_err(|err| PrismaError::SerializationError(format!("{}", err)))
+}
+
 fn playground_handler<T>(_: HttpRequest<T>) -> impl Responder {
     let index_html = StaticFiles::get("playground.html").unwrap();
     HttpResponse::Ok().content_type("text/html").body(index_html)
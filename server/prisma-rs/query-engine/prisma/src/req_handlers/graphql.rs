@@ -1,12 +1,15 @@
 use super::{PrismaRequest, RequestHandler};
 use crate::{context::PrismaContext, data_model::Validatable, error::PrismaError, PrismaResult};
 use core::{
-    ir::{self, Builder},
-    RootBuilder,
+    ir::{self, Builder, Item},
+    IntrospectionResolver, OperationBuilder, RootBuilder,
 };
-use graphql_parser as gql;
+use graphql_parser::{
+    self as gql,
+    query::{Definition, Document, OperationDefinition, Selection},
+};
+use prisma_models::PrismaValue;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 use serde_json::{Map, Value};
 
@@ -19,7 +22,7 @@ type JsonMap = Map<String, Value>;
 pub struct GraphQlBody {
     query: String,
     operation_name: Option<String>,
-    variables: HashMap<String, String>,
+    variables: Option<JsonMap>,
 }
 
 pub struct GraphQlRequestHandler;
@@ -49,17 +52,35 @@ fn handle_safely(req: PrismaRequest<GraphQlBody>, ctx: &PrismaContext) -> Prisma
         Err(e) => return Err(PrismaError::QueryParsingError(format!("{:?}", e))),
     };
 
-    // Let's validate the schema!
-    if let Err(_) = ctx.internal_data_model.validate(&query_doc) {
-        return Err(PrismaError::QueryValidationError(
-            "InternalDataModel validation failed for unknown reasons".into(),
-        ));
+    // Invalid queries are answered with all violations instead of being executed
+    if let Err(errors) = ctx.query_schema.validate(&query_doc) {
+        return Ok(json::serialize_errors(errors));
+    }
+
+    let ob = OperationBuilder {
+        query: &query_doc,
+        query_schema: &ctx.query_schema,
+        operation_name: req.body.operation_name,
+        variables: req.body.variables.unwrap_or_default(),
+    };
+
+    let operation_doc = match ob.build() {
+        Ok(doc) => doc,
+        Err(errors) => return Ok(json::serialize_errors(errors)),
+    };
+
+    // Variable values are only known now, they are validated as arguments of the prepared operation
+    if let Err(errors) = ctx.query_schema.validate(&operation_doc) {
+        return Ok(json::serialize_errors(errors));
     }
 
+    // Introspection fields are answered from the query schema, all other fields are executed
+    let introspection = IntrospectionResolver::new(&ctx.query_schema, &operation_doc).resolve();
+    let response_keys = root_response_keys(&operation_doc);
+
     let rb = RootBuilder {
-        query: query_doc,
+        query: operation_doc,
         internal_data_model: ctx.internal_data_model.clone(),
-        operation_name: req.body.operation_name,
     };
 
     let queries = rb.build();
@@ -75,7 +96,50 @@ fn handle_safely(req: PrismaRequest<GraphQlBody>, ctx: &PrismaContext) -> Prisma
         Err(err) => vec![ir::Response::Error(format!("{:?}", err))], // This is merely a workaround
     };
 
-    Ok(json::serialize(ir))
+    Ok(json::serialize(merge_introspection(ir, introspection, &response_keys)))
+}
+
+/// Merges the introspection results into the executed results, in the order of the selected root fields.
+fn merge_introspection(
+    results: ir::ResponseSet,
+    introspection: Vec<(String, Value)>,
+    response_keys: &[String],
+) -> ir::ResponseSet {
+    let mut responses: ir::ResponseSet = results
+        .into_iter()
+        .chain(
+            introspection
+                .into_iter()
+                .map(|(name, value)| ir::Response::Data(name, Item::Value(PrismaValue::Json(value)))),
+        )
+        .collect();
+
+    // Errors stay in front, only a leading error is serialized.
+    responses.sort_by_key(|response| match response {
+        ir::Response::Data(name, _) => response_keys.iter().position(|key| key == name),
+        ir::Response::Error(_) => None,
+    });
+
+    responses
+}
+
+/// The response keys of the root fields of a prepared operation, see `OperationBuilder`.
+fn root_response_keys(operation: &Document) -> Vec<String> {
+    operation
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => Some(selection_set),
+            Definition::Operation(OperationDefinition::Query(query)) => Some(&query.selection_set),
+            Definition::Operation(OperationDefinition::Mutation(mutation)) => Some(&mutation.selection_set),
+            _ => None,
+        })
+        .flat_map(|selection_set| &selection_set.items)
+        .filter_map(|selection| match selection {
+            Selection::Field(field) => Some(field.alias.clone().unwrap_or_else(|| field.name.clone())),
+            _ => None,
+        })
+        .collect()
 }
 
 /// Create a json envelope
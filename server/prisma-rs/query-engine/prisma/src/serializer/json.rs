@@ -1,10 +1,13 @@
 //! Json serialisation endpoint for IR
 
 use crate::{PrismaError, PrismaResult};
-use core::ir::{Item, Response, ResponseSet};
+use core::{
+    ir::{Item, Response, ResponseSet},
+    ValidationError,
+};
 use indexmap::IndexMap;
 use prisma_models::{GraphqlId, PrismaValue};
-use serde_json::{Map, Number, Value};
+use serde_json::{json, Map, Number, Value};
 
 type JsonMap = Map<String, Value>;
 type JsonVec = Vec<Value>;
@@ -32,6 +35,7 @@ pub fn serialize(resp: ResponseSet) -> Value {
             .map(|res| match res {
                 Response::Data(name, Item::List(list)) => envelope!(name, Value::Array(serialize_list(list))),
                 Response::Data(name, Item::Map(_parent, map)) => envelope!(name, Value::Object(serialize_map(map))),
+                Response::Data(name, Item::Value(value)) => envelope!(name, serialize_prisma_value(value).unwrap()),
                 _ => unreachable!(),
             })
             .collect();
@@ -49,6 +53,25 @@ pub fn serialize(resp: ResponseSet) -> Value {
     Value::Object(map)
 }
 
+/// Serializes errors in the GraphQL format, e.g.
+/// `{ "errors": [{ "message": "...", "locations": [{ "line": 1, "column": 3 }] }] }`
+pub fn serialize_errors(errors: Vec<ValidationError>) -> Value {
+    let errors: JsonVec = errors
+        .into_iter()
+        .map(|error| {
+            let locations: JsonVec = error
+                .locations
+                .iter()
+                .map(|pos| json!({ "line": pos.line, "column": pos.column }))
+                .collect();
+
+            json!({ "message": error.message, "locations": locations })
+        })
+        .collect();
+
+    envelope!("errors".into(), Value::Array(errors))
+}
+
 macro_rules! match_serialize {
     ($val:ident) => {
         match $val {
@@ -92,6 +115,7 @@ fn serialize_prisma_value(value: PrismaValue) -> PrismaResult<Value> {
         PrismaValue::Relation(_) => unreachable!(),
         PrismaValue::Null => Value::Null,
         PrismaValue::Uuid(x) => Value::String(x.to_hyphenated().to_string()),
+        PrismaValue::Bytes(x) => Value::String(base64::encode(&x)),
         PrismaValue::GraphqlId(x) => serialize_graphql_id(&x)?,
         PrismaValue::List(_) => unreachable!(),
     })
@@ -105,5 +129,9 @@ fn serialize_graphql_id(id: &GraphqlId) -> PrismaResult<Value> {
             None => return Err(PrismaError::SerializationError("`f64` number was invalid".into())),
         }),
         GraphqlId::UUID(x) => Value::String(x.to_hyphenated().to_string()),
+        GraphqlId::Compound(parts) => {
+            let parts: PrismaResult<Vec<Value>> = parts.iter().map(serialize_graphql_id).collect();
+            Value::Array(parts?)
+        }
     })
 }